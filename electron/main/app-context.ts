@@ -1,5 +1,6 @@
 import { BrowserWindow, Tray } from 'electron'
 import { logger } from './logger'
+import { HotkeyRegistry } from './hotkey-registry'
 
 // ── 共享应用状态 ──
 
@@ -11,6 +12,8 @@ export let permissionWarned = false
 export let permissionCheckInFlight = false
 export let hotkeysRegistered = false
 export let lastPermissionCheckAt = 0
+// 当前生效的全局热键及注册状态
+export const hotkeyRegistry = new HotkeyRegistry()
 
 export const FLOAT_WIDTH = 116
 export const FLOAT_HEIGHT = 38
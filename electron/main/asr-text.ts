@@ -31,6 +31,254 @@ export function normalizeAsrText(value: unknown): string {
   return String(value)
 }
 
+// 激活词前后可能被识别出的标点与空白
+const ACTIVATION_SEPARATOR_RE = /^[\s，。、,.!?！？:：;；]+/
+
+// VAD 激活词门控：文本以激活词开头时返回去掉激活词后的内容，否则返回 null 表示应丢弃。
+// 未配置激活词时原样返回
+export function stripActivationPhrase(text: string, phrase: string): string | null {
+  const activation = phrase.trim()
+  if (!activation) return text
+  const body = text.replace(ACTIVATION_SEPARATOR_RE, '')
+  if (!body.toLowerCase().startsWith(activation.toLowerCase())) return null
+  return body.slice(activation.length).replace(ACTIVATION_SEPARATOR_RE, '')
+}
+
+// 连续空识别结果计数：多数情况是麦克风增益过低或选错了输入设备。
+// 连续达到阈值（及其整数倍）时返回 true 提示用户检查；任何非空结果都会清零
+export class EmptyResultTracker {
+  private consecutive = 0
+
+  get count(): number { return this.consecutive }
+
+  record(text: string, threshold: number): boolean {
+    if (text.trim()) {
+      this.consecutive = 0
+      return false
+    }
+    this.consecutive += 1
+    return threshold > 0 && this.consecutive % threshold === 0
+  }
+
+  reset(): void {
+    this.consecutive = 0
+  }
+}
+
+// 识别结果长度过滤：单个杂字或异常冗长的结果直接丢弃而不输入。
+// 按 Unicode 字符（码位）计数，中文一字计 1；首尾空白不计，minChars/maxChars 为 0 表示不限
+export type TextLengthFilterReason = 'too-short' | 'too-long'
+
+export interface TextLengthCheck {
+  reason: TextLengthFilterReason
+  length: number
+}
+
+export function checkTextLength(text: string, minChars: number, maxChars: number): TextLengthCheck | null {
+  const length = Array.from(text.trim()).length
+  if (minChars > 0 && length < minChars) return { reason: 'too-short', length }
+  if (maxChars > 0 && length > maxChars) return { reason: 'too-long', length }
+  return null
+}
+
+// 标点模式：server 请求服务端加标点，返回结果仍无标点时本地补最简标点；local 只在本地补；off 不处理
+export type PunctuationMode = 'server' | 'local' | 'off'
+
+// 识别语言（server.language）：同时作为识别请求的语言提示，并决定后处理按中文还是英文规则处理
+export type AsrLanguage = 'zh' | 'en' | 'auto'
+export type TextLocale = 'zh' | 'en'
+
+const HAN_RE = /\p{Script=Han}/u
+
+// auto 时按文本判断：含汉字按中文处理，纯英文（如单独口述的药名）按英文处理
+export function resolveTextLocale(language: AsrLanguage | undefined, text: string): TextLocale {
+  if (language === 'en') return 'en'
+  if (language === 'auto') return HAN_RE.test(text) ? 'zh' : 'en'
+  return 'zh'
+}
+
+export interface PostprocessOptions {
+  dedupeRepeats?: boolean
+  dedupeMinUnitChars?: number
+  punctuation?: PunctuationMode
+  stopwords?: string[]
+  trimTrailingWhitespace?: boolean
+  collapseSpaces?: boolean
+  stripNewlines?: boolean
+  language?: AsrLanguage
+}
+
+export type PostprocessStage = 'textRules' | 'stopwords' | 'dedupe' | 'punctuation' | 'whitespace'
+
+export interface PostprocessTrace {
+  input: string
+  text: string
+  // 依次执行的各步及其输出，未启用的步骤不列出
+  steps: Array<{ stage: PostprocessStage; text: string }>
+}
+
+function postprocessStages(options?: PostprocessOptions | null): Array<[PostprocessStage, (text: string) => string]> {
+  const stages: Array<[PostprocessStage, (text: string) => string]> = []
+  if (options?.stopwords?.length) {
+    const stopwords = options.stopwords
+    stages.push(['stopwords', text => removeStopwords(text, stopwords)])
+  }
+  if (options?.dedupeRepeats) {
+    const minUnitChars = options.dedupeMinUnitChars ?? DEDUPE_DEFAULT_MIN_UNIT_CHARS
+    stages.push(['dedupe', text => collapseRepeatedPhrases(text, minUnitChars)])
+  }
+  if (options?.punctuation === 'server' || options?.punctuation === 'local') {
+    const language = options.language
+    stages.push(['punctuation', text => addMinimalPunctuation(text, resolveTextLocale(language, text))])
+  }
+  if (options?.trimTrailingWhitespace || options?.collapseSpaces || options?.stripNewlines) {
+    const whitespace = {
+      trimTrailingWhitespace: options.trimTrailingWhitespace,
+      collapseSpaces: options.collapseSpaces,
+      stripNewlines: options.stripNewlines,
+    }
+    stages.push(['whitespace', text => cleanWhitespace(text, whitespace)])
+  }
+  return stages
+}
+
+// 识别文本后处理：在文本规则之后、指令匹配与输入之前执行，各项默认关闭
+export function applyPostprocess(text: string, options?: PostprocessOptions | null): string {
+  return postprocessStages(options).reduce((result, [, run]) => run(result), text)
+}
+
+// 完整处理链（文本规则 → 删除填充词 → 合并重复 → 标点 → 空白整理），同时记录每一步的结果。
+// 识别后实际输入与设置页试测共用此函数，保证试测结果就是最终会输入的文字
+export function tracePostprocess(
+  text: string,
+  textRules?: TextRulesConfig | null,
+  options?: PostprocessOptions | null,
+): PostprocessTrace {
+  const steps: PostprocessTrace['steps'] = []
+  let result = applyTextRules(text, textRules)
+  if (textRules?.enabled) steps.push({ stage: 'textRules', text: result })
+  for (const [stage, run] of postprocessStages(options)) {
+    result = run(result)
+    steps.push({ stage, text: result })
+  }
+  return { input: text, text: result, steps }
+}
+
+const NEWLINE_RUN_RE = /[^\S\r\n]*(?:\r\n|\r|\n)\s*/g
+const SPACE_RUN_RE = /[ \u00a0\u3000]{2,}/g
+const LINE_TRAILING_SPACE_RE = /[^\S\r\n]+(?=\r?\n)/g
+const WORD_CHAR_RE = /[\p{L}\p{N}]/u
+
+// 空白整理（依次：去掉换行 → 合并连续空格 → 去掉行尾空白），各项独立开关。
+// 去掉换行时，两侧都是非汉字的字母数字（英文单词、数值）才补一个空格，其余直接相连
+export function cleanWhitespace(
+  text: string,
+  options: Pick<PostprocessOptions, 'trimTrailingWhitespace' | 'collapseSpaces' | 'stripNewlines'>,
+): string {
+  let result = text
+  if (options.stripNewlines) {
+    result = result.replace(NEWLINE_RUN_RE, (match, offset: number, whole: string) => {
+      const before = whole[offset - 1] ?? ''
+      const after = whole[offset + match.length] ?? ''
+      const isWord = (ch: string) => WORD_CHAR_RE.test(ch) && !HAN_RE.test(ch)
+      return isWord(before) && isWord(after) ? ' ' : ''
+    })
+  }
+  if (options.collapseSpaces) result = result.replace(SPACE_RUN_RE, ' ')
+  if (options.trimTrailingWhitespace) result = result.replace(LINE_TRAILING_SPACE_RE, '').trimEnd()
+  return result
+}
+
+const STOPWORD_DELIMITER_RE = /([\s，。！？、；：,.!?;:]+)/u
+
+// 删除口头填充词（“嗯”“那个”“就是”）：只删除被空白、标点或首尾隔开的完整词，
+// 作为更长词语一部分时保留（“那个病灶”“就是说”不变）。删除时连同前面的分隔符一起去掉，
+// 位于开头时去掉后面的分隔符，避免留下多余的逗号；英文不区分大小写
+export function removeStopwords(text: string, stopwords: string[]): string {
+  const words = new Set(stopwords.map(word => word.trim().toLowerCase()).filter(Boolean))
+  if (words.size === 0 || !text) return text
+  const parts = text.split(STOPWORD_DELIMITER_RE)
+  const out: string[] = []
+  let removed = false
+  let dropNextDelimiter = false
+  for (let i = 0; i < parts.length; i++) {
+    const part = parts[i]
+    if (i % 2 === 1) {
+      if (dropNextDelimiter) {
+        dropNextDelimiter = false
+        continue
+      }
+      out.push(part)
+      continue
+    }
+    if (!part || !words.has(part.toLowerCase())) {
+      out.push(part)
+      continue
+    }
+    removed = true
+    if (out.length > 0) out.pop()
+    if (out.join('') === '') dropNextDelimiter = true
+  }
+  return removed ? out.join('') : text
+}
+
+const PUNCTUATION_RE = /[，。！？、；：,.!?;:]/
+const HAN_GAP_RE = /(\p{Script=Han})\s+(?=\p{Script=Han})/gu
+const SENTENCE_END_RE = /[\p{Script=Han}\p{L}\p{N}]$/u
+
+// 最简标点：仅在文本完全没有标点时处理，汉字之间的停顿（空格）改为逗号，句末补句号；
+// 英文只在句末补半角句号（单词间的空格不是停顿）。已有标点（服务端或本地模型已处理）时原样返回，避免重复加标点
+export function addMinimalPunctuation(text: string, locale: TextLocale = 'zh'): string {
+  const trimmed = text.trim()
+  if (!trimmed || PUNCTUATION_RE.test(trimmed)) return text
+  if (locale === 'en') return SENTENCE_END_RE.test(trimmed) ? `${trimmed}.` : trimmed
+  const withCommas = trimmed.replace(HAN_GAP_RE, '$1，')
+  return SENTENCE_END_RE.test(withCommas) ? `${withCommas}。` : withCommas
+}
+
+export const DEDUPE_DEFAULT_MIN_UNIT_CHARS = 2
+const DEDUPE_MAX_UNIT_CHARS = 8
+const CJK_CHAR_RE = /^[\u3400-\u9fff]$/u
+
+// 合并口吃式的紧邻重复：“病人病人”→“病人”、“这个这个这个”→“这个”。
+// 只处理全部由汉字组成、长度不小于 minUnitChars 的重复单元，重复之间允许夹空白；
+// 单字叠词（“谢谢”“看看”）与数字、字母不受影响，从最短单元开始尝试，避免把多次重复只合并一半
+export function collapseRepeatedPhrases(text: string, minUnitChars = DEDUPE_DEFAULT_MIN_UNIT_CHARS): string {
+  const chars = Array.from(text)
+  const minUnit = Math.max(1, Math.floor(minUnitChars))
+  const out: string[] = []
+  let i = 0
+  outer: while (i < chars.length) {
+    for (let len = minUnit; len <= DEDUPE_MAX_UNIT_CHARS && i + len * 2 <= chars.length; len++) {
+      const unit = chars.slice(i, i + len)
+      if (!unit.every(c => CJK_CHAR_RE.test(c))) break
+      let end = i + len
+      let next = skipSpaces(chars, end)
+      while (next + len <= chars.length && unit.every((c, k) => chars[next + k] === c)) {
+        end = next + len
+        next = skipSpaces(chars, end)
+      }
+      if (end > i + len) {
+        out.push(...unit)
+        i = end
+        continue outer
+      }
+    }
+    out.push(chars[i])
+    i++
+  }
+  return out.join('')
+}
+
+function skipSpaces(chars: string[], index: number): number {
+  let i = index
+  while (i < chars.length && /\s/.test(chars[i])) i++
+  return i
+}
+
+// 日志里识别文本的最大字数，避免长段口述刷屏
+export const LOG_TEXT_MAX_CHARS = 200
+
 export interface SizeExpressionRuleOptions {
   multiplicationWords: string[]
   rangeWords: string[]
@@ -71,6 +319,7 @@ const CHINESE_UNIT_MAP: Record<string, number> = {
   千: 1000,
 }
 
+// 文本规则对中英文都生效；英文术语（药名、基因名等，如 B12x2、IL-6）整体跳过，不被数字与连接词转换改写
 export function applyTextRules(text: string, config?: TextRulesConfig | null): string {
   const raw = typeof text === 'string' ? text : ''
   if (!raw.trim() || !config?.enabled || !Array.isArray(config.rules)) {
@@ -97,13 +346,39 @@ function applySizeExpressionRule(text: string, options?: SizeExpressionRuleOptio
     options?.rangeWords,
     ['到', '至', '-', '~', '～', '—', '－'],
   )
+  // 连接词与单位本身（x、cm 等）不算英文术语，仍参与转换
+  const ruleWords = new Set([...multiplicationWords, ...rangeWords, ...LATIN_UNIT_WORDS, outputUnit])
+
+  return transformOutsideLatinTerms(text, ruleWords, (segment) => {
+    let output = segment
+    output = normalizePlainMultiplicationExpressions(output, multiplicationWords)
+    output = normalizeMeasurementTokens(output, outputUnit)
+    output = normalizeRangeExpressions(output, outputUnit, rangeWords)
+    output = normalizeMultiplicationExpressions(output, outputUnit, multiplicationWords)
+    return output
+  })
+}
 
-  let output = text
-  output = normalizePlainMultiplicationExpressions(output, multiplicationWords)
-  output = normalizeMeasurementTokens(output, outputUnit)
-  output = normalizeRangeExpressions(output, outputUnit, rangeWords)
-  output = normalizeMultiplicationExpressions(output, outputUnit, multiplicationWords)
-  return output
+const LATIN_UNIT_WORDS = ['cm', 'CM']
+// 以字母开头、前面不紧挨字母数字的英文词（可含数字与连字符），如 aspirin、B12x2、IL-6；2x3 里的 x 不算
+const LATIN_TERM_RE = /(?<![A-Za-z0-9])[A-Za-z][A-Za-z0-9]*(?:[-.][A-Za-z0-9]+)*/g
+
+// 只对英文术语之外的片段做转换，术语原样保留，也不会和两侧数字拼成表达式
+function transformOutsideLatinTerms(
+  text: string,
+  ruleWords: Set<string>,
+  transform: (segment: string) => string,
+): string {
+  let output = ''
+  let last = 0
+  for (const match of text.matchAll(LATIN_TERM_RE)) {
+    const term = match[0]
+    if (ruleWords.has(term)) continue
+    const start = match.index ?? 0
+    output += transform(text.slice(last, start)) + term
+    last = start + term.length
+  }
+  return output + transform(text.slice(last))
 }
 
 function normalizeMeasurementTokens(text: string, outputUnit: string): string {
@@ -0,0 +1,187 @@
+import WebSocket = require('ws')
+import { parseAsrResponse, validateServerUrl, type RecognizeOptions } from './asr'
+import { logger } from './logger'
+
+// WebSocket 识别通道：保持长连接，逐段发送音频，省去每句话一次 multipart 请求的开销。
+// 协议：{type:'start', asrConfigId, requestId, punctuation} → 若干二进制音频帧 → {type:'end'}，服务端回复与 HTTP 相同的 JSON。
+
+const WS_PATH = '/api/tasks/asr-recognize/ws'
+const FRAME_BYTES = 32 * 1024
+const DEFAULT_TIMEOUT_MS = 15000
+
+// 只用到 WebSocket 的这几个成员，便于测试替换
+export interface AsrWebSocket {
+  readonly readyState: number
+  binaryType: string
+  send(data: string | ArrayBufferLike | ArrayBufferView): void
+  close(): void
+  addEventListener(type: 'open' | 'message' | 'close' | 'error', listener: (event: any) => void): void
+}
+
+export type AsrWebSocketFactory = (url: string) => AsrWebSocket
+
+const WS_OPEN = 1
+
+// 主进程（Node 20）没有全局 WebSocket，使用 ws 包；其 addEventListener 与浏览器 WebSocket 一致，文本帧的 data 为字符串
+export function createDefaultAsrWebSocket(url: string): AsrWebSocket {
+  return new WebSocket(url) as unknown as AsrWebSocket
+}
+
+// http(s)://host → ws(s)://host/api/tasks/asr-recognize/ws
+export function toAsrWebSocketUrl(serverUrl: string, options: { autoPrependScheme?: boolean } = {}): string {
+  const validated = validateServerUrl(serverUrl, options)
+  if (!validated.ok) {
+    throw new Error(validated.error)
+  }
+  return `${validated.url.replace(/^http/i, 'ws')}${WS_PATH}`
+}
+
+interface PendingRequest {
+  resolve: (text: string) => void
+  reject: (error: Error) => void
+  timer: ReturnType<typeof setTimeout>
+  onRawResponse?: (raw: string) => void
+}
+
+export class AsrWsClient {
+  private socket: AsrWebSocket | null = null
+  private socketUrl = ''
+  private connecting: Promise<AsrWebSocket> | null = null
+  private pending: PendingRequest | null = null
+  // 同一连接上串行识别，前一句返回后再发下一句
+  private queue: Promise<unknown> = Promise.resolve()
+
+  constructor(
+    private readonly createSocket: AsrWebSocketFactory = createDefaultAsrWebSocket,
+    private readonly timeoutMs = DEFAULT_TIMEOUT_MS,
+  ) { }
+
+  recognize(
+    serverUrl: string,
+    asrConfigId: string,
+    wavBuffer: Buffer,
+    options: RecognizeOptions = {},
+  ): Promise<string> {
+    const url = toAsrWebSocketUrl(serverUrl, options)
+    const task = this.queue.then(() => this.recognizeOnce(url, asrConfigId, wavBuffer, options))
+    this.queue = task.catch(() => { })
+    return task
+  }
+
+  close(): void {
+    const socket = this.socket
+    this.socket = null
+    this.connecting = null
+    this.failPending(new Error('WebSocket 连接已关闭'))
+    if (socket) {
+      try {
+        socket.close()
+      } catch { /* ignore */ }
+    }
+  }
+
+  private async recognizeOnce(
+    url: string,
+    asrConfigId: string,
+    wavBuffer: Buffer,
+    options: RecognizeOptions,
+  ): Promise<string> {
+    const { onRawResponse, onStage } = options
+    onStage?.('uploading')
+    const socket = await this.connect(url)
+    return new Promise<string>((resolve, reject) => {
+      const timer = setTimeout(() => {
+        this.pending = null
+        reject(new Error(`WebSocket 识别超时（${this.timeoutMs}ms）`))
+        // 超时后连接状态未知，丢弃以免下一句收到本句的迟到结果
+        this.close()
+      }, this.timeoutMs)
+      this.pending = { resolve, reject, timer, onRawResponse }
+      try {
+        socket.send(JSON.stringify({
+          type: 'start',
+          asrConfigId: asrConfigId || undefined,
+          requestId: options.requestId || undefined,
+          punctuation: options.punctuation || undefined,
+          language: options.language || undefined,
+        }))
+        for (let offset = 0; offset < wavBuffer.length; offset += FRAME_BYTES) {
+          socket.send(wavBuffer.subarray(offset, offset + FRAME_BYTES))
+        }
+        socket.send(JSON.stringify({ type: 'end' }))
+        onStage?.('server-processing')
+      } catch (e) {
+        this.failPending(new Error(`WebSocket 发送失败: ${String(e)}`))
+      }
+    })
+  }
+
+  private connect(url: string): Promise<AsrWebSocket> {
+    if (this.socket && this.socketUrl === url && this.socket.readyState === WS_OPEN) {
+      return Promise.resolve(this.socket)
+    }
+    if (this.connecting && this.socketUrl === url) {
+      return this.connecting
+    }
+    if (this.socket) {
+      this.close()
+    }
+    this.socketUrl = url
+    const connecting = new Promise<AsrWebSocket>((resolve, reject) => {
+      const socket = this.createSocket(url)
+      socket.binaryType = 'arraybuffer'
+      let opened = false
+      socket.addEventListener('open', () => {
+        opened = true
+        if (this.connecting !== connecting) return
+        this.connecting = null
+        this.socket = socket
+        logger.info(`[AsrWs] 已连接 ${url}`)
+        resolve(socket)
+      })
+      socket.addEventListener('message', (event) => this.handleMessage(socket, event.data))
+      socket.addEventListener('error', () => {
+        if (!opened) reject(new Error(`WebSocket 连接失败: ${url}`))
+      })
+      socket.addEventListener('close', () => {
+        if (!opened) {
+          reject(new Error(`WebSocket 连接失败: ${url}`))
+        }
+        if (this.socket === socket) {
+          logger.warn('[AsrWs] 连接已断开')
+          this.socket = null
+          this.failPending(new Error('WebSocket 连接已断开'))
+        }
+        if (this.connecting === connecting) {
+          this.connecting = null
+        }
+      })
+    })
+    this.connecting = connecting
+    return connecting
+  }
+
+  private handleMessage(socket: AsrWebSocket, data: unknown): void {
+    if (socket !== this.socket || !this.pending) return
+    const pending = this.pending
+    this.pending = null
+    clearTimeout(pending.timer)
+    try {
+      const raw = typeof data === 'string' ? data : Buffer.from(data as ArrayBuffer).toString('utf8')
+      pending.onRawResponse?.(raw)
+      pending.resolve(parseAsrResponse(raw))
+    } catch (e) {
+      pending.reject(e instanceof Error ? e : new Error(String(e)))
+    }
+  }
+
+  private failPending(error: Error): void {
+    const pending = this.pending
+    if (!pending) return
+    this.pending = null
+    clearTimeout(pending.timer)
+    pending.reject(error)
+  }
+}
+
+export const asrWsClient = new AsrWsClient()
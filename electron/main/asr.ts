@@ -1,31 +1,203 @@
+import { encodeWavToFlac } from './flac'
+
 // ASR API 响应格式
-interface AsrResponse {
+export interface AsrResponse {
   success?: boolean
   message?: string
   data?: { text: string }
 }
 
+// 上传音频编码：flac 为无损压缩，体积约为 WAV 的一半，需服务端支持
+export type AudioUploadEncoding = 'wav' | 'flac'
+
+// 服务端重定向的处理：follow 按原方法与请求体重新提交到新地址（最多 MAX_ASR_REDIRECTS 次），
+// error 不跟随并报错。fetch 自动跟随 301/302/303 时会改成不带请求体的 GET，部分代理链路下导致识别结果为空
+export type AsrRedirectPolicy = 'follow' | 'error'
+
+// WAV 上传的默认 MIME 类型；部分服务端只接受 audio/x-wav 或 audio/wave，可通过 server.audioMime 配置
+export const DEFAULT_WAV_MIME_TYPE = 'audio/wav'
+
+// type/subtype 形式且只含 token 字符；不合法的值放进 Blob 会被静默置空，服务端收到无类型的文件
+export function isValidMimeType(value: string): boolean {
+  return /^[a-z0-9][a-z0-9!#$&^_.+-]*\/[a-z0-9][a-z0-9!#$&^_.+-]*$/i.test(value)
+}
+
+export const MAX_ASR_REDIRECTS = 5
+const REDIRECT_STATUSES = new Set([301, 302, 303, 307, 308])
+
+export interface RecognizeOptions {
+  autoPrependScheme?: boolean
+  audioEncoding?: AudioUploadEncoding
+  // WAV 上传的 MIME 类型，默认 audio/wav；FLAC 始终为 audio/flac
+  audioMimeType?: string
+  // 本句识别的追踪 ID，便于客户端与服务端日志对照
+  requestId?: string
+  // 追踪 ID 放入的请求头名；为空时改为表单字段 requestId 上传
+  requestIdHeader?: string
+  // 音频与识别配置 ID 的表单字段名（不同厂商的接口命名不同），默认 file / asrConfigId
+  fileFieldName?: string
+  configIdFieldName?: string
+  // 请求服务端自动加标点（表单字段 punctuation=true），未设置时不发送该字段
+  punctuation?: boolean
+  // 识别语言提示（表单字段 language，如 zh / en / auto），未设置时不发送该字段
+  language?: string
+  // 收到响应体时回调原文（解析前），用于排查服务端配置问题
+  onRawResponse?: (raw: string) => void
+  // 进入各阶段时回调（见 recognition-stage），HTTP 只区分 encoding 与 uploading
+  onStage?: (stage: 'encoding' | 'uploading' | 'server-processing') => void
+  redirect?: AsrRedirectPolicy
+  // 每次收到重定向响应时回调（跟随或报错前），用于记录代理配置问题
+  onRedirect?: (info: { status: number; from: string; to: string }) => void
+  // 整个请求（含重定向与读取响应体）的超时，默认 DEFAULT_ASR_TIMEOUT_MS
+  timeoutMs?: number
+}
+
+export const DEFAULT_ASR_TIMEOUT_MS = 30000
+
+export type ServerUrlValidation =
+  | { ok: true; url: string }
+  | { ok: false; error: string }
+
+// 校验服务地址：必须为 http/https 且包含主机名。
+// autoPrependScheme 为 true 时，缺少协议的地址（如 192.168.1.100:3000）自动补 http://
+export function validateServerUrl(raw: string, options: { autoPrependScheme?: boolean } = {}): ServerUrlValidation {
+  let value = String(raw ?? '').trim()
+  if (!value) {
+    return { ok: false, error: '服务地址为空，请填写如 http://192.168.1.100:3000 的地址' }
+  }
+  const hasScheme = /^[a-z][a-z0-9+.-]*:\/\//i.test(value)
+  if (!hasScheme) {
+    if (!options.autoPrependScheme) {
+      return { ok: false, error: `服务地址缺少协议头 http:// 或 https://：${value}` }
+    }
+    value = `http://${value}`
+  }
+  let parsed: URL
+  try {
+    parsed = new URL(value)
+  } catch {
+    return { ok: false, error: `服务地址格式无效：${raw}` }
+  }
+  if (parsed.protocol !== 'http:' && parsed.protocol !== 'https:') {
+    return { ok: false, error: `服务地址仅支持 http/https 协议：${raw}` }
+  }
+  if (!parsed.hostname) {
+    return { ok: false, error: `服务地址缺少主机名：${raw}` }
+  }
+  return { ok: true, url: value.replace(/\/+$/, '') }
+}
+
 // 调用 Next.js ASR API 识别语音（使用 Node.js 内置 fetch）
 export async function recognize(
   serverUrl: string,
   asrConfigId: string,
   wavBuffer: Buffer,
+  options: RecognizeOptions = {},
 ): Promise<string> {
-  const url = `${serverUrl.replace(/\/$/, '')}/api/tasks/asr-recognize/sync`
+  const validated = validateServerUrl(serverUrl, options)
+  if (!validated.ok) {
+    throw new Error(validated.error)
+  }
+  const url = `${validated.url}/api/tasks/asr-recognize/sync`
 
   // 使用 FormData + Blob 构建 multipart 请求
+  options.onStage?.('encoding')
+  const upload = buildAudioUpload(wavBuffer, options.audioEncoding ?? 'wav', options.audioMimeType)
   const form = new FormData()
-  form.append('file', new Blob([wavBuffer], { type: 'audio/wav' }), 'recording.wav')
+  try {
+    form.append(options.fileFieldName || 'file', new Blob([upload.body], { type: upload.mimeType }), upload.fileName)
+  } catch (e) {
+    throw new Error(`构建上传音频失败（${upload.fileName}，${upload.mimeType}，${upload.body.length} 字节）: ${e instanceof Error ? e.message : String(e)}`)
+  }
   if (asrConfigId) {
-    form.append('asrConfigId', asrConfigId)
+    form.append(options.configIdFieldName || 'asrConfigId', asrConfigId)
   }
+  if (options.punctuation) {
+    form.append('punctuation', 'true')
+  }
+  if (options.language) {
+    form.append('language', options.language)
+  }
+  const headers: Record<string, string> = {}
+  if (options.requestId) {
+    const headerName = options.requestIdHeader?.trim()
+    if (headerName) headers[headerName] = options.requestId
+    else form.append('requestId', options.requestId)
+  }
+
+  options.onStage?.('uploading')
+  const timeoutMs = options.timeoutMs ?? DEFAULT_ASR_TIMEOUT_MS
+  const signal = AbortSignal.timeout(timeoutMs)
+  try {
+    return await sendRecognizeRequest(url, { method: 'POST', body: form, headers, signal }, options)
+  } catch (e) {
+    if (signal.aborted) throw new Error(`ASR 请求超时（${timeoutMs}ms 内未返回）`)
+    throw e
+  }
+}
 
-  const resp = await fetch(url, { method: 'POST', body: form })
+async function sendRecognizeRequest(url: string, init: RequestInit, options: RecognizeOptions): Promise<string> {
+  const resp = await postFollowingRedirects(url, init, options)
+  if (options.onRawResponse) {
+    // 需要原文时先按文本读取再解析，错误状态的响应体同样回调，便于看到服务端报错详情
+    const raw = await resp.text()
+    options.onRawResponse(raw)
+    if (!resp.ok) {
+      throw new Error(`ASR 返回错误状态: ${resp.status}`)
+    }
+    return parseAsrResponse(raw)
+  }
   if (!resp.ok) {
     throw new Error(`ASR 返回错误状态: ${resp.status}`)
   }
 
-  const body = (await resp.json()) as AsrResponse
+  return extractAsrText((await resp.json()) as AsrResponse)
+}
+
+// 手动处理重定向：保持 POST 与 multipart 请求体不变，避免 fetch 自动跟随时丢失请求体
+async function postFollowingRedirects(url: string, init: RequestInit, options: RecognizeOptions): Promise<Response> {
+  let current = url
+  for (let hop = 0; ; hop++) {
+    const resp = await fetch(current, { ...init, redirect: 'manual' })
+    const location = REDIRECT_STATUSES.has(resp.status) ? resp.headers?.get('location') : null
+    if (!location) return resp
+    const next = new URL(location, current).toString()
+    options.onRedirect?.({ status: resp.status, from: current, to: next })
+    if ((options.redirect ?? 'follow') === 'error') {
+      throw new Error(`ASR 服务地址被重定向 (${resp.status} → ${next})，请将服务地址直接改为重定向后的地址`)
+    }
+    if (hop >= MAX_ASR_REDIRECTS) {
+      throw new Error(`ASR 服务地址重定向次数过多（超过 ${MAX_ASR_REDIRECTS} 次），最后指向 ${next}`)
+    }
+    current = next
+  }
+}
+
+// wavMimeType 不合法时回退 audio/wav，不因配置错误导致识别失败
+export function buildAudioUpload(
+  wavBuffer: Buffer,
+  encoding: AudioUploadEncoding,
+  wavMimeType: string = DEFAULT_WAV_MIME_TYPE,
+): { body: Buffer; mimeType: string; fileName: string } {
+  if (encoding === 'flac') {
+    return { body: encodeWavToFlac(wavBuffer), mimeType: 'audio/flac', fileName: 'recording.flac' }
+  }
+  const mimeType = isValidMimeType(wavMimeType.trim()) ? wavMimeType.trim() : DEFAULT_WAV_MIME_TYPE
+  return { body: wavBuffer, mimeType, fileName: 'recording.wav' }
+}
+
+export function parseAsrResponse(raw: string): string {
+  let body: AsrResponse
+  try {
+    body = JSON.parse(raw) as AsrResponse
+  } catch {
+    throw new Error('ASR 响应不是合法的 JSON')
+  }
+  return extractAsrText(body)
+}
+
+// 解析 ASR 响应体（HTTP 与 WebSocket 通道格式一致）
+export function extractAsrText(body: AsrResponse): string {
   if (body.success === false) {
     throw new Error(`ASR 错误: ${body.message ?? ''}`)
   }
@@ -0,0 +1,39 @@
+// 音频后端选择：录音走 Chromium 的音频栈，无法像原生程序那样任意切换 host，
+// 这里只暴露 Chromium 启动开关能切换的后端。开关必须在 app ready 之前追加，修改后需重启生效。
+
+export const DEFAULT_AUDIO_HOST = 'default'
+
+export interface AudioHostInfo {
+  id: string
+  label: string
+}
+
+interface AudioHostDefinition extends AudioHostInfo {
+  platforms: NodeJS.Platform[]
+  switches: Array<[string, string?]>
+}
+
+const AUDIO_HOSTS: AudioHostDefinition[] = [
+  { id: DEFAULT_AUDIO_HOST, label: '系统默认', platforms: ['win32', 'darwin', 'linux'], switches: [] },
+  // WASAPI 独占模式：绕开系统混音器，部分声卡驱动与其他程序共享设备冲突时可用
+  { id: 'wasapi-exclusive', label: 'WASAPI 独占模式', platforms: ['win32'], switches: [['enable-exclusive-audio']] },
+]
+
+export function listAudioHosts(platform: NodeJS.Platform = process.platform): AudioHostInfo[] {
+  return AUDIO_HOSTS
+    .filter(h => h.platforms.includes(platform))
+    .map(({ id, label }) => ({ id, label }))
+}
+
+export interface ResolvedAudioHost {
+  id: string
+  switches: Array<[string, string?]>
+  fellBack: boolean   // 配置的后端在当前平台不可用，已回退到默认
+}
+
+export function resolveAudioHost(configured: unknown, platform: NodeJS.Platform = process.platform): ResolvedAudioHost {
+  const requested = typeof configured === 'string' && configured.trim() ? configured.trim() : DEFAULT_AUDIO_HOST
+  const host = AUDIO_HOSTS.find(h => h.id === requested && h.platforms.includes(platform))
+  if (host) return { id: host.id, switches: host.switches, fellBack: false }
+  return { id: DEFAULT_AUDIO_HOST, switches: [], fellBack: true }
+}
@@ -0,0 +1,57 @@
+// 麦克风采集的生命周期（诊断用）：采集在渲染进程，设备被拔出、被系统收回或 AudioWorklet 崩溃时
+// 音轨只是静默结束，界面看起来正常却录不到声音。渲染进程把这些节点上报给主进程记录日志，
+// 异常节点再以 audio-thread-error 事件广播，提示用户重新打开麦克风
+
+export type AudioLifecycleStage =
+  | 'stream-opened'      // 成功打开麦克风音轨
+  | 'stream-open-failed' // getUserMedia 失败（无设备、无权限、被占用）
+  | 'stream-ended'       // 音轨意外结束（设备拔出、被系统收回），之后不再有音频
+  | 'capture-started'    // 开始录音，采集节点已连接
+  | 'capture-error'      // 采集节点（AudioWorklet）处理出错，本次录音不再有音频
+
+export type AudioLifecycleSource = 'capture' | 'vad'
+
+export interface AudioLifecycleEvent {
+  stage: AudioLifecycleStage
+  source: AudioLifecycleSource
+  deviceName: string
+  message: string
+  at: string
+}
+
+const STAGES: readonly AudioLifecycleStage[] = ['stream-opened', 'stream-open-failed', 'stream-ended', 'capture-started', 'capture-error']
+const MESSAGE_MAX_CHARS = 500
+
+export function isAudioLifecycleError(stage: AudioLifecycleStage): boolean {
+  return stage === 'stream-open-failed' || stage === 'stream-ended' || stage === 'capture-error'
+}
+
+// 渲染进程上报的数据不可信：未知阶段返回 null，其余字段截断或补默认值
+export function normalizeAudioLifecycleEvent(raw: unknown): AudioLifecycleEvent | null {
+  const data = raw && typeof raw === 'object' ? raw as Record<string, unknown> : {}
+  const stage = STAGES.find(s => s === data.stage)
+  if (!stage) return null
+  return {
+    stage,
+    source: data.source === 'vad' ? 'vad' : 'capture',
+    deviceName: typeof data.deviceName === 'string' ? data.deviceName.slice(0, 200) : '',
+    message: typeof data.message === 'string' ? data.message.slice(0, MESSAGE_MAX_CHARS) : '',
+    at: typeof data.at === 'string' ? data.at : new Date().toISOString(),
+  }
+}
+
+const STAGE_LABELS: Record<AudioLifecycleStage, string> = {
+  'stream-opened': '麦克风已打开',
+  'stream-open-failed': '麦克风打开失败',
+  'stream-ended': '麦克风音轨意外结束',
+  'capture-started': '开始采集',
+  'capture-error': '采集节点出错',
+}
+
+// 日志与界面提示共用的描述
+export function describeAudioLifecycleEvent(event: AudioLifecycleEvent): string {
+  const source = event.source === 'vad' ? 'VAD 监听' : '录音'
+  const device = event.deviceName ? ` device=${JSON.stringify(event.deviceName)}` : ''
+  const detail = event.message ? `: ${event.message}` : ''
+  return `${source}${STAGE_LABELS[event.stage]}${device}${detail}`
+}
@@ -0,0 +1,45 @@
+import { desktopCapturer, type Session, type WebFrameMain } from 'electron'
+
+// 录音来源：input 为麦克风；loopback 为系统声音（WASAPI 环回），用于转写其他程序播放的参考音频做识别验收。
+// 环回经渲染进程 getDisplayMedia 取得，由主进程授予一个屏幕源（Chromium 要求带视频，渲染进程拿到后立即停掉）
+// 与 loopback 音频。仅 Windows 支持，其他平台回退为麦克风
+
+export type AudioSource = 'input' | 'loopback'
+
+export function isLoopbackSupported(platform: NodeJS.Platform = process.platform): boolean {
+  return platform === 'win32'
+}
+
+export function normalizeAudioSource(raw: unknown, platform: NodeJS.Platform = process.platform): AudioSource {
+  return raw === 'loopback' && isLoopbackSupported(platform) ? 'loopback' : 'input'
+}
+
+// 只在录音来源为 loopback 时注册，且只响应浮窗（录音所在页面）发起的请求；切回麦克风时移除。
+// 不支持的平台从不注册，getDisplayMedia 直接失败
+export function installLoopbackCaptureHandler(
+  ses: Session,
+  source: AudioSource,
+  isCaptureFrame: (frame: WebFrameMain | null) => boolean,
+  platform: NodeJS.Platform = process.platform,
+): boolean {
+  if (!isLoopbackSupported(platform)) return false
+  if (source !== 'loopback') {
+    ses.setDisplayMediaRequestHandler(null)
+    return false
+  }
+  ses.setDisplayMediaRequestHandler((request, callback) => {
+    if (!isCaptureFrame(request.frame) || !request.audioRequested) {
+      callback({})
+      return
+    }
+    // 只有请求了视频时才授予屏幕源（Chromium 要求）
+    if (!request.videoRequested) {
+      callback({ audio: 'loopback' })
+      return
+    }
+    desktopCapturer.getSources({ types: ['screen'] })
+      .then((sources) => callback(sources[0] ? { video: sources[0], audio: 'loopback' } : {}))
+      .catch(() => callback({}))
+  })
+  return true
+}
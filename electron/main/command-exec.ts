@@ -0,0 +1,15 @@
+import { spawn } from 'child_process'
+import { logger } from './logger'
+import type { ExecCommand } from './voice-commands'
+
+// 启动 RUN: 指令配置的外部程序：脱离父进程运行、不等待退出、不接管输入输出，
+// 识别流程不会被外部程序阻塞；启动失败（路径不存在等）只记录日志
+export function launchExecCommand(exec: ExecCommand, logPrefix: string): number | undefined {
+  const child = spawn(exec.command, exec.args, { detached: true, stdio: 'ignore', windowsHide: false })
+  child.once('error', (e) => {
+    logger.error(`${logPrefix} 运行程序失败: ${exec.command} ${String(e)}`)
+  })
+  child.unref()
+  logger.info(`${logPrefix} 已启动程序 pid=${child.pid ?? 'unknown'}: ${JSON.stringify([exec.command, ...exec.args])}`)
+  return child.pid
+}
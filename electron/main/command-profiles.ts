@@ -0,0 +1,56 @@
+// 指令方案：在基础指令表（voiceCommands）之上叠加的一组口令，如病理、细胞学等 LIS 模块各配一套，
+// 同名口令以方案为准。当前方案为空字符串时只使用基础指令表
+
+export interface VoiceCommandProfile {
+  name: string
+  commands: Record<string, string>
+}
+
+export interface VoiceCommandProfileEntry {
+  name: string
+  active: boolean
+  commandCount: number
+}
+
+export const BASE_PROFILE_LABEL = '基础指令'
+
+export function profileLabel(name: string): string {
+  return name || BASE_PROFILE_LABEL
+}
+
+export function listCommandProfiles(profiles: VoiceCommandProfile[], active: string): VoiceCommandProfileEntry[] {
+  return profiles.map(profile => ({
+    name: profile.name,
+    active: profile.name === active,
+    commandCount: Object.keys(profile.commands).length,
+  }))
+}
+
+// 校验要切换到的方案名：空字符串表示回到基础指令表，不存在的方案抛错
+export function resolveCommandProfileName(profiles: VoiceCommandProfile[], name: unknown): string {
+  if (name === null || name === undefined) return ''
+  if (typeof name !== 'string') throw new Error('指令方案名称必须是字符串')
+  const trimmed = name.trim()
+  if (!trimmed) return ''
+  if (!profiles.some(profile => profile.name === trimmed)) {
+    throw new Error(`指令方案不存在: "${trimmed}"`)
+  }
+  return trimmed
+}
+
+// 循环切换：基础指令 → 第一个方案 → … → 最后一个方案 → 基础指令；当前方案已被删除时从头开始
+export function nextCommandProfile(profiles: VoiceCommandProfile[], active: string): string {
+  if (profiles.length === 0) return ''
+  const index = profiles.findIndex(profile => profile.name === active)
+  if (index < 0) return active ? '' : profiles[0].name
+  return index + 1 < profiles.length ? profiles[index + 1].name : ''
+}
+
+export function applyCommandProfile(
+  base: Record<string, string>,
+  profiles: VoiceCommandProfile[],
+  active: string,
+): Record<string, string> {
+  const profile = active ? profiles.find(p => p.name === active) : undefined
+  return profile ? { ...base, ...profile.commands } : base
+}
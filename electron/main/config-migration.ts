@@ -0,0 +1,32 @@
+// 配置文件格式迁移：按 configVersion 逐级升级旧配置，缺少该字段视为 0。
+// 启动时对磁盘上的配置执行一次；导入旧版本导出的配置文本时同样执行。
+
+export const CONFIG_VERSION = 1
+
+interface MigratableConfig {
+  configVersion?: unknown
+  input?: unknown
+}
+
+// 0 → 1：旧版本 input.useClipboard 默认 false 且被写入了磁盘，但输入时并不读取、始终剪贴板粘贴；
+// 现在 false 表示键盘逐字键入，沿用旧值会让升级用户在 LIS 输入框中改为键入，因此改回 true
+function migrateV0(config: MigratableConfig): void {
+  const input = config.input
+  if (input && typeof input === 'object' && (input as Record<string, unknown>).useClipboard === false) {
+    (input as Record<string, unknown>).useClipboard = true
+  }
+}
+
+const MIGRATIONS: Array<(config: MigratableConfig) => void> = [migrateV0]
+
+// 原地升级并返回是否有修改；比当前版本新的配置不做处理
+export function migrateConfig(config: MigratableConfig): boolean {
+  const raw = config.configVersion
+  const from = typeof raw === 'number' && Number.isInteger(raw) && raw >= 0 ? raw : 0
+  if (from >= CONFIG_VERSION) return false
+  for (let version = from; version < CONFIG_VERSION; version++) {
+    MIGRATIONS[version](config)
+  }
+  config.configVersion = CONFIG_VERSION
+  return true
+}
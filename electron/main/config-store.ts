@@ -0,0 +1,9 @@
+import Store = require('electron-store')
+import type { AppConfig } from './config'
+
+// 配置文件的读写实现（electron-store）；单独成模块，测试中可替换为内存实现
+export type ConfigStore = Pick<Store<AppConfig>, 'store'>
+
+export function createConfigStore(name: string, defaults: AppConfig): ConfigStore {
+  return new Store<AppConfig>({ name, defaults })
+}
@@ -0,0 +1,135 @@
+import { validateServerUrl } from './asr'
+import { parseShortcutSequence } from './shortcut'
+import { resolveCommandText, resolveExecCommand, EXEC_COMMAND_PREFIX } from './voice-commands'
+import type { AppConfig } from './config'
+import { migrateConfig } from './config-migration'
+
+// 配置导出/导入：用于新工作站部署时复制一份已验证可用的配置，以及设置的备份与恢复。
+// 导出为完整配置的 JSON 文本；导入时先整体校验，任何一项不通过都不修改当前配置。
+
+export type ConfigImportResult =
+  | { ok: true; config: Partial<AppConfig> }
+  | { ok: false; error: string }
+
+export function exportConfigText(config: AppConfig): string {
+  return JSON.stringify(config, null, 2)
+}
+
+export function parseConfigImport(text: string, platform: NodeJS.Platform = process.platform): ConfigImportResult {
+  let data: unknown
+  try {
+    data = JSON.parse(String(text ?? '').trim())
+  } catch (e) {
+    return { ok: false, error: `配置内容不是合法的 JSON: ${e instanceof Error ? e.message : String(e)}` }
+  }
+  if (!data || typeof data !== 'object' || Array.isArray(data)) {
+    return { ok: false, error: '配置内容必须是 JSON 对象' }
+  }
+  const config = data as Partial<AppConfig>
+  // 旧版本导出的配置先按磁盘配置同样的规则升级
+  migrateConfig(config)
+
+  const server = config.server
+  if (server !== undefined && (!server || typeof server !== 'object')) {
+    return { ok: false, error: 'server 配置格式无效' }
+  }
+  const url = typeof server?.url === 'string' ? server.url : ''
+  if ((config.asr?.mode ?? 'api') === 'api' || url.trim()) {
+    const validated = validateServerUrl(url, { autoPrependScheme: server?.autoPrependScheme })
+    if (!validated.ok) return { ok: false, error: validated.error }
+    config.server = { ...server!, url: validated.url }
+  }
+
+  if (config.voiceCommands !== undefined) {
+    if (!config.voiceCommands || typeof config.voiceCommands !== 'object' || Array.isArray(config.voiceCommands)) {
+      return { ok: false, error: 'voiceCommands 配置格式无效' }
+    }
+    const error = validateCommandMap(config.voiceCommands, '', platform)
+    if (error) return { ok: false, error }
+  }
+
+  if (config.voiceCommandProfiles !== undefined) {
+    if (!Array.isArray(config.voiceCommandProfiles)) {
+      return { ok: false, error: 'voiceCommandProfiles 必须是数组' }
+    }
+    for (const profile of config.voiceCommandProfiles as unknown[]) {
+      const commands = (profile as { commands?: unknown } | null)?.commands
+      if (commands === undefined) continue
+      if (!commands || typeof commands !== 'object' || Array.isArray(commands)) {
+        return { ok: false, error: '指令方案的 commands 配置格式无效' }
+      }
+      const name = (profile as { name?: unknown }).name
+      const error = validateCommandMap(commands as Record<string, unknown>, `指令方案「${String(name ?? '')}」的`, platform)
+      if (error) return { ok: false, error }
+    }
+  }
+
+  // 运行程序的总开关只能在本机手动开启：导入的文件不能同时打开开关并带入 RUN: 指令
+  const commandOptions = config.voiceCommandOptions as Record<string, unknown> | undefined
+  if (commandOptions && typeof commandOptions === 'object' && 'allowExec' in commandOptions) {
+    const { allowExec: _allowExec, ...rest } = commandOptions
+    config.voiceCommandOptions = rest as unknown as AppConfig['voiceCommandOptions']
+  }
+
+  if (config.voiceCommandAliases !== undefined) {
+    const aliases = config.voiceCommandAliases as unknown
+    if (!aliases || typeof aliases !== 'object' || Array.isArray(aliases)) {
+      return { ok: false, error: 'voiceCommandAliases 配置格式无效' }
+    }
+    for (const [phrase, list] of Object.entries(aliases as Record<string, unknown>)) {
+      if (!Array.isArray(list) || list.some(alias => typeof alias !== 'string')) {
+        return { ok: false, error: `语音指令「${phrase}」的别名必须是字符串数组` }
+      }
+    }
+  }
+
+  const hotkey = config.hotkey as Record<string, unknown> | undefined
+  for (const name of ['record', 'repeatCommand', 'undoLastInsert', 'cycleProfile']) {
+    const value = hotkey?.[name]
+    if (value === undefined || value === '') continue
+    if (typeof value !== 'string') {
+      return { ok: false, error: `hotkey.${name} 必须是字符串` }
+    }
+    try {
+      if (parseShortcutSequence(value, platform).length !== 1) {
+        return { ok: false, error: `hotkey.${name} 只能是单个组合键: "${value}"` }
+      }
+    } catch (e) {
+      return { ok: false, error: `hotkey.${name} ${e instanceof Error ? e.message : String(e)}` }
+    }
+  }
+
+  const pasteShortcut = (config.input as Record<string, unknown> | undefined)?.pasteShortcut
+  if (pasteShortcut !== undefined && pasteShortcut !== '') {
+    if (typeof pasteShortcut !== 'string') {
+      return { ok: false, error: 'input.pasteShortcut 必须是字符串' }
+    }
+    try {
+      parseShortcutSequence(pasteShortcut, platform)
+    } catch (e) {
+      return { ok: false, error: `input.pasteShortcut ${e instanceof Error ? e.message : String(e)}` }
+    }
+  }
+
+  return { ok: true, config }
+}
+
+// 逐条校验指令：文本指令直接通过，RUN: 指令必须能解析出程序路径，其余按快捷键序列校验
+function validateCommandMap(commands: Record<string, unknown>, label: string, platform: NodeJS.Platform): string | null {
+  for (const [phrase, shortcut] of Object.entries(commands)) {
+    if (typeof shortcut !== 'string') {
+      return `${label}语音指令「${phrase}」的快捷键必须是字符串`
+    }
+    if (resolveCommandText(shortcut) !== null) continue
+    if (shortcut.trim().toUpperCase().startsWith(EXEC_COMMAND_PREFIX)) {
+      if (!resolveExecCommand(shortcut)) return `${label}语音指令「${phrase}」的运行程序指令缺少程序路径`
+      continue
+    }
+    try {
+      parseShortcutSequence(shortcut, platform)
+    } catch (e) {
+      return `${label}语音指令「${phrase}」${e instanceof Error ? e.message : String(e)}`
+    }
+  }
+  return null
+}
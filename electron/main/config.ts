@@ -1,6 +1,17 @@
-import Store = require('electron-store')
+import { app } from 'electron'
+import * as fs from 'fs'
 import * as path from 'path'
 import * as os from 'os'
+import { parseShortcutSequence } from './shortcut'
+import type { LogLevel } from './logger'
+import { DebouncedSaver } from './debounced-save'
+import { createConfigStore, type ConfigStore } from './config-store'
+import { migrateConfig, CONFIG_VERSION } from './config-migration'
+import { clampQuickRecordMs, QUICK_RECORD_DEFAULT_MS } from './quick-record'
+import { clampInputGain, INPUT_GAIN_DEFAULT } from '../../shared/input-gain'
+import { normalizeAudioSource, type AudioSource } from './audio-source'
+import { isValidMimeType } from './asr'
+import type { VoiceCommandProfile } from './command-profiles'
 
 // 热词场景
 export interface HotwordScene {
@@ -119,8 +130,13 @@ export interface LlmConfig {
 }
 
 export interface LoggingConfig {
-  enableDebug: boolean
+  level: LogLevel            // 日志级别，低于该级别的日志不记录；运行时可通过 set-log-level 调整
+  enableDebug: boolean       // 旧版开关，与 level === 'debug' 保持一致；未配置 level 时据此迁移
   showFloatBounds: boolean
+  logAsrResponses: boolean   // 记录远程 ASR 原始响应并发送 asr-raw-response 事件
+  toFile: boolean            // 同时写入 userData/logs 下的日志文件（无控制台的工作站排障用）
+  maxFileSizeMb: number      // 单个日志文件达到该大小后轮转
+  maxFiles: number           // 连同当前文件最多保留的日志文件数，更早的删除
 }
 
 export interface AudioInputConstraintsConfig {
@@ -133,9 +149,62 @@ export interface AudioInputConstraintsConfig {
 
 export interface AudioCaptureConfig {
   inputConstraints: AudioInputConstraintsConfig
+  channels: 'mono' | 'stereo'   // stereo：双声道原样写入 WAV（如降噪耳机的参考声道），默认单声道
   postRollMs: number
   tailSilenceMs: number
+  padMs: number                 // 首尾各补的最短静音，给服务端 VAD 留出起点，句尾与 tailSilenceMs 取较大值；默认 0 不补
   workletFlushTimeoutMs: number
+  inputGain: number             // 软件增益倍数（0.1~10），系统麦克风音量无法调整时使用，超出 ±1 的采样被截断
+  soundCues: boolean            // 录音开始/结束时播放提示音（VAD 模式下不播放，避免被麦克风录入）
+  host: string                  // 音频后端，见 audio-host.ts；修改后需重启生效
+  source: AudioSource           // input 麦克风；loopback 系统声音（仅 Windows，其他平台按 input 处理）
+  retainLastRecording: boolean  // 在内存中保留最近一段录音，供修改配置后重新识别；涉及隐私，默认关闭且不落盘
+}
+
+export interface PostprocessConfig {
+  dedupeRepeats: boolean       // 合并口吃式的紧邻重复词，如“病人病人”→“病人”
+  dedupeMinUnitChars: number   // 参与合并的最短重复单元字数，调大可保留“研究研究”等正常叠词
+  punctuation: 'server' | 'local' | 'off'  // server：请求服务端加标点，结果无标点时本地兜底；local：仅本地补最简标点
+  stopwords: string[]          // 删除的口头填充词（如“嗯”“那个”），只删除被标点/空白隔开的完整词
+  trimTrailingWhitespace: boolean  // 去掉末尾及每行行尾的空格、换行，避免输入后撑乱表单排版
+  collapseSpaces: boolean      // 连续多个空格合并为一个
+  stripNewlines: boolean       // 去掉全部换行，整段作为一行输入
+  minChars: number             // 少于该字数的文字结果丢弃不输入（按 Unicode 字符计），0 表示不限
+  maxChars: number             // 多于该字数的文字结果丢弃不输入，0 表示不限
+}
+
+export interface InputConfig {
+  useClipboard: boolean        // true 剪贴板粘贴（兼容富文本病历编辑器）；false 键盘逐字键入，不占用剪贴板
+  keyboardMethod: 'text' | 'unicode'  // 键盘逐字键入方式：unicode 逐字符按码位发送，避免中文输入法截获改写（平台限制见 input-sim）
+  mapCtrlToCmdOnMac: boolean   // macOS 上发送指令快捷键时 Ctrl↔Cmd 互换
+  shortcutHoldMs: number       // 快捷键全部按下后保持多久再抬起，0 表示立即抬起
+  shortcutKeyDelayMs: number   // 快捷键逐个按下的间隔，0 表示同时按下
+  shortcutStepDelayMs: number  // 多步快捷键序列（"ALT+F; S"）每步之间的间隔
+  releaseModifiersOnFailure: boolean  // 快捷键发送失败时补抬全部修饰键（已按下的键总会逆序抬起）
+  pasteShortcut: string        // 剪贴板注入使用的粘贴快捷键，默认 CTRL+V（macOS 上为 Cmd+V）
+  pasteMode: 'replace' | 'append'  // replace 覆盖当前选区粘贴；append 先按 appendNavigationKey 移到末尾再粘贴
+  appendNavigationKey: string  // 追加模式下粘贴前发送的导航键，默认 END
+  quickRecordMs: number        // 定时录音时长：托盘/浮窗菜单触发一次后固定录音该时长再识别，1000~120000
+  safeMode: boolean            // 安全模式：首次输入前需确认目标输入框，空闲超时后重新确认
+  safeModeIdleMs: number       // 安全模式的空闲超时
+  clipboardOnFailure: boolean  // 文字输入失败时把识别结果复制到剪贴板，便于手动粘贴
+  autoReattempt: boolean       // 点击录音误触（录音过短）时自动重新开始录音，每次触发最多重试 2 次
+  targetWindowTitle: string    // 非空时输入前先激活标题包含该文字的窗口（仅 Windows），找不到时回退当前焦点
+  continuousHold: boolean      // 热键录音时按静音（沿用 VAD 的阈值与静音时长）切段，每段说完即识别输入，松开后识别剩余部分
+  specialChars: Record<string, 'unicode' | 'clipboard'>  // 键盘逐字键入时改走码位发送或剪贴板粘贴的单个字符，如 { "×": "unicode" }
+}
+
+export interface VoiceCommandOptionsConfig {
+  enabled: boolean                            // false 为纯听写：不匹配任何指令，识别结果全部按文字输入
+  cooldownMs: number                          // 同一指令两次执行的最小间隔
+  cooldownOverrides: Record<string, number>   // 按口令单独覆盖冷却时间
+  enterAfterPhrases: string[]                 // 输入这些短语后自动按回车提交
+  candidateThreshold: number                  // 未精确命中时列为候选指令的最低相似度（0-1）
+  autoPickTopCandidate: boolean               // 候选唯一且明显领先时直接执行该指令
+  repeatPhrase: string                        // 内置“重复上一条指令”口令，留空关闭
+  postCommandLockoutMs: number                // 执行任意指令后的锁定期，期间不再执行指令，0 表示不锁定
+  postCommandLockoutAction: 'text' | 'drop'   // 锁定期内的识别结果：text 按普通文字输入，drop 直接丢弃
+  allowExec: boolean                          // 总开关：允许 RUN: 指令启动外部程序，默认关闭
 }
 
 export interface OnboardingConfig {
@@ -146,34 +215,81 @@ export interface OnboardingConfig {
 
 // 应用配置类型
 export interface AppConfig {
+  configVersion?: number  // 配置格式版本，启动时按 config-migration 升级旧配置；不放入默认值，否则旧文件会被默认值补成最新版本
   server: {
     url: string
     asrConfigId: string
+    autoPrependScheme: boolean   // 服务地址缺少 http:// 时自动补全
+    transport: 'http' | 'ws'     // VAD 模式识别通道：ws 为长连接，断开时回退 http
+    audioEncoding: 'wav' | 'flac' // HTTP 上传的音频编码，flac 需服务端支持；WebSocket 通道始终发送 WAV
+    audioMime: string             // WAV 上传的 MIME 类型，部分服务端要求 audio/x-wav 或 audio/wave；不合法时回退 audio/wav
+    requestIdHeader: string       // 每句识别的追踪 ID 所用请求头，留空则以表单字段 requestId 上传
+    healthPath: string            // 连通性巡检请求的路径（如 /health），留空则请求服务地址本身
+    healthCheckIntervalSec: number // 连通性巡检间隔，0 表示不巡检
+    redirect: 'follow' | 'error'  // 服务端重定向：follow 保持 POST 与音频重新提交，error 直接报错提示改地址
+    language: 'zh' | 'en' | 'auto' // 识别语言提示（表单字段 language）；en 时后处理不做中文数字转换、标点用半角，auto 按文本是否含汉字判断
+    fileFieldName: string         // HTTP 识别请求中音频文件的表单字段名
+    configIdFieldName: string     // HTTP 识别请求中识别配置 ID 的表单字段名
+    minRequestIntervalMs: number  // 远程识别请求的最小间隔，过密的 VAD 语音段丢弃并记警告，0 表示不限制
+    rateLimitPushToTalk: boolean  // 按键录音是否同样受最小间隔限制，默认只限制 VAD
+    requestTimeoutMs: number      // HTTP 识别请求超时（含重定向与读取响应），超时即失败，避免一句卡住阻塞后续识别
   }
   hotkey: {
     record: string
+    repeatCommand: string   // 重复上一条语音指令的全局快捷键，留空不注册
+    undoLastInsert: string  // 撤销上一次输入文字（按字数发送退格）的全局快捷键，留空不注册
+    cycleProfile: string    // 循环切换指令方案的全局快捷键，留空不注册
+    recordMode: 'hold' | 'tap-or-hold'  // hold 按住说话；tap-or-hold 轻按开始连续录音、再按结束，按住仍为按住说话
+    tapThresholdMs: number  // tap-or-hold 下按下短于该时长视为轻按
   }
-  input: {
-    useClipboard: boolean
-  }
+  input: InputConfig
   audioCapture: AudioCaptureConfig
   vad: {
     enabled: boolean
     speechThreshold: number
     silenceTimeoutMs: number
     minSpeechDurationMs: number
+    frameMs: number           // VAD 逐帧判断的帧长（与设备缓冲大小无关）
+    activationPhrase: string  // 非空时 VAD 识别结果须以该词开头才会输入，激活词本身不输入
+    minUtteranceMs: number    // 短于该时长的语音段直接丢弃、不送识别（瞬态噪声），0 表示不限制
+    maxUtteranceMs: number    // 语音段超过该时长时强制切段送识别，防止持续噪声导致一直录音
+    autoDisableAfterIdleMs: number  // 持续该时长未检测到语音时自动关闭 VAD（释放麦克风），0 表示不自动关闭
+    sentenceHoldMs: number    // 静音超时后再等待的宽限期，期间重新开口则并入同一语音段（句内停顿不切句），0 表示不等待
+    energySmoothing: number   // 逐帧能量的平滑系数 0~0.95，0 不平滑，越大越不易被噪声毛刺反复触发起止
+    backend: 'energy' | 'fsmn'  // energy 仅能量检测；fsmn 再用本地 FSMN VAD 模型确认语音段，无人声的段不送识别
   }
   voiceCommands: Record<string, string>
+  voiceCommandAliases: Record<string, string[]>  // 指令别名：主口令 -> 其他说法，别名执行主口令的快捷键
+  voiceCommandProfiles: VoiceCommandProfile[]    // 指令方案：叠加在 voiceCommands 之上的口令组，按 LIS 模块切换
+  activeVoiceCommandProfile: string              // 当前指令方案名称，空字符串只使用基础指令表
+  voiceCommandOptions: VoiceCommandOptionsConfig
   hotwords: HotwordScene[]
   textRules: TextRulesConfig
+  postprocess: PostprocessConfig
   asr: {
-    mode: 'api' | 'local'    // 识别模式：远程 API 或本地模型
+    mode: 'api' | 'local' | 'commands'  // 识别模式：远程 API、本地模型，或仅识别已录样本口令的离线指令模式
     localModel: string        // 本地模型标识，如 'paraformer-zh-contextual-quant'
     puncEnabled: boolean      // 本地识别是否启用 PUNC 标点恢复
+    commandSpotMaxDistance: number  // 离线指令模式的样本匹配阈值，越小越严格
+    emptyResultWarningCount: number // 连续多少次识别为空时提示检查麦克风，0 表示不提示
   }
   onboarding?: OnboardingConfig
   llm: LlmConfig
   logging: LoggingConfig
+  tray: {
+    showTrigger: 'left-click' | 'double-click' | 'right-click'  // 托盘图标哪种点击显示浮窗；right-click 时左键单击弹出菜单
+  }
+  webhook: {
+    url: string        // 识别成功后把结果 POST 到该地址（审计/统计），留空不推送
+    timeoutMs: number  // 推送超时，只发不等，失败仅记日志
+  }
+  transcript: {
+    enabled: boolean    // 累计整段口述文字，供整体复制/导出；涉及隐私，默认关闭，仅保存在内存
+    idleGapMs: number   // 未显式开始会话时，与上一句间隔超过该时长自动开始新会话，0 表示不按间隔分段
+  }
+  ui: {
+    resultDisplayMs: number  // 识别结果显示该时长后自动隐藏浮窗（隐藏时先弹出），新的识别开始则取消，0 表示不自动隐藏
+  }
 }
 
 const FALLBACK_RECORD_HOTKEY = 'Alt+E'
@@ -181,9 +297,28 @@ const WIN_FORBIDDEN_RECORD_HOTKEY = 'ALT+SPACE'
 
 // 默认配置
 const defaultConfig: AppConfig = {
-  server: { url: 'http://localhost:3000', asrConfigId: '' },
-  hotkey: { record: FALLBACK_RECORD_HOTKEY },
-  input: { useClipboard: false },
+  server: { url: 'http://localhost:3000', asrConfigId: '', autoPrependScheme: false, transport: 'http', audioEncoding: 'wav', audioMime: 'audio/wav', requestIdHeader: 'X-Request-Id', healthPath: '', healthCheckIntervalSec: 30, redirect: 'follow', language: 'zh', fileFieldName: 'file', configIdFieldName: 'asrConfigId', minRequestIntervalMs: 0, rateLimitPushToTalk: false, requestTimeoutMs: 30000 },
+  hotkey: { record: FALLBACK_RECORD_HOTKEY, repeatCommand: '', undoLastInsert: '', cycleProfile: '', recordMode: 'hold', tapThresholdMs: 300 },
+  input: {
+    useClipboard: true,
+    keyboardMethod: 'text',
+    mapCtrlToCmdOnMac: false,
+    shortcutHoldMs: 0,
+    shortcutKeyDelayMs: 0,
+    shortcutStepDelayMs: 80,
+    releaseModifiersOnFailure: true,
+    pasteShortcut: 'CTRL+V',
+    pasteMode: 'replace',
+    appendNavigationKey: 'END',
+    quickRecordMs: QUICK_RECORD_DEFAULT_MS,
+    safeMode: false,
+    safeModeIdleMs: 5 * 60 * 1000,
+    clipboardOnFailure: true,
+    autoReattempt: false,
+    targetWindowTitle: '',
+    continuousHold: false,
+    specialChars: {},
+  },
   audioCapture: {
     inputConstraints: {
       channelCount: 1,
@@ -191,15 +326,30 @@ const defaultConfig: AppConfig = {
       noiseSuppression: false,
       autoGainControl: false,
     },
+    channels: 'mono',
     postRollMs: 200,
     tailSilenceMs: 120,
+    padMs: 0,
     workletFlushTimeoutMs: 220,
+    inputGain: INPUT_GAIN_DEFAULT,
+    soundCues: false,
+    host: 'default',
+    source: 'input',
+    retainLastRecording: false,
   },
   vad: {
     enabled: false,
     speechThreshold: 0.06,
     silenceTimeoutMs: 800,
     minSpeechDurationMs: 300,
+    frameMs: 30,
+    activationPhrase: '',
+    minUtteranceMs: 0,
+    maxUtteranceMs: 12000,
+    autoDisableAfterIdleMs: 0,
+    sentenceHoldMs: 0,
+    energySmoothing: 0.72,
+    backend: 'energy',
   },
   voiceCommands: {
     肉眼所见: 'ALT+R',
@@ -217,6 +367,21 @@ const defaultConfig: AppConfig = {
     组织名称: 'F7',
     增加切片: 'F6',
   },
+  voiceCommandAliases: {},
+  voiceCommandProfiles: [],
+  activeVoiceCommandProfile: '',
+  voiceCommandOptions: {
+    enabled: true,
+    cooldownMs: 600,
+    cooldownOverrides: {},
+    enterAfterPhrases: [],
+    candidateThreshold: 0.6,
+    autoPickTopCandidate: false,
+    repeatPhrase: '重复',
+    postCommandLockoutMs: 0,
+    postCommandLockoutAction: 'text',
+    allowExec: false,
+  },
   hotwords: [{
     name: '全局',
     words: [
@@ -228,7 +393,18 @@ const defaultConfig: AppConfig = {
     ],
   }],
   textRules: cloneTextRulesConfig(),
-  asr: { mode: 'api', localModel: 'paraformer-zh-contextual-quant', puncEnabled: true },
+  postprocess: {
+    dedupeRepeats: false,
+    dedupeMinUnitChars: 2,
+    punctuation: 'off',
+    stopwords: [],
+    trimTrailingWhitespace: false,
+    collapseSpaces: false,
+    stripNewlines: false,
+    minChars: 0,
+    maxChars: 0,
+  },
+  asr: { mode: 'api', localModel: 'paraformer-zh-contextual-quant', puncEnabled: true, commandSpotMaxDistance: 6, emptyResultWarningCount: 3 },
   onboarding: {
     completed: false,
     completedAt: '',
@@ -255,9 +431,18 @@ const defaultConfig: AppConfig = {
     prompts: cloneLlmPromptsConfig(),
   },
   logging: {
+    level: 'info',
     enableDebug: false,
     showFloatBounds: false,
+    logAsrResponses: false,
+    toFile: true,
+    maxFileSizeMb: 5,
+    maxFiles: 5,
   },
+  tray: { showTrigger: 'left-click' },
+  webhook: { url: '', timeoutMs: 3000 },
+  transcript: { enabled: false, idleGapMs: 120000 },
+  ui: { resultDisplayMs: 0 },
 }
 
 const VAD_SPEECH_THRESHOLD_MIN = 0.01
@@ -266,73 +451,220 @@ const VAD_SILENCE_TIMEOUT_MIN_MS = 200
 const VAD_SILENCE_TIMEOUT_MAX_MS = 4000
 const VAD_MIN_SPEECH_DURATION_MIN_MS = 120
 const VAD_MIN_SPEECH_DURATION_MAX_MS = 4000
+const VAD_FRAME_MIN_MS = 10
+const VAD_FRAME_MAX_MS = 100
+const VAD_MIN_UTTERANCE_MAX_MS = 5000
+const VAD_MAX_UTTERANCE_MIN_MS = 2000
+const VAD_MAX_UTTERANCE_MAX_MS = 120000
+const VAD_AUTO_DISABLE_MAX_MS = 24 * 60 * 60 * 1000
+const VAD_SENTENCE_HOLD_MAX_MS = 3000
+export const VAD_ENERGY_SMOOTHING_MAX = 0.95
 
-// electron-store 实例
-const store = new Store<AppConfig>({
-  name: 'config',
-  defaults: defaultConfig,
-})
+// 受管部署（组策略下发配置）时设置为 1：配置文件缺失视为错误，不自动写入默认配置，
+// 避免带占位服务地址的默认文件掩盖下发失败。
+export const REQUIRE_CONFIG_ENV = 'LOGENE_REQUIRE_CONFIG'
+const CONFIG_FILE_NAME = 'config'
+
+export interface ConfigStoreInitResult {
+  path: string
+  created: boolean          // 本次是否写入了默认配置
+  requireExisting: boolean  // 是否处于强制要求已有配置模式
+}
+
+// electron-store 实例（首次访问时创建，创建时会把默认值写入磁盘）
+let store: ConfigStore | null = null
+
+export function isExistingConfigRequired(): boolean {
+  return process.env[REQUIRE_CONFIG_ENV] === '1'
+}
+
+export function initConfigStore(): ConfigStoreInitResult {
+  const configPath = path.join(app.getPath('userData'), `${CONFIG_FILE_NAME}.json`)
+  const requireExisting = isExistingConfigRequired()
+  const existed = fs.existsSync(configPath)
+  if (!store) {
+    if (requireExisting && !existed) {
+      throw new Error(`未找到配置文件 ${configPath}（${REQUIRE_CONFIG_ENV}=1 时不会自动创建默认配置）`)
+    }
+    store = createConfigStore(CONFIG_FILE_NAME, defaultConfig)
+    const current = store.store
+    if (migrateConfig(current)) {
+      store.store = current
+      console.info(`[Config] 配置已升级到版本 ${CONFIG_VERSION}`)
+    }
+  }
+  return { path: configPath, created: !existed, requireExisting }
+}
+
+// app ready 之前读取磁盘上的原始配置（不创建 store、不写默认值），用于必须在启动前生效的 Chromium 开关
+export function peekConfigFile(): Partial<AppConfig> | null {
+  try {
+    const configPath = path.join(app.getPath('userData'), `${CONFIG_FILE_NAME}.json`)
+    if (!fs.existsSync(configPath)) return null
+    return JSON.parse(fs.readFileSync(configPath, 'utf8')) as Partial<AppConfig>
+  } catch {
+    return null
+  }
+}
+
+function getStore(): ConfigStore {
+  if (!store) initConfigStore()
+  return store!
+}
+
+// 保存合并写盘：连续修改在静默 CONFIG_SAVE_DEBOUNCE_MS 后只写一次，读取时优先返回尚未写盘的最新配置
+const CONFIG_SAVE_DEBOUNCE_MS = 500
+const configSaver = new DebouncedSaver<AppConfig>(
+  (config) => { getStore().store = config },
+  CONFIG_SAVE_DEBOUNCE_MS,
+  (e) => console.error(`[Config] 配置写入失败，将在下次保存或退出时重试: ${String(e)}`),
+)
+
+// 立即写入尚未落盘的配置（退出、重启前调用）
+export function flushConfig(): void {
+  configSaver.flush()
+}
 
 export function getConfig(): AppConfig {
-  const cfg = store.store as AppConfig
+  // 每次返回独立副本，调用方修改后需 saveConfig 才生效（与直接读 store 的行为一致）
+  const pending = configSaver.pending
+  const cfg = (pending ? JSON.parse(JSON.stringify(pending)) : getStore().store) as AppConfig
+  const before = pending ? null : JSON.stringify(cfg)
   cfg.llm = normalizeLlmConfig(cfg.llm as unknown)
   cfg.textRules = normalizeTextRulesConfig(cfg.textRules as unknown)
+  cfg.postprocess = normalizePostprocessConfig(cfg.postprocess as unknown)
   cfg.audioCapture = normalizeAudioCaptureConfig(cfg.audioCapture as unknown)
   cfg.vad = normalizeVadConfig(cfg.vad as unknown)
+  cfg.input = normalizeInputConfig(cfg.input as unknown)
+  cfg.server = normalizeServerConfig(cfg.server as unknown)
+  cfg.voiceCommandOptions = normalizeVoiceCommandOptions(cfg.voiceCommandOptions as unknown)
+  cfg.voiceCommandAliases = normalizeVoiceCommandAliases(cfg.voiceCommandAliases as unknown)
+  cfg.voiceCommandProfiles = normalizeVoiceCommandProfiles(cfg.voiceCommandProfiles as unknown)
+  cfg.activeVoiceCommandProfile = normalizeActiveProfile(cfg.activeVoiceCommandProfile, cfg.voiceCommandProfiles)
   if (!cfg.asr || typeof cfg.asr !== 'object') {
     cfg.asr = { ...defaultConfig.asr }
   }
   if (typeof cfg.asr.puncEnabled !== 'boolean') {
     cfg.asr.puncEnabled = true
   }
+  cfg.asr.commandSpotMaxDistance = clampNumber(
+    cfg.asr.commandSpotMaxDistance,
+    defaultConfig.asr.commandSpotMaxDistance,
+    1,
+    50,
+  )
+  cfg.asr.emptyResultWarningCount = Math.round(clampNumber(
+    cfg.asr.emptyResultWarningCount,
+    defaultConfig.asr.emptyResultWarningCount,
+    0,
+    20,
+  ))
   if (!cfg.logging || typeof cfg.logging !== 'object') {
     cfg.logging = { ...defaultConfig.logging }
   }
-  if (typeof cfg.logging.enableDebug !== 'boolean') {
-    cfg.logging.enableDebug = false
-  }
+  cfg.logging.level = normalizeLogLevel(cfg.logging.level, cfg.logging.enableDebug)
+  cfg.logging.enableDebug = cfg.logging.level === 'debug'
   if (typeof cfg.logging.showFloatBounds !== 'boolean') {
     cfg.logging.showFloatBounds = false
   }
+  if (typeof cfg.logging.logAsrResponses !== 'boolean') {
+    cfg.logging.logAsrResponses = false
+  }
+  if (typeof cfg.logging.toFile !== 'boolean') {
+    cfg.logging.toFile = defaultConfig.logging.toFile
+  }
+  cfg.logging.maxFileSizeMb = Math.round(clampNumber(cfg.logging.maxFileSizeMb, defaultConfig.logging.maxFileSizeMb, 1, 100))
+  cfg.logging.maxFiles = Math.round(clampNumber(cfg.logging.maxFiles, defaultConfig.logging.maxFiles, 1, 20))
   if (!cfg.hotkey || typeof cfg.hotkey !== 'object') {
     cfg.hotkey = { ...defaultConfig.hotkey }
   }
   cfg.hotkey.record = normalizeRecordHotkey((cfg.hotkey as { record?: unknown }).record)
+  cfg.hotkey.repeatCommand = normalizeOptionalHotkey((cfg.hotkey as { repeatCommand?: unknown }).repeatCommand)
+  cfg.hotkey.undoLastInsert = normalizeOptionalHotkey((cfg.hotkey as { undoLastInsert?: unknown }).undoLastInsert)
+  cfg.hotkey.cycleProfile = normalizeOptionalHotkey((cfg.hotkey as { cycleProfile?: unknown }).cycleProfile)
+  cfg.hotkey.recordMode = cfg.hotkey.recordMode === 'tap-or-hold' ? 'tap-or-hold' : 'hold'
+  cfg.hotkey.tapThresholdMs = Math.round(clampNumber(cfg.hotkey.tapThresholdMs, defaultConfig.hotkey.tapThresholdMs, 100, 2000))
   cfg.onboarding = normalizeOnboardingConfig(cfg.onboarding)
+  cfg.tray = normalizeTrayConfig(cfg.tray as unknown)
+  cfg.webhook = normalizeWebhookConfig(cfg.webhook as unknown)
+  cfg.transcript = normalizeTranscriptConfig(cfg.transcript as unknown)
+  cfg.ui = normalizeUiConfig(cfg.ui as unknown)
   // 迁移旧模型 ID：本地识别仅保留 ONNX 量化热词模型。
   if (cfg.asr?.localModel !== 'paraformer-zh-contextual-quant') {
     cfg.asr.localModel = 'paraformer-zh-contextual-quant'
   }
-  store.store = cfg
+  // 磁盘上的旧配置经规范化有变化时才回写（走合并写盘）；有待写入的配置时它本身已规范化，无需回写
+  if (before !== null && JSON.stringify(cfg) !== before) {
+    configSaver.schedule(JSON.parse(JSON.stringify(cfg)) as AppConfig)
+  }
   return cfg
 }
 
 export function saveConfig(config: AppConfig): void {
   config.llm = normalizeLlmConfig(config.llm as unknown)
   config.textRules = normalizeTextRulesConfig(config.textRules as unknown)
+  config.postprocess = normalizePostprocessConfig(config.postprocess as unknown)
   config.audioCapture = normalizeAudioCaptureConfig(config.audioCapture as unknown)
   config.vad = normalizeVadConfig(config.vad as unknown)
+  config.input = normalizeInputConfig(config.input as unknown)
+  config.server = normalizeServerConfig(config.server as unknown)
+  config.voiceCommandOptions = normalizeVoiceCommandOptions(config.voiceCommandOptions as unknown)
+  config.voiceCommandAliases = normalizeVoiceCommandAliases(config.voiceCommandAliases as unknown)
+  config.voiceCommandProfiles = normalizeVoiceCommandProfiles(config.voiceCommandProfiles as unknown)
+  config.activeVoiceCommandProfile = normalizeActiveProfile(config.activeVoiceCommandProfile, config.voiceCommandProfiles)
   if (!config.asr || typeof config.asr !== 'object') {
     config.asr = { ...defaultConfig.asr }
   }
   if (typeof config.asr.puncEnabled !== 'boolean') {
     config.asr.puncEnabled = true
   }
+  config.asr.commandSpotMaxDistance = clampNumber(
+    config.asr.commandSpotMaxDistance,
+    defaultConfig.asr.commandSpotMaxDistance,
+    1,
+    50,
+  )
+  config.asr.emptyResultWarningCount = Math.round(clampNumber(
+    config.asr.emptyResultWarningCount,
+    defaultConfig.asr.emptyResultWarningCount,
+    0,
+    20,
+  ))
   if (!config.logging || typeof config.logging !== 'object') {
     config.logging = { ...defaultConfig.logging }
   }
-  if (typeof config.logging.enableDebug !== 'boolean') {
-    config.logging.enableDebug = false
-  }
+  config.logging.level = normalizeLogLevel(config.logging.level, config.logging.enableDebug)
+  config.logging.enableDebug = config.logging.level === 'debug'
   if (typeof config.logging.showFloatBounds !== 'boolean') {
     config.logging.showFloatBounds = false
   }
+  if (typeof config.logging.logAsrResponses !== 'boolean') {
+    config.logging.logAsrResponses = false
+  }
+  if (typeof config.logging.toFile !== 'boolean') {
+    config.logging.toFile = defaultConfig.logging.toFile
+  }
+  config.logging.maxFileSizeMb = Math.round(clampNumber(config.logging.maxFileSizeMb, defaultConfig.logging.maxFileSizeMb, 1, 100))
+  config.logging.maxFiles = Math.round(clampNumber(config.logging.maxFiles, defaultConfig.logging.maxFiles, 1, 20))
   if (!config.hotkey || typeof config.hotkey !== 'object') {
     config.hotkey = { ...defaultConfig.hotkey }
   }
   config.hotkey.record = normalizeRecordHotkey((config.hotkey as { record?: unknown }).record)
+  config.hotkey.repeatCommand = normalizeOptionalHotkey((config.hotkey as { repeatCommand?: unknown }).repeatCommand)
+  config.hotkey.undoLastInsert = normalizeOptionalHotkey((config.hotkey as { undoLastInsert?: unknown }).undoLastInsert)
+  config.hotkey.cycleProfile = normalizeOptionalHotkey((config.hotkey as { cycleProfile?: unknown }).cycleProfile)
+  config.hotkey.recordMode = config.hotkey.recordMode === 'tap-or-hold' ? 'tap-or-hold' : 'hold'
+  config.hotkey.tapThresholdMs = Math.round(clampNumber(config.hotkey.tapThresholdMs, defaultConfig.hotkey.tapThresholdMs, 100, 2000))
   config.onboarding = normalizeOnboardingConfig(config.onboarding)
-  store.store = config
+  config.tray = normalizeTrayConfig(config.tray as unknown)
+  config.webhook = normalizeWebhookConfig(config.webhook as unknown)
+  config.transcript = normalizeTranscriptConfig(config.transcript as unknown)
+  config.ui = normalizeUiConfig(config.ui as unknown)
+  configSaver.schedule(JSON.parse(JSON.stringify(config)) as AppConfig)
+}
+
+function normalizeOptionalHotkey(raw: unknown): string {
+  return typeof raw === 'string' ? raw.trim() : ''
 }
 
 function normalizeRecordHotkey(raw: unknown): string {
@@ -381,15 +713,276 @@ function normalizeAudioCaptureConfig(raw: unknown): AudioCaptureConfig {
   const source = (raw && typeof raw === 'object' ? raw : {}) as Record<string, unknown>
   return {
     inputConstraints: normalizeAudioInputConstraints(source.inputConstraints),
+    channels: source.channels === 'stereo' ? 'stereo' : 'mono',
     postRollMs: Math.round(clampNumber(source.postRollMs, defaultConfig.audioCapture.postRollMs, 0, 1200)),
     tailSilenceMs: Math.round(clampNumber(source.tailSilenceMs, defaultConfig.audioCapture.tailSilenceMs, 0, 1200)),
+    padMs: Math.round(clampNumber(source.padMs, defaultConfig.audioCapture.padMs, 0, 1000)),
     workletFlushTimeoutMs: Math.round(clampNumber(
       source.workletFlushTimeoutMs,
       defaultConfig.audioCapture.workletFlushTimeoutMs,
       80,
       2000,
     )),
+    inputGain: clampInputGain(source.inputGain),
+    soundCues: typeof source.soundCues === 'boolean' ? source.soundCues : defaultConfig.audioCapture.soundCues,
+    source: normalizeAudioSource(source.source),
+    host: typeof source.host === 'string' && source.host.trim() ? source.host.trim() : defaultConfig.audioCapture.host,
+    retainLastRecording: source.retainLastRecording === true,
+  }
+}
+
+function normalizeServerConfig(raw: unknown): AppConfig['server'] {
+  const source = (raw && typeof raw === 'object' ? raw : {}) as Record<string, unknown>
+  return {
+    url: typeof source.url === 'string' ? source.url.trim() : defaultConfig.server.url,
+    asrConfigId: typeof source.asrConfigId === 'string' ? source.asrConfigId : defaultConfig.server.asrConfigId,
+    autoPrependScheme: typeof source.autoPrependScheme === 'boolean'
+      ? source.autoPrependScheme
+      : defaultConfig.server.autoPrependScheme,
+    transport: source.transport === 'ws' ? 'ws' : 'http',
+    audioEncoding: source.audioEncoding === 'flac' ? 'flac' : 'wav',
+    audioMime: typeof source.audioMime === 'string' && isValidMimeType(source.audioMime.trim())
+      ? source.audioMime.trim().toLowerCase()
+      : defaultConfig.server.audioMime,
+    requestIdHeader: normalizeRequestIdHeader(source.requestIdHeader),
+    healthPath: typeof source.healthPath === 'string' ? source.healthPath.trim() : defaultConfig.server.healthPath,
+    healthCheckIntervalSec: Math.round(clampNumber(
+      source.healthCheckIntervalSec,
+      defaultConfig.server.healthCheckIntervalSec,
+      0,
+      3600,
+    )),
+    redirect: source.redirect === 'error' ? 'error' : 'follow',
+    language: source.language === 'en' || source.language === 'auto' ? source.language : 'zh',
+    fileFieldName: normalizeFormFieldName(source.fileFieldName, defaultConfig.server.fileFieldName),
+    configIdFieldName: normalizeFormFieldName(source.configIdFieldName, defaultConfig.server.configIdFieldName),
+    minRequestIntervalMs: Math.round(clampNumber(source.minRequestIntervalMs, defaultConfig.server.minRequestIntervalMs, 0, 60000)),
+    requestTimeoutMs: Math.round(clampNumber(source.requestTimeoutMs, defaultConfig.server.requestTimeoutMs, 1000, 300000)),
+    rateLimitPushToTalk: typeof source.rateLimitPushToTalk === 'boolean'
+      ? source.rateLimitPushToTalk
+      : defaultConfig.server.rateLimitPushToTalk,
+  }
+}
+
+// 表单字段名：去空白，留空或含控制字符、引号时回退默认值
+function normalizeFormFieldName(raw: unknown, fallback: string): string {
+  if (typeof raw !== 'string') return fallback
+  const value = raw.trim()
+  return value && !/[\x00-\x1f"\\]/.test(value) ? value : fallback
+}
+
+// 请求头名须为合法 token，否则 fetch 会直接抛错导致识别失败；非法值回退默认
+function normalizeRequestIdHeader(raw: unknown): string {
+  if (typeof raw !== 'string') return defaultConfig.server.requestIdHeader
+  const value = raw.trim()
+  if (!value) return ''
+  return /^[!#$%&'*+.^_`|~0-9A-Za-z-]+$/.test(value) ? value : defaultConfig.server.requestIdHeader
+}
+
+const POSTPROCESS_CHARS_MAX = 10000
+
+function normalizePostprocessConfig(raw: unknown): PostprocessConfig {
+  const source = (raw && typeof raw === 'object' ? raw : {}) as Record<string, unknown>
+  return {
+    dedupeRepeats: typeof source.dedupeRepeats === 'boolean'
+      ? source.dedupeRepeats
+      : defaultConfig.postprocess.dedupeRepeats,
+    dedupeMinUnitChars: Math.round(clampNumber(
+      source.dedupeMinUnitChars,
+      defaultConfig.postprocess.dedupeMinUnitChars,
+      1,
+      8,
+    )),
+    punctuation: source.punctuation === 'server' || source.punctuation === 'local'
+      ? source.punctuation
+      : 'off',
+    stopwords: normalizeStopwords(source.stopwords),
+    trimTrailingWhitespace: source.trimTrailingWhitespace === true,
+    collapseSpaces: source.collapseSpaces === true,
+    stripNewlines: source.stripNewlines === true,
+    minChars: Math.round(clampNumber(source.minChars, defaultConfig.postprocess.minChars, 0, POSTPROCESS_CHARS_MAX)),
+    maxChars: Math.round(clampNumber(source.maxChars, defaultConfig.postprocess.maxChars, 0, POSTPROCESS_CHARS_MAX)),
+  }
+}
+
+const STOPWORDS_MAX = 200
+const STOPWORD_MAX_CHARS = 20
+
+function normalizeStopwords(raw: unknown): string[] {
+  if (!Array.isArray(raw)) return []
+  const words = raw
+    .filter((word): word is string => typeof word === 'string')
+    .map(word => word.trim())
+    .filter(word => word && word.length <= STOPWORD_MAX_CHARS)
+  return Array.from(new Set(words)).slice(0, STOPWORDS_MAX)
+}
+
+function normalizeInputConfig(raw: unknown): InputConfig {
+  const source = (raw && typeof raw === 'object' ? raw : {}) as Record<string, unknown>
+  return {
+    useClipboard: typeof source.useClipboard === 'boolean'
+      ? source.useClipboard
+      : defaultConfig.input.useClipboard,
+    keyboardMethod: source.keyboardMethod === 'unicode' ? 'unicode' : 'text',
+    mapCtrlToCmdOnMac: typeof source.mapCtrlToCmdOnMac === 'boolean'
+      ? source.mapCtrlToCmdOnMac
+      : defaultConfig.input.mapCtrlToCmdOnMac,
+    shortcutHoldMs: Math.round(clampNumber(source.shortcutHoldMs, defaultConfig.input.shortcutHoldMs, 0, 1000)),
+    shortcutKeyDelayMs: Math.round(clampNumber(source.shortcutKeyDelayMs, defaultConfig.input.shortcutKeyDelayMs, 0, 500)),
+    shortcutStepDelayMs: Math.round(clampNumber(source.shortcutStepDelayMs, defaultConfig.input.shortcutStepDelayMs, 0, 2000)),
+    releaseModifiersOnFailure: typeof source.releaseModifiersOnFailure === 'boolean'
+      ? source.releaseModifiersOnFailure
+      : defaultConfig.input.releaseModifiersOnFailure,
+    pasteShortcut: normalizePasteShortcut(source.pasteShortcut),
+    pasteMode: source.pasteMode === 'append' ? 'append' : 'replace',
+    appendNavigationKey: normalizeShortcutSetting(source.appendNavigationKey, defaultConfig.input.appendNavigationKey),
+    quickRecordMs: clampQuickRecordMs(source.quickRecordMs),
+    safeMode: typeof source.safeMode === 'boolean' ? source.safeMode : defaultConfig.input.safeMode,
+    safeModeIdleMs: Math.round(clampNumber(source.safeModeIdleMs, defaultConfig.input.safeModeIdleMs, 10000, 8 * 60 * 60 * 1000)),
+    clipboardOnFailure: typeof source.clipboardOnFailure === 'boolean'
+      ? source.clipboardOnFailure
+      : defaultConfig.input.clipboardOnFailure,
+    autoReattempt: typeof source.autoReattempt === 'boolean' ? source.autoReattempt : defaultConfig.input.autoReattempt,
+    targetWindowTitle: typeof source.targetWindowTitle === 'string'
+      ? source.targetWindowTitle.trim().slice(0, 200)
+      : defaultConfig.input.targetWindowTitle,
+    continuousHold: typeof source.continuousHold === 'boolean' ? source.continuousHold : defaultConfig.input.continuousHold,
+    specialChars: normalizeSpecialChars(source.specialChars),
+  }
+}
+
+const SPECIAL_CHARS_MAX = 50
+
+// 键须为单个非空白字符（按码位计），方式只认 unicode / clipboard，其余条目丢弃
+function normalizeSpecialChars(raw: unknown): InputConfig['specialChars'] {
+  if (!raw || typeof raw !== 'object' || Array.isArray(raw)) return {}
+  const result: InputConfig['specialChars'] = {}
+  for (const [ch, strategy] of Object.entries(raw as Record<string, unknown>)) {
+    if (Object.keys(result).length >= SPECIAL_CHARS_MAX) break
+    if (Array.from(ch).length !== 1 || !ch.trim()) continue
+    if (strategy === 'unicode' || strategy === 'clipboard') result[ch] = strategy
+  }
+  return result
+}
+
+const LOG_LEVELS: readonly LogLevel[] = ['debug', 'info', 'warn', 'error']
+
+export function isLogLevel(value: unknown): value is LogLevel {
+  return LOG_LEVELS.some(level => level === value)
+}
+
+// 未配置 level 的旧配置按 enableDebug 迁移
+function normalizeLogLevel(raw: unknown, enableDebug: unknown): LogLevel {
+  if (isLogLevel(raw)) return raw
+  return enableDebug === true ? 'debug' : 'info'
+}
+
+// 粘贴快捷键在加载时校验，非法时回退默认值，避免每次输入才发现配置错误
+function normalizePasteShortcut(raw: unknown): string {
+  return normalizeShortcutSetting(raw, defaultConfig.input.pasteShortcut)
+}
+
+function normalizeShortcutSetting(raw: unknown, fallback: string): string {
+  const value = typeof raw === 'string' ? raw.trim() : ''
+  if (!value) return fallback
+  try {
+    parseShortcutSequence(value)
+    return value
+  } catch {
+    return fallback
+  }
+}
+
+function normalizeVoiceCommandOptions(raw: unknown): VoiceCommandOptionsConfig {
+  const source = (raw && typeof raw === 'object' ? raw : {}) as Record<string, unknown>
+  const overridesRaw = (source.cooldownOverrides && typeof source.cooldownOverrides === 'object'
+    ? source.cooldownOverrides
+    : {}) as Record<string, unknown>
+  const cooldownOverrides: Record<string, number> = {}
+  for (const [phrase, value] of Object.entries(overridesRaw)) {
+    const key = phrase.trim()
+    if (!key || typeof value !== 'number' || !Number.isFinite(value)) continue
+    cooldownOverrides[key] = Math.round(clampNumber(value, 0, 0, 60000))
+  }
+  return {
+    enabled: typeof source.enabled === 'boolean' ? source.enabled : defaultConfig.voiceCommandOptions.enabled,
+    cooldownMs: Math.round(clampNumber(
+      source.cooldownMs,
+      defaultConfig.voiceCommandOptions.cooldownMs,
+      0,
+      60000,
+    )),
+    cooldownOverrides,
+    enterAfterPhrases: normalizeTokenList(source.enterAfterPhrases, []),
+    candidateThreshold: clampNumber(
+      source.candidateThreshold,
+      defaultConfig.voiceCommandOptions.candidateThreshold,
+      0,
+      1,
+    ),
+    autoPickTopCandidate: typeof source.autoPickTopCandidate === 'boolean'
+      ? source.autoPickTopCandidate
+      : defaultConfig.voiceCommandOptions.autoPickTopCandidate,
+    repeatPhrase: typeof source.repeatPhrase === 'string'
+      ? source.repeatPhrase.trim()
+      : defaultConfig.voiceCommandOptions.repeatPhrase,
+    postCommandLockoutMs: Math.round(clampNumber(
+      source.postCommandLockoutMs,
+      defaultConfig.voiceCommandOptions.postCommandLockoutMs,
+      0,
+      60000,
+    )),
+    postCommandLockoutAction: source.postCommandLockoutAction === 'drop' ? 'drop' : 'text',
+    allowExec: source.allowExec === true,
+  }
+}
+
+const VOICE_COMMAND_ALIASES_MAX = 20
+
+// 主口令去空白；别名去空白、去重、去掉与主口令相同的项，空列表整项丢弃
+function normalizeVoiceCommandAliases(raw: unknown): Record<string, string[]> {
+  if (!raw || typeof raw !== 'object' || Array.isArray(raw)) return {}
+  const result: Record<string, string[]> = {}
+  for (const [key, value] of Object.entries(raw as Record<string, unknown>)) {
+    const canonical = key.trim()
+    if (!canonical || !Array.isArray(value)) continue
+    const aliases = value
+      .filter((alias): alias is string => typeof alias === 'string')
+      .map(alias => alias.trim())
+      .filter(alias => alias && alias !== canonical)
+    const unique = Array.from(new Set(aliases)).slice(0, VOICE_COMMAND_ALIASES_MAX)
+    if (unique.length > 0) result[canonical] = unique
   }
+  return result
+}
+
+const VOICE_COMMAND_PROFILES_MAX = 20
+
+// 方案名去空白、去重（同名保留第一个）；口令去空白，非字符串的指令值丢弃
+function normalizeVoiceCommandProfiles(raw: unknown): VoiceCommandProfile[] {
+  if (!Array.isArray(raw)) return []
+  const profiles: VoiceCommandProfile[] = []
+  for (const item of raw) {
+    if (!item || typeof item !== 'object') continue
+    const source = item as Record<string, unknown>
+    const name = typeof source.name === 'string' ? source.name.trim() : ''
+    if (!name || profiles.some(profile => profile.name === name)) continue
+    const commands: Record<string, string> = {}
+    if (source.commands && typeof source.commands === 'object' && !Array.isArray(source.commands)) {
+      for (const [phrase, value] of Object.entries(source.commands as Record<string, unknown>)) {
+        if (phrase.trim() && typeof value === 'string' && value.trim()) commands[phrase.trim()] = value
+      }
+    }
+    profiles.push({ name, commands })
+    if (profiles.length >= VOICE_COMMAND_PROFILES_MAX) break
+  }
+  return profiles
+}
+
+// 当前方案已不存在时回到基础指令表
+function normalizeActiveProfile(raw: unknown, profiles: VoiceCommandProfile[]): string {
+  const name = typeof raw === 'string' ? raw.trim() : ''
+  return profiles.some(profile => profile.name === name) ? name : ''
 }
 
 function normalizeVadConfig(raw: unknown): AppConfig['vad'] {
@@ -416,6 +1009,46 @@ function normalizeVadConfig(raw: unknown): AppConfig['vad'] {
       VAD_MIN_SPEECH_DURATION_MIN_MS,
       VAD_MIN_SPEECH_DURATION_MAX_MS,
     )),
+    frameMs: Math.round(clampNumber(
+      source.frameMs,
+      defaultConfig.vad.frameMs,
+      VAD_FRAME_MIN_MS,
+      VAD_FRAME_MAX_MS,
+    )),
+    activationPhrase: typeof source.activationPhrase === 'string'
+      ? source.activationPhrase.trim()
+      : defaultConfig.vad.activationPhrase,
+    minUtteranceMs: Math.round(clampNumber(
+      source.minUtteranceMs,
+      defaultConfig.vad.minUtteranceMs,
+      0,
+      VAD_MIN_UTTERANCE_MAX_MS,
+    )),
+    maxUtteranceMs: Math.round(clampNumber(
+      source.maxUtteranceMs,
+      defaultConfig.vad.maxUtteranceMs,
+      VAD_MAX_UTTERANCE_MIN_MS,
+      VAD_MAX_UTTERANCE_MAX_MS,
+    )),
+    autoDisableAfterIdleMs: Math.round(clampNumber(
+      source.autoDisableAfterIdleMs,
+      defaultConfig.vad.autoDisableAfterIdleMs,
+      0,
+      VAD_AUTO_DISABLE_MAX_MS,
+    )),
+    sentenceHoldMs: Math.round(clampNumber(
+      source.sentenceHoldMs,
+      defaultConfig.vad.sentenceHoldMs,
+      0,
+      VAD_SENTENCE_HOLD_MAX_MS,
+    )),
+    energySmoothing: clampNumber(
+      source.energySmoothing,
+      defaultConfig.vad.energySmoothing,
+      0,
+      VAD_ENERGY_SMOOTHING_MAX,
+    ),
+    backend: source.backend === 'fsmn' ? 'fsmn' : 'energy',
   }
 }
 
@@ -437,6 +1070,37 @@ function normalizeOnboardingConfig(raw: unknown): OnboardingConfig {
   }
 }
 
+function normalizeTrayConfig(raw: unknown): AppConfig['tray'] {
+  const source = (raw && typeof raw === 'object' ? raw : {}) as Record<string, unknown>
+  const trigger = source.showTrigger
+  return {
+    showTrigger: trigger === 'double-click' || trigger === 'right-click' ? trigger : defaultConfig.tray.showTrigger,
+  }
+}
+
+function normalizeWebhookConfig(raw: unknown): AppConfig['webhook'] {
+  const source = (raw && typeof raw === 'object' ? raw : {}) as Record<string, unknown>
+  return {
+    url: typeof source.url === 'string' ? source.url.trim() : defaultConfig.webhook.url,
+    timeoutMs: Math.round(clampNumber(source.timeoutMs, defaultConfig.webhook.timeoutMs, 500, 30000)),
+  }
+}
+
+function normalizeTranscriptConfig(raw: unknown): AppConfig['transcript'] {
+  const source = (raw && typeof raw === 'object' ? raw : {}) as Record<string, unknown>
+  return {
+    enabled: source.enabled === true,
+    idleGapMs: Math.round(clampNumber(source.idleGapMs, defaultConfig.transcript.idleGapMs, 0, 3600000)),
+  }
+}
+
+function normalizeUiConfig(raw: unknown): AppConfig['ui'] {
+  const source = (raw && typeof raw === 'object' ? raw : {}) as Record<string, unknown>
+  return {
+    resultDisplayMs: Math.round(clampNumber(source.resultDisplayMs, defaultConfig.ui.resultDisplayMs, 0, 60000)),
+  }
+}
+
 function cloneTextRulesConfig(source: TextRulesConfig = DEFAULT_TEXT_RULES): TextRulesConfig {
   return {
     enabled: Boolean(source.enabled),
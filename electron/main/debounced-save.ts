@@ -0,0 +1,46 @@
+// 合并短时间内的多次保存：运行时调整增益、VAD 参数、切换开关等会连续调用 saveConfig，
+// 每次都写盘既慢又容易在写到一半时被打断。最后一次 schedule 之后静默 delayMs 才真正写入最新值，
+// 期间读取应优先使用 pending；退出、重启前必须 flush，保证最后一次修改落盘
+
+export class DebouncedSaver<T> {
+  private timer: ReturnType<typeof setTimeout> | null = null
+  private hasPending = false
+  private value: T | undefined
+
+  constructor(
+    private readonly write: (value: T) => void,
+    private readonly delayMs: number,
+    private readonly onError: (error: unknown) => void = () => { },
+  ) { }
+
+  // 尚未写盘的最新值
+  get pending(): T | undefined {
+    return this.hasPending ? this.value : undefined
+  }
+
+  schedule(value: T): void {
+    this.value = value
+    this.hasPending = true
+    if (this.timer) clearTimeout(this.timer)
+    this.timer = setTimeout(() => {
+      this.timer = null
+      try {
+        this.flush()
+      } catch (e) {
+        this.onError(e)
+      }
+    }, this.delayMs)
+  }
+
+  // 立即写入待保存的值；写入失败时保留待保存值并抛出，下次 flush 重试
+  flush(): void {
+    if (this.timer) {
+      clearTimeout(this.timer)
+      this.timer = null
+    }
+    if (!this.hasPending) return
+    this.write(this.value as T)
+    this.hasPending = false
+    this.value = undefined
+  }
+}
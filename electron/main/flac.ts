@@ -0,0 +1,225 @@
+import { readWavInfo, WAV_HEADER_BYTES } from './wav-info'
+
+// 16-bit PCM WAV → FLAC 无损压缩，供上传前减小体积（语音通常可压到 WAV 的 40%~60%）。
+// 只实现编码器所需的最小子集：固定块长、独立声道、fixed 预测（0~4 阶）+ Rice 残差，
+// 不写 MD5（STREAMINFO 中全 0 表示未知，解码器会跳过校验）
+
+const BLOCK_SIZE = 4096
+const BITS_PER_SAMPLE = 16
+const MAX_FIXED_ORDER = 4
+const MAX_RICE_PARAM = 14
+
+class BitWriter {
+  private bytes: number[] = []
+  private current = 0
+  private filled = 0
+
+  get length(): number { return this.bytes.length }
+
+  // value 须为 [0, 2^bits) 内的整数；超过 24 位时拆开写，保证累加器不超出浮点精确整数范围
+  write(value: number, bits: number): void {
+    if (bits > 24) {
+      this.write(Math.floor(value / 2 ** 24), bits - 24)
+      this.write(value % 2 ** 24, 24)
+      return
+    }
+    this.current = this.current * 2 ** bits + value
+    this.filled += bits
+    while (this.filled >= 8) {
+      this.filled -= 8
+      const byte = Math.floor(this.current / 2 ** this.filled)
+      this.bytes.push(byte)
+      this.current -= byte * 2 ** this.filled
+    }
+  }
+
+  writeSigned(value: number, bits: number): void {
+    this.write(value < 0 ? value + 2 ** bits : value, bits)
+  }
+
+  // q 个 0 后跟一个 1
+  writeUnary(q: number): void {
+    let zeros = q
+    while (zeros > 24) {
+      this.write(0, 24)
+      zeros -= 24
+    }
+    this.write(1, zeros + 1)
+  }
+
+  alignToByte(): void {
+    if (this.filled > 0) this.write(0, 8 - this.filled)
+  }
+
+  slice(start: number, end = this.bytes.length): number[] {
+    return this.bytes.slice(start, end)
+  }
+
+  toBuffer(): Buffer {
+    return Buffer.from(this.bytes)
+  }
+}
+
+export function encodeWavToFlac(wav: Buffer): Buffer {
+  const info = readWavInfo(wav)
+  if (info.channels > 8) {
+    throw new Error(`FLAC 最多支持 8 声道，当前 ${info.channels} 声道`)
+  }
+  const channels: Int32Array[] = Array.from({ length: info.channels }, () => new Int32Array(info.frameCount))
+  for (let i = 0; i < info.frameCount; i++) {
+    for (let c = 0; c < info.channels; c++) {
+      channels[c][i] = wav.readInt16LE(WAV_HEADER_BYTES + (i * info.channels + c) * 2)
+    }
+  }
+
+  const out = new BitWriter()
+  out.write(0x664c6143, 32) // "fLaC"
+  // STREAMINFO（唯一也是最后一个元数据块）
+  out.write(1, 1)
+  out.write(0, 7)
+  out.write(34, 24)
+  const blockSize = Math.max(16, Math.min(BLOCK_SIZE, info.frameCount || 16))
+  out.write(blockSize, 16)
+  out.write(blockSize, 16)
+  out.write(0, 24)
+  out.write(0, 24)
+  out.write(info.sampleRate, 20)
+  out.write(info.channels - 1, 3)
+  out.write(BITS_PER_SAMPLE - 1, 5)
+  out.write(info.frameCount, 36)
+  for (let i = 0; i < 16; i++) out.write(0, 8)
+
+  for (let start = 0, frame = 0; start < info.frameCount; start += blockSize, frame++) {
+    const size = Math.min(blockSize, info.frameCount - start)
+    writeFrame(out, channels.map(ch => ch.subarray(start, start + size)), frame)
+  }
+  return out.toBuffer()
+}
+
+function writeFrame(out: BitWriter, block: Int32Array[], frameNumber: number): void {
+  const frameStart = out.length
+  out.write(0x3ffe, 14)      // 同步码
+  out.write(0, 1)
+  out.write(0, 1)            // 固定块长
+  out.write(0b0111, 4)       // 块长在帧头末尾以 16 位给出
+  out.write(0b0000, 4)       // 采样率取 STREAMINFO
+  out.write(block.length - 1, 4)
+  out.write(0b100, 3)        // 16 bit
+  out.write(0, 1)
+  for (const byte of utf8CodedNumber(frameNumber)) out.write(byte, 8)
+  out.write(block[0].length - 1, 16)
+  out.write(crc8(out.slice(frameStart)), 8)
+
+  for (const samples of block) writeSubframe(out, samples)
+
+  out.alignToByte()
+  out.write(crc16(out.slice(frameStart)), 16)
+}
+
+function writeSubframe(out: BitWriter, samples: Int32Array): void {
+  if (samples.every(v => v === samples[0])) {
+    out.write(0, 1)
+    out.write(0b000000, 6)
+    out.write(0, 1)
+    out.writeSigned(samples[0], BITS_PER_SAMPLE)
+    return
+  }
+
+  let best: { order: number; riceParam: number; bits: number; residual: Int32Array } | null = null
+  for (let order = 0; order <= Math.min(MAX_FIXED_ORDER, samples.length - 1); order++) {
+    const residual = fixedResidual(samples, order)
+    const { riceParam, bits } = bestRiceParam(residual)
+    const total = order * BITS_PER_SAMPLE + 2 + 4 + 4 + bits
+    if (!best || total < best.bits) best = { order, riceParam, bits: total, residual }
+  }
+
+  if (!best || best.riceParam < 0 || best.bits >= samples.length * BITS_PER_SAMPLE) {
+    out.write(0, 1)
+    out.write(0b000001, 6)
+    out.write(0, 1)
+    for (const v of samples) out.writeSigned(v, BITS_PER_SAMPLE)
+    return
+  }
+
+  out.write(0, 1)
+  out.write(0b001000 | best.order, 6)
+  out.write(0, 1)
+  for (let i = 0; i < best.order; i++) out.writeSigned(samples[i], BITS_PER_SAMPLE)
+  out.write(0b00, 2)         // Rice，4 位参数
+  out.write(0, 4)            // 分区阶数 0
+  out.write(best.riceParam, 4)
+  const k = best.riceParam
+  for (const r of best.residual) {
+    const u = r >= 0 ? r * 2 : -r * 2 - 1
+    out.writeUnary(Math.floor(u / 2 ** k))
+    if (k > 0) out.write(u % 2 ** k, k)
+  }
+}
+
+// fixed 预测残差，返回 samples.length - order 个值
+function fixedResidual(samples: Int32Array, order: number): Int32Array {
+  const n = samples.length
+  const residual = new Int32Array(n - order)
+  const s = samples
+  for (let i = order; i < n; i++) {
+    let predicted = 0
+    if (order === 1) predicted = s[i - 1]
+    else if (order === 2) predicted = 2 * s[i - 1] - s[i - 2]
+    else if (order === 3) predicted = 3 * s[i - 1] - 3 * s[i - 2] + s[i - 3]
+    else if (order === 4) predicted = 4 * s[i - 1] - 6 * s[i - 2] + 4 * s[i - 3] - s[i - 4]
+    residual[i - order] = s[i] - predicted
+  }
+  return residual
+}
+
+// 按残差均值估计 Rice 参数，再在估计值附近精确计算编码位数取最小者；riceParam 为 -1 表示无合适参数
+function bestRiceParam(residual: Int32Array): { riceParam: number; bits: number } {
+  let sum = 0
+  for (const r of residual) sum += r >= 0 ? r * 2 : -r * 2 - 1
+  const mean = residual.length > 0 ? sum / residual.length : 0
+  const estimate = mean > 1 ? Math.floor(Math.log2(mean)) : 0
+  let best = { riceParam: -1, bits: Number.POSITIVE_INFINITY }
+  for (let k = Math.max(0, estimate - 1); k <= Math.min(MAX_RICE_PARAM, estimate + 1); k++) {
+    let bits = 0
+    const divisor = 2 ** k
+    for (const r of residual) {
+      const u = r >= 0 ? r * 2 : -r * 2 - 1
+      bits += Math.floor(u / divisor) + 1 + k
+    }
+    if (bits < best.bits) best = { riceParam: k, bits }
+  }
+  return best
+}
+
+function utf8CodedNumber(value: number): number[] {
+  if (value < 0x80) return [value]
+  // n 字节可容纳 5n+1 位
+  let total = 2
+  while (value >= 2 ** (5 * total + 1)) total++
+  const bytes: number[] = []
+  let rest = value
+  for (let i = 1; i < total; i++) {
+    bytes.unshift(0x80 | (rest % 64))
+    rest = Math.floor(rest / 64)
+  }
+  bytes.unshift(((0xff << (8 - total)) & 0xff) | rest)
+  return bytes
+}
+
+function crc8(bytes: number[]): number {
+  let crc = 0
+  for (const byte of bytes) {
+    crc ^= byte
+    for (let i = 0; i < 8; i++) crc = crc & 0x80 ? ((crc << 1) ^ 0x07) & 0xff : (crc << 1) & 0xff
+  }
+  return crc
+}
+
+function crc16(bytes: number[]): number {
+  let crc = 0
+  for (const byte of bytes) {
+    crc ^= byte << 8
+    for (let i = 0; i < 8; i++) crc = crc & 0x8000 ? ((crc << 1) ^ 0x8005) & 0xffff : (crc << 1) & 0xffff
+  }
+  return crc
+}
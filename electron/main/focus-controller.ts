@@ -84,6 +84,21 @@ export async function restoreFocus(appId: string | null): Promise<void> {
   }
 }
 
+// 按标题选择窗口：优先完全一致（忽略大小写），其次标题包含关键字；多个候选时取枚举顺序靠前者（Z 序更靠上）
+export function pickWindowByTitle(windows: { hwnd: string; title: string }[], pattern: string): string | null {
+  const needle = pattern.trim().toLowerCase()
+  if (!needle) return null
+  const exact = windows.find(w => w.title.trim().toLowerCase() === needle)
+  if (exact) return exact.hwnd
+  return windows.find(w => w.title.toLowerCase().includes(needle))?.hwnd ?? null
+}
+
+// 查找标题匹配的顶层窗口，返回可直接用于 restore 的窗口标识；目前仅 Windows 支持
+export function findWindowByTitle(pattern: string): string | null {
+  if (process.platform !== 'win32') return null
+  return pickWindowByTitle(win32Focus.listWin32Windows(), pattern)
+}
+
 // ── FocusController ──
 
 interface FocusControllerOptions {
@@ -0,0 +1,95 @@
+// 当前生效的全局热键：注册/注销时同步更新，供设置页展示实际绑定状态（配置里写了不代表注册成功）
+
+export type HotkeyAction = 'record' | 'repeat-command' | 'undo-last-insert' | 'cycle-profile' | 'vad-toggle' | 'rewrite'
+
+// registered：已生效；failed：被其它应用占用或系统不允许；invalid：无法解析
+export type HotkeyStatus = 'registered' | 'failed' | 'invalid'
+
+export interface HotkeyBinding {
+  action: HotkeyAction
+  accelerator: string
+  status: HotkeyStatus
+  // 失败原因，或已生效但有附带问题（如录音热键的拦截注册失败）
+  detail?: string
+}
+
+// 列表按此顺序输出，与设置页中的排列一致
+const ACTION_ORDER: HotkeyAction[] = ['record', 'repeat-command', 'undo-last-insert', 'cycle-profile', 'vad-toggle', 'rewrite']
+
+export class HotkeyRegistry {
+  private bindings = new Map<HotkeyAction, HotkeyBinding>()
+
+  // 同一动作重新注册时覆盖旧记录
+  set(binding: HotkeyBinding): void {
+    this.bindings.set(binding.action, { ...binding })
+  }
+
+  remove(action: HotkeyAction): void {
+    this.bindings.delete(action)
+  }
+
+  clear(): void {
+    this.bindings.clear()
+  }
+
+  list(): HotkeyBinding[] {
+    return ACTION_ORDER
+      .filter(action => this.bindings.has(action))
+      .map(action => ({ ...this.bindings.get(action)! }))
+  }
+}
+
+// 与其它应用冲突的热键，注册失败时推送给界面
+export interface HotkeyConflict {
+  action: HotkeyAction
+  accelerator: string
+  message: string
+}
+
+export interface HotkeyAvailability {
+  accelerator: string
+  available: boolean
+  // 本程序已注册该热键时为对应动作（可用，只是已被自己占用）
+  ownedBy?: HotkeyAction
+  reason?: 'invalid' | 'conflict'
+  detail?: string
+}
+
+// globalShortcut 中用到的部分，便于测试替换
+export interface GlobalShortcutApi {
+  register(accelerator: string, callback: () => void): boolean
+  unregister(accelerator: string): void
+  isRegistered(accelerator: string): boolean
+}
+
+function sameAccelerator(a: string, b: string): boolean {
+  const normalize = (value: string) => value.normalize('NFKC').replace(/\s+/g, '').toUpperCase()
+  return normalize(a) === normalize(b)
+}
+
+// 保存前检查热键是否可用：临时注册一次并立即注销，不留下测试注册。
+// 已被本程序注册的热键不能再注册一次，直接按可用返回并注明占用的动作
+export function checkHotkeyAvailable(
+  accelerator: string,
+  shortcuts: GlobalShortcutApi,
+  registry: HotkeyRegistry,
+): HotkeyAvailability {
+  const value = accelerator.trim()
+  if (!value) return { accelerator: value, available: false, reason: 'invalid', detail: '热键为空' }
+
+  const owned = registry.list().find(b => b.status === 'registered' && sameAccelerator(b.accelerator, value))
+  if (owned) return { accelerator: value, available: true, ownedBy: owned.action }
+
+  let registered = false
+  try {
+    if (shortcuts.isRegistered(value)) return { accelerator: value, available: true }
+    registered = shortcuts.register(value, () => { })
+  } catch (e) {
+    return { accelerator: value, available: false, reason: 'invalid', detail: String(e) }
+  } finally {
+    if (registered) shortcuts.unregister(value)
+  }
+  return registered
+    ? { accelerator: value, available: true }
+    : { accelerator: value, available: false, reason: 'conflict', detail: '被其它应用占用或系统不允许' }
+}
@@ -4,8 +4,13 @@ import { getConfig } from './config'
 import { logger } from './logger'
 import { FocusController } from './focus-controller'
 import { triggerRewrite } from './rewrite-window'
+import { RecordHotkeyPressTracker } from './record-hotkey-press'
+import { KeyReleaseGate } from './key-release-gate'
+import type { HotkeyAction, HotkeyConflict } from './hotkey-registry'
 import {
   mainWindow,
+  dashboardWindow,
+  hotkeyRegistry,
   vadEnabled,
   VAD_TOGGLE_HOTKEY,
   setHotkeysRegistered,
@@ -21,7 +26,7 @@ interface ParsedHotkey {
 }
 
 function parseHotkey(hotkey: string): ParsedHotkey {
-  const parts = hotkey.split('+').map(p => p.trim().toUpperCase())
+  const parts = hotkey.normalize('NFKC').split('+').map(p => p.trim().toUpperCase())
   let keycode = 0
   let alt = false, ctrl = false, shift = false, meta = false
 
@@ -66,9 +71,72 @@ function nameToKeycode(name: string): number {
   return map[name] ?? 0
 }
 
+// 注册全局快捷键并记录状态；Electron 对无法解析的 accelerator 直接抛错，按无效处理，不影响其余热键
+function registerGlobalShortcut(action: HotkeyAction, accelerator: string, callback: () => void): boolean {
+  let registered = false
+  try {
+    registered = globalShortcut.register(accelerator, callback)
+  } catch (e) {
+    hotkeyRegistry.set({ action, accelerator, status: 'invalid', detail: String(e) })
+    return false
+  }
+  hotkeyRegistry.set({
+    action,
+    accelerator,
+    status: registered ? 'registered' : 'failed',
+    detail: registered ? undefined : '被其它应用占用或系统不允许',
+  })
+  if (!registered) emitHotkeyConflict(action, accelerator)
+  return registered
+}
+
+const HOTKEY_ACTION_NAMES: Record<HotkeyAction, string> = {
+  'record': '录音',
+  'repeat-command': '重复指令',
+  'undo-last-insert': '撤销输入',
+  'vad-toggle': 'VAD 开关',
+  'rewrite': '改写',
+}
+
+// 注册失败多为被其它应用占用，提示用户在设置中换一个组合
+function emitHotkeyConflict(action: HotkeyAction, accelerator: string) {
+  const conflict: HotkeyConflict = {
+    action,
+    accelerator,
+    message: `${HOTKEY_ACTION_NAMES[action]}热键 ${accelerator} 注册失败，可能已被其它应用占用，请在设置中更换`,
+  }
+  mainWindow?.webContents.send('hotkey-conflict', conflict)
+  dashboardWindow?.webContents.send('hotkey-conflict', conflict)
+}
+
+function broadcastHotkeys() {
+  const bindings = hotkeyRegistry.list()
+  mainWindow?.webContents.send('hotkeys-updated', bindings)
+  dashboardWindow?.webContents.send('hotkeys-updated', bindings)
+}
+
+// 录音热键的当前录音状态复位（discard-recording 丢弃录音后调用），未注册热键时为空操作
+let resetRecordHotkey: () => void = () => { }
+
+export function cancelHotkeyRecording(): void {
+  resetRecordHotkey()
+}
+
+// 录音热键（含修饰键）全部松开前为按住状态，连续分段的中间段等松开后再输入
+const recordKeyRelease = new KeyReleaseGate()
+
+export function waitForRecordHotkeyRelease(timeoutMs?: number): Promise<boolean> {
+  return recordKeyRelease.wait(timeoutMs)
+}
+
+export function isRecordHotkeyHeld(): boolean {
+  return recordKeyRelease.isHeld
+}
+
 export function registerHotkey(
   focusController: FocusController,
   setVadEnabledState: (enabled: boolean, emit: boolean) => boolean,
+  cycleCommandProfile: () => void,
 ) {
   if (hotkeysRegistered) return
   const config = getConfig()
@@ -77,12 +145,17 @@ export function registerHotkey(
 
   if (!parsed.keycode) {
     logger.error(`[热键] 无法解析热键: ${config.hotkey.record}`)
+    hotkeyRegistry.set({ action: 'record', accelerator: config.hotkey.record, status: 'invalid', detail: '无法解析主键' })
+    broadcastHotkeys()
     return
   }
 
   let isRecording = false
   let awaitingModifierRelease = false
+  // 主键已松开、修饰键仍按下：修饰键松开后才放行中间段输入
+  let keyReleasePending = false
   let prevApp: string | null = null
+  const press = new RecordHotkeyPressTracker(config.hotkey.recordMode, config.hotkey.tapThresholdMs)
 
   const hasRequiredModifierPressed = (e: {
     altKey: boolean
@@ -104,28 +177,59 @@ export function registerHotkey(
     mainWindow?.webContents.send('hotkey-stop-recording', prevApp)
   }
 
+  resetRecordHotkey = () => {
+    isRecording = false
+    awaitingModifierRelease = false
+    keyReleasePending = false
+    recordKeyRelease.release()
+    prevApp = null
+    press.reset()
+  }
+
+  const startRecording = async (reason: string) => {
+    isRecording = true
+    awaitingModifierRelease = false
+    keyReleasePending = false
+    recordKeyRelease.hold()
+    // 先通知渲染进程开始录音，不等焦点快照（避免 Windows 上 PowerShell 延迟）
+    mainWindow?.webContents.send('hotkey-state', 'recording')
+    prevApp = await focusController.captureSnapshot(reason)
+  }
+
   uIOhook.on('keydown', async (e) => {
-    if (isRecording) return
     if (e.keycode !== parsed.keycode) return
     if (e.altKey !== parsed.alt) return
     if (e.ctrlKey !== parsed.ctrl) return
     if (e.shiftKey !== parsed.shift) return
     if (e.metaKey !== parsed.meta) return
 
-    isRecording = true
-    awaitingModifierRelease = false
-    // 先通知渲染进程开始录音，不等焦点快照（避免 Windows 上 PowerShell 延迟）
-    mainWindow?.webContents.send('hotkey-state', 'recording')
-    prevApp = await focusController.captureSnapshot('hotkey-keydown')
+    const action = press.press()
+    if (action === 'stop') {
+      logger.info('[热键] 再次按下，结束连续录音')
+      stopRecording()
+      return
+    }
+    if (action !== 'start') return
+    await startRecording('hotkey-keydown')
     logger.info(`[热键] 按下，开始录音，前台应用: ${prevApp ?? 'null'}`)
   })
 
   uIOhook.on('keyup', (e) => {
-    if (!isRecording) return
     const hasModifierPressed = hasRequiredModifierPressed(e)
 
     // 主键释放时，如果修饰键仍按下，先进入等待态；避免“先松主键后松修饰键”造成后续输入串键。
     if (e.keycode === parsed.keycode) {
+      if (hasModifierPressed) {
+        keyReleasePending = recordKeyRelease.isHeld
+      } else {
+        recordKeyRelease.release()
+      }
+      const action = press.release()
+      if (action === 'keep') {
+        logger.info(`[热键] 轻按（短于 ${config.hotkey.tapThresholdMs}ms），保持录音，再按一次结束`)
+        return
+      }
+      if (action !== 'stop' || !isRecording) return
       if (hasModifierPressed) {
         awaitingModifierRelease = true
         return
@@ -133,6 +237,11 @@ export function registerHotkey(
       stopRecording()
       return
     }
+    if (keyReleasePending && !hasModifierPressed) {
+      keyReleasePending = false
+      recordKeyRelease.release()
+    }
+    if (!isRecording) return
 
     // 若主键已松开且处于等待态，只要本次事件后修饰键全部释放就结束录音。
     if (!awaitingModifierRelease) return
@@ -145,15 +254,19 @@ export function registerHotkey(
     uIOhook.start()
     logger.info('[热键] uiohook 已启用（按住说话，松开识别）')
   } catch (e) {
+    hotkeyRegistry.set({ action: 'record', accelerator: config.hotkey.record, status: 'failed', detail: `uiohook 启动失败: ${String(e)}` })
+    broadcastHotkeys()
     throw new Error(`[热键] uiohook 启动失败: ${String(e)}`)
   }
 
-  const registered = globalShortcut.register(config.hotkey.record, async () => {
-    if (!isRecording) {
-      isRecording = true
-      // 先通知渲染进程，再异步获取焦点快照
-      mainWindow?.webContents.send('hotkey-state', 'recording')
-      prevApp = await focusController.captureSnapshot('hotkey-shortcut-fallback')
+  // uiohook 已处理的按下在这里得到 ignore；结束连续录音的按下若 uiohook 漏掉，这里同样结束
+  const registered = registerGlobalShortcut('record', config.hotkey.record, async () => {
+    const action = press.press()
+    if (action === 'stop') {
+      logger.info('[热键/拦截网] 捕获再次按下，结束连续录音')
+      stopRecording()
+    } else if (action === 'start') {
+      await startRecording('hotkey-shortcut-fallback')
       logger.info(`[热键/拦截网] 捕获按下，开始录音，前台应用: ${prevApp ?? 'null'}`)
     }
   })
@@ -162,10 +275,61 @@ export function registerHotkey(
     logger.info(`[热键] 已注册拦截: ${config.hotkey.record}`)
   } else {
     logger.error(`[热键] 拦截注册失败，被其它应用占用或系统不允许: ${config.hotkey.record}`)
+    // 按住说话由 uiohook 监听，拦截失败时热键仍可用，只是按键会同时传给前台应用
+    hotkeyRegistry.set({
+      action: 'record',
+      accelerator: config.hotkey.record,
+      status: 'registered',
+      detail: '拦截注册失败，按键会同时传给前台应用',
+    })
+  }
+
+  const repeatHotkey = config.hotkey.repeatCommand
+  if (repeatHotkey) {
+    const repeatRegistered = registerGlobalShortcut('repeat-command', repeatHotkey, async () => {
+      const targetApp = await focusController.captureSnapshot('hotkey-repeat-command')
+      logger.info(`[热键] 重复上一条指令，前台应用: ${targetApp ?? 'null'}`)
+      mainWindow?.webContents.send('hotkey-repeat-command', targetApp)
+    })
+    if (repeatRegistered) {
+      logger.info(`[热键] 已注册重复指令快捷键: ${repeatHotkey}`)
+    } else {
+      logger.error(`[热键] 重复指令快捷键注册失败: ${repeatHotkey}`)
+    }
+  }
+
+  const undoHotkey = config.hotkey.undoLastInsert
+  if (undoHotkey) {
+    const undoRegistered = registerGlobalShortcut('undo-last-insert', undoHotkey, async () => {
+      const targetApp = await focusController.captureSnapshot('hotkey-undo-last-insert')
+      logger.info(`[热键] 撤销上一次输入，前台应用: ${targetApp ?? 'null'}`)
+      mainWindow?.webContents.send('hotkey-undo-last-insert', targetApp)
+    })
+    if (undoRegistered) {
+      logger.info(`[热键] 已注册撤销输入快捷键: ${undoHotkey}`)
+    } else {
+      logger.error(`[热键] 撤销输入快捷键注册失败: ${undoHotkey}`)
+    }
+  }
+
+  const cycleProfileHotkey = config.hotkey.cycleProfile
+  if (cycleProfileHotkey) {
+    const cycleRegistered = registerGlobalShortcut('cycle-profile', cycleProfileHotkey, () => {
+      try {
+        cycleCommandProfile()
+      } catch (e) {
+        logger.error(`[热键] 切换指令方案失败: ${String(e)}`)
+      }
+    })
+    if (cycleRegistered) {
+      logger.info(`[热键] 已注册切换指令方案快捷键: ${cycleProfileHotkey}`)
+    } else {
+      logger.error(`[热键] 切换指令方案快捷键注册失败: ${cycleProfileHotkey}`)
+    }
   }
 
   logger.info(`[VAD] 注册切换快捷键: ${VAD_TOGGLE_HOTKEY}`)
-  const vadToggleRegistered = globalShortcut.register(VAD_TOGGLE_HOTKEY, () => {
+  const vadToggleRegistered = registerGlobalShortcut('vad-toggle', VAD_TOGGLE_HOTKEY, () => {
     const enabled = setVadEnabledState(!vadEnabled, true)
     logger.info(`[VAD] 通过快捷键 ${VAD_TOGGLE_HOTKEY} 切换为 ${enabled ? '开启' : '关闭'}`)
   })
@@ -176,7 +340,7 @@ export function registerHotkey(
   }
 
   logger.info('[Rewrite] 注册快捷键: Alt+W')
-  const rewriteRegistered = globalShortcut.register('Alt+W', () => {
+  const rewriteRegistered = registerGlobalShortcut('rewrite', 'Alt+W', () => {
     logger.info('[Rewrite] 热键 Alt+W 触发')
     triggerRewrite()
   })
@@ -186,5 +350,6 @@ export function registerHotkey(
     logger.error('[Rewrite] 快捷键注册失败: Alt+W')
   }
   setHotkeysRegistered(true)
+  broadcastHotkeys()
   logger.info('[热键] 注册流程完成')
 }
@@ -6,13 +6,26 @@ import {
   nativeImage,
   globalShortcut,
   screen,
-  session,
+  dialog,
+  type MenuItemConstructorOptions,
 } from 'electron'
 import * as path from 'path'
 import { uIOhook } from 'uiohook-napi'
-import { getConfig, saveConfig } from './config'
+import {
+  getConfig,
+  saveConfig,
+  flushConfig,
+  initConfigStore,
+  peekConfigFile,
+  REQUIRE_CONFIG_ENV,
+  type ConfigStoreInitResult,
+} from './config'
+import { resolveAudioHost, DEFAULT_AUDIO_HOST } from './audio-host'
+import { isLoopbackSupported } from './audio-source'
 import { disposeLocalRecognizer } from './local-asr'
-import { initLogger, logger } from './logger'
+import { validateServerUrl } from './asr'
+import { asrWsClient } from './asr-ws'
+import { initLogger, logger, flushLogFileSync } from './logger'
 import { FocusController } from './focus-controller'
 import { closeDb, initDb } from './db'
 import { isSelfAppId } from './self-app'
@@ -31,10 +44,23 @@ import {
   FLOAT_HEIGHT,
   registerProcessErrorHooks,
   attachWebContentsDiagnostics,
+  hotkeyRegistry,
 } from './app-context'
 import { checkPermissionsAndGuide, emitPermissionWarning } from './permissions'
 import { registerHotkey } from './hotkeys'
-import { setupIpc, emitAsrRuntimeStatus, ensureLocalRecognizerReady } from './ipc'
+import { trayMouseAction, usesNativeContextMenu, type TrayMouseEvent } from './tray-trigger'
+import {
+  setupIpc,
+  emitAsrRuntimeStatus,
+  ensureLocalRecognizerReady,
+  startServerHealthMonitor,
+  stopServerHealthMonitor,
+  triggerQuickRecord,
+  setCommandMatching,
+  switchCommandProfile,
+  cycleCommandProfile,
+} from './ipc'
+import { profileLabel } from './command-profiles'
 
 // ── 共享实例 ──
 
@@ -47,20 +73,19 @@ if (!gotSingleInstanceLock) {
   app.quit()
 }
 
-function setVadEnabledState(enabled: boolean, emitToRenderer = false): boolean {
+// reason 区分用户切换与空闲超时自动关闭，随 vad-state 事件通知各窗口
+function setVadEnabledState(enabled: boolean, emitToRenderer = false, reason: 'user' | 'idle-timeout' = 'user'): boolean {
   if (vadEnabled === enabled && !emitToRenderer) return vadEnabled
   setVadEnabled(enabled)
   const cfg = getConfig()
-  cfg.vad = {
-    enabled,
-    speechThreshold: cfg.vad?.speechThreshold ?? 0.06,
-    silenceTimeoutMs: cfg.vad?.silenceTimeoutMs ?? 800,
-    minSpeechDurationMs: cfg.vad?.minSpeechDurationMs ?? 300,
-  }
+  // 保留其余 VAD 配置（帧长、激活词、语音段时长限制等），只切换开关
+  cfg.vad = { ...cfg.vad, enabled }
   saveConfig(cfg)
   if (emitToRenderer) {
     mainWindow?.webContents.send('toggle-vad', enabled)
   }
+  mainWindow?.webContents.send('vad-state', { enabled, reason })
+  dashboardWindow?.webContents.send('vad-state', { enabled, reason })
   updateTrayMenu()
   return enabled
 }
@@ -175,9 +200,49 @@ function createTray() {
   const t = new Tray(icon)
   setTray(t)
   t.setToolTip('朗珈语音输入法')
+  t.on('click', () => handleTrayMouse('click'))
+  t.on('double-click', () => handleTrayMouse('double-click'))
+  t.on('right-click', () => handleTrayMouse('right-click'))
+  updateTrayMenu()
+}
+
+// 按 tray.showTrigger 处理托盘点击：显示浮窗（已显示时不切换隐藏，避免双击被拆成两次单击时来回闪烁）
+function handleTrayMouse(event: TrayMouseEvent) {
+  const action = trayMouseAction(getConfig().tray.showTrigger, event)
+  if (action === 'popup-menu') {
+    if (trayMenu) tray?.popUpContextMenu(trayMenu)
+    return
+  }
+  if (action !== 'show-window' || !mainWindow) return
+  if (mainWindow.isMinimized()) mainWindow.restore()
+  if (!mainWindow.isVisible()) {
+    mainWindow.showInactive()
+    logger.info(`[App] 托盘 ${event} 显示浮窗`)
+  }
   updateTrayMenu()
 }
 
+let trayMenu: Menu | null = null
+
+// 配置了指令方案时显示单选子菜单，未配置时不占菜单位置
+function commandProfileMenuItems(): MenuItemConstructorOptions[] {
+  const cfg = getConfig()
+  if (cfg.voiceCommandProfiles.length === 0) return []
+  const names = ['', ...cfg.voiceCommandProfiles.map(profile => profile.name)]
+  return [{
+    label: '指令方案',
+    submenu: names.map(name => ({
+      label: profileLabel(name),
+      type: 'radio' as const,
+      checked: cfg.activeVoiceCommandProfile === name,
+      click: () => {
+        switchCommandProfile(name)
+        updateTrayMenu()
+      },
+    })),
+  }]
+}
+
 function updateTrayMenu() {
   if (!tray) return
   const menu = Menu.buildFromTemplate([
@@ -195,6 +260,21 @@ function updateTrayMenu() {
         setVadEnabledState(!vadEnabled, true)
       },
     },
+    {
+      label: `定时录音 ${Math.round(getConfig().input.quickRecordMs / 1000)} 秒`,
+      click: () => { void triggerQuickRecord() },
+    },
+    {
+      // 取消勾选为纯听写：识别结果全部按文字输入，不执行任何指令
+      label: '语音指令',
+      type: 'checkbox',
+      checked: getConfig().voiceCommandOptions.enabled,
+      click: () => {
+        setCommandMatching(!getConfig().voiceCommandOptions.enabled)
+        updateTrayMenu()
+      },
+    },
+    ...commandProfileMenuItems(),
     {
       label: '检查权限并引导',
       click: () => {
@@ -204,7 +284,8 @@ function updateTrayMenu() {
     { type: 'separator' },
     { label: '退出', click: () => app.quit() },
   ])
-  tray.setContextMenu(menu)
+  trayMenu = menu
+  tray.setContextMenu(usesNativeContextMenu(getConfig().tray.showTrigger) ? menu : null)
 }
 
 // ── 应用生命周期 ──
@@ -227,22 +308,65 @@ if (process.platform === 'win32') {
   app.commandLine.appendSwitch('disable-features', 'CalculateNativeWinOcclusion')
 }
 
+// 音频后端开关必须在 app ready 之前追加，此时配置 store 尚未初始化，直接读取配置文件
+const configuredAudioHost = peekConfigFile()?.audioCapture?.host
+const audioHost = resolveAudioHost(configuredAudioHost)
+for (const [name, value] of audioHost.switches) {
+  app.commandLine.appendSwitch(name, value)
+}
+
 if (gotSingleInstanceLock) {
   app.whenReady().then(async () => {
     const t0 = Date.now()
     const ts = () => `+${Date.now() - t0}ms`
 
+    // 先初始化配置：日志级别与日志文件设置都从配置读取，先建日志会隐式写入默认配置，
+    // 使“缺少配置时拒绝启动”失效。此时日志尚未初始化，失败原因只能输出到控制台与弹窗
+    let configInit: ConfigStoreInitResult
+    try {
+      configInit = initConfigStore()
+    } catch (e) {
+      const detail = e instanceof Error ? e.message : String(e)
+      console.error(`[Config] 配置加载失败，退出: ${detail}`)
+      dialog.showErrorBox('朗珈语音输入法 - 配置缺失', detail)
+      app.exit(1)
+      return
+    }
+
     initLogger((entry) => {
       mainWindow?.webContents.send('log-entry', entry)
       dashboardWindow?.webContents.send('log-entry', entry)
     })
     logger.info('应用启动')
+    if (configInit.created) {
+      logger.info(`[Config] 未找到配置文件，已写入默认配置: ${configInit.path}`)
+    } else {
+      logger.info(
+        `[Config] 已加载配置文件: ${configInit.path}` +
+        `${configInit.requireExisting ? `（${REQUIRE_CONFIG_ENV}=1，要求显式配置）` : ''}`,
+      )
+    }
+    if (audioHost.fellBack && configuredAudioHost) {
+      logger.warn(`[Audio] 音频后端 "${configuredAudioHost}" 在当前平台不可用，已回退到系统默认`)
+    } else if (audioHost.id !== DEFAULT_AUDIO_HOST) {
+      logger.info(`[Audio] 使用音频后端: ${audioHost.id}`)
+    }
+    if (peekConfigFile()?.audioCapture?.source === 'loopback') {
+      if (isLoopbackSupported()) logger.info('[Audio] 录音来源: 系统声音（环回）')
+      else logger.warn('[Audio] 系统声音（环回）采集仅支持 Windows，已回退为麦克风')
+    }
 
     logger.info(`[Startup] initDb ${ts()}`)
     await initDb()
 
     logger.info(`[Startup] setupIpc ${ts()}`)
     const config = getConfig()
+    if ((config.asr?.mode ?? 'api') === 'api') {
+      const serverUrlCheck = validateServerUrl(config.server.url, { autoPrependScheme: config.server.autoPrependScheme })
+      if (!serverUrlCheck.ok) {
+        logger.warn(`[Config] 服务地址无效，远程识别将失败: ${serverUrlCheck.error}`)
+      }
+    }
     setVadEnabled(Boolean(config.vad?.enabled))
     setupIpc(focusController, setVadEnabledState, updateTrayMenu)
 
@@ -252,6 +376,7 @@ if (gotSingleInstanceLock) {
     createWindow()
     logger.info(`[Startup] createTray ${ts()}`)
     createTray()
+    startServerHealthMonitor()
     logger.info(`[Startup] startFocusTracker ${ts()}`)
     focusController.startTracking()
     logger.info(`[Startup] checkPermissions ${ts()}`)
@@ -260,7 +385,10 @@ if (gotSingleInstanceLock) {
     if (permissionsReady) {
       logger.info(`[Startup] registerHotkey ${ts()}`)
       try {
-        registerHotkey(focusController, setVadEnabledState)
+        registerHotkey(focusController, setVadEnabledState, () => {
+          cycleCommandProfile()
+          updateTrayMenu()
+        })
       } catch (e) {
         logger.error(String(e))
         emitPermissionWarning(`热键初始化失败：${String(e)} 请确认系统权限已授权，然后重启应用。`)
@@ -285,8 +413,15 @@ if (gotSingleInstanceLock) {
   })
 
   app.on('will-quit', () => {
+    try {
+      flushConfig()
+    } catch (e) {
+      logger.error(`[Config] 退出前写入配置失败: ${String(e)}`)
+    }
+    stopServerHealthMonitor()
     closeDb()
     disposeLocalRecognizer()
+    asrWsClient.close()
     focusController.stopTracking()
     try {
       uIOhook.stop()
@@ -294,5 +429,7 @@ if (gotSingleInstanceLock) {
       // ignore
     }
     globalShortcut.unregisterAll()
+    hotkeyRegistry.clear()
+    flushLogFileSync()
   })
 }
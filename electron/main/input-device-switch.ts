@@ -0,0 +1,71 @@
+// 运行时切换麦克风：采集在浮窗渲染进程，主进程把切换请求发给浮窗，由浮窗按名称找到设备并试打开，
+// 打开成功后回报实际设备，主进程才写入配置；打不开或超时则报错，浮窗继续使用原设备
+
+export interface InputDeviceSelection {
+  deviceId: string    // 空字符串表示系统默认设备
+  deviceName: string
+}
+
+export type InputDeviceSwitchResult =
+  | ({ ok: true } & InputDeviceSelection)
+  | { ok: false; message: string }
+
+export interface InputDeviceSwitchRequest {
+  requestId: number
+  name: string
+}
+
+// 渲染进程回报的数据不可信：缺少 requestId 返回 null，其余字段截断或补默认值
+export function normalizeInputDeviceSwitchReport(raw: unknown): { requestId: number; result: InputDeviceSwitchResult } | null {
+  const data = raw && typeof raw === 'object' ? raw as Record<string, unknown> : {}
+  const requestId = Number(data.requestId)
+  if (!Number.isInteger(requestId) || requestId <= 0) return null
+  if (data.ok !== true) {
+    const message = typeof data.message === 'string' && data.message ? data.message.slice(0, 500) : '切换麦克风失败'
+    return { requestId, result: { ok: false, message } }
+  }
+  return {
+    requestId,
+    result: {
+      ok: true,
+      deviceId: typeof data.deviceId === 'string' ? data.deviceId.slice(0, 500) : '',
+      deviceName: typeof data.deviceName === 'string' ? data.deviceName.slice(0, 200) : '',
+    },
+  }
+}
+
+export class InputDeviceSwitchRequests {
+  private seq = 0
+  private readonly pending = new Map<number, (result: InputDeviceSwitchResult) => void>()
+
+  constructor(private readonly timeoutMs = 10000) { }
+
+  // send 返回 false 表示浮窗不可用，直接失败
+  request(name: string, send: (request: InputDeviceSwitchRequest) => boolean): Promise<InputDeviceSwitchResult> {
+    const requestId = ++this.seq
+    return new Promise((resolve) => {
+      const timer = setTimeout(() => {
+        this.pending.delete(requestId)
+        resolve({ ok: false, message: `切换麦克风超时（${Math.round(this.timeoutMs / 1000)}s 内浮窗未响应）` })
+      }, this.timeoutMs)
+      this.pending.set(requestId, (result) => {
+        clearTimeout(timer)
+        this.pending.delete(requestId)
+        resolve(result)
+      })
+      if (!send({ requestId, name })) {
+        this.pending.get(requestId)?.({ ok: false, message: '浮窗未就绪，无法切换麦克风' })
+      }
+    })
+  }
+
+  // 浮窗回报结果；超时后才到达的回报直接忽略，返回是否有对应的请求
+  settle(raw: unknown): boolean {
+    const report = normalizeInputDeviceSwitchReport(raw)
+    if (!report) return false
+    const resolve = this.pending.get(report.requestId)
+    if (!resolve) return false
+    resolve(report.result)
+    return true
+  }
+}
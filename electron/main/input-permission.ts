@@ -0,0 +1,99 @@
+// 输入模拟可用性：macOS 未授予辅助功能、受限 Windows 无法加载 user32、Linux 缺少 xdotool 时，
+// 每次粘贴/发快捷键都会抛出难以理解的错误。这里只检测一次并缓存“输入不可用”状态，
+// 状态由可用变为不可用时回调一次（用于发出 input-permission-denied 事件），之后直接拒绝，直到用户重新检测
+
+export interface InputPermissionState {
+  available: boolean
+  checkedAt: string
+  error?: string
+  guide?: string
+}
+
+export interface InputBackendProbe {
+  ok: boolean
+  error?: string
+}
+
+export class InputPermissionDeniedError extends Error {
+  constructor(readonly state: InputPermissionState) {
+    super(`输入模拟不可用: ${state.guide ?? state.error ?? '未知原因'}`)
+    this.name = 'InputPermissionDeniedError'
+  }
+}
+
+// 各平台权限/环境缺失时的典型报错片段
+const PERMISSION_ERROR_PATTERNS = [
+  /not allowed to send keystrokes/i,
+  /not allowed assistive access/i,
+  /辅助访问/,
+  /\(1002\)|\(-1719\)|\(-1743\)/,
+  /not authori[sz]ed to send apple events/i,
+  /xdotool.*(ENOENT|not found)|ENOENT.*xdotool/i,
+  /can't open display/i,
+]
+
+export function isInputPermissionError(err: unknown): boolean {
+  const text = err instanceof Error ? err.message : String(err)
+  return PERMISSION_ERROR_PATTERNS.some(re => re.test(text))
+}
+
+export function inputPermissionGuide(platform: NodeJS.Platform = process.platform): string {
+  if (platform === 'darwin') {
+    return '请在 系统设置 -> 隐私与安全性 -> 辅助功能 中允许本应用（如已勾选，可先移除再重新添加），授权后点击“重新检测”。'
+  }
+  if (platform === 'win32') {
+    return '无法加载系统输入组件，可能被安全策略限制；请联系管理员放行本应用，或以相同权限运行目标程序后点击“重新检测”。'
+  }
+  return '请安装 xdotool 并确认在 X11 桌面会话中运行（Wayland 下无法模拟输入），处理后点击“重新检测”。'
+}
+
+export class InputPermissionGate {
+  private current: InputPermissionState | null = null
+
+  constructor(
+    private readonly probe: () => Promise<InputBackendProbe>,
+    private readonly onDenied: (state: InputPermissionState) => void,
+    private readonly platform: NodeJS.Platform = process.platform,
+  ) { }
+
+  get state(): InputPermissionState | null { return this.current }
+
+  // 首次输入前检测一次，之后使用缓存结果；不可用时抛出 InputPermissionDeniedError
+  async ensure(): Promise<void> {
+    if (!this.current) await this.recheck()
+    if (!this.current!.available) throw new InputPermissionDeniedError(this.current!)
+  }
+
+  // 执行一次输入动作：运行中出现权限类报错时同样标记为不可用
+  async run<T>(action: () => Promise<T>): Promise<T> {
+    await this.ensure()
+    try {
+      return await action()
+    } catch (e) {
+      if (!isInputPermissionError(e)) throw e
+      this.update({ available: false, checkedAt: new Date().toISOString(), error: String(e), guide: inputPermissionGuide(this.platform) })
+      throw new InputPermissionDeniedError(this.current!)
+    }
+  }
+
+  // 重新检测（用户授权后调用），返回最新状态
+  async recheck(): Promise<InputPermissionState> {
+    let result: InputBackendProbe
+    try {
+      result = await this.probe()
+    } catch (e) {
+      result = { ok: false, error: e instanceof Error ? e.message : String(e) }
+    }
+    const checkedAt = new Date().toISOString()
+    this.update(result.ok
+      ? { available: true, checkedAt }
+      : { available: false, checkedAt, error: result.error, guide: inputPermissionGuide(this.platform) })
+    return this.current!
+  }
+
+  private update(next: InputPermissionState): void {
+    const wasAvailable = this.current?.available ?? true
+    this.current = next
+    if (wasAvailable && !next.available) this.onDenied(next)
+  }
+}
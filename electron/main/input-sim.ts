@@ -1,8 +1,22 @@
 import { exec, execFile } from 'child_process'
 import { promisify } from 'util'
-import { clipboard } from 'electron'
-import { classifyPasteTargetProbe, type PasteTargetAssessment } from './paste-plan'
+import { clipboard, systemPreferences } from 'electron'
+import {
+  buildPasteKeySequence,
+  classifyPasteTargetProbe,
+  type PasteMode,
+  type PasteTargetAssessment,
+} from './paste-plan'
+import type { InputBackendProbe } from './input-permission'
 import * as win32Focus from './win32-focus'
+import { logger } from './logger'
+import {
+  parseShortcut,
+  parseShortcutSequence,
+  splitShortcutSequence,
+  type ShortcutModifier,
+  type ShortcutParseOptions,
+} from './shortcut'
 
 const execAsync = promisify(exec)
 const execFileAsync = promisify(execFile)
@@ -22,56 +36,326 @@ export interface PasteTargetProbe {
   refineOutcome?: 'writable' | 'non-writable' | 'error'
 }
 
-// 将文字输入到目标窗口（剪贴板粘贴方式）
-export async function typeText(text: string): Promise<void> {
-  clipboard.writeText(text)
+export type TextInputMethod = 'clipboard' | 'keyboard'
+
+// 键盘逐字键入的方式：text 按字符串键入（macOS keystroke / xdotool type），
+// unicode 逐字符按 Unicode 码位发送，部分中文输入法激活的输入框中可避免被输入法截获改写
+export type KeyboardTypingMethod = 'text' | 'unicode'
+
+// 特殊字符（如 ×、μ）的替代输入方式：unicode 按码位单独发送，clipboard 单独经剪贴板粘贴
+export type SpecialCharStrategy = 'unicode' | 'clipboard'
+
+export const DEFAULT_PASTE_SHORTCUT = 'CTRL+V'
+
+export interface PasteOptions {
+  // 粘贴快捷键，默认 CTRL+V（macOS 上为 Cmd+V）；部分终端类输入框需要 CTRL+SHIFT+V
+  pasteShortcut?: string
+  shortcutOptions?: SendShortcutOptions
+  // append 时粘贴前先发送 appendNavigationKey，见 PasteMode
+  pasteMode?: PasteMode
+  appendNavigationKey?: string
+  // false 时直接键盘逐字键入、不经过剪贴板（input.useClipboard），默认走剪贴板粘贴
+  useClipboard?: boolean
+  // 键盘逐字键入（含剪贴板失败回退）时使用的方式，默认 text（input.keyboardMethod）
+  keyboardMethod?: KeyboardTypingMethod
+  // 键盘逐字键入时改走替代方式的字符（input.specialChars）；整段剪贴板粘贴时不需要
+  specialChars?: Record<string, SpecialCharStrategy>
+}
+
+// 将文字输入到目标窗口（默认剪贴板粘贴，useClipboard 为 false 时键盘逐字键入），返回实际使用的输入方式
+// 剪贴板被其他程序占用导致写入失败时，本次回退为键盘逐字键入，不改变用户的输入方式配置
+export async function typeText(text: string, options: PasteOptions = {}): Promise<TextInputMethod> {
+  const sequence = buildPasteKeySequence(
+    options.pasteMode ?? 'replace',
+    options.pasteShortcut ?? DEFAULT_PASTE_SHORTCUT,
+    options.appendNavigationKey,
+  )
+  // 导航键在写剪贴板前发送，键盘逐字输入的回退路径同样追加到末尾
+  for (const step of sequence.slice(0, -1)) {
+    await sendShortcut(step, options.shortcutOptions)
+  }
+  if (options.useClipboard === false) {
+    await typeKeyboardSegments(text, options)
+    return 'keyboard'
+  }
+  try {
+    writeClipboardText(text)
+  } catch (e) {
+    logger.warn(`[Input] 剪贴板写入失败，回退为键盘逐字输入: ${String(e)}`)
+    await typeKeyboardSegments(text, options)
+    return 'keyboard'
+  }
   // 短暂延迟确保剪贴板就绪
   await sleep(50)
-  await pasteClipboard()
+  await pasteClipboard(options)
+  return 'clipboard'
 }
 
-// 模拟组合键，如 "ALT+R"、"F2"
-export async function sendShortcut(shortcut: string): Promise<void> {
-  const platform = process.platform
-  if (platform === 'darwin') {
-    await sendShortcutMac(shortcut)
-  } else if (platform === 'win32') {
-    await sendShortcutWin(shortcut)
+export interface TypingSegment {
+  text: string
+  strategy: 'keyboard' | SpecialCharStrategy
+}
+
+// 按特殊字符表把文字切成连续片段，相邻同一方式的字符合并，其余文字保持原样
+export function splitSpecialCharSegments(text: string, specialChars: Record<string, SpecialCharStrategy>): TypingSegment[] {
+  const segments: TypingSegment[] = []
+  for (const ch of text) {
+    const strategy = Object.prototype.hasOwnProperty.call(specialChars, ch) ? specialChars[ch] : 'keyboard'
+    const last = segments[segments.length - 1]
+    if (last && last.strategy === strategy) last.text += ch
+    else segments.push({ text: ch, strategy })
+  }
+  return segments
+}
+
+// 键盘逐字键入，特殊字符按配置改走码位发送或剪贴板粘贴
+async function typeKeyboardSegments(text: string, options: PasteOptions): Promise<void> {
+  for (const segment of splitSpecialCharSegments(text, options.specialChars ?? {})) {
+    if (segment.strategy === 'keyboard') {
+      await typeTextByKeyboard(segment.text, options.keyboardMethod)
+    } else if (segment.strategy === 'unicode') {
+      await typeTextByKeyboard(segment.text, 'unicode')
+    } else {
+      try {
+        writeClipboardText(segment.text)
+      } catch (e) {
+        logger.warn(`[Input] 特殊字符剪贴板写入失败，改为按码位键入: ${String(e)}`)
+        await typeTextByKeyboard(segment.text, 'unicode')
+        continue
+      }
+      await sleep(50)
+      await pasteClipboard(options)
+    }
+  }
+}
+
+function writeClipboardText(text: string): void {
+  // Windows 编辑控件粘贴单独的 \n 可能不换行，统一写入 CRLF
+  clipboard.writeText(process.platform === 'win32' ? text.replace(/\r?\n/g, '\r\n') : text)
+  const normalize = (value: string) => value.replace(/\r\n/g, '\n')
+  if (normalize(clipboard.readText()) !== normalize(text)) {
+    throw new Error('剪贴板内容校验失败（可能被其他程序占用）')
+  }
+}
+
+// 键盘逐字键入（不经过剪贴板）。unicode 方式各平台的限制：
+// - Windows：两种方式相同，本来就以 KEYEVENTF_UNICODE 逐字符发送码位，不经过输入法
+// - Linux：每个字符以 Unicode keysym（如 U4F60）单独发送，避开 xdotool type 的按键映射；
+//   输入法（fcitx/ibus）仍可见到这些按键，处于中文状态时个别输入法仍可能截获
+// - macOS：System Events 无法直接发送码位，unicode 按 text 方式键入并记录警告
+export async function typeTextByKeyboard(text: string, method: KeyboardTypingMethod = 'text'): Promise<void> {
+  if (method === 'unicode' && process.platform === 'linux') {
+    const keysyms = buildUnicodeKeysyms(text)
+    if (keysyms.length === 0) return
+    await execFileAsync('xdotool', ['key', '--clearmodifiers', '--delay', '0', ...keysyms])
+    return
+  }
+  if (method === 'unicode' && process.platform === 'darwin') {
+    logger.warn('[Input] macOS 不支持按 Unicode 码位键入，改用 keystroke 键入')
+  }
+  if (process.platform === 'darwin') {
+    const lines = buildMacKeystrokeLines(text)
+    if (lines.length === 0) return
+    const args = ['-e', 'tell application "System Events"', ...lines.flatMap(line => ['-e', line]), '-e', 'end tell']
+    await execFileAsync('osascript', args)
+  } else if (process.platform === 'win32') {
+    win32Focus.win32TypeText(text)
   } else {
-    await sendShortcutLinux(shortcut)
+    // xdotool type 会把 \n、\t 作为回车、制表键发送
+    await execFileAsync('xdotool', ['type', '--clearmodifiers', '--', text])
   }
 }
 
-// macOS：AppleScript 发送快捷键
-async function sendShortcutMac(shortcut: string): Promise<void> {
-  const parts = shortcut.toUpperCase().split('+').map(s => s.trim())
-  const modifiers: string[] = []
-  let mainKey = ''
-
-  for (const part of parts) {
-    switch (part) {
-      case 'ALT': modifiers.push('option down'); break
-      case 'CTRL': case 'CONTROL': modifiers.push('control down'); break
-      case 'SHIFT': modifiers.push('shift down'); break
-      case 'META': case 'CMD': case 'COMMAND': modifiers.push('command down'); break
-      default: mainKey = part
+// xdotool key 的按键名：换行/制表符为 Return/Tab，其余字符按码位（含 BMP 之外的字符）转为 U+十六进制 keysym
+export function buildUnicodeKeysyms(text: string): string[] {
+  const keysyms: string[] = []
+  for (const ch of text.replace(/\r\n/g, '\n')) {
+    if (ch === '\r') continue
+    if (ch === '\n') keysyms.push('Return')
+    else if (ch === '\t') keysyms.push('Tab')
+    else keysyms.push(`U${ch.codePointAt(0)!.toString(16).toUpperCase().padStart(4, '0')}`)
+  }
+  return keysyms
+}
+
+// AppleScript keystroke 不能直接包含换行/制表符，拆分后用 key code 发送
+function buildMacKeystrokeLines(text: string): string[] {
+  const lines: string[] = []
+  for (const part of text.replace(/\r\n/g, '\n').split(/([\n\t])/)) {
+    if (!part) continue
+    if (part === '\n') lines.push('key code 36')
+    else if (part === '\t') lines.push('key code 48')
+    else lines.push(`keystroke "${part.replace(/\\/g, '\\\\').replace(/"/g, '\\"')}"`)
+  }
+  return lines
+}
+
+// 连续发送 count 次退格（撤销上一次输入）；逐个调用 sendShortcut 在 macOS/Linux 上每次都要起进程，这里合并为一次
+export async function pressBackspace(count: number): Promise<void> {
+  if (count <= 0) return
+  if (process.platform === 'darwin') {
+    const lines = ['tell application "System Events"', `repeat ${count} times`, 'key code 51', 'end repeat', 'end tell']
+    await execFileAsync('osascript', lines.flatMap(line => ['-e', line]))
+  } else if (process.platform === 'win32') {
+    win32Focus.win32PressBackspace(count)
+  } else {
+    await execFileAsync('xdotool', ['key', '--clearmodifiers', '--repeat', String(count), '--delay', '0', 'BackSpace'])
+  }
+}
+
+// 连续左移光标 count 次（展开短语后回到占位符处），与退格一样合并为一次调用，避免每个字符起一次进程
+export async function pressLeftArrow(count: number): Promise<void> {
+  if (count <= 0) return
+  if (process.platform === 'darwin') {
+    const lines = ['tell application "System Events"', `repeat ${count} times`, 'key code 123', 'end repeat', 'end tell']
+    await execFileAsync('osascript', lines.flatMap(line => ['-e', line]))
+  } else if (process.platform === 'win32') {
+    win32Focus.win32PressLeftArrow(count)
+  } else {
+    await execFileAsync('xdotool', ['key', '--clearmodifiers', '--repeat', String(count), '--delay', '0', 'Left'])
+  }
+}
+
+// 检测当前平台的输入模拟能否工作：macOS 看辅助功能授权，Windows 看 user32 是否加载成功，Linux 看 xdotool 能否执行
+export async function probeInputBackend(): Promise<InputBackendProbe> {
+  if (process.platform === 'darwin') {
+    return systemPreferences.isTrustedAccessibilityClient(false)
+      ? { ok: true }
+      : { ok: false, error: '未授予辅助功能权限' }
+  }
+  if (process.platform === 'win32') {
+    const error = win32Focus.getWin32InputInitError()
+    return error ? { ok: false, error } : { ok: true }
+  }
+  try {
+    await execFileAsync('xdotool', ['version'], { timeout: 1500 })
+    return { ok: true }
+  } catch (e) {
+    return { ok: false, error: `xdotool 不可用: ${e instanceof Error ? e.message : String(e)}` }
+  }
+}
+
+export interface SendShortcutOptions extends ShortcutParseOptions {
+  // 最后一个键按下后保持多久再开始抬起（部分老旧 LIS 对话框需要）
+  holdMs?: number
+  // 逐个按下按键之间的间隔
+  keyDelayMs?: number
+  // 多步序列（"ALT+F; S"）相邻两步之间的间隔
+  stepDelayMs?: number
+  // 发送失败时除抬起本步已按下的键外，再抬起全部修饰键，防止 Ctrl/Alt 卡在按下状态
+  releaseModifiersOnFailure?: boolean
+}
+
+// 模拟组合键，如 "ALT+R"、"F2"；也支持分号分隔的多步序列，如 "ALT+F; S; ENTER"
+export async function sendShortcut(shortcut: string, options: SendShortcutOptions = {}): Promise<void> {
+  const platform = process.platform
+  // 先整体校验，任一步非法则一步都不发送
+  parseShortcutSequence(shortcut, platform, options)
+  const steps = splitShortcutSequence(shortcut)
+  const stepDelayMs = Math.max(0, options.stepDelayMs ?? 0)
+  for (let i = 0; i < steps.length; i++) {
+    if (i > 0 && stepDelayMs > 0) await sleep(stepDelayMs)
+    if (platform === 'darwin') {
+      await sendShortcutMac(steps[i], options)
+    } else if (platform === 'win32') {
+      await sendShortcutWin(steps[i], options)
+    } else {
+      await sendShortcutLinux(steps[i], options)
     }
   }
+}
 
-  // F 键用 key code，字母/数字用 keystroke
+// 发送失败时需要补发抬起的修饰键：本步按下的按逆序在前，开启安全选项时再补上其余修饰键
+function modifiersToRelease(pressed: ShortcutModifier[], options: SendShortcutOptions): ShortcutModifier[] {
+  const reversed = [...pressed].reverse()
+  if (!options.releaseModifiersOnFailure) return reversed
+  const all: ShortcutModifier[] = ['ctrl', 'alt', 'shift', 'meta']
+  return [...reversed, ...all.filter(m => !reversed.includes(m))]
+}
+
+// AppleScript key down / key up 使用的修饰键名称
+const MAC_KEY_NAMES: Record<ShortcutModifier, string> = {
+  alt: 'option', ctrl: 'control', shift: 'shift', meta: 'command',
+}
+
+// macOS：AppleScript 发送快捷键
+async function sendShortcutMac(shortcut: string, options: SendShortcutOptions): Promise<void> {
+  const { modifiers: parsedModifiers, key: mainKey } = parseShortcut(shortcut, 'darwin', options)
+  try {
+    await sendParsedShortcutMac(parsedModifiers, mainKey, options)
+  } catch (e) {
+    // keystroke 本身是原子的，只有 key down 的修饰键可能卡住
+    const lines = modifiersToRelease(parsedModifiers, options).flatMap(m => ['-e', `key up ${MAC_KEY_NAMES[m]}`])
+    await releaseKeysAfterFailure('osascript', ['-e', 'tell application "System Events"', ...lines, '-e', 'end tell'], lines.length > 0)
+    throw e
+  }
+}
+
+// 失败后的补救抬起只尽力而为，自身出错只记日志，不掩盖原始错误
+async function releaseKeysAfterFailure(file: string, args: string[], needed: boolean): Promise<void> {
+  if (!needed) return
+  try {
+    await execFileAsync(file, args)
+    logger.warn(`[Input] 快捷键发送失败，已补发按键抬起: ${file} ${args.join(' ')}`)
+  } catch (e) {
+    logger.error(`[Input] 快捷键发送失败后补发按键抬起也失败: ${String(e)}`)
+  }
+}
+
+async function sendParsedShortcutMac(parsedModifiers: ShortcutModifier[], mainKey: string, options: SendShortcutOptions): Promise<void> {
+  const macModifierMap: Record<ShortcutModifier, string> = {
+    alt: 'option down',
+    ctrl: 'control down',
+    shift: 'shift down',
+    meta: 'command down',
+  }
+  const modifiers = parsedModifiers.map(m => macModifierMap[m])
+
+  // F 键与 Enter/方向键/Home 等具名键用 key code，字母/数字用 keystroke
   const isFKey = /^F\d+$/.test(mainKey)
+  const navKeyCode = MAC_NAV_KEY_CODES[mainKey]
   const modStr = modifiers.length ? `using {${modifiers.join(', ')}}` : ''
 
-  let script: string
-  if (isFKey) {
-    const fKeyCode = getFKeyCode(mainKey)
-    script = `tell application "System Events" to key code ${fKeyCode} ${modStr}`
-  } else {
-    const key = mainKey.length === 1 ? mainKey.toLowerCase() : mainKey.toLowerCase()
-    script = `tell application "System Events" to keystroke "${key}" ${modStr}`
+  const keyAction = isFKey
+    ? `key code ${getFKeyCode(mainKey)}`
+    : navKeyCode !== undefined
+      ? `key code ${navKeyCode}`
+      : `keystroke "${mainKey.toLowerCase()}"`
+
+  const holdMs = Math.max(0, options.holdMs ?? 0)
+  const keyDelayMs = Math.max(0, options.keyDelayMs ?? 0)
+  if (holdMs === 0 && keyDelayMs === 0) {
+    const script = `tell application "System Events" to ${keyAction} ${modStr}`
+    await execAsync(`osascript -e '${script}'`)
+    return
+  }
+
+  // 需要延时：逐个 key down 修饰键，按主键后保持 holdMs 再抬起
+  const lines: string[] = []
+  for (const m of parsedModifiers) {
+    lines.push(`key down ${MAC_KEY_NAMES[m]}`)
+    if (keyDelayMs > 0) lines.push(`delay ${keyDelayMs / 1000}`)
   }
+  if (mainKey) lines.push(keyAction)
+  if (holdMs > 0) lines.push(`delay ${holdMs / 1000}`)
+  for (const m of [...parsedModifiers].reverse()) lines.push(`key up ${MAC_KEY_NAMES[m]}`)
+  const args = ['-e', 'tell application "System Events"', ...lines.flatMap(line => ['-e', line]), '-e', 'end tell']
+  await execFileAsync('osascript', args)
+}
+
+function isDefaultPasteShortcut(shortcut: string): boolean {
+  const steps = parseShortcutSequence(shortcut, 'win32')
+  return steps.length === 1 && steps[0].key === 'V'
+    && steps[0].modifiers.length === 1 && steps[0].modifiers[0] === 'ctrl'
+}
 
-  await execAsync(`osascript -e '${script}'`)
+// macOS 具名键（回车、方向键、Home/End、小键盘数字等）的 key code；keystroke 只能键入字符，按不出这些键
+const MAC_NAV_KEY_CODES: Record<string, number> = {
+  ENTER: 36, RETURN: 36, TAB: 48, SPACE: 49, BACKSPACE: 51, ESCAPE: 53, ESC: 53,
+  DELETE: 117, DEL: 117, LEFT: 123, RIGHT: 124, DOWN: 125, UP: 126,
+  HOME: 115, END: 119, PAGEUP: 116, PAGEDOWN: 121,
+  NUMPAD0: 82, NUMPAD1: 83, NUMPAD2: 84, NUMPAD3: 85, NUMPAD4: 86,
+  NUMPAD5: 87, NUMPAD6: 88, NUMPAD7: 89, NUMPAD8: 91, NUMPAD9: 92,
 }
 
 // macOS F 键 key code 映射
@@ -84,24 +368,69 @@ function getFKeyCode(fKey: string): number {
   return map[fKey] ?? 0
 }
 
-// Windows：koffi keybd_event
-async function sendShortcutWin(shortcut: string): Promise<void> {
-  win32Focus.win32SendShortcut(shortcut)
+// Windows：koffi SendInput
+async function sendShortcutWin(shortcut: string, options: SendShortcutOptions): Promise<void> {
+  await win32Focus.win32SendShortcut(shortcut, {
+    holdMs: options.holdMs,
+    keyDelayMs: options.keyDelayMs,
+    releaseModifiersOnFailure: options.releaseModifiersOnFailure,
+  })
+}
+
+// xdotool 按 X keysym 名称区分大小写，具名键需使用规范名称（ENTER 对应 Return）
+const LINUX_KEY_NAMES: Record<string, string> = {
+  ENTER: 'Return', RETURN: 'Return', TAB: 'Tab', SPACE: 'space', BACKSPACE: 'BackSpace',
+  ESCAPE: 'Escape', ESC: 'Escape', DELETE: 'Delete', DEL: 'Delete',
+  LEFT: 'Left', RIGHT: 'Right', UP: 'Up', DOWN: 'Down',
+  HOME: 'Home', END: 'End', PAGEUP: 'Prior', PAGEDOWN: 'Next',
+  NUMPAD0: 'KP_0', NUMPAD1: 'KP_1', NUMPAD2: 'KP_2', NUMPAD3: 'KP_3', NUMPAD4: 'KP_4',
+  NUMPAD5: 'KP_5', NUMPAD6: 'KP_6', NUMPAD7: 'KP_7', NUMPAD8: 'KP_8', NUMPAD9: 'KP_9',
 }
 
 // Linux：xdotool
-async function sendShortcutLinux(shortcut: string): Promise<void> {
-  const parts = shortcut.toUpperCase().split('+').map(s => s.trim())
-  const modMap: Record<string, string> = {
-    ALT: 'alt', CTRL: 'ctrl', CONTROL: 'ctrl', SHIFT: 'shift',
-    META: 'super', CMD: 'super',
+async function sendShortcutLinux(shortcut: string, options: SendShortcutOptions): Promise<void> {
+  const { modifiers, key } = parseShortcut(shortcut, 'linux', options)
+  const modMap: Record<ShortcutModifier, string> = {
+    alt: 'alt', ctrl: 'ctrl', shift: 'shift', meta: 'super',
+  }
+  const mainKeyName = key ? LINUX_KEY_NAMES[key] ?? key.toLowerCase() : ''
+  const keyNames = [...modifiers.map(m => modMap[m]), ...(mainKeyName ? [mainKeyName] : [])]
+  try {
+    await sendKeyNamesLinux(keyNames, options)
+  } catch (e) {
+    // 中途失败时已按下的键可能没有抬起：主键先抬，修饰键逆序抬起
+    const releases = [...(mainKeyName ? [mainKeyName] : []), ...modifiersToRelease(modifiers, options).map(m => modMap[m])]
+    await releaseKeysAfterFailure('xdotool', ['keyup', ...releases], releases.length > 0)
+    throw e
   }
-  const keys = parts.map(p => modMap[p] ?? p.toLowerCase()).join('+')
-  await execAsync(`xdotool key ${keys}`)
 }
 
-// 粘贴剪贴板内容
-export async function pasteClipboard(): Promise<void> {
+async function sendKeyNamesLinux(keyNames: string[], options: SendShortcutOptions): Promise<void> {
+  const holdMs = Math.max(0, options.holdMs ?? 0)
+  const keyDelayMs = Math.max(0, options.keyDelayMs ?? 0)
+  if (holdMs === 0 && keyDelayMs === 0) {
+    await execAsync(`xdotool key ${keyNames.join('+')}`)
+    return
+  }
+
+  // xdotool 支持在同一条命令中串联 keydown / sleep / keyup
+  const args: string[] = []
+  keyNames.forEach((name, i) => {
+    if (i > 0 && keyDelayMs > 0) args.push('sleep', String(keyDelayMs / 1000))
+    args.push('keydown', name)
+  })
+  if (holdMs > 0) args.push('sleep', String(holdMs / 1000))
+  for (const name of [...keyNames].reverse()) args.push('keyup', name)
+  await execFileAsync('xdotool', args)
+}
+
+// 粘贴剪贴板内容：默认快捷键走各平台原生粘贴，自定义快捷键走 sendShortcut
+export async function pasteClipboard(options: PasteOptions = {}): Promise<void> {
+  const shortcut = options.pasteShortcut?.trim()
+  if (shortcut && !isDefaultPasteShortcut(shortcut)) {
+    await sendShortcut(shortcut, options.shortcutOptions)
+    return
+  }
   if (process.platform === 'darwin') {
     await execAsync(`osascript -e 'tell application "System Events" to keystroke "v" using {command down}'`)
   } else if (process.platform === 'win32') {
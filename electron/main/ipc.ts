@@ -1,21 +1,112 @@
-import { ipcMain, clipboard, BrowserWindow, IpcMainInvokeEvent, app, Menu, screen } from 'electron'
+import { ipcMain, clipboard, BrowserWindow, IpcMainInvokeEvent, app, Menu, screen, shell, globalShortcut, dialog, session, type WebFrameMain } from 'electron'
 import * as path from 'path'
-import { getConfig, saveConfig, AppConfig } from './config'
-import { recognize } from './asr'
-import { recognizeLocal, initLocalRecognizer, disposeLocalRecognizer } from './local-asr'
-import { getModelInfoList, inspectLocalModelStatus, deleteModelCache } from './model-manager'
-import { logger, getLogBuffer, clearLogs } from './logger'
-import { matchVoiceCommand } from './voice-commands'
-import { typeText, sendShortcut, assessPasteTarget } from './input-sim'
-import { normalizeAsrText, applyTextRules } from './asr-text'
+import * as fs from 'fs'
+import { randomUUID } from 'crypto'
+import { getConfig, saveConfig, flushConfig, AppConfig, isLogLevel, VAD_ENERGY_SMOOTHING_MAX } from './config'
+import { recognize, validateServerUrl } from './asr'
+import { asrWsClient } from './asr-ws'
+import { recognizeLocal, initLocalRecognizer, disposeLocalRecognizer, detectSpeechLocal } from './local-asr'
+import { MODELS, getModelInfoList, inspectLocalModelStatus, deleteModelCache, type ModelDependencyStatus } from './model-manager'
+import { logger, getLogBuffer, clearLogs, getLogLevel, setLogLevel, applyLogFileSettings, getLogFilePath, type LogLevel } from './logger'
+import {
+  matchVoiceCommand,
+  previewVoiceCommand,
+  listVoiceCommands,
+  normalizeCommandText,
+  findClosestVoiceCommand,
+  findVoiceCommandPhrase,
+  VoiceCommandCooldown,
+  LastCommandTracker,
+  PostCommandLockout,
+  applyPostCommandLockout,
+  isRepeatCommand,
+  resolveCommandCooldownMs,
+  matchVoiceCommandCandidates,
+  isAmbiguousCandidates,
+  resolveCommandPhrase,
+  expandVoiceCommandAliases,
+  canonicalCommandPhrase,
+  EXEC_COMMAND_PREFIX,
+  type ExecCommand,
+  type VoiceCommandMatch,
+} from './voice-commands'
+import { launchExecCommand } from './command-exec'
+import {
+  applyCommandProfile,
+  listCommandProfiles,
+  nextCommandProfile,
+  profileLabel,
+  resolveCommandProfileName,
+} from './command-profiles'
+import {
+  typeText,
+  sendShortcut,
+  pressBackspace,
+  pressLeftArrow,
+  assessPasteTarget,
+  probeInputBackend,
+  type SendShortcutOptions,
+  type PasteOptions,
+  type TextInputMethod,
+} from './input-sim'
+import { InputPermissionGate, InputPermissionDeniedError } from './input-permission'
+import { LastErrorStore } from './last-error'
+import { LastInsertTracker } from './undo-insert'
+import { LastRecordingStore } from './last-recording'
+import { installLoopbackCaptureHandler } from './audio-source'
+import { SessionTranscript, transcriptFileName } from './session-transcript'
+import { isVadBackendName, vadBackendUnavailableReason, VAD_BACKEND_LABELS, VAD_BACKEND_NAMES } from './vad-backend'
+import { validateShortcut } from './shortcut'
+import { SingleFlightQueue } from './single-flight'
+import { ResultAutoHide } from './result-auto-hide'
+import {
+  RecognitionStageTracker,
+  resolveTriggeredAt,
+  type RecognitionStage,
+  type RecognitionStageEvent,
+} from './recognition-stage'
+import {
+  normalizeAsrText,
+  tracePostprocess,
+  stripActivationPhrase,
+  EmptyResultTracker,
+  checkTextLength,
+  LOG_TEXT_MAX_CHARS,
+  type PostprocessTrace,
+} from './asr-text'
+import { truncateText } from '../../shared/text'
+import { clampInputGain } from '../../shared/input-gain'
 import { optimizeAsrTextWithLlm, generateDailySummary } from './llm-service'
-import { FocusController } from './focus-controller'
+import { FocusController, findWindowByTitle } from './focus-controller'
 import { checkPermissionsAndGuide } from './permissions'
 import { insertRecognition, getStats, getRecentHistory, getAllHistory, getRecordsByDate } from './db'
 import { buildPasteExecutionPlan, type FloatPasteFallbackReason } from './paste-plan'
+import { readWavInfo, downmixWavToMono, wavToMonoFloat32, normalizeWavFile } from './wav-info'
+import { getKeywordSpotter, saveKeywordTemplates } from './keyword-spotter'
+import { listAudioHosts } from './audio-host'
+import { exportConfigText, parseConfigImport } from './config-transfer'
+import { InputArmState } from './safe-mode'
+import { runSelfTest, type SelfTestCapture } from './self-test'
+import { encodeWavToFlac } from './flac'
+import { AsrRequestRateLimiter, isRateLimited, type RecognitionSource } from './request-rate-limit'
+import { normalizeAudioLifecycleEvent, isAudioLifecycleError, describeAudioLifecycleEvent } from './audio-lifecycle'
+import { checkHotkeyAvailable } from './hotkey-registry'
+import { cancelHotkeyRecording, isRecordHotkeyHeld, waitForRecordHotkeyRelease } from './hotkeys'
+import { buildWebhookPayload, sendWebhook } from './webhook'
+import { InputDeviceSwitchRequests, type InputDeviceSelection } from './input-device-switch'
+import { QuickRecordRequests, clampQuickRecordMs } from './quick-record'
+import {
+  ServerHealthMonitor,
+  LatencyHistory,
+  checkServerHealth,
+  trayTooltipFor,
+  type ServerHealthTarget,
+  type ServerStatus,
+} from './server-health'
 import {
   mainWindow,
   dashboardWindow,
+  tray,
   setDashboardWindow,
   vadEnabled,
   floatPos,
@@ -25,6 +116,7 @@ import {
   DEFAULT_LOCAL_MODEL_ID,
   stringifyErrorLike,
   attachWebContentsDiagnostics,
+  hotkeyRegistry,
 } from './app-context'
 
 type AsrRuntimePhase = 'idle' | 'starting' | 'ready' | 'error'
@@ -36,12 +128,54 @@ interface AsrRuntimeStatus {
   updatedAt: string
 }
 
+// 识别结果：指令与文字分开返回，界面自行决定展示方式；无结果（静音、被过滤等）时返回 null
+type RecognitionResult =
+  | { kind: 'command'; text: string; phrase: string; shortcut: string; repeated?: boolean }
+  | { kind: 'text'; text: string; phrase?: string }
+
+// 识别阶段的产出：后处理后的文字（text）与进入指令匹配前的原始规整结果（normalizedText）
+interface RecognizedSpeech {
+  cfg: AppConfig
+  asrMode: AppConfig['asr']['mode']
+  text: string
+  normalizedText: string
+}
+
+interface AudioInfo {
+  sourceSampleRate: number | null
+  sourceChannels: number | null
+  outputSampleRate: number
+  outputChannels: number
+  deviceName: string
+  capturing: boolean
+  vadListening: boolean
+  updatedAt: string
+}
+
 interface FloatPasteFallbackPayload {
   requestId: number
+  traceId?: string             // 本句识别的追踪 ID，与发送给服务端的一致
   text: string
   targetAppId: string | null
   reason: FloatPasteFallbackReason
   precheckReason: 'ok' | 'unknown' | 'no-foreground-window' | 'no-focused-control' | 'focused-control-without-caret'
+  copiedToClipboard: boolean   // 已按 input.clipboardOnFailure 把文本放入剪贴板，可手动粘贴
+}
+
+// 输入结果事件：区分“已输入到目标应用”与“仅识别未输入”
+interface InputCompletePayload {
+  requestId: number
+  traceId?: string
+  text: string
+  method: 'clipboard' | 'keyboard' | 'shortcut'
+}
+
+interface InputFailedPayload {
+  requestId: number
+  traceId?: string
+  text: string
+  error: string
+  copiedToClipboard: boolean
 }
 
 interface FloatLayoutMetrics {
@@ -58,7 +192,215 @@ let asrRuntimeStatus: AsrRuntimeStatus = {
   message: '',
   updatedAt: new Date().toISOString(),
 }
+// 浮窗尚未打开麦克风时源格式未知
+let audioInfo: AudioInfo = {
+  sourceSampleRate: null,
+  sourceChannels: null,
+  outputSampleRate: 16000,
+  outputChannels: 1,
+  deviceName: '',
+  capturing: false,
+  vadListening: false,
+  updatedAt: new Date().toISOString(),
+}
+
+// 浮窗上报的音频格式不可信：非正数的采样率、声道数视为未知，输出格式缺失时按 16kHz 单声道
+function normalizeAudioInfo(raw: unknown): AudioInfo {
+  const data = raw && typeof raw === 'object' ? raw as Record<string, unknown> : {}
+  const positive = (v: unknown) => typeof v === 'number' && Number.isFinite(v) && v > 0 ? v : null
+  return {
+    sourceSampleRate: positive(data.sourceSampleRate),
+    sourceChannels: positive(data.sourceChannels),
+    outputSampleRate: positive(data.outputSampleRate) ?? 16000,
+    outputChannels: positive(data.outputChannels) ?? 1,
+    deviceName: typeof data.deviceName === 'string' ? data.deviceName : '',
+    capturing: data.capturing === true,
+    vadListening: data.vadListening === true,
+    updatedAt: typeof data.updatedAt === 'string' ? data.updatedAt : new Date().toISOString(),
+  }
+}
+
 let asrRequestSeq = 0
+const commandCooldown = new VoiceCommandCooldown()
+const lastCommand = new LastCommandTracker()
+const commandLockout = new PostCommandLockout()
+const lastInsert = new LastInsertTracker()
+const lastRecording = new LastRecordingStore()
+const sessionTranscript = new SessionTranscript()
+const asrRateLimiter = new AsrRequestRateLimiter()
+const inputDeviceSwitches = new InputDeviceSwitchRequests()
+const quickRecords = new QuickRecordRequests()
+const inputArm = new InputArmState()
+
+// 原始响应事件体积上限，超出部分截断，避免异常大响应拖慢渲染进程
+const ASR_RAW_RESPONSE_EVENT_MAX_CHARS = 64 * 1024
+const ASR_RAW_RESPONSE_LOG_MAX_CHARS = 2000
+
+interface AsrRawResponsePayload {
+  requestId: number
+  transport: 'http' | 'ws'
+  body: string
+  length: number
+  truncated: boolean
+}
+
+// logging.logAsrResponses 开启时记录并广播服务端原始响应；未开启时不做任何处理
+function rawResponseReporter(cfg: AppConfig, reqId: number, transport: 'http' | 'ws') {
+  if (!cfg.logging?.logAsrResponses) return undefined
+  return (raw: string) => {
+    logger.info(`[ASR#${reqId}] 原始响应(${transport}, ${raw.length} 字符): ${truncateText(raw, ASR_RAW_RESPONSE_LOG_MAX_CHARS)}`)
+    const body = truncateText(raw, ASR_RAW_RESPONSE_EVENT_MAX_CHARS)
+    const payload: AsrRawResponsePayload = { requestId: reqId, transport, body, length: raw.length, truncated: body !== raw }
+    mainWindow?.webContents.send('asr-raw-response', payload)
+    dashboardWindow?.webContents.send('asr-raw-response', payload)
+  }
+}
+
+const emptyResults = new EmptyResultTracker()
+
+// 统计连续空结果，达到阈值时提醒用户检查麦克风（离线指令模式未命中不算空结果）
+function trackEmptyResult(cfg: AppConfig, reqId: number, text: string) {
+  if (!emptyResults.record(text, cfg.asr.emptyResultWarningCount)) return
+  const count = emptyResults.count
+  logger.warn(`[ASR#${reqId}] 已连续 ${count} 次识别结果为空`)
+  const payload = { count, message: `已连续 ${count} 次未识别到文字，请检查麦克风是否选对、音量或增益是否过低` }
+  mainWindow?.webContents.send('recognition-empty-warning', payload)
+  dashboardWindow?.webContents.send('recognition-empty-warning', payload)
+}
+
+// 文字结果超出 postprocess.minChars / maxChars 时丢弃不输入，并通知界面原因
+function filterResultByLength(cfg: AppConfig, reqId: number, text: string): boolean {
+  const { minChars, maxChars } = cfg.postprocess
+  const check = checkTextLength(text, minChars, maxChars)
+  if (!check) return false
+  const limit = check.reason === 'too-short' ? `少于 ${minChars} 字` : `超过 ${maxChars} 字`
+  logger.info(`[ASR#${reqId}] 识别结果${limit}（${check.length} 字），丢弃: "${truncateText(text, LOG_TEXT_MAX_CHARS)}"`)
+  const payload = { requestId: reqId, text, reason: check.reason, length: check.length, minChars, maxChars }
+  mainWindow?.webContents.send('result-filtered', payload)
+  dashboardWindow?.webContents.send('result-filtered', payload)
+  return true
+}
+
+// 远程识别：VAD 模式下可走 WebSocket 长连接，失败时本句回退 HTTP
+async function recognizeRemote(
+  cfg: AppConfig,
+  buf: Buffer,
+  reqId: number,
+  traceId: string,
+  onStage?: (stage: RecognitionStage) => void,
+): Promise<string> {
+  const urlOptions = {
+    autoPrependScheme: cfg.server.autoPrependScheme,
+    requestId: traceId,
+    punctuation: cfg.postprocess.punctuation === 'server',
+    language: cfg.server.language,
+    onStage,
+  }
+  if (cfg.server.transport === 'ws' && cfg.vad?.enabled) {
+    try {
+      return await asrWsClient.recognize(cfg.server.url, cfg.server.asrConfigId, buf, {
+        ...urlOptions,
+        onRawResponse: rawResponseReporter(cfg, reqId, 'ws'),
+      })
+    } catch (e) {
+      logger.warn(`[ASR#${reqId}] WebSocket 识别失败，回退 HTTP: ${String(e)}`)
+    }
+  }
+  return recognize(cfg.server.url, cfg.server.asrConfigId, buf, {
+    ...urlOptions,
+    audioEncoding: cfg.server.audioEncoding,
+    audioMimeType: cfg.server.audioMime,
+    requestIdHeader: cfg.server.requestIdHeader,
+    fileFieldName: cfg.server.fileFieldName,
+    configIdFieldName: cfg.server.configIdFieldName,
+    onRawResponse: rawResponseReporter(cfg, reqId, 'http'),
+    redirect: cfg.server.redirect,
+    timeoutMs: cfg.server.requestTimeoutMs,
+    onRedirect: ({ status, from, to }) => {
+      logger.warn(`[ASR#${reqId}] 服务地址被重定向 ${status}: ${from} → ${to}（建议直接配置为重定向后的地址）`)
+    },
+  })
+}
+
+// 识别文本的规则替换与后处理，按 server.language 选择中文或英文规则
+function postprocessRecognizedText(cfg: AppConfig, text: string): string {
+  return tracePostprocessedText(cfg, text).text
+}
+
+function tracePostprocessedText(cfg: AppConfig, text: string): PostprocessTrace {
+  const language = cfg.server.language
+  return tracePostprocess(text, cfg.textRules, { ...cfg.postprocess, language })
+}
+
+// 基础指令表叠加当前指令方案
+function profileVoiceCommands(cfg: AppConfig): Record<string, string> {
+  return applyCommandProfile(cfg.voiceCommands ?? {}, cfg.voiceCommandProfiles ?? [], cfg.activeVoiceCommandProfile ?? '')
+}
+
+// 匹配时使用的指令表：当前方案的指令表加上展开后的别名
+function effectiveVoiceCommands(cfg: AppConfig): Record<string, string> {
+  return expandVoiceCommandAliases(profileVoiceCommands(cfg), cfg.voiceCommandAliases ?? {})
+}
+
+// 当前本地模型的 VAD 依赖（按文件检查，不启动 sidecar）
+function localVadDependency(cfg: AppConfig): ModelDependencyStatus | undefined {
+  const model = MODELS.find(m => m.id === cfg.asr.localModel)
+  return model ? inspectLocalModelStatus(model).dependencies.find(dep => dep.role === 'VAD') : undefined
+}
+
+// VAD 的 fsmn 后端：用 VAD 模型确认能量检测切出的语音段确有人声；推理失败时不拦截，照常识别
+async function confirmSpeechByVadModel(cfg: AppConfig, buf: Buffer, reqId: number): Promise<boolean> {
+  try {
+    const { segmentCount, speechMs } = await detectSpeechLocal(downmixWavToMono(buf), cfg.asr.localModel)
+    if (segmentCount === 0) {
+      logger.info(`[ASR#${reqId}] FSMN VAD 未检测到语音，丢弃本段`)
+      return false
+    }
+    logger.debug(`[ASR#${reqId}] FSMN VAD 确认语音: segments=${segmentCount}, speechMs=${speechMs}`)
+    return true
+  } catch (e) {
+    logger.warn(`[ASR#${reqId}] FSMN VAD 确认失败，按能量检测结果继续识别: ${String(e)}`)
+    return true
+  }
+}
+
+// 按识别模式识别一段 WAV，返回识别引擎的原始结果；离线指令模式未命中口令样本时返回 null
+async function recognizeWavBuffer(
+  cfg: AppConfig,
+  buf: Buffer,
+  asrMode: AppConfig['asr']['mode'],
+  reqId: number,
+  traceId: string,
+  onStage?: (stage: RecognitionStage) => void,
+): Promise<unknown> {
+  if (asrMode === 'commands') {
+    onStage?.('server-processing')
+    // 离线指令模式：只与已录制的口令样本比对，未命中时不输入任何文字
+    const spotted = getKeywordSpotter().match(wavToMonoFloat32(buf), {
+      phrases: Object.keys(effectiveVoiceCommands(cfg)),
+      maxDistance: cfg.asr.commandSpotMaxDistance,
+    })
+    if (!spotted) {
+      logger.info(`[ASR#${reqId}] 离线指令模式未匹配到口令样本，忽略`)
+      return null
+    }
+    logger.info(`[ASR#${reqId}] 离线指令匹配: "${spotted.phrase}" distance=${spotted.distance.toFixed(2)}`)
+    return spotted.phrase
+  }
+  try {
+    if (asrMode === 'local') {
+      await ensureLocalRecognizerReady(`recognize#${reqId}`)
+      onStage?.('server-processing')
+      return await recognizeLocal(downmixWavToMono(buf))
+    }
+    logger.info(`[ASR#${reqId}] 发送识别请求 trace=${traceId}`)
+    return await recognizeRemote(cfg, buf, reqId, traceId, onStage)
+  } catch (e) {
+    logger.error(`[ASR#${reqId}] 识别失败 trace=${traceId}: ${e}`)
+    throw new Error(`${e instanceof Error ? e.message : String(e)}（请求 ID: ${traceId}）`)
+  }
+}
+
 let localAsrInitPromise: Promise<void> | null = null
 let localAsrInitModelId: string | null = null
 
@@ -70,6 +412,279 @@ function clampVadThreshold(raw: unknown): number {
   return Math.min(VAD_THRESHOLD_MAX, Math.max(VAD_THRESHOLD_MIN, value))
 }
 
+function clampVadEnergySmoothing(raw: unknown): number {
+  if (typeof raw !== 'number' || !Number.isFinite(raw)) {
+    throw new Error(`能量平滑系数无效: ${String(raw)}（可选 0 ~ ${VAD_ENERGY_SMOOTHING_MAX}）`)
+  }
+  return Math.min(VAD_ENERGY_SMOOTHING_MAX, Math.max(0, raw))
+}
+
+function shortcutOptionsFromConfig(cfg: AppConfig): SendShortcutOptions {
+  return {
+    mapCtrlToCmdOnMac: cfg.input.mapCtrlToCmdOnMac,
+    holdMs: cfg.input.shortcutHoldMs,
+    keyDelayMs: cfg.input.shortcutKeyDelayMs,
+    stepDelayMs: cfg.input.shortcutStepDelayMs,
+    releaseModifiersOnFailure: cfg.input.releaseModifiersOnFailure,
+  }
+}
+
+// 系统声音（环回）采集：按当前录音来源注册或移除 getDisplayMedia 授权，只放行浮窗主框架的请求
+function syncLoopbackCapture(source: AppConfig['audioCapture']['source']): void {
+  installLoopbackCaptureHandler(session.defaultSession, source, isFloatWindowFrame)
+}
+
+function isFloatWindowFrame(frame: WebFrameMain | null): boolean {
+  if (!frame || !mainWindow || mainWindow.isDestroyed()) return false
+  const floatFrame = mainWindow.webContents.mainFrame
+  return frame.processId === floatFrame.processId && frame.routingId === floatFrame.routingId
+}
+
+// 配置写入后同步运行时状态（WS 连接、VAD 阈值、托盘、识别模式）
+function applySavedConfig(merged: AppConfig) {
+  setLogLevel(merged.logging.level)
+  applyLogFileSettings(merged.logging)
+  if (merged.server.transport !== 'ws') {
+    asrWsClient.close()
+  }
+  const syncedVadThreshold = clampVadThreshold(merged.vad?.speechThreshold)
+  mainWindow?.webContents.send('vad-threshold-updated', syncedVadThreshold)
+  dashboardWindow?.webContents.send('vad-threshold-updated', syncedVadThreshold)
+  if (typeof merged.vad?.energySmoothing === 'number') {
+    mainWindow?.webContents.send('vad-energy-smoothing-updated', merged.vad.energySmoothing)
+  }
+  const syncedInputGain = clampInputGain(merged.audioCapture?.inputGain)
+  mainWindow?.webContents.send('input-gain-updated', syncedInputGain)
+  dashboardWindow?.webContents.send('input-gain-updated', syncedInputGain)
+  mainWindow?.webContents.send('float-debug-bounds-updated', Boolean(merged.logging?.showFloatBounds))
+  syncLoopbackCapture(merged.audioCapture.source)
+  // 关闭口述记录时立即丢弃已累计的内容
+  if (!merged.transcript.enabled) sessionTranscript.clear()
+  updateTrayMenu()
+  refreshTrayTooltip()
+  void serverHealth.refresh()
+  if ((merged.asr?.mode ?? 'api') === 'local') {
+    void ensureLocalRecognizerReady('config-save').catch(() => { })
+  } else {
+    setAsrRuntimeStatus({
+      phase: 'idle',
+      modelId: null,
+      progress: 0,
+      message: merged.asr?.mode === 'commands' ? '当前为离线指令模式' : '当前为远程识别模式',
+    })
+  }
+}
+
+function pasteOptionsFromConfig(cfg: AppConfig): PasteOptions {
+  return {
+    pasteShortcut: cfg.input.pasteShortcut,
+    shortcutOptions: shortcutOptionsFromConfig(cfg),
+    pasteMode: cfg.input.pasteMode,
+    appendNavigationKey: cfg.input.appendNavigationKey,
+    useClipboard: cfg.input.useClipboard,
+    keyboardMethod: cfg.input.keyboardMethod,
+    specialChars: cfg.input.specialChars,
+  }
+}
+
+function inputModeOf(cfg: AppConfig): TextInputMethod {
+  return cfg.input.useClipboard ? 'clipboard' : 'keyboard'
+}
+
+// 语音指令匹配开关（纯听写模式）：持久化并通知界面；托盘菜单由调用方刷新
+export function setCommandMatching(enabled: boolean): boolean {
+  const cfg = getConfig()
+  cfg.voiceCommandOptions = { ...cfg.voiceCommandOptions, enabled }
+  saveConfig(cfg)
+  logger.info(`[Command] 语音指令匹配${enabled ? '已开启' : '已关闭（纯听写）'}`)
+  mainWindow?.webContents.send('command-matching-updated', enabled)
+  dashboardWindow?.webContents.send('command-matching-updated', enabled)
+  return enabled
+}
+
+function commandProfileState(cfg: AppConfig) {
+  const active = cfg.activeVoiceCommandProfile
+  return { active, profiles: listCommandProfiles(cfg.voiceCommandProfiles, active) }
+}
+
+// 切换指令方案并持久化；名称不存在时抛错，空字符串回到基础指令表
+export function switchCommandProfile(name: unknown) {
+  const cfg = getConfig()
+  const active = resolveCommandProfileName(cfg.voiceCommandProfiles, name)
+  cfg.activeVoiceCommandProfile = active
+  saveConfig(cfg)
+  logger.info(`[Command] 切换指令方案: ${profileLabel(active)}`)
+  const state = commandProfileState(cfg)
+  mainWindow?.webContents.send('profile-changed', state)
+  dashboardWindow?.webContents.send('profile-changed', state)
+  refreshTrayTooltip()
+  return state
+}
+
+// 热键循环切换：基础指令 → 各方案 → 基础指令
+export function cycleCommandProfile() {
+  const cfg = getConfig()
+  return switchCommandProfile(nextCommandProfile(cfg.voiceCommandProfiles, cfg.activeVoiceCommandProfile))
+}
+
+// 运行时调整日志级别（现场复现问题时临时开 debug，之后改回），立即生效并持久化
+function applyLogLevel(level: LogLevel): LogLevel {
+  const cfg = getConfig()
+  cfg.logging = { ...cfg.logging, level, enableDebug: level === 'debug' }
+  saveConfig(cfg)
+  setLogLevel(level)
+  mainWindow?.webContents.send('log-level-updated', level)
+  dashboardWindow?.webContents.send('log-level-updated', level)
+  return level
+}
+
+function setUseClipboard(enabled: boolean): boolean {
+  const cfg = getConfig()
+  cfg.input = { ...cfg.input, useClipboard: enabled }
+  saveConfig(cfg)
+  const mode = inputModeOf(cfg)
+  logger.info(`[Input] 输入方式切换为 ${mode === 'clipboard' ? '剪贴板粘贴' : '键盘逐字键入'}`)
+  mainWindow?.webContents.send('input-mode-updated', mode)
+  dashboardWindow?.webContents.send('input-mode-updated', mode)
+  return enabled
+}
+
+// 服务器连通性巡检：仅远程识别模式下进行，状态变化时更新托盘提示并通知界面
+function serverHealthTarget(): ServerHealthTarget | null {
+  const cfg = getConfig()
+  if ((cfg.asr?.mode ?? 'api') !== 'api' || cfg.server.healthCheckIntervalSec <= 0) return null
+  return {
+    url: cfg.server.url,
+    healthPath: cfg.server.healthPath,
+    autoPrependScheme: cfg.server.autoPrependScheme,
+    intervalMs: cfg.server.healthCheckIntervalSec * 1000,
+  }
+}
+
+// 巡检与手动 ping 的往返耗时都记入延迟历史
+const latencyHistory = new LatencyHistory()
+
+function recordServerLatency(status: ServerStatus) {
+  const sample = latencyHistory.record(status)
+  dashboardWindow?.webContents.send('server-latency', sample)
+}
+
+let serverPingController: AbortController | null = null
+
+// 手动 ping：对当前配置的服务地址（或健康检查路径）发一次轻量 GET，不受识别模式与巡检间隔影响。
+// 新的 ping 会取消仍在进行的上一次；被取消的 ping 不记入历史
+async function pingServer(): Promise<number> {
+  serverPingController?.abort()
+  const controller = new AbortController()
+  serverPingController = controller
+  const cfg = getConfig()
+  try {
+    const status = await checkServerHealth({
+      url: cfg.server.url,
+      healthPath: cfg.server.healthPath,
+      autoPrependScheme: cfg.server.autoPrependScheme,
+      intervalMs: 0,
+    }, Date.now, controller.signal)
+    if (controller.signal.aborted) throw new Error('ping 已取消')
+    recordServerLatency(status)
+    if (status.state !== 'connected' || status.latencyMs === null) {
+      throw new Error(status.error ?? status.message)
+    }
+    logger.info(`[Health] ping ${status.latencyMs}ms`)
+    return status.latencyMs
+  } finally {
+    if (serverPingController === controller) serverPingController = null
+  }
+}
+
+function cancelServerPing(): boolean {
+  if (!serverPingController) return false
+  serverPingController.abort()
+  serverPingController = null
+  logger.info('[Health] 已取消 ping')
+  return true
+}
+
+const serverHealth = new ServerHealthMonitor(serverHealthTarget, (status, previous) => {
+  if (status.state === 'unreachable') {
+    logger.warn(`[Health] ${status.message}${status.error ? `: ${status.error}` : ''}`)
+  } else if (status.state === 'connected' && previous.state === 'unreachable') {
+    logger.info(`[Health] 服务器已恢复连接 (${status.latencyMs}ms)`)
+  }
+  emitServerStatus(status)
+}, async (target) => {
+  const status = await checkServerHealth(target)
+  recordServerLatency(status)
+  return status
+})
+
+// 托盘提示：服务状态，启用指令方案时附带方案名
+function refreshTrayTooltip(status: ServerStatus = serverHealth.status) {
+  const profile = getConfig().activeVoiceCommandProfile
+  const base = trayTooltipFor(status)
+  tray?.setToolTip(profile ? `${base}（指令方案：${profile}）` : base)
+}
+
+function emitServerStatus(status: ServerStatus) {
+  refreshTrayTooltip(status)
+  mainWindow?.webContents.send('server-status', status)
+  dashboardWindow?.webContents.send('server-status', status)
+}
+
+// 定时录音：录音在浮窗渲染进程中进行，等浮窗回报真正开始录音后才返回 true；durationMs 缺省时使用 input.quickRecordMs
+export async function triggerQuickRecord(durationMs?: number): Promise<boolean> {
+  const ms = clampQuickRecordMs(typeof durationMs === 'number' ? durationMs : getConfig().input.quickRecordMs)
+  const result = await quickRecords.request(ms, (request) => {
+    if (!mainWindow || mainWindow.isDestroyed()) return false
+    mainWindow.webContents.send('quick-record', request)
+    return true
+  })
+  if (!result.ok) {
+    logger.warn(`[QuickRecord] 定时录音未开始: ${result.message}`)
+    return false
+  }
+  logger.info(`[QuickRecord] 开始定时录音 ${ms}ms`)
+  return true
+}
+
+export function startServerHealthMonitor() {
+  emitServerStatus(serverHealth.status)
+  void serverHealth.refresh()
+}
+
+export function stopServerHealthMonitor() {
+  serverHealth.stop()
+}
+
+// 输入模拟不可用时只提示一次，之后的识别结果走浮球回显/剪贴板兜底，直到用户重新检测
+const inputPermission = new InputPermissionGate(probeInputBackend, (state) => {
+  logger.warn(`[Input] 输入模拟不可用，已暂停自动输入: ${state.error ?? 'unknown'}`)
+  mainWindow?.webContents.send('input-permission-denied', state)
+  dashboardWindow?.webContents.send('input-permission-denied', state)
+})
+
+// 识别串行执行：上一句尚未输入完成时新的识别排队等待，避免两段文字交替键入
+const recognitionFlight = new SingleFlightQueue((state) => {
+  mainWindow?.webContents.send('recognition-busy', state)
+  dashboardWindow?.webContents.send('recognition-busy', state)
+})
+// 输入按识别完成的顺序串行执行。连续分段的中间段在热键松开前只在这里排队，
+// 不占用识别队列，后续分段照常识别
+const inputFlight = new SingleFlightQueue()
+// 中间段等待热键松开的上限：超过后视为松开事件丢失，照常输入，避免识别结果一直挂起
+const SEGMENT_RELEASE_TIMEOUT_MS = 120000
+
+// 最近一次错误：界面显示时通过 get-last-error 查询，避免窗口晚加载错过错误事件
+const lastError = new LastErrorStore((error) => {
+  mainWindow?.webContents.send('last-error', error)
+  dashboardWindow?.webContents.send('last-error', error)
+})
+
+function emitRecognitionStage(event: RecognitionStageEvent) {
+  mainWindow?.webContents.send('recognition-stage', event)
+  dashboardWindow?.webContents.send('recognition-stage', event)
+}
+
 export function emitAsrRuntimeStatus() {
   mainWindow?.webContents.send('asr-runtime-status', asrRuntimeStatus)
   dashboardWindow?.webContents.send('asr-runtime-status', asrRuntimeStatus)
@@ -92,7 +707,7 @@ export async function ensureLocalRecognizerReady(reason: string): Promise<void>
       phase: 'idle',
       modelId: null,
       progress: 0,
-      message: '当前为远程识别模式',
+      message: mode === 'commands' ? '当前为离线指令模式' : '当前为远程识别模式',
     })
     return
   }
@@ -154,6 +769,7 @@ export async function ensureLocalRecognizerReady(reason: string): Promise<void>
         message: detail,
       })
       logger.error(`[ASR] 本地识别启动失败(model=${modelId}, reason=${reason}): ${detail}`)
+      lastError.record('model', `本地识别启动失败: ${detail}`)
       throw e
     })
     .finally(() => {
@@ -166,11 +782,12 @@ export async function ensureLocalRecognizerReady(reason: string): Promise<void>
 
 export function setupIpc(
   focusController: FocusController,
-  setVadEnabledState: (enabled: boolean, emit?: boolean) => boolean,
+  setVadEnabledState: (enabled: boolean, emit?: boolean, reason?: 'user' | 'idle-timeout') => boolean,
   updateTrayMenu: () => void,
 ) {
   const config = getConfig()
   let floatExpanded = false
+  syncLoopbackCapture(config.audioCapture.source)
   let floatLayout: FloatLayoutMetrics = {
     width: FLOAT_WIDTH,
     height: FLOAT_HEIGHT,
@@ -179,6 +796,23 @@ export function setupIpc(
   }
   // vadEnabled is set externally via app-context
 
+  const resultAutoHide = new ResultAutoHide(() => {
+    if (!mainWindow || mainWindow.isDestroyed() || !mainWindow.isVisible()) return
+    mainWindow.hide()
+    updateTrayMenu()
+    logger.info('[UI] 识别结果显示完毕，自动隐藏浮窗')
+  })
+
+  // 按 ui.resultDisplayMs 在结果显示后隐藏浮窗；浮窗已隐藏时先弹出，让结果可见
+  const scheduleResultAutoHide = (delayMs: number) => {
+    if (delayMs <= 0 || !mainWindow || mainWindow.isDestroyed()) return
+    if (!mainWindow.isVisible()) {
+      mainWindow.showInactive()
+      updateTrayMenu()
+    }
+    resultAutoHide.schedule(delayMs)
+  }
+
   const handle = (
     channel: string,
     fn: (event: IpcMainInvokeEvent, ...args: any[]) => unknown | Promise<unknown>,
@@ -256,11 +890,50 @@ export function setupIpc(
     )
   }
 
-  function emitFloatPasteFallback(payload: FloatPasteFallbackPayload) {
+  function emitInputComplete(payload: InputCompletePayload) {
+    mainWindow?.webContents.send('input-complete', payload)
+    dashboardWindow?.webContents.send('input-complete', payload)
+  }
+
+  function emitInputFailed(payload: InputFailedPayload) {
+    mainWindow?.webContents.send('input-failed', payload)
+    dashboardWindow?.webContents.send('input-failed', payload)
+  }
+
+  // 输入失败时把识别文本放入剪贴板兜底，避免口述内容丢失；输入成功时不调用，不覆盖用户剪贴板
+  function copyTextOnInputFailure(requestId: number, text: string): boolean {
+    if (!getConfig().input.clipboardOnFailure || !text) return false
+    try {
+      clipboard.writeText(text)
+      logger.info(`[ASR#${requestId}] 输入失败，识别文本已复制到剪贴板`)
+      return true
+    } catch (e) {
+      logger.warn(`[ASR#${requestId}] 输入失败后写入剪贴板也失败: ${String(e)}`)
+      return false
+    }
+  }
+
+  function emitFloatPasteFallback(fallback: Omit<FloatPasteFallbackPayload, 'copiedToClipboard'>, error?: string) {
+    // 安全模式等待确认不算输入失败，不覆盖用户剪贴板
+    const payload: FloatPasteFallbackPayload = {
+      ...fallback,
+      copiedToClipboard: fallback.reason === 'safe-mode-unarmed'
+        ? false
+        : copyTextOnInputFailure(fallback.requestId, fallback.text),
+    }
+    if (fallback.reason !== 'safe-mode-unarmed') lastError.record('input', error ?? `输入失败: ${fallback.reason}`)
     logger.warn(
-      `[FloatFallback] emit req=${payload.requestId} reason=${payload.reason} ` +
-      `precheck=${payload.precheckReason} target=${payload.targetAppId ?? 'null'} textLen=${payload.text.length}`,
+      `[FloatFallback] emit req=${payload.requestId} trace=${payload.traceId ?? 'none'} reason=${payload.reason} ` +
+      `precheck=${payload.precheckReason} target=${payload.targetAppId ?? 'null'} textLen=${payload.text.length} ` +
+      `copied=${payload.copiedToClipboard}`,
     )
+    emitInputFailed({
+      requestId: payload.requestId,
+      traceId: payload.traceId,
+      text: payload.text,
+      error: error ?? payload.reason,
+      copiedToClipboard: payload.copiedToClipboard,
+    })
     floatExpanded = true
     mainWindow?.webContents.send('float-paste-fallback', payload)
   }
@@ -295,6 +968,8 @@ export function setupIpc(
       },
       vad: { ...current.vad, ...cfg.vad, enabled: vadEnabled },
       voiceCommands: cfg.voiceCommands ?? current.voiceCommands,
+      voiceCommandAliases: cfg.voiceCommandAliases ?? current.voiceCommandAliases,
+      voiceCommandOptions: { ...current.voiceCommandOptions, ...cfg.voiceCommandOptions },
       hotwords: cfg.hotwords ?? current.hotwords,
       textRules: cfg.textRules ? {
         ...current.textRules,
@@ -306,6 +981,7 @@ export function setupIpc(
         ? { ...current.onboarding, ...cfg.onboarding }
         : current.onboarding,
       logging: { ...current.logging, ...cfg.logging },
+      tray: { ...current.tray, ...cfg.tray },
       llm: cfg.llm ? {
         ...current.llm,
         ...cfg.llm,
@@ -332,22 +1008,42 @@ export function setupIpc(
         },
       } : current.llm,
     }
+    if ((merged.asr?.mode ?? 'api') === 'api' || merged.server.url.trim()) {
+      const validated = validateServerUrl(merged.server.url, { autoPrependScheme: merged.server.autoPrependScheme })
+      if (!validated.ok) {
+        throw new Error(validated.error)
+      }
+      merged.server.url = validated.url
+    }
     saveConfig(merged)
-    const syncedVadThreshold = clampVadThreshold(merged.vad?.speechThreshold)
-    mainWindow?.webContents.send('vad-threshold-updated', syncedVadThreshold)
-    dashboardWindow?.webContents.send('vad-threshold-updated', syncedVadThreshold)
-    mainWindow?.webContents.send('float-debug-bounds-updated', Boolean(merged.logging?.showFloatBounds))
-    updateTrayMenu()
-    if ((merged.asr?.mode ?? 'api') === 'local') {
-      void ensureLocalRecognizerReady('config-save').catch(() => { })
-    } else {
-      setAsrRuntimeStatus({
-        phase: 'idle',
-        modelId: null,
-        progress: 0,
-        message: '当前为远程识别模式',
-      })
+    applySavedConfig(merged)
+  })
+
+  handle('export-config', () => exportConfigText(getConfig()))
+
+  // 导入整份配置：先校验（服务地址、指令快捷键、热键），不通过时抛错且不修改当前配置
+  handle('import-config', (_event, text: string) => {
+    const parsed = parseConfigImport(text)
+    if (!parsed.ok) {
+      logger.warn(`[Config] 导入配置校验失败: ${parsed.error}`)
+      throw new Error(parsed.error)
     }
+    const current = getConfig()
+    const merged = {
+      ...current,
+      ...parsed.config,
+      vad: { ...current.vad, ...parsed.config.vad, enabled: vadEnabled },
+      // 解析时已去掉导入文件中的 allowExec，保留本机的运行程序开关
+      voiceCommandOptions: {
+        ...current.voiceCommandOptions,
+        ...parsed.config.voiceCommandOptions,
+        allowExec: current.voiceCommandOptions.allowExec,
+      },
+    } as AppConfig
+    saveConfig(merged)
+    applySavedConfig(merged)
+    logger.info(`[Config] 已导入配置，包含字段: ${Object.keys(parsed.config).join(', ')}`)
+    return getConfig()
   })
 
   handle('check-mic-permission', async () => {
@@ -355,9 +1051,39 @@ export function setupIpc(
   })
 
   handle('get-vad-enabled', () => vadEnabled)
+  // 运行时切换实时 VAD 后端并保存；浮窗收到 vad-backend-updated 后重启 VAD 监听
+  handle('get-vad-backend', () => getConfig().vad.backend)
+  handle('set-vad-backend', (_event, name: string) => {
+    if (!isVadBackendName(name)) {
+      throw new Error(`未知的 VAD 后端: ${name}（可选: ${VAD_BACKEND_NAMES.join(' / ')}）`)
+    }
+    const cfg = getConfig()
+    const reason = vadBackendUnavailableReason(name, name === 'energy' ? undefined : localVadDependency(cfg))
+    if (reason) throw new Error(reason)
+    if (cfg.vad.backend !== name) {
+      cfg.vad = { ...cfg.vad, backend: name }
+      saveConfig(cfg)
+      logger.info(`[VAD] 后端已切换为: ${VAD_BACKEND_LABELS[name]}`)
+    }
+    mainWindow?.webContents.send('vad-backend-updated', name)
+    dashboardWindow?.webContents.send('vad-backend-updated', name)
+    return name
+  })
   handle('set-vad-enabled', (_event, enabled: boolean) => {
     return setVadEnabledState(Boolean(enabled))
   })
+  // 浮窗 VAD 长时间未检测到语音：关闭 VAD 并同步托盘勾选与各窗口
+  handle('report-vad-idle-timeout', (_event, idleMs: number) => {
+    if (!vadEnabled) return false
+    logger.info(`[VAD] ${Math.round(Number(idleMs) / 1000)}s 未检测到语音，自动关闭 VAD`)
+    setVadEnabledState(false, true, 'idle-timeout')
+    return true
+  })
+  // 浮窗开始录音（按键、点击、定时录音或 VAD 检测到语音）：上一句结果的自动隐藏作废，避免说话中途浮窗被隐藏
+  handle('report-recording-started', () => {
+    resultAutoHide.cancel()
+    lastRecording.beginRecording()
+  })
   handle('set-vad-threshold', (_event, threshold: number) => {
     const normalizedThreshold = clampVadThreshold(threshold)
     const cfg = getConfig()
@@ -370,8 +1096,160 @@ export function setupIpc(
     dashboardWindow?.webContents.send('vad-threshold-updated', normalizedThreshold)
     return normalizedThreshold
   })
+  handle('set-vad-energy-smoothing', (_event, smoothing: number) => {
+    const normalized = clampVadEnergySmoothing(smoothing)
+    const cfg = getConfig()
+    cfg.vad = {
+      ...cfg.vad,
+      energySmoothing: normalized,
+    }
+    saveConfig(cfg)
+    logger.info(`[VAD] 能量平滑系数已调整为 ${normalized}`)
+    mainWindow?.webContents.send('vad-energy-smoothing-updated', normalized)
+    dashboardWindow?.webContents.send('vad-energy-smoothing-updated', normalized)
+    return normalized
+  })
+  handle('set-input-gain', (_event, factor: number) => {
+    const gain = clampInputGain(factor)
+    const cfg = getConfig()
+    cfg.audioCapture = { ...cfg.audioCapture, inputGain: gain }
+    saveConfig(cfg)
+    logger.info(`[Audio] 软件增益设为 ${gain}`)
+    mainWindow?.webContents.send('input-gain-updated', gain)
+    dashboardWindow?.webContents.send('input-gain-updated', gain)
+    return gain
+  })
+  // 运行时切换剪贴板粘贴 / 键盘逐字键入，便于在某个输入框表现异常时直接对比，无需改配置文件
+  handle('get-input-mode', () => inputModeOf(getConfig()))
+  handle('set-use-clipboard', (_event, enabled: boolean) => setUseClipboard(Boolean(enabled)))
+  handle('get-command-matching', () => getConfig().voiceCommandOptions.enabled)
+  handle('set-command-matching', (_event, enabled: boolean) => {
+    const next = setCommandMatching(Boolean(enabled))
+    updateTrayMenu()
+    return next
+  })
+  // 浮窗采集到的电平转发给主界面，用于调节增益时实时显示
+  handle('report-input-level', (_event, level: unknown) => {
+    dashboardWindow?.webContents.send('input-level', level)
+  })
+  // 定时录音：浮窗真正开始录音（或拒绝）后通过 report-quick-record 回报，quick-record 据此返回
+  handle('quick-record', (_event, durationMs?: number) => triggerQuickRecord(durationMs))
+  handle('report-quick-record', (_event, raw: unknown) => {
+    if (!quickRecords.settle(raw)) logger.warn('[QuickRecord] 收到过期或无效的定时录音回报，已忽略')
+  })
+  // 丢弃当前录音、不送识别，同时清除保留的最近录音；未在录音时浮窗忽略，可随时调用
+  handle('discard-recording', () => {
+    cancelHotkeyRecording()
+    lastRecording.clear()
+    logger.info('[录音] 请求丢弃当前录音')
+    mainWindow?.webContents.send('hotkey-state', 'idle')
+    dashboardWindow?.webContents.send('hotkey-state', 'idle')
+  })
+  // 浮窗上报麦克风实际格式，主进程缓存供设置页查询；源采样率与 16kHz 不一致时记录一次，便于排查识别效果差
+  handle('report-audio-info', (_event, info: unknown) => {
+    const next = normalizeAudioInfo(info)
+    if (next.sourceSampleRate !== audioInfo.sourceSampleRate || next.deviceName !== audioInfo.deviceName) {
+      logger.info(
+        `[Audio] 麦克风格式: device=${JSON.stringify(next.deviceName)} ` +
+        `source=${next.sourceSampleRate ?? 'unknown'}Hz/${next.sourceChannels ?? 'unknown'}ch ` +
+        `output=${next.outputSampleRate}Hz/${next.outputChannels}ch`,
+      )
+    }
+    audioInfo = next
+    dashboardWindow?.webContents.send('audio-info', audioInfo)
+  })
+  handle('get-audio-info', () => audioInfo)
+  // 当前平台可选的音频后端（audioCapture.host），修改后需重启生效
+  handle('list-audio-hosts', () => listAudioHosts())
+  // 运行时切换麦克风（按设备名称或 deviceId，空串为系统默认）：浮窗打开新设备成功后才持久化，
+  // 失败时抛错且浮窗继续使用原设备；成功后广播 input-device-updated，浮窗据此在新设备上重启 VAD
+  handle('get-input-device', (): InputDeviceSelection => ({
+    deviceId: getConfig().audioCapture.inputConstraints.deviceId ?? '',
+    deviceName: audioInfo.deviceName,
+  }))
+  handle('set-input-device', async (_event, name: unknown): Promise<InputDeviceSelection> => {
+    const wanted = typeof name === 'string' ? name.trim() : ''
+    const result = await inputDeviceSwitches.request(wanted, (request) => {
+      if (!mainWindow || mainWindow.isDestroyed()) return false
+      mainWindow.webContents.send('switch-input-device', request)
+      return true
+    })
+    if (!result.ok) {
+      logger.warn(`[Audio] 切换麦克风失败 target=${JSON.stringify(wanted)}: ${result.message}`)
+      throw new Error(result.message)
+    }
+    const device: InputDeviceSelection = { deviceId: result.deviceId, deviceName: result.deviceName }
+    const cfg = getConfig()
+    cfg.audioCapture = {
+      ...cfg.audioCapture,
+      inputConstraints: { ...cfg.audioCapture.inputConstraints, deviceId: device.deviceId || undefined },
+    }
+    saveConfig(cfg)
+    logger.info(`[Audio] 麦克风已切换为 ${JSON.stringify(device.deviceName || '系统默认')}`)
+    mainWindow?.webContents.send('input-device-updated', device)
+    dashboardWindow?.webContents.send('input-device-updated', device)
+    return device
+  })
+  handle('report-input-device-switch', (_event, raw: unknown) => {
+    if (!inputDeviceSwitches.settle(raw)) logger.warn('[Audio] 收到过期或无效的麦克风切换回报，已忽略')
+  })
+  // 麦克风采集生命周期：全部写日志，异常节点广播 audio-thread-error，避免麦克风已失效却毫无提示
+  handle('report-audio-lifecycle', (_event, raw: unknown) => {
+    const event = normalizeAudioLifecycleEvent(raw)
+    if (!event) return
+    const description = describeAudioLifecycleEvent(event)
+    if (!isAudioLifecycleError(event.stage)) {
+      logger.info(`[Audio] ${description}`)
+      return
+    }
+    logger.error(`[Audio] ${description}`)
+    mainWindow?.webContents.send('audio-thread-error', event)
+    dashboardWindow?.webContents.send('audio-thread-error', event)
+  })
+  // 录音采样率与识别要求不一致：醒目记录日志（附实际采样率），便于排查识别准确率投诉，并通知各窗口
+  handle('report-sample-rate-warning', (_event, raw: { actualRate?: unknown; expectedRate?: unknown }) => {
+    const actualRate = Math.round(Number(raw?.actualRate) || 0)
+    const expectedRate = Math.round(Number(raw?.expectedRate) || 0)
+    if (actualRate <= 0 || expectedRate <= 0 || actualRate === expectedRate) return
+    const warning = {
+      actualRate,
+      expectedRate,
+      message: `录音采样率为 ${actualRate}Hz，与识别要求的 ${expectedRate}Hz 不一致，当前不会重采样，识别准确率可能明显下降`,
+    }
+    logger.warn(`[Audio] ⚠ 采样率不一致: ${warning.message}（设备 ${JSON.stringify(audioInfo.deviceName)}）`)
+    mainWindow?.webContents.send('sample-rate-warning', warning)
+    dashboardWindow?.webContents.send('sample-rate-warning', warning)
+  })
+  // 录音过短（误触）未送识别：记录日志并通知设置页
+  handle('report-reattempt-suggested', (_event, info: { capturedMs?: unknown; mode?: unknown; autoRearm?: unknown; attempt?: unknown }) => {
+    const suggestion = {
+      capturedMs: Math.max(0, Math.round(Number(info?.capturedMs) || 0)),
+      mode: info?.mode === 'hold' ? 'hold' : 'toggle',
+      autoRearm: Boolean(info?.autoRearm),
+      attempt: Math.max(0, Math.round(Number(info?.attempt) || 0)),
+    }
+    logger.info(
+      `[录音] 录音过短 (${suggestion.capturedMs}ms)，跳过识别，` +
+      `${suggestion.autoRearm ? `自动重新录音 第 ${suggestion.attempt} 次` : '提示用户重试'}`,
+    )
+    mainWindow?.webContents.send('reattempt-suggested', suggestion)
+    dashboardWindow?.webContents.send('reattempt-suggested', suggestion)
+  })
 
   handle('get-asr-runtime-status', () => asrRuntimeStatus)
+  handle('get-last-error', () => lastError.current)
+  handle('clear-last-error', () => { lastError.clear() })
+  handle('recheck-input-permissions', async () => {
+    const state = await inputPermission.recheck()
+    logger.info(`[Input] 重新检测输入权限: ${state.available ? '可用' : `不可用 (${state.error ?? 'unknown'})`}`)
+    return state
+  })
+  handle('get-server-status', () => serverHealth.status)
+  handle('check-server-status', () => serverHealth.refresh())
+  // 测量到服务地址的往返耗时（ms），记入延迟历史；无法连接时抛出原因
+  handle('ping-server', () => pingServer())
+  handle('cancel-ping-server', () => cancelServerPing())
+  handle('get-server-latency-history', () => ({ samples: latencyHistory.list(), summary: latencyHistory.summary() }))
 
   handle('report-renderer-error', (_event, payload: unknown) => {
     const data = payload && typeof payload === 'object' ? payload as Record<string, unknown> : {}
@@ -453,6 +1331,23 @@ export function setupIpc(
           setVadEnabledState(!vadEnabled, true)
         },
       },
+      {
+        label: `定时录音 ${Math.round(getConfig().input.quickRecordMs / 1000)} 秒`,
+        click: () => { void triggerQuickRecord() },
+      },
+      {
+        label: getConfig().input.useClipboard ? '改为键盘逐字输入' : '改为剪贴板粘贴输入',
+        click: () => { setUseClipboard(!getConfig().input.useClipboard) },
+      },
+      {
+        label: '语音指令',
+        type: 'checkbox',
+        checked: getConfig().voiceCommandOptions.enabled,
+        click: () => {
+          setCommandMatching(!getConfig().voiceCommandOptions.enabled)
+          updateTrayMenu()
+        },
+      },
       { type: 'separator' },
       { label: '打开控制台', click: () => { openDashboardWindow() } },
       { type: 'separator' },
@@ -468,28 +1363,397 @@ export function setupIpc(
 
   handle('restart-app', () => {
     logger.info('[App] 收到重启请求')
+    // app.exit 不触发 will-quit，先把合并中的配置写盘
+    flushConfig()
     app.relaunch()
     app.exit(0)
     return true
   })
 
-  handle('recognize-wav', async (_event, wavBuffer: ArrayBuffer, prevAppId: string | null) => {
+  // 试测后处理规则：按已保存的配置走完整处理链，返回每一步的结果，不模拟输入、不写识别记录
+  handle('preview-postprocess', (_event, text: string) => (
+    tracePostprocessedText(getConfig(), normalizeAsrText(text))
+  ))
+  // 仅预览匹配结果，不模拟任何键盘输入
+  handle('preview-voice-command', (_event, text: string) => {
+    const cfg = getConfig()
+    const normalizedText = postprocessRecognizedText(cfg, normalizeAsrText(text))
+    return previewVoiceCommand(normalizedText, effectiveVoiceCommands(cfg), cfg.voiceCommandOptions)
+  })
+
+  // 设置页指令表：读取当前生效的指令（即匹配时使用的配置），并标出快捷键无法解析的条目
+  handle('list-voice-commands', () => listVoiceCommands(effectiveVoiceCommands(getConfig())))
+  // 仅解析快捷键并返回各步按键名称，不发送任何按键
+  handle('validate-shortcut', (_event, shortcut: unknown) => (
+    validateShortcut(typeof shortcut === 'string' ? shortcut : '')
+  ))
+  // 指令方案：列出全部方案并切换当前方案，切换后刷新托盘菜单
+  handle('list-profiles', () => commandProfileState(getConfig()))
+  handle('switch-profile', (_event, name: unknown) => {
+    const state = switchCommandProfile(name)
+    updateTrayMenu()
+    return state
+  })
+  // 当前实际生效的全局热键及每项的注册状态（与配置不一致时以此为准）
+  handle('list-hotkeys', () => hotkeyRegistry.list())
+  // 保存前检查热键是否被其它应用占用（临时注册后立即注销）
+  handle('check-hotkey-available', (_event, accelerator: unknown) => (
+    checkHotkeyAvailable(typeof accelerator === 'string' ? accelerator : '', globalShortcut, hotkeyRegistry)
+  ))
+
+  // 回归测试用：磁盘上的 WAV 走与录音相同的识别与指令匹配流程，只返回结果，不模拟输入、不写识别记录
+  handle('transcribe-file', async (_event, filePath: string) => {
+    const target = String(filePath ?? '').trim()
+    if (!target) throw new Error('文件路径为空')
+    let fileBuffer: Buffer
+    try {
+      fileBuffer = await fs.promises.readFile(target)
+    } catch (e) {
+      throw new Error(`无法读取文件 ${target}: ${e instanceof Error ? e.message : String(e)}`)
+    }
+    let buf: Buffer
+    try {
+      buf = normalizeWavFile(fileBuffer)
+    } catch (e) {
+      throw new Error(`${target}: ${e instanceof Error ? e.message : String(e)}`)
+    }
+
+    const reqId = ++asrRequestSeq
+    const traceId = randomUUID()
+    const cfg = getConfig()
+    const asrMode = cfg.asr?.mode ?? 'api'
+    const { durationMs } = readWavInfo(buf)
+    logger.info(`[ASR#${reqId}] 转写文件 ${target}，时长 ${durationMs}ms，模式: ${asrMode}，trace=${traceId}`)
+    const rawText = normalizeAsrText(await recognizeWavBuffer(cfg, buf, asrMode, reqId, traceId) ?? '')
+    const text = asrMode === 'commands'
+      ? rawText
+      : postprocessRecognizedText(cfg, rawText)
+    logger.info(`[ASR#${reqId}] 文件转写结果 trace=${traceId}: "${truncateText(text, LOG_TEXT_MAX_CHARS)}"`)
+    return {
+      path: target,
+      traceId,
+      durationMs,
+      rawText,
+      text,
+      match: text.trim() ? previewVoiceCommand(text, effectiveVoiceCommands(cfg), cfg.voiceCommandOptions) : null,
+    }
+  })
+
+  // 一键自检：渲染进程录好一段音频后交给主进程，逐步检查编码、网络与服务端识别，不模拟按键
+  handle('run-self-test', async (_event, capture: SelfTestCapture) => {
     const reqId = ++asrRequestSeq
+    const traceId = randomUUID()
+    const cfg = getConfig()
+    const asrMode = cfg.asr?.mode ?? 'api'
+    logger.info(`[SelfTest#${reqId}] 开始自检，模式: ${asrMode}，trace=${traceId}`)
+    const report = await runSelfTest(capture, {
+      encode: (wav) => {
+        if (asrMode === 'local') return { format: 'wav', bytes: downmixWavToMono(wav).byteLength }
+        if (asrMode === 'api' && cfg.server.audioEncoding === 'flac') return { format: 'flac', bytes: encodeWavToFlac(wav).byteLength }
+        return { format: 'wav', bytes: wav.byteLength }
+      },
+      checkNetwork: async () => {
+        if (asrMode !== 'api') return null
+        const status = await checkServerHealth({
+          url: cfg.server.url,
+          healthPath: cfg.server.healthPath,
+          autoPrependScheme: cfg.server.autoPrependScheme,
+          intervalMs: 0,
+        })
+        const ok = status.state === 'connected'
+        return { ok, message: ok ? `已连接（${status.latencyMs}ms）` : `${status.message}${status.error ? `: ${status.error}` : ''}` }
+      },
+      recognize: async (wav) => {
+        const raw = await recognizeWavBuffer(cfg, wav, asrMode, reqId, traceId)
+        if (raw === null && asrMode === 'commands') return ''
+        return normalizeAsrText(raw ?? '')
+      },
+    })
+    for (const s of report.stages) {
+      const line = `[SelfTest#${reqId}] ${s.label}: ${s.status} (${s.durationMs}ms) ${s.message}`
+      if (s.status === 'failed') logger.warn(line)
+      else logger.info(line)
+    }
+    return report
+  })
+
+  // 离线指令模式的口令样本：每个口令可录制多条，样本越多匹配越稳
+  handle('enroll-command-template', (_event, phrase: string, wavBuffer: ArrayBuffer) => {
+    const key = String(phrase ?? '').trim()
+    if (!key || !effectiveVoiceCommands(getConfig())[key]) {
+      throw new Error(`口令不存在：${key}`)
+    }
+    const count = getKeywordSpotter().enroll(key, wavToMonoFloat32(Buffer.from(wavBuffer)))
+    saveKeywordTemplates()
+    logger.info(`[Spotter] 已录制口令样本 "${key}"，共 ${count} 条`)
+    return count
+  })
+  handle('list-command-templates', () => getKeywordSpotter().counts())
+  handle('clear-command-templates', (_event, phrase: string) => {
+    getKeywordSpotter().clear(String(phrase ?? '').trim())
+    saveKeywordTemplates()
+  })
+
+  // 配置了固定输入窗口时，按标题查找并作为本次输入目标；找不到时回退当前焦点
+  function resolveTitleTarget(cfg: AppConfig, reqId: number): string | null {
+    const title = cfg.input.targetWindowTitle
+    if (!title) return null
+    if (process.platform !== 'win32') {
+      logger.warn(`[ASR#${reqId}] 固定输入窗口仅支持 Windows，回退为当前焦点`)
+      return null
+    }
+    const hwnd = findWindowByTitle(title)
+    if (!hwnd) {
+      logger.warn(`[ASR#${reqId}] 未找到标题包含 "${title}" 的窗口，回退为当前焦点`)
+      return null
+    }
+    logger.info(`[ASR#${reqId}] 固定输入窗口: "${title}" → hwnd=${hwnd}`)
+    return hwnd
+  }
+
+  // 再次执行上一条指令（“重复”口令或快捷键触发），不重新识别、不受指令冷却限制
+  async function repeatLastCommand(reqId: number, prevAppId: string | null): Promise<RecognitionResult> {
+    const last = lastCommand.last
+    if (!last) {
+      logger.info(`[ASR#${reqId}] 重复指令：尚无已执行的指令，忽略`)
+      throw new Error('没有可重复的指令')
+    }
+    const focusTarget = prevAppId || focusController.getLastExternalAppId()
+    if (focusTarget) {
+      await focusController.restore(focusTarget, `repeat#${reqId}`)
+    }
+    const cfg = getConfig()
+    logger.info(`[ASR#${reqId}] 重复指令: ${last.phrase} → ${last.shortcut}`)
+    try {
+      await inputPermission.run(() => sendShortcut(last.shortcut, shortcutOptionsFromConfig(cfg)))
+    } catch (e) {
+      emitInputFailed({ requestId: reqId, text: last.shortcut, error: String(e), copiedToClipboard: false })
+      throw e
+    }
+    emitInputComplete({ requestId: reqId, text: last.shortcut, method: 'shortcut' })
+    commandCooldown.markFired(last.phrase)
+    commandLockout.markExecuted()
+    lastInsert.recordCommand()
+    return { kind: 'command', text: last.phrase, phrase: last.phrase, shortcut: last.shortcut, repeated: true }
+  }
+
+  handle('repeat-last-command', async (_event, prevAppId: string | null) => {
+    return repeatLastCommand(++asrRequestSeq, prevAppId ?? null)
+  })
+
+  // 撤销上一次输入的文字：发送与其字符数相同的退格键，返回删除的字符数；上一次是指令或已撤销过时返回 0。
+  // 退格发送成功后才清空记录，恢复焦点或发送失败时仍可再次撤销；撤销进行中再次触发直接忽略，避免重复删除
+  let undoInFlight = false
+  handle('undo-last-insert', async (_event, prevAppId: string | null) => {
+    const count = lastInsert.pending
+    if (count === null) {
+      logger.info('[Input] 撤销输入：上一次动作不是文字输入，忽略')
+      return 0
+    }
+    if (undoInFlight) {
+      logger.info('[Input] 撤销输入：上一次撤销仍在进行，忽略')
+      return 0
+    }
+    undoInFlight = true
+    try {
+      const focusTarget = prevAppId || focusController.getLastExternalAppId()
+      if (focusTarget) {
+        await focusController.restore(focusTarget, 'undo-last-insert')
+      }
+      logger.info(`[Input] 撤销上一次输入：发送 ${count} 次退格`)
+      await inputPermission.run(() => pressBackspace(count))
+      lastInsert.take()
+      return count
+    } finally {
+      undoInFlight = false
+    }
+  })
+
+  // triggeredAt：渲染进程触发识别（松开热键、点击停止、VAD 切段）的时间，阶段事件的耗时从此起算；
+  // source 区分 VAD 切段、按键录音与连续分段录音的中间段，用于请求限流；中间段之后录音仍在继续，不自动隐藏浮窗
+  handle('recognize-wav', (
+    _event,
+    wavBuffer: ArrayBuffer,
+    prevAppId: string | null,
+    triggeredAt?: number,
+    source?: RecognitionSource,
+  ) => {
+    const requestSource: RecognitionSource = source === 'vad' || source === 'segment' ? source : 'manual'
+    if (!getConfig().audioCapture.retainLastRecording) lastRecording.clear()
+    else if (requestSource === 'segment') lastRecording.appendSegment(wavBuffer)
+    else lastRecording.retain(wavBuffer)
+    return runRecognition(wavBuffer, prevAppId, resolveTriggeredAt(triggeredAt, Date.now()), requestSource)
+  })
+  // 用保留的最近一段录音重新走一遍识别流程（修改服务端配置后对照验证），不重新录音
+  handle('recognize-last-recording', () => {
+    if (!getConfig().audioCapture.retainLastRecording) {
+      lastRecording.clear()
+      throw new Error('未开启保留最近录音（audioCapture.retainLastRecording）')
+    }
+    const retained = lastRecording.current
+    if (!retained) throw new Error('没有可重新识别的录音')
+    logger.info(`[ASR] 重新识别最近录音（录于 ${new Date(retained.recordedAt).toISOString()}）`)
+    return runRecognition(retained.wav, null, Date.now(), 'manual')
+  })
+  handle('clear-last-recording', () => {
+    if (lastRecording.clear()) logger.info('[录音] 已清除保留的最近录音')
+  })
+
+  // ── 整段口述记录 ──
+  function requireTranscriptEnabled(): void {
+    if (!getConfig().transcript.enabled) {
+      sessionTranscript.clear()
+      throw new Error('未开启整段口述记录（transcript.enabled）')
+    }
+  }
+  handle('start-session', () => {
+    requireTranscriptEnabled()
+    sessionTranscript.start()
+    logger.info('[口述记录] 开始新会话')
+    return sessionTranscript.state
+  })
+  handle('end-session', () => {
+    requireTranscriptEnabled()
+    const state = sessionTranscript.end()
+    logger.info(`[口述记录] 结束会话，共 ${state.segmentCount} 句`)
+    return state
+  })
+  handle('get-session-transcript', () => {
+    requireTranscriptEnabled()
+    return sessionTranscript.state
+  })
+  handle('export-session-transcript', async (event) => {
+    requireTranscriptEnabled()
+    const { text, startedAt } = sessionTranscript.state
+    if (!text) throw new Error('当前会话没有口述内容')
+    const owner = BrowserWindow.fromWebContents(event.sender)
+    const options = {
+      title: '导出口述记录',
+      defaultPath: path.join(app.getPath('documents'), transcriptFileName(new Date(startedAt ?? Date.now()))),
+      filters: [{ name: '文本文件', extensions: ['txt'] }],
+    }
+    const { canceled, filePath } = owner
+      ? await dialog.showSaveDialog(owner, options)
+      : await dialog.showSaveDialog(options)
+    if (canceled || !filePath) return null
+    await fs.promises.writeFile(filePath, text + '\n', 'utf-8')
+    logger.info(`[口述记录] 已导出: ${filePath}`)
+    return filePath
+  })
+
+  async function runRecognition(
+    wavBuffer: ArrayBuffer,
+    prevAppId: string | null,
+    startAt: number,
+    requestSource: RecognitionSource,
+  ): Promise<RecognitionResult | null> {
+    // 新的识别开始，上一句结果的自动隐藏作废
+    resultAutoHide.cancel()
+    if (recognitionFlight.state.busy) logger.info('[ASR] 上一句识别尚未完成，本次排队等待')
+    const reqId = ++asrRequestSeq
+    // 每句识别一个全局唯一追踪 ID，随请求发给服务端并写入日志与结果事件，便于两端对照排查
+    const traceId = randomUUID()
+    const stages = new RecognitionStageTracker(reqId, traceId, emitRecognitionStage, startAt)
+    try {
+      // 识别队列的任务把输入 Promise 包在对象里返回，避免被 await 展开后一直占着队列
+      const queued = await recognitionFlight.run(async () => {
+        const speech = await recognizeWavRequest(reqId, traceId, stages, wavBuffer, requestSource)
+        if (!speech) return null
+        // 按住说话的中间段：热键（含修饰键）仍按下时先不输入，松开后按顺序输入，避免与 Alt 等修饰键串键
+        const awaitRelease = requestSource === 'segment' && isRecordHotkeyHeld()
+        const input = inputFlight.run(async () => {
+          if (awaitRelease) await waitForSegmentRelease(reqId)
+          return applyRecognizedText(reqId, traceId, stages, speech, prevAppId)
+        })
+        // 普通输入完成前仍占着识别队列；等待松开的中间段立即让出，后续分段照常识别
+        if (!awaitRelease) await input.catch(() => undefined)
+        return { input }
+      })
+      const result = queued ? await queued.input : null
+      // 还有排队的识别时不隐藏，等最后一句结果显示后再计时
+      if (result && requestSource !== 'segment' && recognitionFlight.state.pending === 0) {
+        scheduleResultAutoHide(getConfig().ui.resultDisplayMs)
+      }
+      return result
+    } catch (e) {
+      lastError.record(e instanceof InputPermissionDeniedError ? 'input' : 'recognition', e)
+      throw e
+    } finally {
+      const summary = stages.finish()
+      if (summary) logger.info(`[ASR#${reqId}] 阶段耗时 trace=${traceId}: ${summary}`)
+    }
+  }
+
+  async function waitForSegmentRelease(reqId: number): Promise<void> {
+    if (!isRecordHotkeyHeld()) return
+    logger.info(`[ASR#${reqId}] 录音热键仍按下，中间段等松开后再输入`)
+    const released = await waitForRecordHotkeyRelease(SEGMENT_RELEASE_TIMEOUT_MS)
+    if (!released) {
+      logger.warn(`[ASR#${reqId}] ${SEGMENT_RELEASE_TIMEOUT_MS / 1000}s 内未收到录音热键松开事件，按已松开处理并输入中间段`)
+    }
+  }
+
+  // RUN: 指令：启动外部程序后立即返回，不恢复焦点、不等待程序退出
+  function runExecCommand(
+    reqId: number,
+    text: string,
+    commandPhrase: string,
+    exec: ExecCommand,
+    cfg: AppConfig,
+  ): RecognitionResult | null {
+    if (!cfg.voiceCommandOptions.allowExec) {
+      logger.warn(`[ASR#${reqId}] 运行程序指令未启用（voiceCommandOptions.allowExec），忽略: ${commandPhrase}`)
+      return null
+    }
+    const cooldownMs = resolveCommandCooldownMs(commandPhrase, cfg.voiceCommandOptions)
+    const cooldownRemaining = commandCooldown.remaining(commandPhrase, cooldownMs)
+    if (cooldownRemaining > 0) {
+      logger.info(`[ASR#${reqId}] 语音指令冷却中，忽略: ${commandPhrase} (cooldown=${cooldownMs}ms, remaining=${cooldownRemaining}ms)`)
+      return null
+    }
+    const value = profileVoiceCommands(cfg)[commandPhrase] ?? `${EXEC_COMMAND_PREFIX}${exec.command}`
+    try {
+      launchExecCommand(exec, `[ASR#${reqId}]`)
+    } catch (e) {
+      logger.error(`[ASR#${reqId}] 运行程序失败: ${exec.command} ${String(e)}`)
+      throw e
+    }
+    commandCooldown.markFired(commandPhrase)
+    commandLockout.markExecuted()
+    lastInsert.recordCommand()
+    try {
+      insertRecognition({ text: text.trim(), mode: cfg.asr?.mode ?? 'api', isCommand: true, commandShortcut: value })
+      dashboardWindow?.webContents.send('recognition-added')
+    } catch (e) {
+      logger.error(`[ASR#${reqId}] 写入识别记录失败: ${e}`)
+    }
+    sendWebhook(cfg.webhook, buildWebhookPayload(text.trim(), commandPhrase), `[ASR#${reqId}]`)
+    return { kind: 'command', text: text.trim(), phrase: commandPhrase, shortcut: value }
+  }
+
+  // 识别阶段：音频检查、限流、ASR 与文本后处理，返回待输入的文字；不涉及目标应用
+  async function recognizeWavRequest(
+    reqId: number,
+    traceId: string,
+    stages: RecognitionStageTracker,
+    wavBuffer: ArrayBuffer,
+    source: RecognitionSource,
+  ): Promise<RecognizedSpeech | null> {
     const cfg = getConfig()
     const buf = Buffer.from(wavBuffer)
     const asrMode = cfg.asr?.mode ?? 'api'
-    logger.info(`[ASR#${reqId}] 收到 WAV，大小 ${buf.byteLength} 字节，模式: ${asrMode}`)
+    logger.info(`[ASR#${reqId}] 收到 WAV，大小 ${buf.byteLength} 字节，模式: ${asrMode}，trace=${traceId}`)
 
-    const wavPayloadBytes = Math.max(0, buf.byteLength - 44)
-    const pcmSampleCount = Math.floor(wavPayloadBytes / 2)
-    const audioDurationMs = Math.round((pcmSampleCount / 16000) * 1000)
+    const wavInfo = readWavInfo(buf)
+    const pcmSampleCount = wavInfo.frameCount * wavInfo.channels
+    const audioDurationMs = wavInfo.durationMs
     if (pcmSampleCount <= 0) {
       logger.info(`[ASR#${reqId}] WAV 无有效 PCM 数据，跳过识别`)
-      return ''
+      return null
     }
     if (audioDurationMs < 90) {
       logger.info(`[ASR#${reqId}] 音频时长过短 (${audioDurationMs}ms < 90ms)，跳过识别`)
-      return ''
+      return null
     }
 
     // 检测音频能量，静音或极低活跃度时跳过识别（避免模型幻觉）
@@ -512,49 +1776,179 @@ export function setupIpc(
         `[ASR#${reqId}] 音频活跃度过低 (durationMs=${audioDurationMs}, rms=${rms.toFixed(1)}, ` +
         `activeRatio=${activeRatio.toFixed(4)}), 跳过识别`,
       )
-      return ''
+      return null
     }
 
-    let rawText: unknown
-    try {
-      if (asrMode === 'local') {
-        await ensureLocalRecognizerReady(`recognize#${reqId}`)
-        rawText = await recognizeLocal(buf)
-      } else {
-        rawText = await recognize(cfg.server.url, cfg.server.asrConfigId, buf)
+    if (vadEnabled && cfg.vad.backend === 'fsmn' && !(await confirmSpeechByVadModel(cfg, buf, reqId))) {
+      return null
+    }
+
+    // 限流只保护远程识别服务；本地模型与离线指令模式不受限
+    if (asrMode === 'api') {
+      const intervalMs = cfg.server.minRequestIntervalMs
+      const waitMs = isRateLimited(source, cfg.server) ? asrRateLimiter.remaining(intervalMs) : 0
+      if (waitMs > 0) {
+        logger.warn(
+          `[ASR#${reqId}] 距上次识别请求不足 ${intervalMs}ms（还差 ${waitMs}ms），丢弃本段 source=${source} durationMs=${audioDurationMs}`,
+        )
+        return null
       }
-    } catch (e) {
-      logger.error(`[ASR#${reqId}] 识别失败: ${e}`)
-      throw e
+      asrRateLimiter.markSent()
     }
 
-    const normalizedText = normalizeAsrText(rawText)
-    const text = applyTextRules(normalizedText, cfg.textRules)
-    logger.info(`[ASR#${reqId}] 识别结果: "${text}"`)
-    if (!text.trim()) return ''
+    const rawText = await recognizeWavBuffer(cfg, buf, asrMode, reqId, traceId, (stage) => stages.enter(stage))
+    if (rawText === null) return null
+    // 识别成功即清除之前的错误；之后的输入失败会重新记录
+    lastError.clear()
+    stages.enter('matching')
+
+    let normalizedText = normalizeAsrText(rawText)
+    if (asrMode !== 'commands') trackEmptyResult(cfg, reqId, normalizedText)
+    // VAD 激活词门控：未以激活词开头的语音视为旁人交谈，直接丢弃
+    if (cfg.vad.enabled && cfg.vad.activationPhrase && asrMode !== 'commands') {
+      const activated = stripActivationPhrase(normalizedText, cfg.vad.activationPhrase)
+      if (activated === null) {
+        logger.info(`[ASR#${reqId}] 未以激活词开头，丢弃: "${truncateText(normalizedText, LOG_TEXT_MAX_CHARS)}"`)
+        return null
+      }
+      normalizedText = activated
+    }
+    // 离线指令模式的结果就是口令本身，不再套用文本替换规则与后处理
+    const text = asrMode === 'commands'
+      ? normalizedText
+      : postprocessRecognizedText(cfg, normalizedText)
+    logger.info(`[ASR#${reqId}] 识别结果 trace=${traceId}: "${truncateText(text, LOG_TEXT_MAX_CHARS)}"`)
+    if (!text.trim()) return null
+    return { cfg, asrMode, text, normalizedText }
+  }
 
-    const result = matchVoiceCommand(text, cfg.voiceCommands)
+  // 输入阶段：指令匹配、恢复焦点并执行指令或输入文字，在 inputFlight 中按识别顺序执行
+  async function applyRecognizedText(
+    reqId: number,
+    traceId: string,
+    stages: RecognitionStageTracker,
+    speech: RecognizedSpeech,
+    prevAppId: string | null,
+  ): Promise<RecognitionResult | null> {
+    const { cfg, asrMode, text, normalizedText } = speech
+    // 指令后锁定期：drop 时丢弃本句；text 时不执行“重复”与其他指令，按普通文字输入
+    const { postCommandLockoutMs, postCommandLockoutAction } = cfg.voiceCommandOptions
+    const lockoutRemaining = commandLockout.remaining(postCommandLockoutMs)
+    if (lockoutRemaining > 0 && postCommandLockoutAction === 'drop') {
+      logger.info(`[ASR#${reqId}] 指令后锁定期内，丢弃: "${truncateText(text, LOG_TEXT_MAX_CHARS)}" (remaining=${lockoutRemaining}ms)`)
+      return null
+    }
+
+    const commands = effectiveVoiceCommands(cfg)
+    const matchingEnabled = cfg.voiceCommandOptions.enabled
+    if (matchingEnabled && lockoutRemaining <= 0 && isRepeatCommand(text, cfg.voiceCommandOptions.repeatPhrase, commands)) {
+      stages.enter('inserting')
+      return repeatLastCommand(reqId, prevAppId)
+    }
+
+    let result: VoiceCommandMatch = matchVoiceCommand(text, commands, cfg.voiceCommandOptions)
+    const matchText = normalizeCommandText(text)
+    let commandPhrase = findVoiceCommandPhrase(text, commands) ?? matchText
+    if (result.type === 'command') {
+      logger.info(`[ASR#${reqId}] 指令匹配: raw="${normalizedText}" normalized="${matchText}" decision=exact shortcut=${result.shortcut}`)
+    } else if (result.type === 'exec') {
+      logger.info(`[ASR#${reqId}] 指令匹配: raw="${normalizedText}" normalized="${matchText}" decision=exact exec=${result.command}`)
+    } else if (result.phrase) {
+      logger.info(
+        `[ASR#${reqId}] 指令匹配: raw="${normalizedText}" normalized="${matchText}" decision=exact ` +
+        `text=${JSON.stringify(result.text)}`,
+      )
+    } else if (!matchingEnabled) {
+      logger.info(`[ASR#${reqId}] 语音指令已关闭（纯听写），按文字输入`)
+    } else {
+      const closest = findClosestVoiceCommand(matchText, commands)
+      logger.info(
+        `[ASR#${reqId}] 指令匹配: raw="${normalizedText}" normalized="${matchText}" decision=none ` +
+        `closest=${closest ? `"${closest.phrase}"(distance=${closest.distance})` : 'none'}`,
+      )
+      const candidates = matchVoiceCommandCandidates(matchText, commands, {
+        threshold: cfg.voiceCommandOptions.candidateThreshold,
+      })
+      if (candidates.length > 0) {
+        const ambiguous = isAmbiguousCandidates(candidates)
+        logger.info(
+          `[ASR#${reqId}] 候选指令: ${candidates.map(c => `"${c.phrase}"(${c.score})`).join(', ')} ambiguous=${ambiguous}`,
+        )
+        if (ambiguous) {
+          // 多个口令同样接近时不自动选择，交给界面提示用户
+          const payload = { requestId: reqId, text: text.trim(), candidates }
+          mainWindow?.webContents.send('voice-command-candidates', payload)
+          dashboardWindow?.webContents.send('voice-command-candidates', payload)
+        } else if (cfg.voiceCommandOptions.autoPickTopCandidate) {
+          commandPhrase = candidates[0].phrase
+          result = resolveCommandPhrase(commandPhrase, commands)
+          logger.info(`[ASR#${reqId}] 自动选择候选指令: "${commandPhrase}"`)
+        }
+      }
+    }
+    // 别名命中时冷却、冷却覆盖与“重复”都按主口令计
+    commandPhrase = canonicalCommandPhrase(commandPhrase, profileVoiceCommands(cfg), cfg.voiceCommandAliases)
+    if (lockoutRemaining > 0 && (result.type === 'command' || result.phrase)) {
+      logger.info(`[ASR#${reqId}] 指令后锁定期内，不执行指令，按文字输入: "${commandPhrase}" (remaining=${lockoutRemaining}ms)`)
+      result = applyPostCommandLockout(result, text, lockoutRemaining, postCommandLockoutAction) ?? result
+    }
+    if (asrMode === 'commands' && result.type === 'text' && !result.phrase) {
+      return null
+    }
+    if (result.type === 'exec') {
+      return runExecCommand(reqId, text, commandPhrase, result, cfg)
+    }
+    // 长度过滤只针对口述文字，指令与文字指令不受影响
+    if (result.type === 'text' && !result.phrase && filterResultByLength(cfg, reqId, result.text)) {
+      return null
+    }
+    // 冷却中的指令直接忽略，不再切换焦点（与 RUN: 指令一致，先判冷却再动目标应用）
+    if (result.type === 'command') {
+      const cooldownMs = resolveCommandCooldownMs(commandPhrase, cfg.voiceCommandOptions)
+      const cooldownRemaining = commandCooldown.remaining(commandPhrase, cooldownMs)
+      if (cooldownRemaining > 0) {
+        logger.info(
+          `[ASR#${reqId}] 语音指令冷却中，忽略: ${commandPhrase} → ${result.shortcut} ` +
+          `(cooldown=${cooldownMs}ms, remaining=${cooldownRemaining}ms)`,
+        )
+        return null
+      }
+    }
+    stages.enter('inserting')
     const fallbackTarget = focusController.getLastExternalAppId()
-    let focusTarget = prevAppId || fallbackTarget
+    let focusTarget = resolveTitleTarget(cfg, reqId) || prevAppId || fallbackTarget
     if (!focusTarget) {
       focusTarget = await focusController.captureSnapshot(`asr#${reqId}-pre-restore`)
     }
       const restoreResult = await focusController.restore(focusTarget, `asr#${reqId}`)
 
       if (result.type === 'command') {
-      logger.info(`[ASR#${reqId}] 语音指令: ${text.trim()} → ${result.shortcut}`)
-      await sendShortcut(result.shortcut)
+      logger.info(`[ASR#${reqId}] 语音指令: ${truncateText(text.trim(), LOG_TEXT_MAX_CHARS)} → ${result.shortcut}`)
+      try {
+        await inputPermission.run(() => sendShortcut(result.shortcut, shortcutOptionsFromConfig(cfg)))
+      } catch (e) {
+        emitInputFailed({ requestId: reqId, traceId, text: result.shortcut, error: String(e), copiedToClipboard: false })
+        throw e
+      }
+      emitInputComplete({ requestId: reqId, traceId, text: result.shortcut, method: 'shortcut' })
+      commandCooldown.markFired(commandPhrase)
+      commandLockout.markExecuted()
+      lastCommand.record(commandPhrase, result.shortcut)
+      lastInsert.recordCommand()
       try {
         insertRecognition({ text: text.trim(), mode: asrMode, isCommand: true, commandShortcut: result.shortcut })
         dashboardWindow?.webContents.send('recognition-added')
       } catch (e) {
         logger.error(`[ASR#${reqId}] 写入识别记录失败: ${e}`)
       }
-      return `${text.trim()} ⌨ ${result.shortcut}`
+      sendWebhook(cfg.webhook, buildWebhookPayload(text.trim(), commandPhrase), `[ASR#${reqId}]`)
+      return { kind: 'command', text: text.trim(), phrase: commandPhrase, shortcut: result.shortcut }
     } else {
       let outputText = result.text
       const llmCfg = cfg.llm
-      const shouldOptimizeByLlm = !Boolean(cfg.vad?.enabled)
+      // 指令输入的文字（换行、文本扩展）是配置好的原文，不做 LLM 后处理
+      const shouldOptimizeByLlm = !result.phrase
+        && !Boolean(cfg.vad?.enabled)
         && outputText.trim().length > 8
         && Boolean(llmCfg?.enabled)
         && Boolean(llmCfg?.asrPostProcessEnabled)
@@ -565,7 +1959,10 @@ export function setupIpc(
         try {
           const optimized = (await optimizeAsrTextWithLlm(outputText)).trim()
           if (optimized) {
-            logger.info(`[ASR#${reqId}] LLM 后处理: "${outputText}" -> "${optimized}"`)
+            logger.info(
+              `[ASR#${reqId}] LLM 后处理: "${truncateText(outputText, LOG_TEXT_MAX_CHARS)}" ` +
+              `-> "${truncateText(optimized, LOG_TEXT_MAX_CHARS)}"`,
+            )
             outputText = optimized
           }
         } catch (e) {
@@ -574,13 +1971,24 @@ export function setupIpc(
       }
 
       const pasteTarget = focusTarget || focusController.getLastExternalAppId()
-      if (!restoreResult.success) {
+      if (cfg.input.safeMode && !inputArm.isArmed(Date.now(), cfg.input.safeModeIdleMs)) {
+        logger.info(`[ASR#${reqId}] 安全模式：尚未确认输入目标，暂存识别结果等待确认`)
+        emitFloatPasteFallback({
+          requestId: reqId,
+          traceId,
+          text: outputText,
+          targetAppId: pasteTarget,
+          reason: 'safe-mode-unarmed',
+          precheckReason: 'unknown',
+        })
+      } else if (!restoreResult.success) {
         logger.warn(
           `[ASR#${reqId}] 目标应用焦点恢复失败，直接触发浮球回显 ` +
           `restore=${restoreResult.reason} final=${restoreResult.finalFrontmostAppId ?? 'null'}`,
         )
         emitFloatPasteFallback({
           requestId: reqId,
+          traceId,
           text: outputText,
           targetAppId: pasteTarget,
           reason: 'restore-failed',
@@ -590,7 +1998,7 @@ export function setupIpc(
         const targetAssessment = await assessPasteTarget({ maxAttempts: 2, retryDelayMs: 25 })
         const pastePlan = buildPasteExecutionPlan(restoreResult, targetAssessment)
         logger.info(
-          `[ASR#${reqId}] 输入文字: ${outputText} ` +
+          `[ASR#${reqId}] 输入文字: ${JSON.stringify(truncateText(outputText, LOG_TEXT_MAX_CHARS))} ` +
           `(restore=${restoreResult.reason}/${restoreResult.success ? 'ok' : 'fail'}, ` +
           `precheck=${targetAssessment.reason}, readiness=${targetAssessment.status}, target=${pasteTarget ?? 'null'})`,
         )
@@ -602,6 +2010,7 @@ export function setupIpc(
           )
           emitFloatPasteFallback({
             requestId: reqId,
+            traceId,
             text: outputText,
             targetAppId: pasteTarget,
             reason: pastePlan.fallbackReason!,
@@ -609,8 +2018,25 @@ export function setupIpc(
           })
         } else {
           try {
-            await typeText(outputText)
-            logger.info(`[ASR#${reqId}] 粘贴动作已发送（未抛错）`)
+            const inputMethod = await inputPermission.run(() => typeText(outputText, pasteOptionsFromConfig(cfg)))
+            logger.info(`[ASR#${reqId}] 粘贴动作已发送（未抛错） method=${inputMethod}`)
+            inputArm.touch(Date.now())
+            lastInsert.recordText(outputText)
+            if (result.cursorLeft) {
+              const cursorLeft = result.cursorLeft
+              await inputPermission.run(() => pressLeftArrow(cursorLeft))
+              logger.info(`[ASR#${reqId}] 光标已移回占位符处（左移 ${cursorLeft} 次）`)
+              // 光标已不在末尾，退格会删错字
+              lastInsert.recordCommand()
+            }
+            if (result.pressEnter) {
+              await inputPermission.run(() => sendShortcut('ENTER', shortcutOptionsFromConfig(cfg)))
+              logger.info(`[ASR#${reqId}] 已按回车提交短语: ${matchText}`)
+              // 回车已提交，退格无法撤回
+              lastInsert.recordCommand()
+            }
+            emitInputComplete({ requestId: reqId, traceId, text: outputText, method: inputMethod })
+            if (result.phrase) commandLockout.markExecuted()
             const probeAfter = await assessPasteTarget({ maxAttempts: 1, retryDelayMs: 0 })
             if (probeAfter.status !== 'ready') {
               logger.warn(
@@ -622,11 +2048,12 @@ export function setupIpc(
             logger.warn(`[ASR#${reqId}] 直接粘贴失败，转浮球回显: ${String(e)}`)
             emitFloatPasteFallback({
               requestId: reqId,
+              traceId,
               text: outputText,
               targetAppId: pasteTarget,
               reason: 'type-failed',
               precheckReason: targetAssessment.reason,
-            })
+            }, String(e))
           }
         }
       }
@@ -636,9 +2063,12 @@ export function setupIpc(
       } catch (e) {
         logger.error(`[ASR#${reqId}] 写入识别记录失败: ${e}`)
       }
-      return outputText
+      // 带 phrase 的是文本指令的展开内容，不算口述
+      if (cfg.transcript.enabled && !result.phrase) sessionTranscript.record(outputText, cfg.transcript.idleGapMs)
+      sendWebhook(cfg.webhook, buildWebhookPayload(outputText, result.phrase), `[ASR#${reqId}]`)
+      return result.phrase ? { kind: 'text', text: outputText, phrase: result.phrase } : { kind: 'text', text: outputText }
     }
-  })
+  }
 
   // ── 统计与历史 IPC ──
   handle('get-stats', async () => getStats())
@@ -718,6 +2148,23 @@ export function setupIpc(
   // ── 日志 IPC ──
   handle('get-logs', () => getLogBuffer())
   handle('clear-logs', () => clearLogs())
+  handle('get-log-level', () => getLogLevel())
+  handle('get-log-path', () => getLogFilePath())
+  // 在文件管理器中定位当前日志文件；文件尚未生成（或文件日志已关闭）时打开日志目录
+  handle('open-log-folder', async () => {
+    const logPath = getLogFilePath()
+    if (fs.existsSync(logPath)) {
+      shell.showItemInFolder(logPath)
+      return
+    }
+    fs.mkdirSync(path.dirname(logPath), { recursive: true })
+    const error = await shell.openPath(path.dirname(logPath))
+    if (error) throw new Error(`打开日志目录失败: ${error}`)
+  })
+  handle('set-log-level', (_event, level: unknown) => {
+    if (!isLogLevel(level)) throw new Error(`无效的日志级别: ${String(level)}（可选 debug / info / warn / error）`)
+    return applyLogLevel(level)
+  })
   handle('copy-to-clipboard', (_event, text: string) => {
     clipboard.writeText(String(text ?? ''))
     return true
@@ -745,6 +2192,18 @@ export function setupIpc(
   })
 
   handle('retry-float-paste', async (_event, text: string, targetAppId: string | null) => {
+    return retryFloatPaste(text, targetAppId)
+  })
+
+  // 安全模式下用户确认目标输入框：解除锁定直到空闲超时，并输入暂存的识别结果
+  handle('arm-input', async (_event, text?: string, targetAppId?: string | null) => {
+    inputArm.arm(Date.now())
+    logger.info('[Input] 安全模式：用户已确认输入目标')
+    if (!String(text ?? '').trim()) return { success: true, reason: 'ok' }
+    return retryFloatPaste(String(text), targetAppId ?? null)
+  })
+
+  async function retryFloatPaste(text: string, targetAppId: string | null) {
     const output = String(text ?? '')
     if (!output.trim()) {
       return { success: false, reason: 'empty-text' }
@@ -760,13 +2219,15 @@ export function setupIpc(
       return { success: false, reason: assessment.reason }
     }
     try {
-      await typeText(output)
+      await inputPermission.run(() => typeText(output, pasteOptionsFromConfig(getConfig())))
+      inputArm.touch(Date.now())
+      lastInsert.recordText(output)
       return { success: true, reason: assessment.status === 'ready' ? 'ok' : 'unknown' }
     } catch (e) {
       logger.warn(`[Float] retry paste failed: ${String(e)}`)
       return { success: false, reason: 'type-failed' }
     }
-  })
+  }
 
   handle('set-ignore-mouse-events', (_event, ignore: boolean, opts?: { forward: boolean }) => {
     if (mainWindow) {
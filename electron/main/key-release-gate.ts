@@ -0,0 +1,40 @@
+// 按键松开闸门：录音热键按住期间（含修饰键）暂缓向目标应用输入，松开后按等待顺序放行。
+// 按住说话开启连续分段时，中间段在热键仍按下时就识别完成，若立即粘贴/键入，
+// Windows 上 Alt 与 Ctrl+V 叠成 Ctrl+Alt+V（部分布局为 AltGr+V），逐字键入还会触发菜单加速键
+
+export class KeyReleaseGate {
+  private held = false
+  private waiters: Array<() => void> = []
+
+  get isHeld(): boolean { return this.held }
+
+  hold(): void {
+    this.held = true
+  }
+
+  release(): void {
+    this.held = false
+    const waiters = this.waiters
+    this.waiters = []
+    for (const resolve of waiters) resolve()
+  }
+
+  // 未按住时立即放行，松开时返回 true；超过 timeoutMs 仍未松开（如松开事件丢失）时单独放行并返回 false，
+  // 闸门本身保持按住，等下一次松开再复位
+  wait(timeoutMs?: number): Promise<boolean> {
+    if (!this.held) return Promise.resolve(true)
+    return new Promise((resolve) => {
+      let timer: ReturnType<typeof setTimeout> | null = null
+      const onRelease = () => {
+        if (timer) clearTimeout(timer)
+        resolve(true)
+      }
+      this.waiters.push(onRelease)
+      if (timeoutMs === undefined) return
+      timer = setTimeout(() => {
+        this.waiters = this.waiters.filter(waiter => waiter !== onRelease)
+        resolve(false)
+      }, Math.max(0, timeoutMs))
+    })
+  }
+}
@@ -0,0 +1,249 @@
+import fs from 'fs'
+import path from 'path'
+import { app } from 'electron'
+import { logger } from './logger'
+
+// 离线指令模式的关键词识别：把录音与预先录制的口令样本做 DTW 模板匹配，
+// 不依赖任何 ASR 服务，只能识别已录制样本的固定口令。
+
+const SAMPLE_RATE = 16000
+const FRAME_SAMPLES = 400   // 25ms
+const HOP_SAMPLES = 160     // 10ms
+const FFT_SIZE = 512
+const MEL_BANDS = 20
+const ENERGY_FLOOR = 1e-6
+// 能量低于最大帧该比例的首尾帧视为静音，匹配前裁掉
+const TRIM_RATIO = 0.05
+const TEMPLATES_FILE_NAME = 'command-templates.json'
+
+export const DEFAULT_SPOT_MAX_DISTANCE = 6
+
+export type FeatureFrames = number[][]
+
+export interface SpotResult {
+  phrase: string
+  distance: number
+}
+
+// 16k 单声道 PCM → 每帧去均值的对数梅尔能量（去均值消除音量差异，只保留频谱形状）
+export function extractFeatures(pcm: Float32Array): FeatureFrames {
+  const frames: { energy: number; bands: number[] }[] = []
+  const window = hannWindow(FRAME_SAMPLES)
+  const filters = melFilterBank()
+  for (let start = 0; start + FRAME_SAMPLES <= pcm.length; start += HOP_SAMPLES) {
+    const re = new Float64Array(FFT_SIZE)
+    const im = new Float64Array(FFT_SIZE)
+    let energy = 0
+    for (let i = 0; i < FRAME_SAMPLES; i++) {
+      const v = pcm[start + i]
+      energy += v * v
+      re[i] = v * window[i]
+    }
+    fft(re, im)
+    const power = new Float64Array(FFT_SIZE / 2 + 1)
+    for (let k = 0; k < power.length; k++) power[k] = re[k] * re[k] + im[k] * im[k]
+    const bands = filters.map(filter => {
+      let sum = 0
+      for (const [bin, weight] of filter) sum += power[bin] * weight
+      return Math.log(sum + ENERGY_FLOOR)
+    })
+    const mean = bands.reduce((a, b) => a + b, 0) / bands.length
+    frames.push({ energy, bands: bands.map(v => v - mean) })
+  }
+  const maxEnergy = frames.reduce((max, f) => Math.max(max, f.energy), 0)
+  if (maxEnergy <= 0) return []
+  const voiced = (f: { energy: number }) => f.energy >= maxEnergy * TRIM_RATIO
+  const first = frames.findIndex(voiced)
+  let last = frames.length - 1
+  while (last > first && !voiced(frames[last])) last--
+  return frames.slice(first, last + 1).map(f => f.bands)
+}
+
+// DTW 距离，按路径长度归一化，便于不同长度的录音之间比较
+export function dtwDistance(a: FeatureFrames, b: FeatureFrames): number {
+  if (a.length === 0 || b.length === 0) return Number.POSITIVE_INFINITY
+  const n = a.length
+  const m = b.length
+  let prev = new Float64Array(m + 1).fill(Number.POSITIVE_INFINITY)
+  let prevSteps = new Float64Array(m + 1)
+  prev[0] = 0
+  for (let i = 1; i <= n; i++) {
+    const curr = new Float64Array(m + 1).fill(Number.POSITIVE_INFINITY)
+    const currSteps = new Float64Array(m + 1)
+    for (let j = 1; j <= m; j++) {
+      const cost = frameDistance(a[i - 1], b[j - 1])
+      let best = prev[j - 1]
+      let steps = prevSteps[j - 1]
+      if (prev[j] < best) {
+        best = prev[j]
+        steps = prevSteps[j]
+      }
+      if (curr[j - 1] < best) {
+        best = curr[j - 1]
+        steps = currSteps[j - 1]
+      }
+      curr[j] = best + cost
+      currSteps[j] = steps + 1
+    }
+    prev = curr
+    prevSteps = currSteps
+  }
+  return prev[m] / prevSteps[m]
+}
+
+export class KeywordSpotter {
+  private templates = new Map<string, FeatureFrames[]>()
+
+  enroll(phrase: string, pcm: Float32Array): number {
+    const features = extractFeatures(pcm)
+    if (features.length === 0) {
+      throw new Error('样本录音为空或全为静音')
+    }
+    const list = this.templates.get(phrase) ?? []
+    list.push(features)
+    this.templates.set(phrase, list)
+    return list.length
+  }
+
+  clear(phrase: string): void {
+    this.templates.delete(phrase)
+  }
+
+  counts(): Record<string, number> {
+    const result: Record<string, number> = {}
+    for (const [phrase, list] of this.templates) result[phrase] = list.length
+    return result
+  }
+
+  // 返回距离最近且不超过阈值的口令；phrases 限定参与匹配的口令（已删除的指令不再触发）
+  match(pcm: Float32Array, options: { phrases?: string[]; maxDistance?: number } = {}): SpotResult | null {
+    const features = extractFeatures(pcm)
+    if (features.length === 0) return null
+    const maxDistance = options.maxDistance ?? DEFAULT_SPOT_MAX_DISTANCE
+    let best: SpotResult | null = null
+    for (const [phrase, list] of this.templates) {
+      if (options.phrases && !options.phrases.includes(phrase)) continue
+      for (const template of list) {
+        const distance = dtwDistance(features, template)
+        if (!best || distance < best.distance) best = { phrase, distance }
+      }
+    }
+    if (!best || best.distance > maxDistance) return null
+    return best
+  }
+
+  toJSON(): Record<string, FeatureFrames[]> {
+    return Object.fromEntries(this.templates)
+  }
+
+  load(data: unknown): void {
+    this.templates.clear()
+    if (!data || typeof data !== 'object') return
+    for (const [phrase, list] of Object.entries(data as Record<string, unknown>)) {
+      if (!Array.isArray(list)) continue
+      const valid = list.filter((t): t is FeatureFrames => Array.isArray(t) && t.length > 0)
+      if (valid.length > 0) this.templates.set(phrase, valid)
+    }
+  }
+}
+
+// ── 模板持久化（userData/command-templates.json） ──
+
+let sharedSpotter: KeywordSpotter | null = null
+
+function templatesPath(): string {
+  return path.join(app.getPath('userData'), TEMPLATES_FILE_NAME)
+}
+
+export function getKeywordSpotter(): KeywordSpotter {
+  if (sharedSpotter) return sharedSpotter
+  sharedSpotter = new KeywordSpotter()
+  try {
+    const file = templatesPath()
+    if (fs.existsSync(file)) {
+      sharedSpotter.load(JSON.parse(fs.readFileSync(file, 'utf8')))
+    }
+  } catch (e) {
+    logger.warn(`[Spotter] 读取口令样本失败，忽略: ${String(e)}`)
+  }
+  return sharedSpotter
+}
+
+export function saveKeywordTemplates(): void {
+  if (!sharedSpotter) return
+  fs.writeFileSync(templatesPath(), JSON.stringify(sharedSpotter.toJSON()))
+}
+
+function frameDistance(a: number[], b: number[]): number {
+  let sum = 0
+  for (let i = 0; i < a.length; i++) {
+    const d = a[i] - b[i]
+    sum += d * d
+  }
+  return Math.sqrt(sum)
+}
+
+function hannWindow(size: number): Float64Array {
+  const w = new Float64Array(size)
+  for (let i = 0; i < size; i++) w[i] = 0.5 - 0.5 * Math.cos((2 * Math.PI * i) / (size - 1))
+  return w
+}
+
+let cachedFilters: Array<Array<[number, number]>> | null = null
+
+function melFilterBank(): Array<Array<[number, number]>> {
+  if (cachedFilters) return cachedFilters
+  const toMel = (hz: number) => 2595 * Math.log10(1 + hz / 700)
+  const toHz = (mel: number) => 700 * (10 ** (mel / 2595) - 1)
+  const maxMel = toMel(SAMPLE_RATE / 2)
+  const points = Array.from({ length: MEL_BANDS + 2 }, (_, i) =>
+    Math.floor(((FFT_SIZE + 1) * toHz((maxMel * i) / (MEL_BANDS + 1))) / SAMPLE_RATE))
+  cachedFilters = []
+  for (let band = 1; band <= MEL_BANDS; band++) {
+    const filter: Array<[number, number]> = []
+    const [left, center, right] = [points[band - 1], points[band], points[band + 1]]
+    for (let bin = left; bin <= right; bin++) {
+      const weight = bin <= center
+        ? (center === left ? 1 : (bin - left) / (center - left))
+        : (right === center ? 1 : (right - bin) / (right - center))
+      if (weight > 0) filter.push([bin, weight])
+    }
+    cachedFilters.push(filter)
+  }
+  return cachedFilters
+}
+
+// 原地基 2 FFT
+function fft(re: Float64Array, im: Float64Array): void {
+  const n = re.length
+  for (let i = 1, j = 0; i < n; i++) {
+    let bit = n >> 1
+    for (; j & bit; bit >>= 1) j ^= bit
+    j ^= bit
+    if (i < j) {
+      const tr = re[i]
+      re[i] = re[j]
+      re[j] = tr
+      const ti = im[i]
+      im[i] = im[j]
+      im[j] = ti
+    }
+  }
+  for (let len = 2; len <= n; len <<= 1) {
+    const angle = (-2 * Math.PI) / len
+    for (let i = 0; i < n; i += len) {
+      for (let k = 0; k < len / 2; k++) {
+        const wr = Math.cos(angle * k)
+        const wi = Math.sin(angle * k)
+        const ar = re[i + k + len / 2]
+        const ai = im[i + k + len / 2]
+        const tr = ar * wr - ai * wi
+        const ti = ar * wi + ai * wr
+        re[i + k + len / 2] = re[i + k] - tr
+        im[i + k + len / 2] = im[i + k] - ti
+        re[i + k] += tr
+        im[i + k] += ti
+      }
+    }
+  }
+}
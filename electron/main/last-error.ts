@@ -0,0 +1,35 @@
+// 最近一次错误：错误事件只推送一次，界面晚于事件加载（窗口隐藏、控制台延迟打开）时会错过。
+// 这里保存最近一次错误供界面显示时主动查询；下一次识别成功或用户手动清除时清空
+
+export type LastErrorCategory = 'recognition' | 'input' | 'model'
+
+export interface LastError {
+  category: LastErrorCategory
+  message: string
+  at: string
+}
+
+export class LastErrorStore {
+  private last: LastError | null = null
+
+  constructor(
+    private readonly onChange: (error: LastError | null) => void = () => { },
+    private readonly now: () => number = Date.now,
+  ) { }
+
+  get current(): LastError | null { return this.last }
+
+  record(category: LastErrorCategory, error: unknown): LastError {
+    const message = (error instanceof Error ? error.message : String(error)).replace(/^Error:\s*/i, '')
+    this.last = { category, message, at: new Date(this.now()).toISOString() }
+    this.onChange(this.last)
+    return this.last
+  }
+
+  // 已经为空时不回调，避免每次识别成功都推送事件
+  clear(): void {
+    if (!this.last) return
+    this.last = null
+    this.onChange(null)
+  }
+}
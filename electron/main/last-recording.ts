@@ -0,0 +1,58 @@
+// 最近一段录音（WAV）：修正服务端地址、配置 ID 等之后可直接用同一段音频重新识别，
+// 无需让医生再说一遍。涉及患者隐私，仅在 audioCapture.retainLastRecording 开启时保存，
+// 且只保留最后一段；丢弃录音或关闭开关时清空。
+// 连续分段录音的各中间段与松开后的剩余部分拼成一整段，而不是只留下最后一个片段
+
+import { concatWavs } from './wav-info'
+
+export interface RetainedRecording {
+  wav: ArrayBuffer
+  recordedAt: number
+}
+
+export class LastRecordingStore {
+  private last: RetainedRecording | null = null
+  // 当前保存的是本次录音已收到的中间段，之后的片段继续拼接
+  private collecting = false
+
+  constructor(private readonly now: () => number = Date.now) { }
+
+  get current(): RetainedRecording | null { return this.last }
+
+  // 保存一段完整录音；本次录音此前已有中间段时拼在其后。复制一份保存，避免调用方之后复用或转移同一块内存
+  retain(wav: ArrayBuffer): void {
+    this.append(wav)
+    this.collecting = false
+  }
+
+  // 连续分段录音的中间段：先按顺序累积，录音剩余部分到达（retain）时收尾
+  appendSegment(wav: ArrayBuffer): void {
+    this.append(wav)
+    this.collecting = true
+  }
+
+  // 新录音开始：上一次未收尾的中间段不再与之后的片段拼接（保留内容不变）
+  beginRecording(): void {
+    this.collecting = false
+  }
+
+  // 返回是否确实清除了内容
+  clear(): boolean {
+    const had = this.last !== null
+    this.last = null
+    this.collecting = false
+    return had
+  }
+
+  private append(wav: ArrayBuffer): void {
+    if (!this.collecting || !this.last) {
+      this.last = { wav: wav.slice(0), recordedAt: this.now() }
+      return
+    }
+    const joined = concatWavs([Buffer.from(this.last.wav), Buffer.from(wav)])
+    this.last = {
+      wav: joined.buffer.slice(joined.byteOffset, joined.byteOffset + joined.byteLength),
+      recordedAt: this.last.recordedAt,
+    }
+  }
+}
@@ -5,7 +5,9 @@ import { app } from 'electron'
 import { logger } from './logger'
 import { MODELS, ModelInfo, isHotwordCapableModel } from './model-manager'
 import { getConfig } from './config'
-import { normalizeAsrText } from './asr-text'
+import { normalizeAsrText, LOG_TEXT_MAX_CHARS } from './asr-text'
+import { truncateText } from '../../shared/text'
+import { expandVoiceCommandAliases } from './voice-commands'
 
 export interface DependencyStatus {
   role: string
@@ -346,7 +348,9 @@ function collectHotwords(): string[] {
       if (word.trim()) allWords.add(word.trim())
     }
   }
-  const voiceCommands = config.voiceCommands || {}
+  // 所有指令方案的口令都加入热词，切换方案时无需重启识别器
+  const allCommands = Object.assign({}, config.voiceCommands || {}, ...(config.voiceCommandProfiles || []).map(p => p.commands))
+  const voiceCommands = expandVoiceCommandAliases(allCommands, config.voiceCommandAliases || {})
   for (const commandWord of Object.keys(voiceCommands)) {
     const word = commandWord.trim()
     if (word) allWords.add(word)
@@ -492,10 +496,32 @@ export async function recognizeLocal(wavBuffer: Buffer): Promise<string> {
     logger.debug(`[ASR] sidecar rawText: "${resp.rawText}"`)
   }
   const text = normalizeAsrText(resp?.text)
-  logger.info(`本地识别结果: "${text}"`)
+  logger.info(`本地识别结果: "${truncateText(text, LOG_TEXT_MAX_CHARS)}"`)
   return text
 }
 
+// 用模型配套的 VAD 模型检测 WAV（16k 单声道）中的语音区间，供实时 VAD 的 fsmn 后端确认语音段；
+// 不要求识别器已初始化，sidecar 未启动时先启动并只加载 VAD 模型
+export async function detectSpeechLocal(
+  wavBuffer: Buffer,
+  modelId: string,
+): Promise<{ segmentCount: number; speechMs: number }> {
+  const modelInfo = MODELS.find(m => m.id === modelId)
+  if (!modelInfo?.vadModel) throw new Error(`模型 ${modelId} 没有配套的 VAD 模型`)
+  await spawnSidecar()
+  const resp = await sendRequest({
+    cmd: 'detectSpeech',
+    vadModelName: modelInfo.vadModel,
+    vadBackend: modelInfo.vadBackend,
+    vadQuantize: Boolean(modelInfo.vadQuantized),
+    wavBase64: wavBuffer.toString('base64'),
+  }, 30000)
+  return {
+    segmentCount: Number(resp?.segmentCount) || 0,
+    speechMs: Number(resp?.speechMs) || 0,
+  }
+}
+
 // 通过 sidecar 检查模型是否已下载
 export async function checkModelDownloaded(modelId: string): Promise<boolean> {
   const status = await checkModelStatus(modelId)
@@ -0,0 +1,124 @@
+import * as fs from 'fs'
+import * as path from 'path'
+
+// 日志文件按大小轮转：当前文件为 voice-input.log，超过上限后依次改名为 voice-input.1.log、voice-input.2.log……
+// 连同当前文件最多保留 maxFiles 个，最旧的直接删除。写入在后台异步进行，不阻塞主进程；
+// 轮转前先关闭句柄再改名，Windows 上改名不会被占用
+
+export const LOG_FILE_BASENAME = 'voice-input'
+
+export interface LogFileOptions {
+  maxBytes: number
+  maxFiles: number
+}
+
+// index 为 0 时是当前写入的文件
+export function rotatedLogPath(dir: string, index: number): string {
+  return path.join(dir, index === 0 ? `${LOG_FILE_BASENAME}.log` : `${LOG_FILE_BASENAME}.${index}.log`)
+}
+
+export function rotateLogFiles(dir: string, maxFiles: number): void {
+  const keep = Math.max(1, Math.floor(maxFiles))
+  fs.rmSync(rotatedLogPath(dir, keep - 1), { force: true })
+  for (let i = keep - 2; i >= 0; i--) {
+    const from = rotatedLogPath(dir, i)
+    if (fs.existsSync(from)) fs.renameSync(from, rotatedLogPath(dir, i + 1))
+  }
+}
+
+export class RotatingLogFile {
+  private handle: fs.promises.FileHandle | null = null
+  private size = 0
+  private pending: string[] = []
+  private draining: Promise<void> | null = null
+  private closeRequested = false
+
+  constructor(private readonly dir: string, private options: LogFileOptions) { }
+
+  get path(): string {
+    return rotatedLogPath(this.dir, 0)
+  }
+
+  setOptions(options: LogFileOptions): void {
+    this.options = options
+  }
+
+  // 只排队，不阻塞调用方；排队的多行由后台按顺序合并写入
+  write(line: string): void {
+    this.pending.push(line)
+    if (!this.draining) {
+      this.draining = this.drain().finally(() => { this.draining = null })
+    }
+  }
+
+  // 等排队的日志写完后释放文件句柄；之后再写入会重新打开
+  close(): Promise<void> {
+    if (this.draining) {
+      this.closeRequested = true
+      return this.draining
+    }
+    return this.closeHandle()
+  }
+
+  // 退出前同步写出还在排队的日志（进程即将结束，等不到后台写入）；不轮转，当前文件最多略超上限
+  flushSync(): void {
+    if (this.pending.length === 0) return
+    const text = this.pending.splice(0).join('')
+    try {
+      fs.mkdirSync(this.dir, { recursive: true })
+      fs.appendFileSync(this.path, text)
+      if (this.handle) this.size += Buffer.byteLength(text)
+    } catch (e) {
+      console.error(`[LOG] file write failed: ${String(e)}`)
+    }
+  }
+
+  private async drain(): Promise<void> {
+    while (this.pending.length > 0) {
+      try {
+        if (!this.handle) await this.open()
+        // 打开期间排队的日志可能已被 flushSync 写出
+        if (this.pending.length === 0) break
+        const bytes = Buffer.byteLength(this.pending[0])
+        // 空文件时即使单行超限也直接写入，避免无限轮转
+        if (this.size > 0 && this.size + bytes > this.options.maxBytes) {
+          await this.closeHandle()
+          rotateLogFiles(this.dir, this.options.maxFiles)
+          continue
+        }
+        // 不会触发轮转的连续多行合并为一次写入
+        let chunk = ''
+        let chunkBytes = 0
+        while (this.pending.length > 0) {
+          const next = Buffer.byteLength(this.pending[0])
+          if (chunk && this.size + chunkBytes + next > this.options.maxBytes) break
+          chunk += this.pending.shift()
+          chunkBytes += next
+        }
+        await this.handle!.write(chunk)
+        this.size += chunkBytes
+      } catch (e) {
+        // 写入失败时丢弃已排队的日志，下次写入重新打开文件，避免失败后反复重试
+        this.pending.length = 0
+        await this.closeHandle()
+        console.error(`[LOG] file write failed: ${String(e)}`)
+      }
+    }
+    if (this.closeRequested) {
+      this.closeRequested = false
+      await this.closeHandle()
+    }
+  }
+
+  private async open(): Promise<void> {
+    await fs.promises.mkdir(this.dir, { recursive: true })
+    this.handle = await fs.promises.open(this.path, 'a')
+    this.size = (await this.handle.stat()).size
+  }
+
+  private async closeHandle(): Promise<void> {
+    const handle = this.handle
+    this.handle = null
+    if (handle) await handle.close().catch(() => { })
+  }
+}
@@ -1,8 +1,8 @@
 import { app } from 'electron'
-import * as fs from 'fs'
 import * as path from 'path'
 import { insertAppLog, getRecentLogs, clearAppLogs } from './db'
-import { getConfig } from './config'
+import { getConfig, type LoggingConfig } from './config'
+import { RotatingLogFile, rotatedLogPath, type LogFileOptions } from './log-rotation'
 
 // 日志级别
 export type LogLevel = 'debug' | 'info' | 'warn' | 'error'
@@ -17,9 +17,13 @@ interface LogEntry {
 const LOG_BUFFER_SIZE = 2000
 const buffer: LogEntry[] = []
 
-// 日志文件路径
-let logFilePath = ''
-let logStream: fs.WriteStream | null = null
+// 日志文件：userData/logs 下按大小轮转，logging.toFile 关闭时只写 SQLite 与控制台
+let logFile: RotatingLogFile | null = null
+let logToFile = true
+
+// 运行时日志级别：启动时取 logging.level，之后由 setLogLevel 调整；未初始化前按配置读取
+const LEVEL_ORDER: Record<LogLevel, number> = { debug: 0, info: 1, warn: 2, error: 3 }
+let activeLevel: LogLevel | null = null
 
 // 主进程窗口引用，用于推送日志到渲染进程
 let _sendToRenderer: ((entry: LogEntry) => void) | null = null
@@ -30,21 +34,23 @@ function localTimestamp(): string {
   return d.toISOString().slice(0, 19).replace('T', ' ')
 }
 
-/** 东八区日期 YYYY-MM-DD */
-function localDateStr(): string {
-  return new Date(Date.now() + 8 * 3600000).toISOString().slice(0, 10)
+function logDir(): string {
+  return path.join(app.getPath('userData'), 'logs')
 }
 
-export function initLogger(sendToRenderer?: (entry: LogEntry) => void) {
-  const logDir = path.join(app.getPath('userData'), 'logs')
-  if (!fs.existsSync(logDir)) fs.mkdirSync(logDir, { recursive: true })
+type LogFileSettings = Pick<LoggingConfig, 'toFile' | 'maxFileSizeMb' | 'maxFiles'>
 
-  // 按日期命名日志文件
-  const date = localDateStr()
-  logFilePath = path.join(logDir, `${date}.log`)
-  logStream = fs.createWriteStream(logFilePath, { flags: 'a' })
+function logFileOptionsOf(settings: LogFileSettings): LogFileOptions {
+  return { maxBytes: settings.maxFileSizeMb * 1024 * 1024, maxFiles: settings.maxFiles }
+}
+
+export function initLogger(sendToRenderer?: (entry: LogEntry) => void) {
+  const settings = getConfig().logging
+  logFile = new RotatingLogFile(logDir(), logFileOptionsOf(settings))
+  logToFile = settings.toFile
 
   if (sendToRenderer) _sendToRenderer = sendToRenderer
+  activeLevel = configuredLevel()
 
   // 启动时从 SQLite 预热最近日志，保证前端首屏可读取历史日志。
   try {
@@ -61,8 +67,11 @@ export function initLogger(sendToRenderer?: (entry: LogEntry) => void) {
 }
 
 function write(level: LogLevel, msg: string) {
-  if (level === 'debug' && !isDebugEnabled()) return
+  if (LEVEL_ORDER[level] < LEVEL_ORDER[getLogLevel()]) return
+  append(level, msg)
+}
 
+function append(level: LogLevel, msg: string) {
   const entry: LogEntry = {
     time: localTimestamp(),
     level,
@@ -74,8 +83,13 @@ function write(level: LogLevel, msg: string) {
   if (buffer.length > LOG_BUFFER_SIZE) buffer.shift()
 
   // 写入文件
-  const line = `[${entry.time}] [${level.toUpperCase()}] ${msg}\n`
-  logStream?.write(line)
+  if (logToFile && logFile) {
+    try {
+      logFile.write(`[${entry.time}] [${level.toUpperCase()}] ${msg}\n`)
+    } catch (e) {
+      console.error(`[LOG] file write failed: ${String(e)}`)
+    }
+  }
 
   // 写入 SQLite（用于前台日志页持久化）
   try {
@@ -132,10 +146,38 @@ function normalizeLevel(level: string): LogLevel {
   return 'info'
 }
 
-function isDebugEnabled(): boolean {
+function configuredLevel(): LogLevel {
   try {
-    return Boolean(getConfig().logging?.enableDebug)
+    return getConfig().logging?.level ?? 'info'
   } catch {
-    return false
+    return 'info'
   }
 }
+
+// 配置保存后调整文件日志：关闭时释放文件句柄，大小与份数在下次写入时生效
+export function applyLogFileSettings(settings: LogFileSettings): void {
+  logToFile = settings.toFile
+  if (!logToFile) void logFile?.close()
+  logFile?.setOptions(logFileOptionsOf(settings))
+}
+
+// 退出前同步写出尚在排队的文件日志，避免最后几行丢失
+export function flushLogFileSync(): void {
+  logFile?.flushSync()
+}
+
+// 当前日志文件路径，供界面“打开日志”使用（文件日志关闭时文件可能不存在）
+export function getLogFilePath(): string {
+  return logFile?.path ?? rotatedLogPath(logDir(), 0)
+}
+
+export function getLogLevel(): LogLevel {
+  return activeLevel ?? configuredLevel()
+}
+
+// 无需重启即可调整级别；级别变化本身总会记录，便于排查时确认抓取区间
+export function setLogLevel(level: LogLevel): void {
+  const previous = getLogLevel()
+  activeLevel = level
+  if (previous !== level) append('info', `[Log] 日志级别 ${previous} → ${level}`)
+}
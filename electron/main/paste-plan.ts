@@ -1,6 +1,20 @@
 import type { PasteTargetProbeReason } from './input-sim'
 
-export type FloatPasteFallbackReason = PasteTargetProbeReason | 'type-failed' | 'restore-failed'
+export type FloatPasteFallbackReason = PasteTargetProbeReason | 'type-failed' | 'restore-failed' | 'safe-mode-unarmed'
+
+// 文字粘贴方式：replace 直接粘贴（覆盖当前选区，为原有行为）；
+// append 先按导航键（默认 END）取消选区并把光标移到末尾，再粘贴，连续口述同一输入框时内容依次追加。
+// 追加只移动光标，不合并剪贴板内容，避免把上一段文字重复粘贴一次
+export type PasteMode = 'replace' | 'append'
+
+export const DEFAULT_APPEND_NAVIGATION_KEY = 'END'
+
+// 一次文字输入依次发送的按键：导航键（仅 append）+ 粘贴快捷键
+export function buildPasteKeySequence(mode: PasteMode, pasteShortcut: string, navigationKey = DEFAULT_APPEND_NAVIGATION_KEY): string[] {
+  const navigation = navigationKey.trim()
+  if (mode !== 'append' || !navigation) return [pasteShortcut]
+  return [navigation, pasteShortcut]
+}
 
 export interface FocusRestoreResult {
   success: boolean
@@ -0,0 +1,68 @@
+// 定时录音（快速记录）：触发一次后固定录音一段时间再走完整的识别、指令匹配与输入流程。
+// 录音在浮窗渲染进程中进行，主进程把请求发给浮窗，浮窗真正开始录音（或拒绝）后回报，
+// 调用方据此得知是否已开始，而不是消息发出就算成功
+
+// 时长上限与单个语音段上限（vad.maxUtteranceMs 最大值）一致
+export const QUICK_RECORD_DEFAULT_MS = 5000
+export const QUICK_RECORD_MIN_MS = 1000
+export const QUICK_RECORD_MAX_MS = 120000
+
+export function clampQuickRecordMs(raw: unknown): number {
+  const value = typeof raw === 'number' && Number.isFinite(raw) ? raw : QUICK_RECORD_DEFAULT_MS
+  return Math.round(Math.min(QUICK_RECORD_MAX_MS, Math.max(QUICK_RECORD_MIN_MS, value)))
+}
+
+export type QuickRecordResult =
+  | { ok: true }
+  | { ok: false; message: string }
+
+export interface QuickRecordRequest {
+  requestId: number
+  durationMs: number
+}
+
+// 渲染进程回报的数据不可信：缺少 requestId 返回 null，失败原因截断或补默认值
+export function normalizeQuickRecordReport(raw: unknown): { requestId: number; result: QuickRecordResult } | null {
+  const data = raw && typeof raw === 'object' ? raw as Record<string, unknown> : {}
+  const requestId = Number(data.requestId)
+  if (!Number.isInteger(requestId) || requestId <= 0) return null
+  if (data.ok === true) return { requestId, result: { ok: true } }
+  const message = typeof data.message === 'string' && data.message ? data.message.slice(0, 500) : '浮窗未能开始定时录音'
+  return { requestId, result: { ok: false, message } }
+}
+
+export class QuickRecordRequests {
+  private seq = 0
+  private readonly pending = new Map<number, (result: QuickRecordResult) => void>()
+
+  constructor(private readonly timeoutMs = 10000) { }
+
+  // send 返回 false 表示浮窗不可用，直接失败
+  request(durationMs: number, send: (request: QuickRecordRequest) => boolean): Promise<QuickRecordResult> {
+    const requestId = ++this.seq
+    return new Promise((resolve) => {
+      const timer = setTimeout(() => {
+        this.pending.delete(requestId)
+        resolve({ ok: false, message: `开始定时录音超时（${Math.round(this.timeoutMs / 1000)}s 内浮窗未响应）` })
+      }, this.timeoutMs)
+      this.pending.set(requestId, (result) => {
+        clearTimeout(timer)
+        this.pending.delete(requestId)
+        resolve(result)
+      })
+      if (!send({ requestId, durationMs })) {
+        this.pending.get(requestId)?.({ ok: false, message: '浮窗未就绪，无法开始定时录音' })
+      }
+    })
+  }
+
+  // 浮窗回报结果；超时后才到达的回报直接忽略，返回是否有对应的请求
+  settle(raw: unknown): boolean {
+    const report = normalizeQuickRecordReport(raw)
+    if (!report) return false
+    const resolve = this.pending.get(report.requestId)
+    if (!resolve) return false
+    resolve(report.result)
+    return true
+  }
+}
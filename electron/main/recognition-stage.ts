@@ -0,0 +1,62 @@
+// 识别流程阶段事件：从触发（松开热键/点击停止/VAD 切段）起计时，依次经过
+// encoding（压缩/组装上传数据）→ uploading → server-processing → matching → inserting，
+// 每进入一个阶段通知界面一次，结束时汇总各阶段耗时写日志，便于区分慢在编码、网络还是服务端。
+// HTTP 上传无法区分上传完成与服务端开始处理的时刻，uploading 包含等待服务端响应的时间；
+// WebSocket 在音频发送完毕后进入 server-processing；本地识别与离线指令直接进入 server-processing
+
+export type RecognitionStage = 'encoding' | 'uploading' | 'server-processing' | 'matching' | 'inserting'
+
+export interface RecognitionStageEvent {
+  requestId: number
+  traceId: string
+  stage: RecognitionStage
+  // 自触发起经过的毫秒数
+  elapsedMs: number
+}
+
+// 渲染进程传来的触发时间超出该范围（时钟异常或参数缺失）时以收到请求的时间为准
+const TRIGGER_MAX_AGE_MS = 60_000
+
+export function resolveTriggeredAt(raw: unknown, now: number): number {
+  const value = Number(raw)
+  if (!Number.isFinite(value) || value > now || now - value > TRIGGER_MAX_AGE_MS) return now
+  return value
+}
+
+export class RecognitionStageTracker {
+  private current: { stage: RecognitionStage; at: number } | null = null
+  private readonly durations = new Map<RecognitionStage, number>()
+
+  constructor(
+    private readonly requestId: number,
+    private readonly traceId: string,
+    private readonly emit: (event: RecognitionStageEvent) => void,
+    private readonly triggeredAt: number,
+    private readonly now: () => number = Date.now,
+  ) { }
+
+  enter(stage: RecognitionStage): void {
+    if (this.current?.stage === stage) return
+    const at = this.now()
+    this.closeCurrent(at)
+    this.current = { stage, at }
+    this.emit({ requestId: this.requestId, traceId: this.traceId, stage, elapsedMs: Math.max(0, at - this.triggeredAt) })
+  }
+
+  // 结束计时并返回各阶段耗时摘要；未进入任何阶段（如音频过短被跳过）时返回空字符串
+  finish(): string {
+    const at = this.now()
+    this.closeCurrent(at)
+    this.current = null
+    if (this.durations.size === 0) return ''
+    const parts = Array.from(this.durations, ([stage, ms]) => `${stage}=${ms}ms`)
+    parts.push(`total=${Math.max(0, at - this.triggeredAt)}ms`)
+    return parts.join(' ')
+  }
+
+  private closeCurrent(at: number): void {
+    if (!this.current) return
+    const { stage, at: startedAt } = this.current
+    this.durations.set(stage, (this.durations.get(stage) ?? 0) + Math.max(0, at - startedAt))
+  }
+}
@@ -0,0 +1,61 @@
+// 录音热键的“轻按切换 / 按住说话”：hotkey.recordMode 为 tap-or-hold 时按按下时长区分，
+// 短于 hotkey.tapThresholdMs 的轻按开始连续录音，松开后继续录，再按一次结束并识别；
+// 按住超过阈值则与 hold 模式相同，松开即识别。hold 模式下每次松开都结束录音
+
+export type RecordHotkeyMode = 'hold' | 'tap-or-hold'
+export type RecordPressKind = 'tap' | 'hold'
+
+export type RecordPressAction = 'start' | 'stop' | 'ignore'
+// keep：轻按松开后保持录音
+export type RecordReleaseAction = 'stop' | 'keep' | 'ignore'
+
+// 按下时长恰好等于阈值按“按住”处理
+export function classifyRecordPress(durationMs: number, tapThresholdMs: number): RecordPressKind {
+  return durationMs < tapThresholdMs ? 'tap' : 'hold'
+}
+
+export class RecordHotkeyPressTracker {
+  // pressed：按住中；latched：轻按后保持录音；stopping：结束录音的那次按键尚未松开
+  private phase: 'idle' | 'pressed' | 'latched' | 'stopping' = 'idle'
+  private pressStartedAt = 0
+
+  constructor(
+    private readonly mode: RecordHotkeyMode,
+    private readonly tapThresholdMs: number,
+    private readonly now: () => number = Date.now,
+  ) { }
+
+  // 主键按下（含系统按键重复与 globalShortcut 兜底的重复通知）
+  press(): RecordPressAction {
+    if (this.phase === 'idle') {
+      this.phase = 'pressed'
+      this.pressStartedAt = this.now()
+      return 'start'
+    }
+    if (this.phase === 'latched') {
+      this.phase = 'stopping'
+      return 'stop'
+    }
+    return 'ignore'
+  }
+
+  // 录音被外部丢弃后复位：按键仍按住时等它松开（期间的按键重复不再开始录音），轻按保持中直接回到空闲
+  reset(): void {
+    this.phase = this.phase === 'pressed' || this.phase === 'stopping' ? 'stopping' : 'idle'
+  }
+
+  release(): RecordReleaseAction {
+    if (this.phase === 'stopping') {
+      this.phase = 'idle'
+      return 'ignore'
+    }
+    if (this.phase !== 'pressed') return 'ignore'
+    const kind = classifyRecordPress(this.now() - this.pressStartedAt, this.tapThresholdMs)
+    if (this.mode === 'tap-or-hold' && kind === 'tap') {
+      this.phase = 'latched'
+      return 'keep'
+    }
+    this.phase = 'idle'
+    return 'stop'
+  }
+}
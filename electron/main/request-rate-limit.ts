@@ -0,0 +1,32 @@
+// 远程识别请求限流：嘈杂环境下 VAD 每分钟可能切出很多语音段，多台工作站共用的 ASR 服务会被打满。
+// 两次请求之间至少间隔 server.minRequestIntervalMs（0 不限制），过密的语音段直接丢弃并记警告日志；
+// 按键录音（含连续分段录音切出的段）默认不受限，server.rateLimitPushToTalk 开启后同样限流。所有实际发出的请求都计入间隔
+
+// segment 为按住热键连续分段录音中切出的段，录音仍在继续
+export type RecognitionSource = 'vad' | 'manual' | 'segment'
+
+export interface RateLimitOptions {
+  minRequestIntervalMs: number
+  rateLimitPushToTalk: boolean
+}
+
+export function isRateLimited(source: RecognitionSource, options: RateLimitOptions): boolean {
+  if (options.minRequestIntervalMs <= 0) return false
+  return source === 'vad' || options.rateLimitPushToTalk
+}
+
+export class AsrRequestRateLimiter {
+  private lastSentAt: number | null = null
+
+  constructor(private readonly now: () => number = Date.now) { }
+
+  // 距离允许下一次请求还需等待的毫秒数，0 表示可以发送
+  remaining(intervalMs: number): number {
+    if (intervalMs <= 0 || this.lastSentAt === null) return 0
+    return Math.max(0, intervalMs - (this.now() - this.lastSentAt))
+  }
+
+  markSent(): void {
+    this.lastSentAt = this.now()
+  }
+}
@@ -0,0 +1,29 @@
+// 识别结果显示一段时间后自动隐藏浮窗，避免浮窗长时间遮挡 LIS 等业务界面。
+// 开始录音或新的识别开始时取消待执行的隐藏；重复 schedule 以最后一次为准
+
+export class ResultAutoHide {
+  private timer: ReturnType<typeof setTimeout> | null = null
+
+  constructor(private readonly hide: () => void) { }
+
+  get pending(): boolean {
+    return this.timer !== null
+  }
+
+  // delayMs 为 0 或非法值时不隐藏，返回是否已安排
+  schedule(delayMs: number): boolean {
+    this.cancel()
+    if (!Number.isFinite(delayMs) || delayMs <= 0) return false
+    this.timer = setTimeout(() => {
+      this.timer = null
+      this.hide()
+    }, delayMs)
+    return true
+  }
+
+  cancel(): void {
+    if (!this.timer) return
+    clearTimeout(this.timer)
+    this.timer = null
+  }
+}
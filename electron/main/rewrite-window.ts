@@ -4,6 +4,8 @@ import { copySelectedText, pasteClipboard } from './input-sim'
 import { restoreFocus, getFrontmostApp } from './focus-controller'
 import { rewriteText } from './llm-service'
 import { logger } from './logger'
+import { getConfig } from './config'
+import { truncateText } from '../../shared/text'
 import { stringifyErrorLike, attachWebContentsDiagnostics } from './app-context'
 
 let rewriteWindow: BrowserWindow | null = null
@@ -51,8 +53,8 @@ export function initRewriteWindow() {
         // 短暂延迟确保焦点切换与剪贴板完全就绪
         setTimeout(async () => {
             try {
-                await pasteClipboard()
-                logger.info(`[Rewrite] 文本覆盖替换成功。\n片段: [${newText.slice(0, 15)}...]`)
+                await pasteClipboard({ pasteShortcut: getConfig().input.pasteShortcut })
+                logger.info(`[Rewrite] 文本覆盖替换成功。\n片段: [${truncateText(newText, 15)}]`)
             } catch (e) {
                 logger.error(`[Rewrite] 文本粘贴宏执行失败: ${e}`)
             }
@@ -66,7 +68,7 @@ export async function triggerRewrite() {
     let text = ''
     try {
         text = await copySelectedText()
-        logger.info(`[Rewrite] 从系统中捕获划取文本: ${text.slice(0, 20)}...`)
+        logger.info(`[Rewrite] 从系统中捕获划取文本: ${truncateText(text, 20)}`)
     } catch (e) {
         logger.error(`[Rewrite] 提取文本失败: ${e}`)
     }
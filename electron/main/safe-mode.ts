@@ -0,0 +1,31 @@
+// 安全模式：每个会话首次输入前需要用户确认目标输入框（arm），
+// 确认后正常输入；超过空闲时长没有输入则自动解除，下次输入前重新确认，防止文字落到错误窗口
+
+export class InputArmState {
+  private armedAt = 0
+  private lastActivityAt = 0
+
+  isArmed(now: number, idleTimeoutMs: number): boolean {
+    if (!this.armedAt) return false
+    if (now - this.lastActivityAt > idleTimeoutMs) {
+      this.disarm()
+      return false
+    }
+    return true
+  }
+
+  arm(now: number): void {
+    this.armedAt = now
+    this.lastActivityAt = now
+  }
+
+  // 每次成功输入后刷新空闲计时
+  touch(now: number): void {
+    if (this.armedAt) this.lastActivityAt = now
+  }
+
+  disarm(): void {
+    this.armedAt = 0
+    this.lastActivityAt = 0
+  }
+}
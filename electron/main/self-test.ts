@@ -0,0 +1,162 @@
+import { readWavInfo, wavToMonoFloat32 } from './wav-info'
+
+// 整条识别链路的一键自检（现场支持用）：麦克风 -> 录音 -> 非静音 -> 编码 -> 网络 -> 服务端识别。
+// 麦克风与录音两步在渲染进程完成后连同录音一起交给主进程；每一步单独给出成功/失败与耗时，
+// 只有缺少前一步的产物时才跳过。自检不模拟任何按键、不写识别记录
+
+export type SelfTestStage = 'microphone' | 'recording' | 'signal' | 'encoding' | 'network' | 'server'
+export type SelfTestStatus = 'passed' | 'failed' | 'skipped'
+
+export interface SelfTestStageResult {
+  stage: SelfTestStage
+  label: string       // 中文步骤名，渲染进程直接展示
+  status: SelfTestStatus
+  durationMs: number
+  message: string
+}
+
+export interface SelfTestReport {
+  ok: boolean
+  startedAt: string
+  totalMs: number
+  stages: SelfTestStageResult[]
+}
+
+// 渲染进程的采集结果：error 为空表示该步成功
+export interface SelfTestCapture {
+  microphone: { durationMs: number; error?: string }
+  recording: { durationMs: number; error?: string }
+  wav: ArrayBuffer | null
+}
+
+export interface SelfTestDeps {
+  // 按当前配置编码上传内容，返回格式与字节数；失败时抛出
+  encode: (wav: Buffer) => { format: string; bytes: number }
+  // 返回 null 表示当前识别模式不需要网络
+  checkNetwork: () => Promise<{ ok: boolean; message: string } | null>
+  recognize: (wav: Buffer) => Promise<string>
+  now?: () => number
+}
+
+// 低于该 RMS 视为静音（约 -50 dBFS），多为麦克风被静音或选错了输入设备
+export const SELF_TEST_MIN_RMS = 0.003
+
+export const SELF_TEST_STAGE_LABELS: Record<SelfTestStage, string> = {
+  microphone: '麦克风',
+  recording: '录音',
+  signal: '音量检测',
+  encoding: '音频编码',
+  network: '网络连接',
+  server: '服务端识别',
+}
+
+function errorMessage(e: unknown): string {
+  return e instanceof Error ? e.message : String(e)
+}
+
+function wavRms(wav: Buffer): number {
+  const samples = wavToMonoFloat32(wav)
+  if (samples.length === 0) return 0
+  let sum = 0
+  for (let i = 0; i < samples.length; i++) sum += samples[i] * samples[i]
+  return Math.sqrt(sum / samples.length)
+}
+
+export async function runSelfTest(capture: SelfTestCapture, deps: SelfTestDeps): Promise<SelfTestReport> {
+  const now = deps.now ?? Date.now
+  const startedAt = now()
+  const stages: Omit<SelfTestStageResult, 'label'>[] = []
+  const skip = (stage: SelfTestStage, reason: string) => {
+    stages.push({ stage, status: 'skipped', durationMs: 0, message: reason })
+  }
+  // 执行一步并计时：返回 false 或抛出都记为失败，message 为失败原因或成功说明
+  const step = async (stage: SelfTestStage, fn: () => Promise<string> | string): Promise<boolean> => {
+    const begin = now()
+    try {
+      const message = await fn()
+      stages.push({ stage, status: 'passed', durationMs: now() - begin, message })
+      return true
+    } catch (e) {
+      stages.push({ stage, status: 'failed', durationMs: now() - begin, message: errorMessage(e) })
+      return false
+    }
+  }
+
+  const micOk = !capture.microphone.error
+  stages.push({
+    stage: 'microphone',
+    status: micOk ? 'passed' : 'failed',
+    durationMs: capture.microphone.durationMs,
+    message: capture.microphone.error ?? '已找到可用的麦克风',
+  })
+  const wav = capture.wav && capture.wav.byteLength > 0 ? Buffer.from(capture.wav) : null
+  if (!micOk) {
+    skip('recording', '麦克风不可用，跳过录音')
+  } else {
+    const recordError = capture.recording.error ?? (wav ? undefined : '未录到任何音频')
+    stages.push({
+      stage: 'recording',
+      status: recordError ? 'failed' : 'passed',
+      durationMs: capture.recording.durationMs,
+      message: recordError ?? `已录制 ${readWavInfo(wav!).durationMs}ms`,
+    })
+  }
+
+  if (!wav) {
+    skip('signal', '没有录音，跳过音量检测')
+  } else {
+    await step('signal', () => {
+      const rms = wavRms(wav)
+      if (rms < SELF_TEST_MIN_RMS) {
+        throw new Error(`录音几乎是静音（RMS ${rms.toFixed(4)}），请检查麦克风是否被静音、输入设备是否选对`)
+      }
+      return `录音音量正常（RMS ${rms.toFixed(4)}）`
+    })
+  }
+
+  let encoded = false
+  if (!wav) {
+    skip('encoding', '没有录音，跳过编码')
+  } else {
+    encoded = await step('encoding', () => {
+      const { durationMs } = readWavInfo(wav)
+      if (durationMs <= 0) throw new Error('录音数据为空或 WAV 格式不正确')
+      const { format, bytes } = deps.encode(wav)
+      return `${format.toUpperCase()} 编码成功，${bytes} 字节`
+    })
+  }
+
+  // 网络不依赖录音，麦克风故障时也单独检查
+  let networkOk = true
+  const networkBegin = now()
+  let network: { ok: boolean; message: string } | null
+  try {
+    network = await deps.checkNetwork()
+  } catch (e) {
+    network = { ok: false, message: errorMessage(e) }
+  }
+  if (network === null) {
+    skip('network', '当前识别模式无需网络')
+  } else {
+    networkOk = network.ok
+    stages.push({ stage: 'network', status: network.ok ? 'passed' : 'failed', durationMs: now() - networkBegin, message: network.message })
+  }
+
+  if (!wav || !encoded) {
+    skip('server', wav ? '音频编码失败，跳过识别' : '没有录音，跳过识别')
+  } else if (!networkOk) {
+    skip('server', '无法连接服务器，跳过识别')
+  } else {
+    await step('server', async () => {
+      const text = (await deps.recognize(wav)).trim()
+      return text ? `识别成功：“${text}”` : '识别成功，但服务端返回空文本'
+    })
+  }
+
+  return {
+    ok: stages.every(s => s.status !== 'failed'),
+    startedAt: new Date(startedAt).toISOString(),
+    totalMs: now() - startedAt,
+    stages: stages.map(s => ({ ...s, label: SELF_TEST_STAGE_LABELS[s.stage] })),
+  }
+}
@@ -0,0 +1,194 @@
+import { validateServerUrl } from './asr'
+
+// 服务器连通性巡检：定时对服务地址（或 server.healthPath）发一个轻量 GET，结果用于托盘提示与界面状态。
+// 只要收到非 5xx 响应即视为可连接（服务端未实现健康检查路径时 404 也说明网络与服务正常）；
+// 检查失败不影响识别流程，也不抛出异常
+
+export type ServerConnectionState = 'unknown' | 'connected' | 'unreachable' | 'disabled'
+
+export interface ServerStatus {
+  state: ServerConnectionState
+  message: string
+  checkedAt: string
+  latencyMs: number | null
+  error?: string
+}
+
+export interface ServerHealthTarget {
+  url: string
+  healthPath: string
+  autoPrependScheme: boolean
+  intervalMs: number
+  timeoutMs?: number
+}
+
+const DEFAULT_HEALTH_TIMEOUT_MS = 3000
+
+export const SERVER_STATUS_MESSAGES: Record<ServerConnectionState, string> = {
+  unknown: '正在检查服务器连接',
+  connected: '已连接',
+  unreachable: '无法连接到服务器',
+  disabled: '未检查服务器连接',
+}
+
+export function trayTooltipFor(status: ServerStatus): string {
+  return status.state === 'disabled' ? '朗珈语音输入法' : `朗珈语音输入法 - ${status.message}`
+}
+
+export function buildHealthCheckUrl(baseUrl: string, healthPath: string): string {
+  const path = healthPath.trim()
+  if (!path) return baseUrl
+  return `${baseUrl}${path.startsWith('/') ? '' : '/'}${path}`
+}
+
+// signal 用于取消手动 ping；取消时 fetch 以 AbortError 失败，结果同样为 unreachable
+export async function checkServerHealth(
+  target: ServerHealthTarget,
+  now: () => number = Date.now,
+  signal?: AbortSignal,
+): Promise<ServerStatus> {
+  const checkedAt = new Date(now()).toISOString()
+  const validated = validateServerUrl(target.url, { autoPrependScheme: target.autoPrependScheme })
+  if (!validated.ok) {
+    return { state: 'unreachable', message: SERVER_STATUS_MESSAGES.unreachable, checkedAt, latencyMs: null, error: validated.error }
+  }
+  const timeout = AbortSignal.timeout(target.timeoutMs ?? DEFAULT_HEALTH_TIMEOUT_MS)
+  const startedAt = now()
+  try {
+    const resp = await fetch(buildHealthCheckUrl(validated.url, target.healthPath), {
+      method: 'GET',
+      signal: signal ? AbortSignal.any([timeout, signal]) : timeout,
+    })
+    const latencyMs = now() - startedAt
+    if (resp.status >= 500) {
+      return {
+        state: 'unreachable',
+        message: SERVER_STATUS_MESSAGES.unreachable,
+        checkedAt,
+        latencyMs,
+        error: `服务器返回错误状态: ${resp.status}`,
+      }
+    }
+    return { state: 'connected', message: SERVER_STATUS_MESSAGES.connected, checkedAt, latencyMs }
+  } catch (e) {
+    return {
+      state: 'unreachable',
+      message: SERVER_STATUS_MESSAGES.unreachable,
+      checkedAt,
+      latencyMs: null,
+      error: e instanceof Error ? e.message : String(e),
+    }
+  }
+}
+
+export interface LatencySample {
+  at: string
+  latencyMs: number | null  // null 表示本次未连通
+  error?: string
+}
+
+export interface LatencySummary {
+  count: number
+  failures: number
+  lastMs: number | null
+  avgMs: number | null
+  maxMs: number | null
+}
+
+const LATENCY_HISTORY_DEFAULT_CAPACITY = 120
+
+// 最近若干次健康检查（定时巡检与手动 ping）的往返耗时，供诊断页画延迟曲线；
+// 只保留最近 capacity 条，网络变差时在听写真正失败之前就能看到延迟上升或连续失败
+export class LatencyHistory {
+  private samples: LatencySample[] = []
+
+  constructor(private readonly capacity = LATENCY_HISTORY_DEFAULT_CAPACITY) { }
+
+  record(status: ServerStatus): LatencySample {
+    const sample: LatencySample = { at: status.checkedAt, latencyMs: status.state === 'connected' ? status.latencyMs : null }
+    if (sample.latencyMs === null) sample.error = status.error ?? status.message
+    this.samples.push(sample)
+    if (this.samples.length > this.capacity) this.samples.splice(0, this.samples.length - this.capacity)
+    return { ...sample }
+  }
+
+  list(): LatencySample[] {
+    return this.samples.map(sample => ({ ...sample }))
+  }
+
+  summary(): LatencySummary {
+    const latencies = this.samples.flatMap(sample => sample.latencyMs === null ? [] : [sample.latencyMs])
+    const last = this.samples[this.samples.length - 1]
+    return {
+      count: this.samples.length,
+      failures: this.samples.length - latencies.length,
+      lastMs: last?.latencyMs ?? null,
+      avgMs: latencies.length ? Math.round(latencies.reduce((sum, ms) => sum + ms, 0) / latencies.length) : null,
+      maxMs: latencies.length ? Math.max(...latencies) : null,
+    }
+  }
+
+  clear(): void {
+    this.samples = []
+  }
+}
+
+// 定时巡检：getTarget 返回 null 表示当前不需要检查（如本地识别模式或关闭了巡检），状态变化时回调 onChange
+export class ServerHealthMonitor {
+  private timer: ReturnType<typeof setTimeout> | null = null
+  private running = false
+  private generation = 0
+  private current: ServerStatus = {
+    state: 'unknown',
+    message: SERVER_STATUS_MESSAGES.unknown,
+    checkedAt: '',
+    latencyMs: null,
+  }
+
+  constructor(
+    private readonly getTarget: () => ServerHealthTarget | null,
+    private readonly onChange: (status: ServerStatus, previous: ServerStatus) => void,
+    private readonly check: (target: ServerHealthTarget) => Promise<ServerStatus> = checkServerHealth,
+  ) { }
+
+  get status(): ServerStatus { return this.current }
+
+  // 立即检查一次并按最新配置重新安排下次检查；配置变更后调用
+  async refresh(): Promise<ServerStatus> {
+    this.running = true
+    this.clearTimer()
+    const generation = ++this.generation
+    const target = this.getTarget()
+    if (!target) {
+      this.update({ state: 'disabled', message: SERVER_STATUS_MESSAGES.disabled, checkedAt: new Date().toISOString(), latencyMs: null })
+      return this.current
+    }
+    const next = await this.check(target)
+    // 检查期间配置已变更或已停止，丢弃过期结果
+    if (generation !== this.generation || !this.running) return this.current
+    this.update(next)
+    if (target.intervalMs > 0) {
+      this.timer = setTimeout(() => { void this.refresh() }, target.intervalMs)
+    }
+    return this.current
+  }
+
+  stop(): void {
+    this.running = false
+    this.generation++
+    this.clearTimer()
+  }
+
+  private update(next: ServerStatus): void {
+    const previous = this.current
+    this.current = next
+    if (previous.state !== next.state) this.onChange(next, previous)
+  }
+
+  private clearTimer(): void {
+    if (this.timer) {
+      clearTimeout(this.timer)
+      this.timer = null
+    }
+  }
+}
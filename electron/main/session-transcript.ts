@@ -0,0 +1,91 @@
+// 整段口述记录：把一次口述会话中多句识别出的文字按顺序拼接，便于长段报告口述后整体复制或导出。
+// 会话由显式 start/end 界定；未显式开始时，与上一句间隔超过 idleGapMs 自动开始新会话。
+// 只记录口述文字，语音指令不计入；涉及隐私，需开启 transcript.enabled 才会记录
+
+export interface SessionTranscriptState {
+  // 显式开始且尚未结束
+  active: boolean
+  startedAt: number | null
+  segmentCount: number
+  text: string
+}
+
+const LATIN_WORD_END_RE = /[A-Za-z0-9]$/
+const LATIN_WORD_START_RE = /^[A-Za-z0-9]/
+
+// 拼接两句：英文单词、数字相接时补一个空格，中文直接相连
+export function joinTranscriptSegments(segments: string[]): string {
+  let result = ''
+  for (const segment of segments) {
+    const text = segment.trim()
+    if (!text) continue
+    const needsSpace = LATIN_WORD_END_RE.test(result) && LATIN_WORD_START_RE.test(text)
+    result += (needsSpace ? ' ' : '') + text
+  }
+  return result
+}
+
+export class SessionTranscript {
+  private segments: string[] = []
+  private explicit = false
+  // 显式结束后，下一句开始新会话
+  private closed = false
+  private startedAt: number | null = null
+  private lastRecordedAt: number | null = null
+
+  constructor(private readonly now: () => number = Date.now) { }
+
+  get state(): SessionTranscriptState {
+    return {
+      active: this.explicit,
+      startedAt: this.startedAt,
+      segmentCount: this.segments.length,
+      text: joinTranscriptSegments(this.segments),
+    }
+  }
+
+  // 显式开始新会话，清空之前的内容
+  start(): void {
+    this.segments = []
+    this.explicit = true
+    this.closed = false
+    this.startedAt = this.now()
+    this.lastRecordedAt = null
+  }
+
+  // 结束显式会话；内容保留到下一次开始，供复制或导出
+  end(): SessionTranscriptState {
+    if (this.explicit) this.closed = true
+    this.explicit = false
+    return this.state
+  }
+
+  // idleGapMs 为 0 表示不按间隔自动分段
+  record(text: string, idleGapMs: number): void {
+    if (!text.trim()) return
+    const at = this.now()
+    const idle = this.lastRecordedAt !== null && idleGapMs > 0 && at - this.lastRecordedAt > idleGapMs
+    if (!this.explicit && (idle || this.closed || this.startedAt === null)) {
+      this.segments = []
+      this.closed = false
+      this.startedAt = at
+    }
+    this.segments.push(text)
+    this.lastRecordedAt = at
+  }
+
+  clear(): void {
+    this.segments = []
+    this.explicit = false
+    this.closed = false
+    this.startedAt = null
+    this.lastRecordedAt = null
+  }
+}
+
+// 导出文件名：口述记录-20260316-0930.txt（本地时间）
+export function transcriptFileName(at: Date): string {
+  const pad = (n: number) => String(n).padStart(2, '0')
+  const date = `${at.getFullYear()}${pad(at.getMonth() + 1)}${pad(at.getDate())}`
+  return `口述记录-${date}-${pad(at.getHours())}${pad(at.getMinutes())}.txt`
+}
@@ -0,0 +1,162 @@
+// 快捷键字符串解析（"ALT+R"、"CMD+SHIFT+F2"），与平台相关的修饰键映射集中在这里
+
+export type ShortcutModifier = 'ctrl' | 'alt' | 'shift' | 'meta'
+
+export interface ParsedShortcut {
+  modifiers: ShortcutModifier[]
+  key: string
+}
+
+export interface ShortcutParseOptions {
+  // macOS 上把 Ctrl 与 Cmd 互换，便于同一套 Windows 口令配置在 Mac 上复用
+  mapCtrlToCmdOnMac?: boolean
+}
+
+const MODIFIER_ALIASES: Record<string, ShortcutModifier> = {
+  ALT: 'alt',
+  OPTION: 'alt',
+  CTRL: 'ctrl',
+  CONTROL: 'ctrl',
+  SHIFT: 'shift',
+  META: 'meta',
+  CMD: 'meta',
+  COMMAND: 'meta',
+  WIN: 'meta',
+  SUPER: 'meta',
+}
+
+export function parseShortcut(
+  shortcut: string,
+  platform: NodeJS.Platform = process.platform,
+  options: ShortcutParseOptions = {},
+): ParsedShortcut {
+  const parts = shortcut.normalize('NFKC').toUpperCase().split('+').map(s => s.trim()).filter(Boolean)
+  const swapCtrlMeta = platform === 'darwin' && Boolean(options.mapCtrlToCmdOnMac)
+  const modifiers: ShortcutModifier[] = []
+  let key = ''
+
+  for (const part of parts) {
+    let modifier = MODIFIER_ALIASES[part]
+    if (!modifier) {
+      key = part
+      continue
+    }
+    if (swapCtrlMeta) {
+      if (modifier === 'ctrl') modifier = 'meta'
+      else if (modifier === 'meta') modifier = 'ctrl'
+    }
+    if (!modifiers.includes(modifier)) modifiers.push(modifier)
+  }
+
+  return { modifiers, key }
+}
+
+// 多步快捷键序列以分号分隔，如 "ALT+F; S; ENTER"，按顺序逐步发送
+export const SHORTCUT_STEP_SEPARATOR = ';'
+
+// 全角输入（“ＡＬＴ＋Ｒ”“Ｆ２；Ｓ”）经 NFKC 归一为半角后再解析
+export function splitShortcutSequence(value: string): string[] {
+  return value.normalize('NFKC').split(SHORTCUT_STEP_SEPARATOR).map(step => step.trim())
+}
+
+// 解析整条序列；任一步为空或不含任何按键时抛错，避免只发出前半段
+export function parseShortcutSequence(
+  value: string,
+  platform: NodeJS.Platform = process.platform,
+  options: ShortcutParseOptions = {},
+): ParsedShortcut[] {
+  const steps = splitShortcutSequence(value)
+  return steps.map((step, index) => {
+    const parsed = parseShortcut(step, platform, options)
+    if (!parsed.key && parsed.modifiers.length === 0) {
+      const label = steps.length > 1 ? `第 ${index + 1} 步` : '快捷键'
+      throw new Error(`${label}无效: "${step}"（完整配置: "${value}"）`)
+    }
+    return parsed
+  })
+}
+
+// 可发送的具名主键及其展示名；其余仅接受单个可打印字符（字母、数字、标点）
+const NAMED_KEYS: Record<string, string> = {
+  SPACE: 'Space', ENTER: 'Enter', RETURN: 'Enter', TAB: 'Tab',
+  ESCAPE: 'Esc', ESC: 'Esc', BACKSPACE: 'Backspace', DELETE: 'Delete', DEL: 'Delete',
+  UP: 'Up', DOWN: 'Down', LEFT: 'Left', RIGHT: 'Right',
+  HOME: 'Home', END: 'End', PAGEUP: 'PageUp', PAGEDOWN: 'PageDown',
+}
+for (let i = 1; i <= 12; i++) NAMED_KEYS[`F${i}`] = `F${i}`
+for (let i = 0; i <= 9; i++) NAMED_KEYS[`NUMPAD${i}`] = `Numpad${i}`
+
+const MODIFIER_ORDER: ShortcutModifier[] = ['ctrl', 'alt', 'shift', 'meta']
+
+function modifierDisplayName(modifier: ShortcutModifier, platform: NodeJS.Platform): string {
+  if (modifier === 'ctrl') return 'Ctrl'
+  if (modifier === 'alt') return platform === 'darwin' ? 'Option' : 'Alt'
+  if (modifier === 'shift') return 'Shift'
+  return platform === 'darwin' ? 'Cmd' : platform === 'win32' ? 'Win' : 'Super'
+}
+
+function keyDisplayName(key: string): string | null {
+  if (NAMED_KEYS[key]) return NAMED_KEYS[key]
+  return /^[\x21-\x7e]$/.test(key) ? key : null
+}
+
+// macOS 用 keystroke "x"（经 shell 单引号传给 osascript）发送字符键，引号与反斜杠会破坏脚本；
+// Linux 的 xdotool 标点需写 keysym 名称（如 slash）且命令经过 shell，字符键只接受字母与数字
+function isKeySendable(key: string, platform: NodeJS.Platform): boolean {
+  if (NAMED_KEYS[key]) return true
+  if (platform === 'darwin') return !['"', "'", '\\'].includes(key)
+  if (platform === 'linux') return /^[A-Z0-9]$/.test(key)
+  return true
+}
+
+// 校验整条序列并返回每一步的按键展示名（如 [['Ctrl', 'Shift', 'F2'], ['S']]），不发送任何按键。
+// 比 parseShortcutSequence 更严格：未知键名、当前平台发送不了的字符键、一步内多个主键、Windows 上的 Win 键均报错
+export function describeShortcutSequence(
+  value: string,
+  platform: NodeJS.Platform = process.platform,
+  options: ShortcutParseOptions = {},
+): string[][] {
+  const steps = splitShortcutSequence(value)
+  const parsedSteps = parseShortcutSequence(value, platform, options)
+  return parsedSteps.map((parsed, index) => {
+    const label = steps.length > 1 ? `第 ${index + 1} 步` : '快捷键'
+    const parts = steps[index].toUpperCase().split('+').map(s => s.trim()).filter(Boolean)
+    const keys = parts.filter(part => !MODIFIER_ALIASES[part])
+    if (keys.length > 1) {
+      throw new Error(`${label}包含多个主键: ${keys.join('、')}（完整配置: "${value}"）`)
+    }
+    if (platform === 'win32' && parsed.modifiers.includes('meta')) {
+      throw new Error(`${label}使用了 Windows 上无法模拟的 Win 键（完整配置: "${value}"）`)
+    }
+    const names = MODIFIER_ORDER
+      .filter(modifier => parsed.modifiers.includes(modifier))
+      .map(modifier => modifierDisplayName(modifier, platform))
+    if (parsed.key) {
+      const keyName = keyDisplayName(parsed.key)
+      if (!keyName) throw new Error(`${label}包含未知按键: "${parsed.key}"（完整配置: "${value}"）`)
+      if (!isKeySendable(parsed.key, platform)) {
+        throw new Error(`${label}的按键 "${parsed.key}" 在当前系统上无法模拟（完整配置: "${value}"）`)
+      }
+      names.push(keyName)
+    }
+    return names
+  })
+}
+
+export interface ShortcutValidation {
+  ok: boolean
+  steps: string[][]
+  error?: string
+}
+
+export function validateShortcut(
+  value: string,
+  platform: NodeJS.Platform = process.platform,
+  options: ShortcutParseOptions = {},
+): ShortcutValidation {
+  try {
+    return { ok: true, steps: describeShortcutSequence(value, platform, options) }
+  } catch (e) {
+    return { ok: false, steps: [], error: e instanceof Error ? e.message : String(e) }
+  }
+}
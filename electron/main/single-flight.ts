@@ -0,0 +1,31 @@
+// 识别请求串行化：快速松开又按下热键时，前后两次识别不能同时运行并交替向输入框键入文字。
+// 新请求在前一个完成后才开始（排队而不是丢弃，保证口述顺序与文字顺序一致）；
+// 无论任务成功或抛错都会释放占用，busy 状态变化时回调通知界面
+
+export interface SingleFlightState {
+  busy: boolean
+  // 正在等待前序任务完成的请求数
+  pending: number
+}
+
+export class SingleFlightQueue {
+  private tail: Promise<void> = Promise.resolve()
+  private active = 0
+
+  constructor(private readonly onChange: (state: SingleFlightState) => void = () => { }) { }
+
+  get state(): SingleFlightState {
+    return { busy: this.active > 0, pending: Math.max(0, this.active - 1) }
+  }
+
+  run<T>(task: () => Promise<T>): Promise<T> {
+    this.active++
+    this.onChange(this.state)
+    const result = this.tail.then(() => task())
+    this.tail = result.then(() => undefined, () => undefined).then(() => {
+      this.active--
+      this.onChange(this.state)
+    })
+    return result
+  }
+}
@@ -0,0 +1,25 @@
+import type { AppConfig } from './config'
+
+// 托盘图标鼠标事件的处理：tray.showTrigger 决定哪种点击显示浮窗。
+// right-click 时不挂载系统右键菜单（否则右键只会弹出菜单），改为左键单击弹出菜单
+
+export type TrayShowTrigger = AppConfig['tray']['showTrigger']
+export type TrayMouseEvent = 'click' | 'double-click' | 'right-click'
+export type TrayMouseAction = 'show-window' | 'popup-menu' | 'none'
+
+const TRIGGER_EVENTS: Record<TrayShowTrigger, TrayMouseEvent> = {
+  'left-click': 'click',
+  'double-click': 'double-click',
+  'right-click': 'right-click',
+}
+
+export function trayMouseAction(trigger: TrayShowTrigger, event: TrayMouseEvent): TrayMouseAction {
+  if (TRIGGER_EVENTS[trigger] === event) return 'show-window'
+  if (trigger === 'right-click' && event === 'click') return 'popup-menu'
+  return 'none'
+}
+
+// 是否把菜单挂为系统右键菜单
+export function usesNativeContextMenu(trigger: TrayShowTrigger): boolean {
+  return trigger !== 'right-click'
+}
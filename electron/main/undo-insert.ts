@@ -0,0 +1,29 @@
+// 撤销上一次输入：记录最近一次输入到目标窗口的文字长度，撤销时发送同样数量的退格键。
+// 最近一次动作是指令（快捷键、回车提交）时不可撤销，避免把用户原有的内容删掉；撤销一次后即清空
+
+// 退格键数量：按码点计数（一个退格删除一个字符，含中文与 emoji），CRLF 在编辑框中只占一个换行
+export function undoKeystrokeCount(text: string): number {
+  return Array.from(text.replace(/\r\n/g, '\n')).length
+}
+
+export class LastInsertTracker {
+  private count: number | null = null
+
+  get pending(): number | null { return this.count }
+
+  recordText(text: string): void {
+    const count = undoKeystrokeCount(text)
+    this.count = count > 0 ? count : null
+  }
+
+  recordCommand(): void {
+    this.count = null
+  }
+
+  // 取出待撤销的退格数并清空；没有可撤销的文字时返回 null
+  take(): number | null {
+    const count = this.count
+    this.count = null
+    return count
+  }
+}
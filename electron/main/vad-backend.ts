@@ -0,0 +1,31 @@
+import type { AppConfig } from './config'
+import type { ModelDependencyStatus } from './model-manager'
+
+// 实时 VAD 后端：energy 只用渲染进程的能量检测切句；fsmn 在能量检测切出语音段后，
+// 再用本地 FSMN VAD 模型确认其中确有人声，模型判定无语音的段直接丢弃、不送识别
+
+export type VadBackendName = AppConfig['vad']['backend']
+
+export const VAD_BACKEND_NAMES: VadBackendName[] = ['energy', 'fsmn']
+
+export const VAD_BACKEND_LABELS: Record<VadBackendName, string> = {
+  energy: '能量检测',
+  fsmn: 'FSMN 模型',
+}
+
+export function isVadBackendName(value: unknown): value is VadBackendName {
+  return typeof value === 'string' && (VAD_BACKEND_NAMES as string[]).includes(value)
+}
+
+// 后端不可用的原因，可用时返回 null；vadDependency 为本地模型的 VAD 依赖检查结果
+export function vadBackendUnavailableReason(
+  backend: VadBackendName,
+  vadDependency: ModelDependencyStatus | undefined,
+): string | null {
+  if (backend === 'energy') return null
+  if (!vadDependency) return '当前本地模型没有配套的 VAD 模型'
+  if (!vadDependency.complete) {
+    return `VAD 模型文件缺失（${vadDependency.issue || vadDependency.modelName}），请先在「模型」页下载本地模型`
+  }
+  return null
+}
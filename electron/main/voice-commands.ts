@@ -1,16 +1,446 @@
-// 语音指令匹配：精确匹配（trim + 去除尾部标点后完全相等）
+import { describeShortcutSequence } from './shortcut'
+
+export type VoiceCommandMatch =
+  | { type: 'command'; shortcut: string }
+  | { type: 'text'; text: string; pressEnter?: true; phrase?: string; cursorLeft?: number }
+  | { type: 'exec'; command: string; args: string[]; phrase: string }
+
+// 指令值为以下占位符时输入对应控制字符（作为文字输入，而不是发送快捷键，
+// 避免部分 LIS 输入框拦截 ENTER 快捷键）
+export const TEXT_COMMAND_TOKENS: Readonly<Record<string, string>> = {
+  '{NEWLINE}': '\n',
+  '{TAB}': '\t',
+}
+
+// 返回占位符对应的文字，非占位符返回 null（不区分大小写）
+export function resolveTextCommandToken(value: string): string | null {
+  const key = value.trim().toUpperCase()
+  return Object.prototype.hasOwnProperty.call(TEXT_COMMAND_TOKENS, key) ? TEXT_COMMAND_TOKENS[key] : null
+}
+
+// 文本扩展指令：指令值以 TEXT: 开头（不区分大小写）时，说出口令后原样输入前缀之后的文字，
+// 如 "标准诊断": "TEXT:（胃窦）黏膜慢性炎。"；前缀后为空时不是有效的文本扩展
+export const TEXT_EXPANSION_PREFIX = 'TEXT:'
+
+export function resolveTextExpansion(value: string): string | null {
+  const start = value.search(/\S/)
+  if (start < 0 || value.slice(start, start + TEXT_EXPANSION_PREFIX.length).toUpperCase() !== TEXT_EXPANSION_PREFIX) return null
+  const text = value.slice(start + TEXT_EXPANSION_PREFIX.length)
+  return text.trim() ? text : null
+}
+
+// 文本扩展中的光标占位符：输入后按左方向键把光标移回占位处，便于接着口述填空。
+// 多个占位符时光标停在第一个，全部占位符都不会输入
+export const CURSOR_PLACEHOLDER = '[]'
+
+export interface CursorPlacement {
+  text: string
+  // 输入完成后需要发送的左方向键次数，0 表示不移动
+  cursorLeft: number
+}
+
+export function placeCursorAtPlaceholder(text: string): CursorPlacement {
+  const index = text.indexOf(CURSOR_PLACEHOLDER)
+  if (index < 0) return { text, cursorLeft: 0 }
+  const before = text.slice(0, index)
+  const after = text.slice(index + CURSOR_PLACEHOLDER.length).split(CURSOR_PLACEHOLDER).join('')
+  return { text: before + after, cursorLeft: countCursorSteps(after) }
+}
+
+// 按光标移动步数计：一个 Unicode 字符一步，\r\n 视为一次换行
+function countCursorSteps(text: string): number {
+  return Array.from(text.replace(/\r\n/g, '\n')).length
+}
+
+// 运行外部程序指令：指令值以 RUN: 开头（不区分大小写），如 "计算器": "RUN:calc.exe"、
+// "检验系统": "RUN:\"C:\\Program Files\\LIS\\lis.exe\" --patient"。
+// 命令行按空白拆分参数，双引号包住含空格的路径或参数；反斜杠不转义（Windows 路径原样保留）。
+// 只有 voiceCommandOptions.allowExec 开启时才会执行
+export const EXEC_COMMAND_PREFIX = 'RUN:'
+
+export interface ExecCommand {
+  command: string
+  args: string[]
+}
+
+export function resolveExecCommand(value: string): ExecCommand | null {
+  const start = value.search(/\S/)
+  if (start < 0 || value.slice(start, start + EXEC_COMMAND_PREFIX.length).toUpperCase() !== EXEC_COMMAND_PREFIX) return null
+  const [command, ...args] = splitCommandLine(value.slice(start + EXEC_COMMAND_PREFIX.length))
+  return command ? { command, args } : null
+}
+
+// 未闭合的引号视为一直到行尾；"" 表示空参数
+export function splitCommandLine(line: string): string[] {
+  const tokens: string[] = []
+  let current = ''
+  let inToken = false
+  let quoted = false
+  for (const ch of line) {
+    if (ch === '"') {
+      quoted = !quoted
+      inToken = true
+    } else if (!quoted && /\s/.test(ch)) {
+      if (inToken) tokens.push(current)
+      current = ''
+      inToken = false
+    } else {
+      current += ch
+      inToken = true
+    }
+  }
+  if (inToken) tokens.push(current)
+  return tokens
+}
+
+// 指令值对应的输入文字（换行/制表占位符或文本扩展），快捷键指令返回 null
+export function resolveCommandText(value: string): string | null {
+  return resolveTextCommandToken(value) ?? resolveTextExpansion(value)
+}
+
+export interface VoiceCommandMatchOptions {
+  // 输入文字后追加回车提交的短语（匹配方式与指令口令相同）
+  enterAfterPhrases?: string[]
+  // false 时为纯听写：任何文本都按普通文字输入，不匹配指令与回车短语
+  enabled?: boolean
+}
+
+// 语音指令匹配：精确匹配（trim + 去除首尾标点、NFKC 全半角归一、拉丁字母转小写后完全相等）
 export function matchVoiceCommand(
   text: string,
   commands: Record<string, string>,
-): { type: 'command'; shortcut: string } | { type: 'text'; text: string } {
+  options: VoiceCommandMatchOptions = {},
+): VoiceCommandMatch {
   const trimmed = text.trim()
-  const stripped = stripPunctuation(trimmed)
-  if (commands[stripped]) {
-    return { type: 'command', shortcut: commands[stripped] }
+  if (options.enabled === false) {
+    return { type: 'text', text: trimmed }
+  }
+  const phrase = findVoiceCommandPhrase(trimmed, commands)
+  if (phrase !== null) {
+    return resolveCommandPhrase(phrase, commands)
+  }
+  const stripped = normalizeCommandText(trimmed)
+  if (stripped && options.enterAfterPhrases?.some(p => normalizeCommandText(p) === stripped)) {
+    return { type: 'text', text: trimmed, pressEnter: true }
   }
   return { type: 'text', text: trimmed }
 }
 
+// 文本命中的指令口令（指令表中的原始写法），未命中返回 null
+export function findVoiceCommandPhrase(text: string, commands: Record<string, string>): string | null {
+  const phrase = findCommandKey(normalizeCommandText(text), commands)
+  return phrase !== null && commands[phrase] ? phrase : null
+}
+
+// 按归一化形式查找指令表中的口令：优先原样命中，其次比较口令归一化后的形式
+function findCommandKey(normalized: string, commands: Record<string, string>): string | null {
+  if (!normalized) return null
+  if (Object.prototype.hasOwnProperty.call(commands, normalized)) return normalized
+  return Object.keys(commands).find(phrase => normalizeCommandText(phrase) === normalized) ?? null
+}
+
+// 口令已确定时的执行动作：快捷键，或换行/制表占位符、文本扩展对应的文字
+export function resolveCommandPhrase(phrase: string, commands: Record<string, string>): VoiceCommandMatch {
+  const commandText = resolveCommandText(commands[phrase])
+  if (commandText !== null) {
+    const { text, cursorLeft } = placeCursorAtPlaceholder(commandText)
+    return cursorLeft > 0 ? { type: 'text', text, phrase, cursorLeft } : { type: 'text', text, phrase }
+  }
+  const exec = resolveExecCommand(commands[phrase])
+  if (exec) return { type: 'exec', ...exec, phrase }
+  return { type: 'command', shortcut: commands[phrase] }
+}
+
+// 指令别名：同一动作的多种说法（如“保存”“存一下”）共用主口令的快捷键。
+// 展开为扁平指令表后参与匹配；别名与已有口令同名时以指令表为准，主口令不存在的别名忽略
+export function expandVoiceCommandAliases(
+  commands: Record<string, string>,
+  aliases: Record<string, string[]>,
+): Record<string, string> {
+  const expanded = { ...commands }
+  for (const [canonical, phrases] of Object.entries(aliases)) {
+    if (!Object.prototype.hasOwnProperty.call(commands, canonical)) continue
+    for (const alias of phrases) {
+      const phrase = normalizeCommandText(alias)
+      if (phrase && findCommandKey(phrase, expanded) === null) {
+        expanded[phrase] = commands[canonical]
+      }
+    }
+  }
+  return expanded
+}
+
+// 别名对应的主口令（冷却、单口令冷却覆盖、“重复”按主口令计）；不是别名时原样返回
+export function canonicalCommandPhrase(
+  phrase: string,
+  commands: Record<string, string>,
+  aliases: Record<string, string[]>,
+): string {
+  if (Object.prototype.hasOwnProperty.call(commands, phrase)) return phrase
+  for (const [canonical, phrases] of Object.entries(aliases)) {
+    if (!Object.prototype.hasOwnProperty.call(commands, canonical)) continue
+    if (phrases.some(alias => normalizeCommandText(alias) === normalizeCommandText(phrase))) return canonical
+  }
+  return phrase
+}
+
+export interface VoiceCommandCandidate {
+  phrase: string
+  shortcut: string
+  // 相似度 0-1，1 为完全一致
+  score: number
+}
+
+export interface VoiceCommandCandidateOptions {
+  // 低于该相似度的口令不列为候选
+  threshold?: number
+  limit?: number
+}
+
+// 列出与文本相近的候选指令（按相似度降序）。
+// 互相包含的按长度比计分，其余按编辑距离计分；仅供消歧提示，不改变 matchVoiceCommand 的精确匹配
+export function matchVoiceCommandCandidates(
+  text: string,
+  commands: Record<string, string>,
+  options: VoiceCommandCandidateOptions = {},
+): VoiceCommandCandidate[] {
+  const threshold = options.threshold ?? 0.6
+  const limit = options.limit ?? 5
+  const normalized = normalizeCommandText(text)
+  if (!normalized) return []
+  const candidates: VoiceCommandCandidate[] = []
+  for (const [phrase, shortcut] of Object.entries(commands)) {
+    const score = similarity(normalized, normalizeCommandText(phrase))
+    if (score >= threshold) {
+      candidates.push({ phrase, shortcut, score })
+    }
+  }
+  candidates.sort((a, b) => b.score - a.score || a.phrase.localeCompare(b.phrase, 'zh'))
+  return candidates.slice(0, limit)
+}
+
+// 前两名相似度差距小于 margin 时视为有歧义，不应自动选择
+export function isAmbiguousCandidates(candidates: VoiceCommandCandidate[], margin = 0.1): boolean {
+  return candidates.length >= 2 && candidates[0].score - candidates[1].score < margin
+}
+
+function similarity(text: string, phrase: string): number {
+  const textLength = Array.from(text).length
+  const phraseLength = Array.from(phrase).length
+  const maxLength = Math.max(textLength, phraseLength)
+  if (maxLength === 0) return 0
+  if (text.includes(phrase) || phrase.includes(text)) {
+    return round3(Math.min(textLength, phraseLength) / maxLength)
+  }
+  return round3(1 - editDistance(text, phrase) / maxLength)
+}
+
+function round3(value: number): number {
+  return Math.round(value * 1000) / 1000
+}
+
+export interface VoiceCommandPreview {
+  type: 'command' | 'text' | 'exec'
+  // 参与匹配的文本（trim + 去首尾标点 + 全半角归一、小写）
+  normalized: string
+  // 命中的指令口令（含换行/制表占位符指令），未命中为 null
+  phrase: string | null
+  // 快捷键指令为快捷键，运行程序指令为指令值原文
+  shortcut: string | null
+  // 未命中时将输入的文本
+  text: string
+  // 输入文字后是否追加回车
+  pressEnter: boolean
+  // 未精确命中但存在相近口令时列出候选
+  candidates?: VoiceCommandCandidate[]
+}
+
+// 指令匹配预览：只描述匹配结果，不触发任何键盘动作（供设置页试听使用）
+export function previewVoiceCommand(
+  text: string,
+  commands: Record<string, string>,
+  options: VoiceCommandMatchOptions & { candidateThreshold?: number } = {},
+): VoiceCommandPreview {
+  const normalized = normalizeCommandText(text)
+  const result = matchVoiceCommand(text, commands, options)
+  if (result.type === 'command') {
+    const phrase = findVoiceCommandPhrase(text, commands) ?? normalized
+    return { type: 'command', normalized, phrase, shortcut: result.shortcut, text: '', pressEnter: false }
+  }
+  if (result.type === 'exec') {
+    return { type: 'exec', normalized, phrase: result.phrase, shortcut: commands[result.phrase], text: '', pressEnter: false }
+  }
+  const preview: VoiceCommandPreview = {
+    type: 'text',
+    normalized,
+    phrase: result.phrase ?? null,
+    shortcut: null,
+    text: result.text,
+    pressEnter: Boolean(result.pressEnter),
+  }
+  if (!result.phrase && options.enabled !== false) {
+    const candidates = matchVoiceCommandCandidates(text, commands, { threshold: options.candidateThreshold })
+    if (candidates.length > 0) preview.candidates = candidates
+  }
+  return preview
+}
+
+export interface VoiceCommandEntry {
+  phrase: string
+  shortcut: string
+  // shortcut 为按键序列，text 为换行/制表占位符或文本扩展（作为文字输入），exec 为运行外部程序
+  kind: 'shortcut' | 'text' | 'exec'
+  valid: boolean
+  error?: string
+}
+
+// 列出全部指令及快捷键解析结果（供设置页标出无法执行的条目），按口令排序
+export function listVoiceCommands(
+  commands: Record<string, string>,
+  platform: NodeJS.Platform = process.platform,
+): VoiceCommandEntry[] {
+  return Object.entries(commands)
+    .sort((a, b) => a[0].localeCompare(b[0], 'zh'))
+    .map(([phrase, shortcut]) => {
+      const value = typeof shortcut === 'string' ? shortcut : ''
+      if (resolveCommandText(value) !== null) {
+        return { phrase, shortcut: value, kind: 'text', valid: true }
+      }
+      if (resolveExecCommand(value) !== null) {
+        return { phrase, shortcut: value, kind: 'exec', valid: true }
+      }
+      try {
+        describeShortcutSequence(value, platform)
+        return { phrase, shortcut: value, kind: 'shortcut', valid: true }
+      } catch (e) {
+        return { phrase, shortcut: value, kind: 'shortcut', valid: false, error: e instanceof Error ? e.message : String(e) }
+      }
+    })
+}
+
+// 指令冷却：同一口令在冷却时间内不重复执行（防止回声等导致连续触发两次）
+export class VoiceCommandCooldown {
+  private lastFiredAt = new Map<string, number>()
+
+  constructor(private readonly now: () => number = Date.now) { }
+
+  // 返回剩余冷却毫秒数，0 表示可以执行
+  remaining(phrase: string, cooldownMs: number): number {
+    if (cooldownMs <= 0) return 0
+    const last = this.lastFiredAt.get(phrase)
+    if (last === undefined) return 0
+    return Math.max(0, last + cooldownMs - this.now())
+  }
+
+  markFired(phrase: string): void {
+    this.lastFiredAt.set(phrase, this.now())
+  }
+}
+
+// 指令后锁定期：执行任意指令（如“保存报告”）后的一段时间内不再执行指令，
+// 避免尾音或紧跟的词在刚切换的病例上被误当作另一条指令
+export class PostCommandLockout {
+  private lastExecutedAt: number | null = null
+
+  constructor(private readonly now: () => number = Date.now) { }
+
+  markExecuted(): void {
+    this.lastExecutedAt = this.now()
+  }
+
+  // 返回剩余锁定毫秒数，0 表示未锁定
+  remaining(lockoutMs: number): number {
+    if (lockoutMs <= 0 || this.lastExecutedAt === null) return 0
+    return Math.max(0, this.lastExecutedAt + lockoutMs - this.now())
+  }
+}
+
+// 锁定期内的匹配结果：drop 时丢弃任何输入（返回 null）；text 时指令按说出的原文作为普通文字输入
+export function applyPostCommandLockout(
+  match: VoiceCommandMatch,
+  spokenText: string,
+  remainingMs: number,
+  action: 'text' | 'drop',
+): VoiceCommandMatch | null {
+  if (remainingMs <= 0) return match
+  if (action === 'drop') return null
+  if (match.type === 'command' || match.phrase) return { type: 'text', text: spokenText.trim() }
+  return match
+}
+
+// 记录最近一次成功执行的指令，供“重复”口令/快捷键再次执行
+export interface ExecutedCommand {
+  phrase: string
+  shortcut: string
+}
+
+export class LastCommandTracker {
+  private lastCommand: ExecutedCommand | null = null
+
+  get last(): ExecutedCommand | null { return this.lastCommand }
+
+  record(phrase: string, shortcut: string): void {
+    this.lastCommand = { phrase, shortcut }
+  }
+
+  clear(): void {
+    this.lastCommand = null
+  }
+}
+
+// 是否为内置“重复”口令；用户自定义了同名指令时以用户指令为准
+export function isRepeatCommand(text: string, repeatPhrase: string, commands: Record<string, string>): boolean {
+  const phrase = normalizeCommandText(repeatPhrase)
+  if (!phrase) return false
+  const normalized = normalizeCommandText(text)
+  return normalized === phrase && findCommandKey(normalized, commands) === null
+}
+
+export function resolveCommandCooldownMs(
+  phrase: string,
+  options: { cooldownMs: number; cooldownOverrides: Record<string, number> },
+): number {
+  const override = options.cooldownOverrides[phrase]
+  return typeof override === 'number' ? override : options.cooldownMs
+}
+
+// 参与指令匹配的文本形式：NFKC 把全角字母数字、全角标点归一为半角（“ＡＬＴ”→“ALT”、“Ｆ２”→“F2”），
+// 拉丁字母统一小写，再去除首尾标点
+export function normalizeCommandText(text: string): string {
+  return stripPunctuation(text.normalize('NFKC').toLowerCase().trim())
+}
+
+// 找出与文本编辑距离最近的指令口令（仅用于诊断日志，不参与匹配）
+export function findClosestVoiceCommand(
+  text: string,
+  commands: Record<string, string>,
+): { phrase: string; distance: number } | null {
+  const normalized = normalizeCommandText(text)
+  let best: { phrase: string; distance: number } | null = null
+  for (const phrase of Object.keys(commands)) {
+    const distance = editDistance(normalized, normalizeCommandText(phrase))
+    if (!best || distance < best.distance) {
+      best = { phrase, distance }
+    }
+  }
+  return best
+}
+
+function editDistance(a: string, b: string): number {
+  const s = Array.from(a)
+  const t = Array.from(b)
+  let prev = Array.from({ length: t.length + 1 }, (_, i) => i)
+  for (let i = 1; i <= s.length; i++) {
+    const curr = [i]
+    for (let j = 1; j <= t.length; j++) {
+      const cost = s[i - 1] === t[j - 1] ? 0 : 1
+      curr[j] = Math.min(prev[j] + 1, curr[j - 1] + 1, prev[j - 1] + cost)
+    }
+    prev = curr
+  }
+  return prev[t.length]
+}
+
 // 去除首尾中英文标点
 function stripPunctuation(s: string): string {
   return s.replace(/^[\s。，！？、；：.,!?;:"'「」""'']+|[\s。，！？、；：.,!?;:"'「」""'']+$/gu, '')
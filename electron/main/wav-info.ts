@@ -0,0 +1,127 @@
+// 解析渲染进程编码的 16-bit PCM WAV（固定 44 字节头）
+
+export const WAV_HEADER_BYTES = 44
+
+export interface WavInfo {
+  channels: number
+  sampleRate: number
+  // 每个声道的采样帧数
+  frameCount: number
+  durationMs: number
+}
+
+export function readWavInfo(buf: Buffer): WavInfo {
+  if (buf.byteLength < WAV_HEADER_BYTES) {
+    return { channels: 1, sampleRate: 16000, frameCount: 0, durationMs: 0 }
+  }
+  const channels = Math.max(1, buf.readUInt16LE(22))
+  const sampleRate = buf.readUInt32LE(24) || 16000
+  const frameCount = Math.floor((buf.byteLength - WAV_HEADER_BYTES) / 2 / channels)
+  return {
+    channels,
+    sampleRate,
+    frameCount,
+    durationMs: Math.round((frameCount / sampleRate) * 1000),
+  }
+}
+
+// 多声道 WAV 平均混音为单声道（本地识别 sidecar 只接受单声道）
+export function downmixWavToMono(buf: Buffer): Buffer {
+  const info = readWavInfo(buf)
+  if (info.channels === 1) return buf
+  const out = Buffer.alloc(WAV_HEADER_BYTES + info.frameCount * 2)
+  buf.copy(out, 0, 0, WAV_HEADER_BYTES)
+  out.writeUInt32LE(36 + info.frameCount * 2, 4)
+  out.writeUInt16LE(1, 22)
+  out.writeUInt32LE(info.sampleRate * 2, 28)
+  out.writeUInt16LE(2, 32)
+  out.writeUInt32LE(info.frameCount * 2, 40)
+  for (let i = 0; i < info.frameCount; i++) {
+    let sum = 0
+    for (let c = 0; c < info.channels; c++) {
+      sum += buf.readInt16LE(WAV_HEADER_BYTES + (i * info.channels + c) * 2)
+    }
+    out.writeInt16LE(Math.round(sum / info.channels), WAV_HEADER_BYTES + i * 2)
+  }
+  return out
+}
+
+// 按顺序拼接同一次录音的多段 WAV（连续分段录音的中间段与剩余部分），沿用第一段的格式并回填长度
+export function concatWavs(parts: Buffer[]): Buffer {
+  const valid = parts.filter(part => part.byteLength >= WAV_HEADER_BYTES)
+  if (valid.length === 0) return Buffer.alloc(0)
+  const pcm = Buffer.concat(valid.map(part => part.subarray(WAV_HEADER_BYTES)))
+  const out = Buffer.concat([valid[0].subarray(0, WAV_HEADER_BYTES), pcm])
+  out.writeUInt32LE(36 + pcm.byteLength, 4)
+  out.writeUInt32LE(pcm.byteLength, 40)
+  return out
+}
+
+// 混音为单声道并转为 [-1, 1) 浮点采样（离线指令模式的模板匹配使用）
+export function wavToMonoFloat32(buf: Buffer): Float32Array {
+  const mono = downmixWavToMono(buf)
+  const { frameCount } = readWavInfo(mono)
+  const out = new Float32Array(frameCount)
+  for (let i = 0; i < frameCount; i++) {
+    out[i] = mono.readInt16LE(WAV_HEADER_BYTES + i * 2) / 32768
+  }
+  return out
+}
+
+// 读取磁盘上的 WAV 文件（可能带 LIST 等额外块），校验为 16-bit PCM 后转为固定 44 字节头的格式，
+// 以便走与录音相同的识别流程；格式不支持时抛出说明原因的错误
+export function normalizeWavFile(buf: Buffer): Buffer {
+  if (buf.byteLength < 12 || buf.toString('ascii', 0, 4) !== 'RIFF' || buf.toString('ascii', 8, 12) !== 'WAVE') {
+    throw new Error('不是有效的 WAV 文件（缺少 RIFF/WAVE 文件头）')
+  }
+  let fmt: { format: number; channels: number; sampleRate: number; bitsPerSample: number } | null = null
+  let data: Buffer | null = null
+  let offset = 12
+  while (offset + 8 <= buf.byteLength && !data) {
+    const id = buf.toString('ascii', offset, offset + 4)
+    const size = buf.readUInt32LE(offset + 4)
+    const body = offset + 8
+    if (id === 'fmt ') {
+      if (size < 16 || body + 16 > buf.byteLength) throw new Error('WAV 文件的 fmt 块不完整')
+      let format = buf.readUInt16LE(body)
+      // WAVE_FORMAT_EXTENSIBLE：实际编码在子格式 GUID 的前两个字节
+      if (format === 0xfffe && size >= 26 && body + 26 <= buf.byteLength) format = buf.readUInt16LE(body + 24)
+      fmt = {
+        format,
+        channels: buf.readUInt16LE(body + 2),
+        sampleRate: buf.readUInt32LE(body + 4),
+        bitsPerSample: buf.readUInt16LE(body + 14),
+      }
+    } else if (id === 'data') {
+      // 流式写入的文件 data 大小可能未回填，按实际文件长度截取
+      data = buf.subarray(body, Math.min(buf.byteLength, body + size))
+    }
+    offset = body + size + (size % 2)
+  }
+  if (!fmt) throw new Error('WAV 文件缺少 fmt 块')
+  if (!data) throw new Error('WAV 文件缺少 data 块')
+  if (fmt.format !== 1 || fmt.bitsPerSample !== 16) {
+    const actual = fmt.format === 1 ? `${fmt.bitsPerSample}-bit PCM` : `编码格式 0x${fmt.format.toString(16)}`
+    throw new Error(`仅支持 16-bit PCM WAV，当前为 ${actual}`)
+  }
+  if (fmt.channels < 1 || fmt.channels > 8) throw new Error(`不支持的声道数: ${fmt.channels}`)
+  if (fmt.sampleRate <= 0) throw new Error('WAV 文件采样率无效')
+
+  const pcmBytes = data.length - (data.length % (fmt.channels * 2))
+  const out = Buffer.alloc(WAV_HEADER_BYTES + pcmBytes)
+  out.write('RIFF', 0, 'ascii')
+  out.writeUInt32LE(36 + pcmBytes, 4)
+  out.write('WAVE', 8, 'ascii')
+  out.write('fmt ', 12, 'ascii')
+  out.writeUInt32LE(16, 16)
+  out.writeUInt16LE(1, 20)
+  out.writeUInt16LE(fmt.channels, 22)
+  out.writeUInt32LE(fmt.sampleRate, 24)
+  out.writeUInt32LE(fmt.sampleRate * fmt.channels * 2, 28)
+  out.writeUInt16LE(fmt.channels * 2, 32)
+  out.writeUInt16LE(16, 34)
+  out.write('data', 36, 'ascii')
+  out.writeUInt32LE(pcmBytes, 40)
+  data.copy(out, WAV_HEADER_BYTES, 0, pcmBytes)
+  return out
+}
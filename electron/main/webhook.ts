@@ -0,0 +1,51 @@
+import { logger } from './logger'
+
+// 识别结果推送：配置 webhook.url 后，每次识别成功（文字输入或执行指令）把结果 POST 到该地址，
+// 供审计、统计等外部系统使用。只发不等，失败仅记日志，不影响输入
+
+export interface WebhookConfig {
+  url: string
+  timeoutMs: number
+}
+
+export interface WebhookPayload {
+  text: string
+  timestamp: string
+  // 命中的指令口令（快捷键指令、换行或文本扩展），普通听写不带该字段
+  matched_command?: string
+}
+
+export function buildWebhookPayload(
+  text: string,
+  matchedCommand?: string | null,
+  now: () => number = Date.now,
+): WebhookPayload {
+  const payload: WebhookPayload = { text, timestamp: new Date(now()).toISOString() }
+  if (matchedCommand) payload.matched_command = matchedCommand
+  return payload
+}
+
+// 未配置地址时返回 false（未发送）；请求失败或非 2xx 时抛错
+export async function postWebhook(
+  config: WebhookConfig,
+  payload: WebhookPayload,
+  fetchImpl: typeof fetch = fetch,
+): Promise<boolean> {
+  const url = config.url.trim()
+  if (!url) return false
+  const resp = await fetchImpl(url, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify(payload),
+    signal: AbortSignal.timeout(config.timeoutMs),
+  })
+  if (!resp.ok) throw new Error(`HTTP ${resp.status}`)
+  return true
+}
+
+export function sendWebhook(config: WebhookConfig, payload: WebhookPayload, tag: string): void {
+  if (!config.url.trim()) return
+  postWebhook(config, payload)
+    .then(() => logger.debug(`${tag} webhook 已推送`))
+    .catch((e) => logger.warn(`${tag} webhook 推送失败: ${e instanceof Error ? e.message : String(e)}`))
+}
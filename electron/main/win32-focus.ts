@@ -16,6 +16,9 @@ let _GetCurrentThreadId: KoffiFunc | null = null
 let _AttachThreadInput: KoffiFunc | null = null
 let _GetGUIThreadInfo: KoffiFunc | null = null
 let _GetFocus: KoffiFunc | null = null
+let _EnumWindows: KoffiFunc | null = null
+let _GetWindowTextW: KoffiFunc | null = null
+let _IsWindowVisible: KoffiFunc | null = null
 let _inputSize = 0
 let _guiThreadInfoSize = 0
 
@@ -51,6 +54,8 @@ const GUITHREADINFO = koffi.struct('GUITHREADINFO', {
   rcCaret: RECT,
 })
 const GUI_CARETBLINKING = 0x00000001
+// EnumWindows 回调原型（按名称在 loadUser32 中引用），返回 false 停止枚举
+koffi.proto('bool __stdcall EnumWindowsProc(intptr_t hwnd, intptr_t lParam)')
 
 function loadUser32(): void {
   if (_GetForegroundWindow) return
@@ -63,14 +68,29 @@ function loadUser32(): void {
   _AttachThreadInput = user32.func('bool __stdcall AttachThreadInput(uint32 idAttach, uint32 idAttachTo, bool fAttach)')
   _GetGUIThreadInfo = user32.func('bool __stdcall GetGUIThreadInfo(uint32 idThread, GUITHREADINFO *lpgui)')
   _GetFocus = user32.func('intptr_t __stdcall GetFocus()')
+  _EnumWindows = user32.func('bool __stdcall EnumWindows(EnumWindowsProc *lpEnumFunc, intptr_t lParam)')
+  _GetWindowTextW = user32.func('int __stdcall GetWindowTextW(intptr_t hWnd, _Out_ uint8_t *lpString, int nMaxCount)')
+  _IsWindowVisible = user32.func('bool __stdcall IsWindowVisible(intptr_t hWnd)')
   _GetCurrentThreadId = kernel32.func('uint32 __stdcall GetCurrentThreadId()')
   _inputSize = koffi.sizeof(INPUT_KB)
   _guiThreadInfoSize = koffi.sizeof(GUITHREADINFO)
 }
 
-// 仅在 Windows 上预加载，macOS/Linux 不执行
+let _loadError: string | null = null
+
+// 仅在 Windows 上预加载，macOS/Linux 不执行；加载失败（如受限环境禁止加载 user32）时记录原因，
+// 由输入权限检查统一提示，避免每次按键都抛出难以理解的错误
 if (process.platform === 'win32') {
-  loadUser32()
+  try {
+    loadUser32()
+  } catch (e) {
+    _loadError = e instanceof Error ? e.message : String(e)
+  }
+}
+
+/** user32 加载失败的原因，加载成功或非 Windows 平台返回 null */
+export function getWin32InputInitError(): string | null {
+  return _loadError
 }
 
 /** 获取当前前台窗口句柄，返回十进制字符串 */
@@ -105,6 +125,23 @@ export function getWin32WindowProcessId(hwnd: string): number | null {
   }
 }
 
+/** 枚举可见的顶层窗口及其标题（跳过无标题窗口），hwnd 为十进制字符串 */
+export function listWin32Windows(): { hwnd: string; title: string }[] {
+  const windows: { hwnd: string; title: string }[] = []
+  try {
+    const buf = Buffer.alloc(512 * 2)
+    _EnumWindows!((hwnd: bigint | number) => {
+      if (!_IsWindowVisible!(hwnd)) return true
+      const len = Number(_GetWindowTextW!(hwnd, buf, 512))
+      if (len > 0) windows.push({ hwnd: String(hwnd), title: buf.toString('utf16le', 0, len * 2) })
+      return true
+    }, 0)
+  } catch {
+    // 枚举失败时按无匹配窗口处理
+  }
+  return windows
+}
+
 export interface Win32TextInputProbeResult {
   hasForegroundWindow: boolean
   hasFocusedControl: boolean
@@ -271,12 +308,24 @@ const VK_CONTROL = 0x11
 const VK_SHIFT = 0x10
 const VK_MENU = 0x12  // Alt
 const VK_V = 0x56
+const VK_RETURN = 0x0D
+const VK_TAB = 0x09
+const KEYEVENTF_UNICODE = 0x0004
 
 /** 构造一个键盘 INPUT 结构体 */
 function makeKeyInput(vk: number, flags: number) {
   return { type: INPUT_KEYBOARD, ki: { wVk: vk, wScan: 0, dwFlags: flags, time: 0, dwExtraInfo: 0 }, _pad: new Array(8).fill(0) }
 }
 
+/** 构造一个 Unicode 字符 INPUT 结构体（wScan 为 UTF-16 码元） */
+function makeUnicodeInput(codeUnit: number, flags: number) {
+  return {
+    type: INPUT_KEYBOARD,
+    ki: { wVk: 0, wScan: codeUnit, dwFlags: KEYEVENTF_UNICODE | flags, time: 0, dwExtraInfo: 0 },
+    _pad: new Array(8).fill(0),
+  }
+}
+
 /**
  * 将当前线程 attach 到前台窗口的输入线程，执行回调后 detach。
  * 解决 SetForegroundWindow 后焦点尚未就绪导致 SendInput 被拒绝的竞态问题。
@@ -319,13 +368,33 @@ const VK_MAP: Record<string, number> = {
   SPACE: 0x20, ENTER: 0x0D, RETURN: 0x0D, TAB: 0x09,
   ESCAPE: 0x1B, ESC: 0x1B, BACKSPACE: 0x08, DELETE: 0x2E, DEL: 0x2E,
   UP: 0x26, DOWN: 0x28, LEFT: 0x25, RIGHT: 0x27,
+  HOME: 0x24, END: 0x23, PAGEUP: 0x21, PAGEDOWN: 0x22,
   F1: 0x70, F2: 0x71, F3: 0x72, F4: 0x73, F5: 0x74, F6: 0x75,
   F7: 0x76, F8: 0x77, F9: 0x78, F10: 0x79, F11: 0x7A, F12: 0x7B,
+  NUMPAD0: 0x60, NUMPAD1: 0x61, NUMPAD2: 0x62, NUMPAD3: 0x63, NUMPAD4: 0x64,
+  NUMPAD5: 0x65, NUMPAD6: 0x66, NUMPAD7: 0x67, NUMPAD8: 0x68, NUMPAD9: 0x69,
+}
+
+function sendKeyInputs(inputs: ReturnType<typeof makeKeyInput>[], label: string): void {
+  if (inputs.length === 0) return
+  const sent = withAttachedInput(() => Number(_SendInput!(inputs.length, inputs, _inputSize)))
+  if (sent !== inputs.length) {
+    throw new Error(`SendInput ${label}失败: sent=${sent}, expected=${inputs.length}`)
+  }
 }
 
-/** 模拟快捷键，如 "ALT+R"、"CTRL+SHIFT+F2" */
-export function win32SendShortcut(shortcut: string): void {
-  const parts = shortcut.toUpperCase().split('+').map(s => s.trim())
+/**
+ * 模拟快捷键，如 "ALT+R"、"CTRL+SHIFT+F2"
+ * holdMs：最后一个键按下后、开始抬起前的保持时间；keyDelayMs：逐个按下时的间隔。
+ * 两者均为 0 时与旧行为一致（一次 SendInput 发送全部事件）。
+ * 任一步 SendInput 失败时，已尝试按下的键按逆序补发抬起后再抛出原错误；
+ * releaseModifiersOnFailure 为 true 时再补抬全部修饰键。
+ */
+export async function win32SendShortcut(
+  shortcut: string,
+  timing: { holdMs?: number; keyDelayMs?: number; releaseModifiersOnFailure?: boolean } = {},
+): Promise<void> {
+  const parts = shortcut.normalize('NFKC').toUpperCase().split('+').map(s => s.trim())
   const modifiers: number[] = []
   const keys: number[] = []
 
@@ -339,17 +408,93 @@ export function win32SendShortcut(shortcut: string): void {
     }
   }
 
-  const inputs = [
-    ...modifiers.map(vk => makeKeyInput(vk, 0)),
-    ...keys.map(vk => makeKeyInput(vk, 0)),
-    ...[...keys].reverse().map(vk => makeKeyInput(vk, KEYEVENTF_KEYUP)),
-    ...[...modifiers].reverse().map(vk => makeKeyInput(vk, KEYEVENTF_KEYUP)),
-  ]
-  if (inputs.length > 0) {
-    const sent = withAttachedInput(() => Number(_SendInput!(inputs.length, inputs, _inputSize)))
-    if (sent !== inputs.length) {
-      throw new Error(`SendInput 快捷键失败: sent=${sent}, expected=${inputs.length}`)
+  const pressOrder = [...modifiers, ...keys]
+  const presses = pressOrder.map(vk => makeKeyInput(vk, 0))
+  const releases = [...[...keys].reverse(), ...[...modifiers].reverse()].map(vk => makeKeyInput(vk, KEYEVENTF_KEYUP))
+  const holdMs = Math.max(0, timing.holdMs ?? 0)
+  const keyDelayMs = Math.max(0, timing.keyDelayMs ?? 0)
+  // 已发出按下事件的键数（SendInput 失败时可能只送达了一部分，按已尝试的全部抬起）
+  let attempted = 0
+
+  try {
+    if (holdMs === 0 && keyDelayMs === 0) {
+      attempted = presses.length
+      sendKeyInputs([...presses, ...releases], '快捷键')
+      return
     }
+
+    if (keyDelayMs > 0) {
+      for (let i = 0; i < presses.length; i++) {
+        if (i > 0) await delay(keyDelayMs)
+        attempted = i + 1
+        sendKeyInputs([presses[i]], '快捷键')
+      }
+    } else {
+      attempted = presses.length
+      sendKeyInputs(presses, '快捷键')
+    }
+    if (holdMs > 0) await delay(holdMs)
+    sendKeyInputs(releases, '快捷键')
+  } catch (e) {
+    releaseAfterFailure(pressOrder.slice(0, attempted), Boolean(timing.releaseModifiersOnFailure))
+    throw e
+  }
+}
+
+const VK_LWIN = 0x5B
+const VK_RWIN = 0x5C
+
+// 补发抬起只尽力而为，自身失败不掩盖原始错误
+function releaseAfterFailure(pressed: number[], releaseAllModifiers: boolean): void {
+  const vks = [...pressed].reverse()
+  if (releaseAllModifiers) {
+    for (const vk of [VK_CONTROL, VK_MENU, VK_SHIFT, VK_LWIN, VK_RWIN]) {
+      if (!vks.includes(vk)) vks.push(vk)
+    }
+  }
+  if (vks.length === 0) return
+  try {
+    sendKeyInputs(vks.map(vk => makeKeyInput(vk, KEYEVENTF_KEYUP)), '补发抬起')
+  } catch {
+    // 忽略：调用方会抛出原始错误
   }
 }
 
+function delay(ms: number): Promise<void> {
+  return new Promise(resolve => setTimeout(resolve, ms))
+}
+
+/** 连续按 count 次退格（一次 SendInput 发送，撤销上一次输入用） */
+export function win32PressBackspace(count: number): void {
+  pressKeyRepeatedly(VK_MAP.BACKSPACE, count, '退格')
+}
+
+/** 连续按 count 次左方向键（一次 SendInput 发送，展开短语后把光标移回占位符用） */
+export function win32PressLeftArrow(count: number): void {
+  pressKeyRepeatedly(VK_MAP.LEFT, count, '左移光标')
+}
+
+function pressKeyRepeatedly(vk: number, count: number, label: string): void {
+  const inputs: ReturnType<typeof makeKeyInput>[] = []
+  for (let i = 0; i < count; i++) {
+    inputs.push(makeKeyInput(vk, 0), makeKeyInput(vk, KEYEVENTF_KEYUP))
+  }
+  sendKeyInputs(inputs, label)
+}
+
+/** 逐字符键入文本（KEYEVENTF_UNICODE，不经过剪贴板），换行/制表符按 Enter/Tab 键发送 */
+export function win32TypeText(text: string): void {
+  const inputs: ReturnType<typeof makeKeyInput>[] = []
+  for (let i = 0; i < text.length; i++) {
+    const ch = text[i]
+    if (ch === '\r') continue
+    if (ch === '\n' || ch === '\t') {
+      const vk = ch === '\n' ? VK_RETURN : VK_TAB
+      inputs.push(makeKeyInput(vk, 0), makeKeyInput(vk, KEYEVENTF_KEYUP))
+      continue
+    }
+    const code = text.charCodeAt(i)
+    inputs.push(makeUnicodeInput(code, 0), makeUnicodeInput(code, KEYEVENTF_KEYUP))
+  }
+  sendKeyInputs(inputs, '文本键入')
+}
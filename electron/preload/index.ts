@@ -6,15 +6,70 @@ contextBridge.exposeInMainWorld('electronAPI', {
   getConfig: () => ipcRenderer.invoke('get-config'),
   getAppVersion: () => ipcRenderer.invoke('get-app-version'),
   saveConfig: (config: unknown) => ipcRenderer.invoke('save-config', config),
+  exportConfig: () => ipcRenderer.invoke('export-config'),
+  importConfig: (text: string) => ipcRenderer.invoke('import-config', text),
   getFrontmostApp: () => ipcRenderer.invoke('get-frontmost-app'),
   captureFocusSnapshot: (reason?: string) => ipcRenderer.invoke('capture-focus-snapshot', reason),
   restoreFocus: (appId: string | null) => ipcRenderer.invoke('restore-focus', appId),
   getVadEnabled: () => ipcRenderer.invoke('get-vad-enabled'),
+  getVadBackend: () => ipcRenderer.invoke('get-vad-backend'),
+  setVadBackend: (name: string) => ipcRenderer.invoke('set-vad-backend', name),
   setVadEnabled: (enabled: boolean) => ipcRenderer.invoke('set-vad-enabled', enabled),
+  reportVadIdleTimeout: (idleMs: number) => ipcRenderer.invoke('report-vad-idle-timeout', idleMs),
+  reportRecordingStarted: () => ipcRenderer.invoke('report-recording-started'),
   setVadThreshold: (threshold: number) => ipcRenderer.invoke('set-vad-threshold', threshold),
+  setVadEnergySmoothing: (smoothing: number) => ipcRenderer.invoke('set-vad-energy-smoothing', smoothing),
+  setInputGain: (factor: number) => ipcRenderer.invoke('set-input-gain', factor),
+  quickRecord: (durationMs?: number) => ipcRenderer.invoke('quick-record', durationMs),
+  reportQuickRecord: (result: unknown) => ipcRenderer.invoke('report-quick-record', result),
+  discardRecording: () => ipcRenderer.invoke('discard-recording'),
+  recognizeLastRecording: () => ipcRenderer.invoke('recognize-last-recording'),
+  clearLastRecording: () => ipcRenderer.invoke('clear-last-recording'),
+  startSession: () => ipcRenderer.invoke('start-session'),
+  endSession: () => ipcRenderer.invoke('end-session'),
+  getSessionTranscript: () => ipcRenderer.invoke('get-session-transcript'),
+  exportSessionTranscript: () => ipcRenderer.invoke('export-session-transcript'),
+  getInputMode: () => ipcRenderer.invoke('get-input-mode'),
+  setUseClipboard: (enabled: boolean) => ipcRenderer.invoke('set-use-clipboard', enabled),
+  getCommandMatching: () => ipcRenderer.invoke('get-command-matching'),
+  setCommandMatching: (enabled: boolean) => ipcRenderer.invoke('set-command-matching', enabled),
+  reportInputLevel: (level: { rms: number; peak: number; gain: number; clipped: boolean }) =>
+    ipcRenderer.invoke('report-input-level', level),
+  reportAudioInfo: (info: unknown) => ipcRenderer.invoke('report-audio-info', info),
+  reportAudioLifecycle: (event: unknown) => ipcRenderer.invoke('report-audio-lifecycle', event),
+  reportSampleRateWarning: (warning: unknown) => ipcRenderer.invoke('report-sample-rate-warning', warning),
+  reportReattemptSuggested: (info: unknown) => ipcRenderer.invoke('report-reattempt-suggested', info),
+  getAudioInfo: () => ipcRenderer.invoke('get-audio-info'),
+  getInputDevice: () => ipcRenderer.invoke('get-input-device'),
+  setInputDevice: (name: string) => ipcRenderer.invoke('set-input-device', name),
+  reportInputDeviceSwitch: (result: unknown) => ipcRenderer.invoke('report-input-device-switch', result),
   getAsrRuntimeStatus: () => ipcRenderer.invoke('get-asr-runtime-status'),
-  recognizeWav: (wavBuffer: ArrayBuffer, prevAppId: string | null) =>
-    ipcRenderer.invoke('recognize-wav', wavBuffer, prevAppId),
+  getLastError: () => ipcRenderer.invoke('get-last-error'),
+  clearLastError: () => ipcRenderer.invoke('clear-last-error'),
+  getServerStatus: () => ipcRenderer.invoke('get-server-status'),
+  checkServerStatus: () => ipcRenderer.invoke('check-server-status'),
+  pingServer: () => ipcRenderer.invoke('ping-server'),
+  cancelPingServer: () => ipcRenderer.invoke('cancel-ping-server'),
+  getServerLatencyHistory: () => ipcRenderer.invoke('get-server-latency-history'),
+  recheckInputPermissions: () => ipcRenderer.invoke('recheck-input-permissions'),
+  recognizeWav: (wavBuffer: ArrayBuffer, prevAppId: string | null, triggeredAt?: number, source?: 'vad' | 'manual' | 'segment') =>
+    ipcRenderer.invoke('recognize-wav', wavBuffer, prevAppId, triggeredAt, source),
+  previewVoiceCommand: (text: string) => ipcRenderer.invoke('preview-voice-command', text),
+  previewPostprocess: (text: string) => ipcRenderer.invoke('preview-postprocess', text),
+  validateShortcut: (shortcut: string) => ipcRenderer.invoke('validate-shortcut', shortcut),
+  listVoiceCommands: () => ipcRenderer.invoke('list-voice-commands'),
+  listProfiles: () => ipcRenderer.invoke('list-profiles'),
+  switchProfile: (name: string) => ipcRenderer.invoke('switch-profile', name),
+  listHotkeys: () => ipcRenderer.invoke('list-hotkeys'),
+  checkHotkeyAvailable: (accelerator: string) => ipcRenderer.invoke('check-hotkey-available', accelerator),
+  transcribeFile: (filePath: string) => ipcRenderer.invoke('transcribe-file', filePath),
+  runSelfTest: (capture: { microphone: { durationMs: number; error?: string }; recording: { durationMs: number; error?: string }; wav: ArrayBuffer | null }) =>
+    ipcRenderer.invoke('run-self-test', capture),
+  enrollCommandTemplate: (phrase: string, wav: ArrayBuffer) => ipcRenderer.invoke('enroll-command-template', phrase, wav),
+  listCommandTemplates: () => ipcRenderer.invoke('list-command-templates'),
+  clearCommandTemplates: (phrase: string) => ipcRenderer.invoke('clear-command-templates', phrase),
+  repeatLastCommand: (prevAppId: string | null) => ipcRenderer.invoke('repeat-last-command', prevAppId),
+  undoLastInsert: (prevAppId: string | null) => ipcRenderer.invoke('undo-last-insert', prevAppId),
   getWindowPosition: () => ipcRenderer.invoke('get-window-position'),
   setWindowPosition: (x: number, y: number) => ipcRenderer.invoke('set-window-position', x, y),
   setFloatExpanded: (expanded: boolean) => ipcRenderer.invoke('set-float-expanded', expanded),
@@ -22,6 +77,7 @@ contextBridge.exposeInMainWorld('electronAPI', {
     ipcRenderer.invoke('sync-float-layout', layout),
   retryFloatPaste: (text: string, targetAppId: string | null) =>
     ipcRenderer.invoke('retry-float-paste', text, targetAppId),
+  armInput: (text?: string, targetAppId?: string | null) => ipcRenderer.invoke('arm-input', text, targetAppId),
   setIgnoreMouseEvents: (ignore: boolean, opts?: { forward: boolean }) =>
     ipcRenderer.invoke('set-ignore-mouse-events', ignore, opts),
 
@@ -32,8 +88,13 @@ contextBridge.exposeInMainWorld('electronAPI', {
   deleteModel: (modelId: string) => ipcRenderer.invoke('delete-model', modelId),
 
   // 日志
+  listAudioHosts: () => ipcRenderer.invoke('list-audio-hosts'),
   getLogs: () => ipcRenderer.invoke('get-logs'),
   clearLogs: () => ipcRenderer.invoke('clear-logs'),
+  getLogLevel: () => ipcRenderer.invoke('get-log-level'),
+  getLogPath: () => ipcRenderer.invoke('get-log-path'),
+  openLogFolder: () => ipcRenderer.invoke('open-log-folder'),
+  setLogLevel: (level: string) => ipcRenderer.invoke('set-log-level', level),
   copyToClipboard: (text: string) => ipcRenderer.invoke('copy-to-clipboard', text),
 
   // 统计与历史
@@ -68,9 +129,85 @@ contextBridge.exposeInMainWorld('electronAPI', {
   onToggleVad: (cb: (enabled: boolean) => void) => {
     ipcRenderer.on('toggle-vad', (_e, enabled) => cb(Boolean(enabled)))
   },
+  // VAD 开关状态变化（含空闲超时自动关闭）
+  onVadState: (cb: (state: { enabled: boolean; reason: 'user' | 'idle-timeout' }) => void) => {
+    ipcRenderer.on('vad-state', (_e, state) => cb(state))
+  },
   onVadThresholdUpdated: (cb: (threshold: number) => void) => {
     ipcRenderer.on('vad-threshold-updated', (_e, threshold) => cb(Number(threshold)))
   },
+  onVadEnergySmoothingUpdated: (cb: (smoothing: number) => void) => {
+    ipcRenderer.on('vad-energy-smoothing-updated', (_e, smoothing) => cb(Number(smoothing)))
+  },
+  onVadBackendUpdated: (cb: (backend: 'energy' | 'fsmn') => void) => {
+    ipcRenderer.on('vad-backend-updated', (_e, backend) => cb(backend))
+  },
+  onInputGainUpdated: (cb: (gain: number) => void) => {
+    ipcRenderer.on('input-gain-updated', (_e, gain) => cb(Number(gain)))
+  },
+  onInputModeUpdated: (cb: (mode: 'clipboard' | 'keyboard') => void) => {
+    ipcRenderer.on('input-mode-updated', (_e, mode) => cb(mode))
+  },
+  onProfileChanged: (cb: (state: {
+    active: string
+    profiles: Array<{ name: string; active: boolean; commandCount: number }>
+  }) => void) => {
+    ipcRenderer.on('profile-changed', (_e, state) => cb(state))
+  },
+  onCommandMatchingUpdated: (cb: (enabled: boolean) => void) => {
+    ipcRenderer.on('command-matching-updated', (_e, enabled) => cb(enabled))
+  },
+  onInputLevel: (cb: (level: { rms: number; peak: number; gain: number; clipped: boolean }) => void) => {
+    ipcRenderer.on('input-level', (_e, level) => cb(level))
+  },
+  onAudioInfo: (cb: (info: {
+    sourceSampleRate: number | null
+    sourceChannels: number | null
+    outputSampleRate: number
+    outputChannels: number
+    deviceName: string
+    capturing: boolean
+    vadListening: boolean
+    updatedAt: string
+  }) => void) => {
+    ipcRenderer.on('audio-info', (_e, info) => cb(info))
+  },
+  // 主进程请求浮窗切换麦克风，浮窗打开新设备后以 reportInputDeviceSwitch 回报结果
+  onSwitchInputDevice: (cb: (request: { requestId: number; name: string }) => void) => {
+    ipcRenderer.on('switch-input-device', (_e, request) => cb(request))
+  },
+  onInputDeviceUpdated: (cb: (device: { deviceId: string; deviceName: string }) => void) => {
+    ipcRenderer.on('input-device-updated', (_e, device) => cb(device))
+  },
+  // 麦克风打开失败、音轨意外结束或采集节点出错
+  onAudioThreadError: (cb: (event: {
+    stage: 'stream-open-failed' | 'stream-ended' | 'capture-error'
+    source: 'capture' | 'vad'
+    deviceName: string
+    message: string
+    at: string
+  }) => void) => {
+    ipcRenderer.on('audio-thread-error', (_e, event) => cb(event))
+  },
+  onSampleRateWarning: (cb: (warning: { actualRate: number; expectedRate: number; message: string }) => void) => {
+    ipcRenderer.on('sample-rate-warning', (_e, warning) => cb(warning))
+  },
+  // 识别流程阶段（encoding/uploading/server-processing/matching/inserting），elapsedMs 自触发起计时
+  onRecognitionStage: (cb: (event: {
+    requestId: number
+    traceId: string
+    stage: 'encoding' | 'uploading' | 'server-processing' | 'matching' | 'inserting'
+    elapsedMs: number
+  }) => void) => {
+    ipcRenderer.on('recognition-stage', (_e, event) => cb(event))
+  },
+  // 识别串行队列状态：pending > 0 表示有识别在排队等待上一句完成
+  onRecognitionBusy: (cb: (state: { busy: boolean; pending: number }) => void) => {
+    ipcRenderer.on('recognition-busy', (_e, state) => cb(state))
+  },
+  onReattemptSuggested: (cb: (info: { capturedMs: number; mode: 'toggle' | 'hold'; autoRearm: boolean; attempt: number }) => void) => {
+    ipcRenderer.on('reattempt-suggested', (_e, info) => cb(info))
+  },
   onAsrRuntimeStatus: (cb: (status: {
     phase: 'idle' | 'starting' | 'ready' | 'error'
     modelId: string | null
@@ -80,10 +217,36 @@ contextBridge.exposeInMainWorld('electronAPI', {
   }) => void) => {
     ipcRenderer.on('asr-runtime-status', (_e, status) => cb(status))
   },
+  onLastError: (cb: (error: { category: 'recognition' | 'input' | 'model'; message: string; at: string } | null) => void) => {
+    ipcRenderer.on('last-error', (_e, error) => cb(error))
+  },
+  onServerStatus: (cb: (status: {
+    state: 'unknown' | 'connected' | 'unreachable' | 'disabled'
+    message: string
+    checkedAt: string
+    latencyMs: number | null
+    error?: string
+  }) => void) => {
+    ipcRenderer.on('server-status', (_e, status) => cb(status))
+  },
+  // 每次巡检或 ping 记录的往返耗时
+  onServerLatency: (cb: (sample: { at: string; latencyMs: number | null; error?: string }) => void) => {
+    ipcRenderer.on('server-latency', (_e, sample) => cb(sample))
+  },
   // 热键触发停止录音（toggle 模式）
+  // 主进程请求浮窗开始定时录音，浮窗开始（或拒绝）后以 reportQuickRecord 回报
+  onQuickRecord: (cb: (request: { requestId: number; durationMs: number }) => void) => {
+    ipcRenderer.on('quick-record', (_e, request) => cb(request))
+  },
   onHotkeyStopRecording: (cb: (prevAppId: string | null) => void) => {
     ipcRenderer.on('hotkey-stop-recording', (_e, prevAppId) => cb(prevAppId))
   },
+  onHotkeyRepeatCommand: (cb: (prevAppId: string | null) => void) => {
+    ipcRenderer.on('hotkey-repeat-command', (_e, prevAppId) => cb(prevAppId))
+  },
+  onHotkeyUndoLastInsert: (cb: (prevAppId: string | null) => void) => {
+    ipcRenderer.on('hotkey-undo-last-insert', (_e, prevAppId) => cb(prevAppId))
+  },
   // 模型下载进度
   onModelDownloadProgress: (cb: (data: { modelId: string; percent: number }) => void) => {
     ipcRenderer.on('model-download-progress', (_e, data) => cb(data))
@@ -92,18 +255,73 @@ contextBridge.exposeInMainWorld('electronAPI', {
   onLogEntry: (cb: (entry: { time: string; level: string; msg: string }) => void) => {
     ipcRenderer.on('log-entry', (_e, entry) => cb(entry))
   },
+  onLogLevelUpdated: (cb: (level: 'debug' | 'info' | 'warn' | 'error') => void) => {
+    ipcRenderer.on('log-level-updated', (_e, level) => cb(level))
+  },
   onPermissionWarning: (cb: (message: string) => void) => {
     ipcRenderer.on('permission-warning', (_e, message) => cb(String(message || '')))
   },
+  // 输入模拟不可用（未授权辅助功能等），只在状态变为不可用时推送一次
+  onInputPermissionDenied: (cb: (state: { available: boolean; checkedAt: string; error?: string; guide?: string }) => void) => {
+    ipcRenderer.on('input-permission-denied', (_e, state) => cb(state))
+  },
   onFloatPasteFallback: (cb: (payload: {
     requestId: number
+    traceId?: string
     text: string
     targetAppId: string | null
-    reason: 'no-foreground-window' | 'no-focused-control' | 'focused-control-without-caret' | 'type-failed' | 'restore-failed'
+    reason: 'no-foreground-window' | 'no-focused-control' | 'focused-control-without-caret' | 'type-failed' | 'restore-failed' | 'safe-mode-unarmed'
     precheckReason: 'ok' | 'unknown' | 'no-foreground-window' | 'no-focused-control' | 'focused-control-without-caret'
+    copiedToClipboard: boolean
   }) => void) => {
     ipcRenderer.on('float-paste-fallback', (_e, payload) => cb(payload))
   },
+  onInputComplete: (cb: (payload: { requestId: number; traceId?: string; text: string; method: 'clipboard' | 'keyboard' | 'shortcut' }) => void) => {
+    ipcRenderer.on('input-complete', (_e, payload) => cb(payload))
+  },
+  onInputFailed: (cb: (payload: { requestId: number; traceId?: string; text: string; error: string; copiedToClipboard: boolean }) => void) => {
+    ipcRenderer.on('input-failed', (_e, payload) => cb(payload))
+  },
+  onAsrRawResponse: (cb: (payload: {
+    requestId: number
+    transport: 'http' | 'ws'
+    body: string
+    length: number
+    truncated: boolean
+  }) => void) => {
+    ipcRenderer.on('asr-raw-response', (_e, payload) => cb(payload))
+  },
+  onRecognitionEmptyWarning: (cb: (payload: { count: number; message: string }) => void) => {
+    ipcRenderer.on('recognition-empty-warning', (_e, payload) => cb(payload))
+  },
+  onResultFiltered: (cb: (payload: {
+    requestId: number
+    text: string
+    reason: 'too-short' | 'too-long'
+    length: number
+    minChars: number
+    maxChars: number
+  }) => void) => {
+    ipcRenderer.on('result-filtered', (_e, payload) => cb(payload))
+  },
+  onVoiceCommandCandidates: (cb: (payload: {
+    requestId: number
+    text: string
+    candidates: Array<{ phrase: string; shortcut: string; score: number }>
+  }) => void) => {
+    ipcRenderer.on('voice-command-candidates', (_e, payload) => cb(payload))
+  },
+  onHotkeysUpdated: (cb: (bindings: Array<{
+    action: string
+    accelerator: string
+    status: 'registered' | 'failed' | 'invalid'
+    detail?: string
+  }>) => void) => {
+    ipcRenderer.on('hotkeys-updated', (_e, bindings) => cb(bindings))
+  },
+  onHotkeyConflict: (cb: (conflict: { action: string; accelerator: string; message: string }) => void) => {
+    ipcRenderer.on('hotkey-conflict', (_e, conflict) => cb(conflict))
+  },
   onFloatDebugBoundsUpdated: (cb: (enabled: boolean) => void) => {
     ipcRenderer.on('float-debug-bounds-updated', (_e, enabled) => cb(Boolean(enabled)))
   },
@@ -5,6 +5,9 @@ class PcmCaptureProcessor extends AudioWorkletProcessor {
     this.chunkSize = Number.isFinite(requestedSize) && requestedSize >= 128
       ? Math.floor(requestedSize)
       : 1024
+    // channels=2 时输出 L/R 交错数据，chunkSize 按帧计
+    this.channels = options?.processorOptions?.channels === 2 ? 2 : 1
+    this.chunkSize *= this.channels
     this.buffer = new Float32Array(this.chunkSize * 2)
     this.length = 0
 
@@ -55,7 +58,17 @@ class PcmCaptureProcessor extends AudioWorkletProcessor {
     const firstInput = inputs[0]
     const channel = firstInput && firstInput[0]
     if (channel && channel.length > 0) {
-      this.append(channel)
+      if (this.channels === 2) {
+        const right = firstInput[1] || channel
+        const interleaved = new Float32Array(channel.length * 2)
+        for (let i = 0; i < channel.length; i++) {
+          interleaved[i * 2] = channel[i]
+          interleaved[i * 2 + 1] = right[i]
+        }
+        this.append(interleaved)
+      } else {
+        this.append(channel)
+      }
     }
     return true
   }
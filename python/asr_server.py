@@ -43,6 +43,7 @@ from inference import (
     reset_runtime_models,
     decode_wav,
     split_segments_by_vad,
+    detect_speech_pairs,
     run_asr_once,
     run_punc,
     inspect_hotword_state_for_model,
@@ -248,6 +249,54 @@ def handle_message(msg: dict) -> dict:
             "asrPasses": 1,
         }
 
+    # 实时 VAD 选用模型后端时，对能量检测切出的语音段做二次确认；未执行 init 时只加载 VAD 模型
+    if cmd == "detectSpeech":
+        if inference.vad_model is None:
+            vad_model_name = msg.get("vadModelName", "")
+            vad_backend = msg.get("vadBackend", "funasr_onnx_vad")
+            vad_quantize = bool(msg.get("vadQuantize", True))
+            try:
+                inference.vad_model = create_vad_model(vad_model_name, vad_backend, vad_quantize)
+            except Exception as e:
+                return error_from_exception(
+                    msg_id=msg_id,
+                    code="VAD_MODEL_INIT_FAILED",
+                    message=f"VAD 模型初始化失败: {vad_model_name}",
+                    phase="detect/vad",
+                    exc=e,
+                    data={
+                        "modelName": vad_model_name,
+                        "backend": vad_backend,
+                        "quantize": vad_quantize,
+                    },
+                )
+
+        try:
+            samples = decode_wav(base64.b64decode(msg["wavBase64"]))
+        except Exception as e:
+            return error_from_exception(
+                msg_id=msg_id,
+                code="AUDIO_DECODE_FAILED",
+                message="音频解码失败",
+                phase="detect/decode",
+                exc=e,
+            )
+        if not isinstance(samples, np.ndarray) or samples.size == 0:
+            return {"id": msg_id, "ok": True, "segmentCount": 0, "speechMs": 0}
+
+        try:
+            pairs = detect_speech_pairs(samples)
+        except Exception as e:
+            return error_from_exception(
+                msg_id=msg_id,
+                code="VAD_INFER_FAILED",
+                message="VAD 推理失败",
+                phase="detect/vad",
+                exc=e,
+            )
+        speech_ms = sum(max(0.0, end_ms - start_ms) for start_ms, end_ms in pairs)
+        return {"id": msg_id, "ok": True, "segmentCount": len(pairs), "speechMs": int(round(speech_ms))}
+
     if cmd == "check":
         model_name = msg["modelName"]
         backend = msg.get("backend", "funasr_onnx_contextual")
@@ -145,11 +145,8 @@ def _extract_vad_pairs(node, pairs):
             _extract_vad_pairs(item, pairs)
 
 
-def split_segments_by_vad(samples: np.ndarray):
-    if not isinstance(samples, np.ndarray) or samples.size == 0:
-        return []
-    if vad_model is None:
-        return [samples]
+def detect_speech_pairs(samples: np.ndarray) -> list[tuple[float, float]]:
+    """VAD 模型检测到的语音区间（毫秒），去重并按起点排序；需已加载 vad_model。"""
     try:
         vad_output = vad_model(samples)
     except Exception as e:
@@ -157,8 +154,6 @@ def split_segments_by_vad(samples: np.ndarray):
 
     pairs: list[tuple[float, float]] = []
     _extract_vad_pairs(vad_output, pairs)
-    if not pairs:
-        return [samples]
 
     deduped: list[tuple[float, float]] = []
     seen: set[tuple[int, int]] = set()
@@ -169,6 +164,17 @@ def split_segments_by_vad(samples: np.ndarray):
         seen.add(key)
         deduped.append((float(start_ms), float(end_ms)))
     deduped.sort(key=lambda item: (item[0], item[1]))
+    return deduped
+
+
+def split_segments_by_vad(samples: np.ndarray):
+    if not isinstance(samples, np.ndarray) or samples.size == 0:
+        return []
+    if vad_model is None:
+        return [samples]
+    deduped = detect_speech_pairs(samples)
+    if not deduped:
+        return [samples]
 
     segmented = []
     total = len(samples)
@@ -0,0 +1,10 @@
+// 软件增益范围：主进程保存配置与浮窗采集共用，保证两端限制一致
+
+export const INPUT_GAIN_MIN = 0.1
+export const INPUT_GAIN_MAX = 10
+export const INPUT_GAIN_DEFAULT = 1
+
+export function clampInputGain(raw: unknown): number {
+  const value = typeof raw === 'number' && Number.isFinite(raw) ? raw : INPUT_GAIN_DEFAULT
+  return Math.min(INPUT_GAIN_MAX, Math.max(INPUT_GAIN_MIN, value))
+}
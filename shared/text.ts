@@ -0,0 +1,17 @@
+// 主进程与渲染进程共用的文本工具（日志截断、浮窗结果预览），只依赖标准 JS，两端都能直接引入
+
+// 按字素（用户看到的“一个字”）截断，不会切开代理对或组合字符；超出时以省略号结尾，总长度不超过 maxChars
+export function truncateText(text: string, maxChars: number, ellipsis = '…'): string {
+  const graphemes = splitGraphemes(text)
+  if (graphemes.length <= maxChars) return text
+  const ellipsisLength = splitGraphemes(ellipsis).length
+  if (maxChars <= ellipsisLength) return graphemes.slice(0, Math.max(0, maxChars)).join('')
+  return graphemes.slice(0, maxChars - ellipsisLength).join('') + ellipsis
+}
+
+function splitGraphemes(text: string): string[] {
+  if (typeof Intl !== 'undefined' && typeof Intl.Segmenter === 'function') {
+    return Array.from(new Intl.Segmenter('zh', { granularity: 'grapheme' }).segment(text), (s) => s.segment)
+  }
+  return Array.from(text)
+}
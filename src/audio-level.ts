@@ -0,0 +1,74 @@
+import type { InputLevel } from './types'
+
+// 软件增益与输入电平：系统麦克风音量被策略锁定时，在采集到的采样上再乘一个系数，
+// 并按固定间隔汇总电平供界面显示，便于用户边说话边调节增益
+
+// 原地乘以增益并截断到 [-1, 1]，返回被截断（削波）的采样数；增益为 1 时不做任何处理
+export function applyInputGain(samples: Float32Array, gain: number): number {
+  if (gain === 1) return 0
+  let clipped = 0
+  for (let i = 0; i < samples.length; i++) {
+    const value = samples[i] * gain
+    if (value > 1) {
+      samples[i] = 1
+      clipped++
+    } else if (value < -1) {
+      samples[i] = -1
+      clipped++
+    } else {
+      samples[i] = value
+    }
+  }
+  return clipped
+}
+
+// VAD 在增益之前测量能量：先复制末尾 tailSamples 个原始采样再原地应用增益，
+// 这样调节增益只影响录音与电平显示，不会让同样音量的环境噪声越过阈值。
+// 未采用“阈值乘以增益”的做法，是因为高增益下削波会让能量不再与增益成正比
+export function applyInputGainKeepingTail(samples: Float32Array, gain: number, tailSamples: number): Float32Array {
+  const tail = samples.slice(Math.max(0, samples.length - tailSamples))
+  applyInputGain(samples, gain)
+  return tail
+}
+
+// 累积一段时间内的采样，每隔 intervalMs 输出一次电平，避免每个音频回调都推送界面
+export class InputLevelMeter {
+  private sumSquares = 0
+  private count = 0
+  private peak = 0
+  private clipped = 0
+  private lastEmitAt = 0
+
+  constructor(
+    private readonly intervalMs = 100,
+    private readonly now: () => number = Date.now,
+  ) { }
+
+  push(samples: Float32Array, gain: number, clippedSamples = 0): InputLevel | null {
+    for (const v of samples) {
+      this.sumSquares += v * v
+      const abs = Math.abs(v)
+      if (abs > this.peak) this.peak = abs
+    }
+    this.count += samples.length
+    this.clipped += clippedSamples
+    const now = this.now()
+    if (this.count === 0 || now - this.lastEmitAt < this.intervalMs) return null
+    const level: InputLevel = {
+      rms: Math.sqrt(this.sumSquares / this.count),
+      peak: this.peak,
+      gain,
+      clipped: this.clipped > 0,
+    }
+    this.lastEmitAt = now
+    this.reset()
+    return level
+  }
+
+  reset(): void {
+    this.sumSquares = 0
+    this.count = 0
+    this.peak = 0
+    this.clipped = 0
+  }
+}
@@ -1,5 +1,23 @@
-import { encodeWav } from './wav'
-import type { AudioCaptureConfig } from './types'
+import { encodeWav, monoToInterleavedStereo, describeSampleRateMismatch } from './wav'
+import {
+  VoiceActivityDetector,
+  VadFrameAccumulator,
+  VadIdleTimer,
+  VadSampleClock,
+  computeRms,
+  frameSamplesForMs,
+  isSpeechSegmentLongEnough,
+  SilenceSegmenter,
+  type VadFrameEvent,
+  type VadFrameParams,
+} from './vad'
+import {
+  InputLevelMeter,
+  applyInputGain,
+  applyInputGainKeepingTail,
+} from './audio-level'
+import { INPUT_GAIN_DEFAULT, clampInputGain } from '../shared/input-gain'
+import type { AudioCaptureConfig, AudioInfo, AudioLifecycleEvent, AudioLifecycleStage, InputDeviceSelection, InputLevel, RecognitionResult, SampleRateWarning } from './types'
 
 let audioCtx: AudioContext | null = null
 let mediaStream: MediaStream | null = null
@@ -11,56 +29,65 @@ let isCapturing = false
 let captureStopPromise: Promise<ArrayBuffer> | null = null
 let workletModuleReady = false
 let pendingCaptureFlushResolve: ((elapsedMs: number) => void) | null = null
+let captureChannelCount = 1
+let captureStartedAt = 0
+let lastCapturedMs = 0
+let captureSegmentation: { segmenter: SilenceSegmenter; params: VadFrameParams; onSegment: (wav: ArrayBuffer) => void } | null = null
+let captureSegmentCount = 0
+let lastCaptureSegmentCount = 0
 
 const CAPTURE_BUFFER_SIZE = 1024
 const CAPTURE_WORKLET_NAME = 'pcm-capture-processor'
 const PCM_SAMPLE_RATE = 16000
 
 const VAD_SAMPLE_INTERVAL_MS = 40
-const VAD_RMS_EMA_ALPHA = 0.28
-const VAD_STOP_HYSTERESIS_RATIO = 0.72
-const VAD_NOISE_FLOOR_EMA_ALPHA = 0.08
-const VAD_NOISE_FLOOR_START_RATIO = 1.6
-const VAD_NOISE_FLOOR_STOP_RATIO = 1.18
-const VAD_START_TRIGGER_MS = 60
-const VAD_RELEASE_TRIGGER_MS = 80
-const VAD_ENDPOINT_HANGOVER_MS = 60
-const VAD_MAX_SPEECH_MS = 12000
 const VAD_PRE_ROLL_MS = 260
-const VAD_HARD_MIN_WAV_MS = 90
+const VAD_MAX_QUEUED_SEGMENTS = 4
 
-const DEFAULT_AUDIO_CAPTURE_CONFIG: AudioCaptureConfig = {
+// 提示音与音频后端由其他模块处理，采集只关心以下字段
+type CaptureConfig = Omit<AudioCaptureConfig, 'soundCues' | 'host'>
+
+const DEFAULT_AUDIO_CAPTURE_CONFIG: CaptureConfig = {
   inputConstraints: {
     channelCount: 1,
     echoCancellation: false,
     noiseSuppression: false,
     autoGainControl: false,
   },
+  channels: 'mono',
   postRollMs: 100,
   tailSilenceMs: 120,
+  padMs: 0,
   workletFlushTimeoutMs: 220,
+  inputGain: INPUT_GAIN_DEFAULT,
+  source: 'input',
 }
 
 type AudioCaptureConfigInput = Partial<AudioCaptureConfig> & {
   inputConstraints?: Partial<AudioCaptureConfig['inputConstraints']>
 }
 
-let runtimeAudioCaptureConfig: AudioCaptureConfig = cloneAudioCaptureConfig(DEFAULT_AUDIO_CAPTURE_CONFIG)
+let runtimeAudioCaptureConfig: CaptureConfig = cloneAudioCaptureConfig(DEFAULT_AUDIO_CAPTURE_CONFIG)
 let inputConstraintVersion = 0
 let mediaStreamConstraintVersion = -1
 let vadStreamConstraintVersion = -1
 
-function cloneAudioCaptureConfig(config: AudioCaptureConfig): AudioCaptureConfig {
+function cloneAudioCaptureConfig(config: CaptureConfig): CaptureConfig {
   return {
     inputConstraints: {
       channelCount: config.inputConstraints.channelCount,
       echoCancellation: config.inputConstraints.echoCancellation,
       noiseSuppression: config.inputConstraints.noiseSuppression,
       autoGainControl: config.inputConstraints.autoGainControl,
+      deviceId: config.inputConstraints.deviceId,
     },
+    channels: config.channels,
     postRollMs: config.postRollMs,
     tailSilenceMs: config.tailSilenceMs,
+    padMs: config.padMs,
     workletFlushTimeoutMs: config.workletFlushTimeoutMs,
+    inputGain: config.inputGain,
+    source: config.source,
   }
 }
 
@@ -68,7 +95,7 @@ function clampInt(value: number, min: number, max: number): number {
   return Math.round(Math.min(max, Math.max(min, value)))
 }
 
-function normalizeAudioCaptureConfig(raw: AudioCaptureConfigInput | null | undefined): AudioCaptureConfig {
+function normalizeAudioCaptureConfig(raw: AudioCaptureConfigInput | null | undefined): CaptureConfig {
   const source = raw ?? {}
   const input = source.inputConstraints ?? {}
   return {
@@ -87,7 +114,9 @@ function normalizeAudioCaptureConfig(raw: AudioCaptureConfigInput | null | undef
       autoGainControl: typeof input.autoGainControl === 'boolean'
         ? input.autoGainControl
         : DEFAULT_AUDIO_CAPTURE_CONFIG.inputConstraints.autoGainControl,
+      deviceId: typeof input.deviceId === 'string' && input.deviceId ? input.deviceId : undefined,
     },
+    channels: source.channels === 'stereo' ? 'stereo' : 'mono',
     postRollMs: clampInt(
       Number.isFinite(Number(source.postRollMs)) ? Number(source.postRollMs) : DEFAULT_AUDIO_CAPTURE_CONFIG.postRollMs,
       0,
@@ -98,6 +127,11 @@ function normalizeAudioCaptureConfig(raw: AudioCaptureConfigInput | null | undef
       0,
       1200,
     ),
+    padMs: clampInt(
+      Number.isFinite(Number(source.padMs)) ? Number(source.padMs) : DEFAULT_AUDIO_CAPTURE_CONFIG.padMs,
+      0,
+      1000,
+    ),
     workletFlushTimeoutMs: clampInt(
       Number.isFinite(Number(source.workletFlushTimeoutMs))
         ? Number(source.workletFlushTimeoutMs)
@@ -105,16 +139,22 @@ function normalizeAudioCaptureConfig(raw: AudioCaptureConfigInput | null | undef
       80,
       2000,
     ),
+    inputGain: clampInputGain(source.inputGain),
+    // 平台是否支持环回由主进程归一化配置时判断
+    source: source.source === 'loopback' ? 'loopback' : 'input',
   }
 }
 
-function hasInputConstraintChanged(prev: AudioCaptureConfig, next: AudioCaptureConfig): boolean {
+function hasInputConstraintChanged(prev: CaptureConfig, next: CaptureConfig): boolean {
   const a = prev.inputConstraints
   const b = next.inputConstraints
-  return a.channelCount !== b.channelCount
+  return prev.channels !== next.channels
+    || prev.source !== next.source
+    || a.channelCount !== b.channelCount
     || a.echoCancellation !== b.echoCancellation
     || a.noiseSuppression !== b.noiseSuppression
     || a.autoGainControl !== b.autoGainControl
+    || (a.deviceId ?? '') !== (b.deviceId ?? '')
 }
 
 function stopStream(stream: MediaStream | null): void {
@@ -133,7 +173,7 @@ function safeJson(value: unknown): string {
 }
 
 function buildAudioConstraints(config: AudioCaptureConfig): MediaTrackConstraints {
-  const channelCount = config.inputConstraints.channelCount
+  const channelCount = config.channels === 'stereo' ? 2 : config.inputConstraints.channelCount
   const constraints: MediaTrackConstraints = {
     channelCount: channelCount === 1 ? { ideal: 1, max: 1 } : { ideal: channelCount },
     echoCancellation: config.inputConstraints.echoCancellation,
@@ -199,11 +239,109 @@ export function setAudioCaptureConfig(raw: AudioCaptureConfigInput | null | unde
   }
 
   console.debug(
-    `[录音] 采集配置已更新: postRollMs=${next.postRollMs}, tailSilenceMs=${next.tailSilenceMs}, ` +
-    `flushTimeoutMs=${next.workletFlushTimeoutMs}, input=${safeJson(next.inputConstraints)}`,
+    `[录音] 采集配置已更新: postRollMs=${next.postRollMs}, tailSilenceMs=${next.tailSilenceMs}, padMs=${next.padMs}, ` +
+    `flushTimeoutMs=${next.workletFlushTimeoutMs}, gain=${next.inputGain}, input=${safeJson(next.inputConstraints)}`,
   )
 }
 
+// 调整软件增益立即生效，正在进行的录音与 VAD 监听都会使用新值
+export function setInputGain(gain: number): number {
+  runtimeAudioCaptureConfig.inputGain = clampInputGain(gain)
+  return runtimeAudioCaptureConfig.inputGain
+}
+
+let inputLevelListener: ((level: InputLevel) => void) | null = null
+const inputLevelMeter = new InputLevelMeter()
+
+// 电平回调（VU 表）：录音与 VAD 监听期间约每 100ms 回调一次增益后的电平
+export function setInputLevelListener(listener: ((level: InputLevel) => void) | null): void {
+  inputLevelListener = listener
+  inputLevelMeter.reset()
+}
+
+// 对新采集的采样原地施加增益，并计入电平；meter 为 false 时只施加增益
+function processInputSamples(samples: Float32Array, meter = true): void {
+  const gain = runtimeAudioCaptureConfig.inputGain
+  const clipped = applyInputGain(samples, gain)
+  if (!meter || !inputLevelListener) return
+  const level = inputLevelMeter.push(samples, gain, clipped)
+  if (level) inputLevelListener(level)
+}
+
+let audioInfoListener: ((info: AudioInfo) => void) | null = null
+
+// 麦克风格式回调：设备打开、开始/停止录音、开关 VAD 时回调当前实际格式
+export function setAudioInfoListener(listener: ((info: AudioInfo) => void) | null): void {
+  audioInfoListener = listener
+  if (listener) listener(getAudioInfo())
+}
+
+export function getAudioInfo(): AudioInfo {
+  // 录音流优先，未打开时取 VAD 监听流
+  const track = (mediaStream ?? vadStream)?.getAudioTracks()[0]
+  const settings = track && typeof track.getSettings === 'function' ? track.getSettings() : {}
+  return {
+    sourceSampleRate: typeof settings.sampleRate === 'number' ? settings.sampleRate : null,
+    sourceChannels: typeof settings.channelCount === 'number' ? settings.channelCount : null,
+    outputSampleRate: PCM_SAMPLE_RATE,
+    outputChannels: runtimeAudioCaptureConfig.channels === 'stereo' ? 2 : 1,
+    deviceName: track?.label ?? '',
+    capturing: isCapturing,
+    vadListening: vadTimer !== null,
+    updatedAt: new Date().toISOString(),
+  }
+}
+
+function emitAudioInfo(): void {
+  audioInfoListener?.(getAudioInfo())
+}
+
+// 采样率不一致提示：每次编码都写控制台警告，回调只在本窗口生命周期内触发一次
+let sampleRateWarningListener: ((warning: SampleRateWarning) => void) | null = null
+let sampleRateWarned = false
+
+export function setSampleRateWarningListener(listener: ((warning: SampleRateWarning) => void) | null): void {
+  sampleRateWarningListener = listener
+}
+
+function checkCaptureSampleRate(ctx: AudioContext): void {
+  const message = describeSampleRateMismatch(ctx.sampleRate, PCM_SAMPLE_RATE)
+  if (!message) return
+  console.error(`[录音] ⚠ ${message}`)
+  if (sampleRateWarned) return
+  sampleRateWarned = true
+  sampleRateWarningListener?.({ actualRate: ctx.sampleRate, expectedRate: PCM_SAMPLE_RATE, message })
+}
+
+let audioLifecycleListener: ((event: AudioLifecycleEvent) => void) | null = null
+
+// 采集生命周期回调：打开/打开失败/音轨意外结束/开始录音/采集节点出错，供主进程记录诊断日志
+export function setAudioLifecycleListener(listener: ((event: AudioLifecycleEvent) => void) | null): void {
+  audioLifecycleListener = listener
+}
+
+function emitAudioLifecycle(
+  stage: AudioLifecycleStage,
+  source: 'capture' | 'vad',
+  message = '',
+  stream: MediaStream | null = null,
+): void {
+  const deviceName = stream?.getAudioTracks()[0]?.label ?? ''
+  audioLifecycleListener?.({ stage, source, deviceName, message, at: new Date().toISOString() })
+}
+
+// 音轨意外结束（设备拔出、被系统收回）后不会再有音频：上报并作废该流，下次录音/开启 VAD 时重新打开
+function watchTrackEnded(stream: MediaStream, source: 'capture' | 'vad'): void {
+  const track = stream.getAudioTracks()[0]
+  if (!track) return
+  track.addEventListener('ended', () => {
+    console.error(`[录音] ${source} 音轨意外结束: ${track.label}`)
+    if (source === 'capture' && mediaStream === stream) mediaStreamConstraintVersion = -1
+    if (source === 'vad' && vadStream === stream) vadStreamConstraintVersion = -1
+    emitAudioLifecycle('stream-ended', source, '设备已断开或被系统收回，请检查麦克风后重新开始录音', stream)
+  })
+}
+
 // 检测是否有可用的音频输入设备
 async function hasAudioInputDevice(): Promise<boolean> {
   try {
@@ -218,10 +356,31 @@ async function hasAudioInputDevice(): Promise<boolean> {
 async function throwMicError(tag: string): Promise<never> {
   const hasDevice = await hasAudioInputDevice()
   window.electronAPI?.checkMicPermission?.()
-  if (!hasDevice) {
-    throw new Error('未检测到麦克风设备，请连接麦克风或在声音设置中启用录音设备')
+  const message = hasDevice
+    ? '麦克风访问被拒绝，请在系统隐私设置中允许本应用使用麦克风'
+    : '未检测到麦克风设备，请连接麦克风或在声音设置中启用录音设备'
+  emitAudioLifecycle('stream-open-failed', tag === 'VAD' ? 'vad' : 'capture', message)
+  throw new Error(message)
+}
+
+// 系统声音（环回）：主进程为 getDisplayMedia 授予屏幕源与 loopback 音频，视频轨道用不到，拿到后立即停掉
+async function getLoopbackStream(): Promise<MediaStream> {
+  const stream = await navigator.mediaDevices.getDisplayMedia({ video: true, audio: true })
+  for (const track of stream.getVideoTracks()) {
+    track.stop()
+    stream.removeTrack(track)
+  }
+  if (stream.getAudioTracks().length === 0) {
+    stopStream(stream)
+    throw new DOMException('未获取到系统声音音轨', 'NotFoundError')
   }
-  throw new Error('麦克风访问被拒绝，请在系统隐私设置中允许本应用使用麦克风')
+  return stream
+}
+
+// 按录音来源打开音频流：麦克风走 getUserMedia，系统声音走环回
+function openAudioStream(constraints: MediaTrackConstraints): Promise<MediaStream> {
+  if (runtimeAudioCaptureConfig.source === 'loopback') return getLoopbackStream()
+  return navigator.mediaDevices.getUserMedia({ audio: constraints, video: false })
 }
 
 // 初始化麦克风
@@ -234,23 +393,30 @@ async function initMic(): Promise<void> {
 
   const constraints = buildAudioConstraints(runtimeAudioCaptureConfig)
   try {
-    mediaStream = await navigator.mediaDevices.getUserMedia({ audio: constraints, video: false })
+    mediaStream = await openAudioStream(constraints)
   } catch (e) {
     const err = e as DOMException
     console.error(`[录音] 麦克风初始化失败: ${err.name}: ${err.message}`)
-    if (err.name === 'NotFoundError' || err.name === 'DevicesNotFoundError') {
-      throw new Error('未检测到麦克风设备，请连接麦克风后重试')
+    let message: string
+    if (runtimeAudioCaptureConfig.source === 'loopback') {
+      message = `系统声音（环回）采集失败: ${err.message || err.name}`
+    } else if (err.name === 'NotFoundError' || err.name === 'DevicesNotFoundError') {
+      message = '未检测到麦克风设备，请连接麦克风后重试'
     } else if (err.name === 'NotAllowedError' || err.name === 'PermissionDeniedError') {
-      throw new Error('麦克风权限被拒绝，请在系统设置中允许访问麦克风')
+      message = '麦克风权限被拒绝，请在系统设置中允许访问麦克风'
     } else if (err.name === 'NotReadableError' || err.name === 'TrackStartError') {
-      throw new Error('麦克风被其他应用占用或无法读取，请关闭其他录音程序后重试')
+      message = '麦克风被其他应用占用或无法读取，请关闭其他录音程序后重试'
     } else {
-      throw new Error(`麦克风初始化失败: ${err.message || err.name}`)
+      message = `麦克风初始化失败: ${err.message || err.name}`
     }
+    emitAudioLifecycle('stream-open-failed', 'capture', message)
+    throw new Error(message)
   }
   mediaStreamConstraintVersion = inputConstraintVersion
   applySpeechContentHint(mediaStream, 'capture')
   logTrackDiagnostics(mediaStream, 'capture', constraints)
+  watchTrackEnded(mediaStream, 'capture')
+  emitAudioLifecycle('stream-opened', 'capture', '', mediaStream)
   const track = mediaStream.getAudioTracks()[0]
   console.warn(`[录音] 麦克风已获取，track: ${track?.label}, readyState: ${track?.readyState}, enabled: ${track?.enabled}`)
 }
@@ -264,8 +430,14 @@ async function initVadMic(): Promise<void> {
 
   const constraints = buildAudioConstraints(runtimeAudioCaptureConfig)
   try {
-    vadStream = await navigator.mediaDevices.getUserMedia({ audio: constraints, video: false })
+    vadStream = await openAudioStream(constraints)
   } catch (err) {
+    if (runtimeAudioCaptureConfig.source === 'loopback') {
+      const message = `系统声音（环回）采集失败: ${String(err)}`
+      console.warn(`[VAD] ${message}`)
+      emitAudioLifecycle('stream-open-failed', 'vad', message)
+      throw new Error(message)
+    }
     if (err instanceof DOMException && (err.name === 'NotFoundError' || err.name === 'OverconstrainedError')) {
       console.warn(`[VAD] getUserMedia 失败(${err.name})，尝试简约束 {audio:true}`)
       try {
@@ -282,10 +454,65 @@ async function initVadMic(): Promise<void> {
   vadStreamConstraintVersion = inputConstraintVersion
   applySpeechContentHint(vadStream, 'vad')
   logTrackDiagnostics(vadStream, 'vad', constraints)
+  watchTrackEnded(vadStream, 'vad')
+  emitAudioLifecycle('stream-opened', 'vad', '', vadStream)
+}
+
+// 运行时切换麦克风：按设备名称（精确或包含，不区分大小写）或 deviceId 查找，空串为系统默认。
+// 先试打开新设备，失败时抛错并保留原设备；成功后替换录音流，VAD 流在下次启动监听时按新设备重新打开
+export async function switchInputDevice(name: string): Promise<InputDeviceSelection> {
+  if (isCapturing) throw new Error('正在录音，请结束录音后再切换麦克风')
+  if (runtimeAudioCaptureConfig.source === 'loopback') throw new Error('当前录音来源为系统声音，无法切换麦克风')
+  const wanted = name.trim()
+  let device: MediaDeviceInfo | undefined
+  if (wanted) {
+    const inputs = (await navigator.mediaDevices.enumerateDevices())
+      .filter(d => d.kind === 'audioinput' && d.deviceId)
+    const lower = wanted.toLowerCase()
+    device = inputs.find(d => d.deviceId === wanted || d.label === wanted)
+      ?? inputs.find(d => d.label.toLowerCase().includes(lower))
+    if (!device) throw new Error(`未找到麦克风「${wanted}」，仍使用原设备`)
+  }
+
+  const next = cloneAudioCaptureConfig(runtimeAudioCaptureConfig)
+  next.inputConstraints.deviceId = device?.deviceId
+  const constraints = buildAudioConstraints(next)
+  let stream: MediaStream
+  try {
+    stream = await navigator.mediaDevices.getUserMedia({ audio: constraints, video: false })
+  } catch (e) {
+    const err = e as DOMException
+    throw new Error(`无法打开麦克风「${device?.label || '系统默认'}」，仍使用原设备: ${err.message || err.name}`)
+  }
+  // 试打开期间开始了录音：放弃切换，不打断正在进行的录音
+  if (isCapturing) {
+    stopStream(stream)
+    throw new Error('正在录音，请结束录音后再切换麦克风')
+  }
+
+  runtimeAudioCaptureConfig.inputConstraints.deviceId = next.inputConstraints.deviceId
+  inputConstraintVersion += 1
+  stopStream(mediaStream)
+  mediaStream = stream
+  mediaStreamConstraintVersion = inputConstraintVersion
+  applySpeechContentHint(stream, 'capture')
+  logTrackDiagnostics(stream, 'capture', constraints)
+  watchTrackEnded(stream, 'capture')
+  emitAudioLifecycle('stream-opened', 'capture', '', stream)
+  emitAudioInfo()
+  return { deviceId: device?.deviceId ?? '', deviceName: stream.getAudioTracks()[0]?.label || device?.label || '' }
 }
 
 // 开始采集 PCM
-export async function startCapture(initialChunks?: Float32Array[]): Promise<void> {
+// 连续分段录音（input.continuousHold）：录音过程中按静音切段，每段编码为 WAV 后交给 onSegment 送识别，
+// stopCapture 只返回最后一次切段之后的剩余部分
+export interface CaptureSegmentation {
+  params: VadFrameParams
+  frameMs: number
+  onSegment: (wav: ArrayBuffer) => void
+}
+
+export async function startCapture(initialChunks?: Float32Array[], segmentation?: CaptureSegmentation): Promise<void> {
   if (captureStopPromise) {
     try { await captureStopPromise } catch { /* ignore */ }
   }
@@ -300,29 +527,110 @@ export async function startCapture(initialChunks?: Float32Array[]): Promise<void
   }
   await audioCtx.resume()
   captureSource = audioCtx.createMediaStreamSource(mediaStream!)
+  // 立体声录音保留两路声道直到编码 WAV；VAD 预录音为单声道，复制到两路后拼接
+  captureChannelCount = runtimeAudioCaptureConfig.channels === 'stereo' ? 2 : 1
   pcmSamples = Array.isArray(initialChunks)
-    ? initialChunks.map((chunk) => new Float32Array(chunk))
+    ? initialChunks.map((chunk) => captureChannelCount === 2 ? monoToInterleavedStereo(chunk) : new Float32Array(chunk))
     : []
+  captureSegmentation = segmentation
+    ? {
+      segmenter: new SilenceSegmenter(PCM_SAMPLE_RATE, captureChannelCount, segmentation.frameMs),
+      params: segmentation.params,
+      onSegment: segmentation.onSegment,
+    }
+    : null
+  captureSegmentCount = 0
   isCapturing = true
+  captureStartedAt = Date.now()
 
-  captureWorkletNode = await createCaptureWorkletNode(audioCtx)
+  captureWorkletNode = await createCaptureWorkletNode(audioCtx, captureChannelCount)
   if (captureWorkletNode) {
+    // 处理器抛错后该节点不再输出音频，本次录音之后的内容都会丢失
+    captureWorkletNode.onprocessorerror = () => {
+      console.error('[录音] AudioWorklet 处理器出错，采集中断')
+      emitAudioLifecycle('capture-error', 'capture', 'AudioWorklet 处理器出错，本次录音后续音频丢失，请重新录音', mediaStream)
+    }
     captureSource.connect(captureWorkletNode)
     console.warn('[录音] 使用 AudioWorklet 采集')
   } else {
     // Fallback: 在不支持 AudioWorklet 的环境退回 ScriptProcessor。
-    scriptProcessor = audioCtx.createScriptProcessor(CAPTURE_BUFFER_SIZE, 1, 1)
+    const channels = captureChannelCount
+    scriptProcessor = audioCtx.createScriptProcessor(CAPTURE_BUFFER_SIZE, channels, 1)
     scriptProcessor.onaudioprocess = (e) => {
       if (!isCapturing) return
-      const data = e.inputBuffer.getChannelData(0)
-      pcmSamples.push(new Float32Array(data))
+      const left = e.inputBuffer.getChannelData(0)
+      if (channels === 1) {
+        pushCapturedSamples(new Float32Array(left))
+        return
+      }
+      const right = e.inputBuffer.numberOfChannels > 1 ? e.inputBuffer.getChannelData(1) : left
+      const interleaved = new Float32Array(left.length * 2)
+      for (let i = 0; i < left.length; i++) {
+        interleaved[i * 2] = left[i]
+        interleaved[i * 2 + 1] = right[i]
+      }
+      pushCapturedSamples(interleaved)
     }
     captureSource.connect(scriptProcessor)
     scriptProcessor.connect(audioCtx.destination)
     console.warn('[录音] 使用 ScriptProcessor 采集（AudioWorklet 不可用）')
   }
 
-  console.warn('[录音] 开始采集，AudioContext state:', audioCtx.state, 'sampleRate:', audioCtx.sampleRate)
+  console.warn(
+    '[录音] 开始采集，AudioContext state:', audioCtx.state,
+    'sampleRate:', audioCtx.sampleRate, 'channels:', captureChannelCount,
+  )
+  emitAudioLifecycle('capture-started', 'capture', captureWorkletNode ? 'AudioWorklet' : 'ScriptProcessor', mediaStream)
+  emitAudioInfo()
+}
+
+// 分段判断使用增益前的采样（分段器内部会复制），之后再原地施加增益并写入缓冲
+function pushCapturedSamples(samples: Float32Array): void {
+  const cut = captureSegmentation?.segmenter.push(samples, captureSegmentation.params) ?? false
+  processInputSamples(samples)
+  pcmSamples.push(samples)
+  if (cut) emitCaptureSegment()
+}
+
+// 切出当前缓冲送识别：切点落在本批采样之后，最多比静音判断晚一个采集块，仍在静音中
+function emitCaptureSegment(): void {
+  if (!captureSegmentation || pcmSamples.length === 0) return
+  const chunks = pcmSamples
+  pcmSamples = []
+  const channels = captureChannelCount
+  if (audioCtx) checkCaptureSampleRate(audioCtx)
+  const padding = silencePaddingSamples(chunks, PCM_SAMPLE_RATE, runtimeAudioCaptureConfig, channels)
+  const wav = encodeWav(chunks, PCM_SAMPLE_RATE, channels, padding.tail, padding.lead)
+  captureSegmentCount += 1
+  const durationMs = Math.round((countSamples(chunks) / channels / PCM_SAMPLE_RATE) * 1000)
+  console.warn(`[录音] 连续分段：第 ${captureSegmentCount} 段，durationMs=${durationMs}，WAV=${wav.byteLength} 字节`)
+  try {
+    captureSegmentation.onSegment(wav)
+  } catch (e) {
+    console.warn(`[录音] 分段回调出错: ${String(e)}`)
+  }
+}
+
+// 当前录音已持续的毫秒数，未在录音时为 0
+export function getRecordingDurationMs(): number {
+  if (!isCapturing || !captureStartedAt) return 0
+  return Math.max(0, Date.now() - captureStartedAt)
+}
+
+// 上一次 stopCapture 实际采集到的音频时长（不含补齐的尾部静音），用于识别误触录音
+export function getLastCapturedMs(): number {
+  return lastCapturedMs
+}
+
+// 上一次录音在连续分段中已切出送识别的段数；大于 0 时剩余部分再短也不算误触
+export function getLastCaptureSegmentCount(): number {
+  return lastCaptureSegmentCount
+}
+
+function endCaptureSegmentation(): void {
+  captureSegmentation = null
+  lastCaptureSegmentCount = captureSegmentCount
+  captureSegmentCount = 0
 }
 
 function countSamples(chunks: Float32Array[]): number {
@@ -331,6 +639,26 @@ function countSamples(chunks: Float32Array[]): number {
   return total
 }
 
+// 丢弃当前录音：停止采集并清空缓冲，不编码也不送识别；未在录音（或正在停止）时返回 false
+export function discardCapture(): boolean {
+  if (!isCapturing || captureStopPromise) return false
+  isCapturing = false
+  captureStartedAt = 0
+  if (captureWorkletNode) captureWorkletNode.port.onmessage = null
+  try { captureSource?.disconnect() } catch { /* ignore */ }
+  try { captureWorkletNode?.disconnect() } catch { /* ignore */ }
+  try { scriptProcessor?.disconnect() } catch { /* ignore */ }
+  scriptProcessor = null
+  captureSource = null
+  captureWorkletNode = null
+  endCaptureSegmentation()
+  const droppedMs = Math.round((countSamples(pcmSamples) / captureChannelCount / PCM_SAMPLE_RATE) * 1000)
+  pcmSamples = []
+  console.warn(`[录音] 已丢弃当前录音，durationMs=${droppedMs}`)
+  emitAudioInfo()
+  return true
+}
+
 // 停止采集，返回 WAV ArrayBuffer
 export async function stopCapture(): Promise<ArrayBuffer> {
   if (captureStopPromise) return captureStopPromise
@@ -342,21 +670,26 @@ export async function stopCapture(): Promise<ArrayBuffer> {
     const ctx = audioCtx
     const workletNode = captureWorkletNode
     const captureCfg = cloneAudioCaptureConfig(runtimeAudioCaptureConfig)
+    const channels = captureChannelCount
 
+    captureStartedAt = 0
     if (!ctx) {
       isCapturing = false
       scriptProcessor = null
       captureSource = null
       captureWorkletNode = null
       audioCtx = null
+      endCaptureSegmentation()
       const chunks = pcmSamples
       pcmSamples = []
-      const chunksWithTail = appendTailSilence(chunks, PCM_SAMPLE_RATE, captureCfg.tailSilenceMs)
-      const wav = encodeWav(chunksWithTail)
-      const durationMs = Math.round((countSamples(chunksWithTail) / PCM_SAMPLE_RATE) * 1000)
+      const capturedSamples = countSamples(chunks)
+      lastCapturedMs = Math.round((capturedSamples / channels / PCM_SAMPLE_RATE) * 1000)
+      const padding = silencePaddingSamples(chunks, PCM_SAMPLE_RATE, captureCfg, channels)
+      const wav = encodeWav(chunks, PCM_SAMPLE_RATE, channels, padding.tail, padding.lead)
+      const durationMs = Math.round(((capturedSamples + padding.lead + padding.tail) / channels / PCM_SAMPLE_RATE) * 1000)
       console.warn(
         `[录音] 停止采集(空上下文)，chunks=${chunks.length}，durationMs=${durationMs}，` +
-        `tailSilenceMs=${captureCfg.tailSilenceMs}，WAV=${wav.byteLength} 字节`,
+        `tailSilenceMs=${captureCfg.tailSilenceMs}，padMs=${captureCfg.padMs}，WAV=${wav.byteLength} 字节`,
       )
       return wav
     }
@@ -381,18 +714,23 @@ export async function stopCapture(): Promise<ArrayBuffer> {
     captureSource = null
     captureWorkletNode = null
     // AudioContext 保留复用，不关闭（避免 Windows 上重建延迟）
+    endCaptureSegmentation()
 
     const chunks = pcmSamples
     pcmSamples = []
-    const chunksWithTail = appendTailSilence(chunks, PCM_SAMPLE_RATE, captureCfg.tailSilenceMs)
-    const wav = encodeWav(chunksWithTail)
-    const durationMs = Math.round((countSamples(chunksWithTail) / PCM_SAMPLE_RATE) * 1000)
+    const capturedSamples = countSamples(chunks)
+    lastCapturedMs = Math.round((capturedSamples / channels / PCM_SAMPLE_RATE) * 1000)
+    checkCaptureSampleRate(ctx)
+    const padding = silencePaddingSamples(chunks, PCM_SAMPLE_RATE, captureCfg, channels)
+    const wav = encodeWav(chunks, PCM_SAMPLE_RATE, channels, padding.tail, padding.lead)
+    const durationMs = Math.round(((capturedSamples + padding.lead + padding.tail) / channels / PCM_SAMPLE_RATE) * 1000)
     const stopElapsedMs = Date.now() - stopStartAt
     console.warn(
       `[录音] 停止采集，chunks=${chunks.length}，durationMs=${durationMs}，` +
-      `postRollMs=${captureCfg.postRollMs}，tailSilenceMs=${captureCfg.tailSilenceMs}，` +
+      `postRollMs=${captureCfg.postRollMs}，tailSilenceMs=${captureCfg.tailSilenceMs}，padMs=${captureCfg.padMs}，` +
       `flushWaitMs=${flushWaitMs}，stopElapsedMs=${stopElapsedMs}，WAV=${wav.byteLength} 字节`,
     )
+    emitAudioInfo()
     return wav
   })().finally(() => {
     captureStopPromise = null
@@ -401,14 +739,25 @@ export async function stopCapture(): Promise<ArrayBuffer> {
   return captureStopPromise
 }
 
-function appendTailSilence(chunks: Float32Array[], sampleRate: number, tailSilenceMs: number): Float32Array[] {
-  if (chunks.length === 0) return chunks
-  const tailSamples = Math.max(0, Math.round((sampleRate * tailSilenceMs) / 1000))
-  if (tailSamples <= 0) return chunks
-  return [...chunks, new Float32Array(tailSamples)]
+// 首尾补齐的静音时长：句首为 padMs，句尾取 tailSilenceMs 与 padMs 的较大值（两者都是“保证句尾至少有这么长静音”，不叠加）
+function silencePaddingMs(cfg: Pick<CaptureConfig, 'tailSilenceMs' | 'padMs'>): { lead: number; tail: number } {
+  return { lead: cfg.padMs, tail: Math.max(cfg.tailSilenceMs, cfg.padMs) }
+}
+
+// 首尾补齐的静音采样数（交错后），由 encodeWav 直接预留在输出中；空录音不补
+function silencePaddingSamples(
+  chunks: Float32Array[],
+  sampleRate: number,
+  cfg: Pick<CaptureConfig, 'tailSilenceMs' | 'padMs'>,
+  channels = 1,
+): { lead: number; tail: number } {
+  if (chunks.length === 0) return { lead: 0, tail: 0 }
+  const ms = silencePaddingMs(cfg)
+  const toSamples = (durationMs: number) => Math.max(0, Math.round((sampleRate * durationMs) / 1000)) * channels
+  return { lead: toSamples(ms.lead), tail: toSamples(ms.tail) }
 }
 
-async function createCaptureWorkletNode(ctx: AudioContext): Promise<AudioWorkletNode | null> {
+async function createCaptureWorkletNode(ctx: AudioContext, channels: number): Promise<AudioWorkletNode | null> {
   if (!ctx.audioWorklet || typeof ctx.audioWorklet.addModule !== 'function') {
     return null
   }
@@ -421,9 +770,11 @@ async function createCaptureWorkletNode(ctx: AudioContext): Promise<AudioWorklet
     const node = new AudioWorkletNode(ctx, CAPTURE_WORKLET_NAME, {
       numberOfInputs: 1,
       numberOfOutputs: 0,
-      channelCount: 1,
+      channelCount: channels,
+      ...(channels === 2 ? { channelCountMode: 'explicit' as const } : {}),
       processorOptions: {
         chunkSize: CAPTURE_BUFFER_SIZE,
+        channels,
       },
     })
     node.port.onmessage = (event: MessageEvent<unknown>) => {
@@ -434,10 +785,10 @@ async function createCaptureWorkletNode(ctx: AudioContext): Promise<AudioWorklet
       }
       if (!isCapturing) return
       if (payload instanceof Float32Array) {
-        if (pcmSamples.length === 0) {
+        if (pcmSamples.length === 0 && captureSegmentCount === 0) {
           console.warn(`[录音] worklet 首次收到音频数据，长度=${payload.length}`)
         }
-        pcmSamples.push(payload)
+        pushCapturedSamples(payload)
       } else {
         console.warn(`[录音] worklet 收到非 Float32Array 数据: type=${typeof payload}, constructor=${payload?.constructor?.name}`)
       }
@@ -483,15 +834,22 @@ export interface VadState {
   threshold: number
   silenceMs: number
   minSpeechMs: number
+  frameMs: number
+  minUtteranceMs: number
+  maxUtteranceMs: number
+  autoDisableAfterIdleMs: number
+  sentenceHoldMs: number
+  energySmoothing: number
 }
 
 export interface VadCallbacks {
   getState: () => string
   setState: (state: string, text?: string) => void
   showError: (msg: string) => void
-  showResult: (text: string) => void
+  showResult: (result: RecognitionResult) => void
   captureFocusSnapshot: (reason: string) => Promise<string | null>
-  recognizeWav: (wav: ArrayBuffer, prevAppId: string | null) => Promise<string>
+  recognizeWav: (wav: ArrayBuffer, prevAppId: string | null, triggeredAt: number) => Promise<RecognitionResult | null>
+  onIdleTimeout: (idleMs: number) => void
 }
 
 let vadAudioCtx: AudioContext | null = null
@@ -499,35 +857,78 @@ let vadAnalyser: AnalyserNode | null = null
 let vadSource: MediaStreamAudioSourceNode | null = null
 let vadStream: MediaStream | null = null
 let vadTimer: ReturnType<typeof setInterval> | null = null
-let vadSpeakingStart = 0
-let vadSilenceStart = 0
-let vadIsSpeaking = false
+// VAD 时间以已处理的采样数计算，与墙钟无关
+const vadClock = new VadSampleClock(PCM_SAMPLE_RATE)
+const vadDetector = new VoiceActivityDetector(() => vadClock.nowMs)
 let vadIsProcessing = false
 let vadCapturePromise: Promise<void> | null = null
 let vadPrevAppId: string | null = null
-let vadSmoothedRms = 0
-let vadNoiseFloorRms = 0
-let vadAboveThresholdSince = 0
-let vadBelowThresholdSince = 0
 let vadPreRollChunks: Float32Array[] = []
 
+interface VadQueuedSegment {
+  wav: ArrayBuffer
+  prevAppId: string | null
+  // 切段时刻，识别阶段耗时从此起算
+  triggeredAt: number
+}
+
+// 识别期间继续监听：已切好的语音段按顺序排队识别，避免连续说话时丢掉后一句
+let vadSegmentQueue: VadQueuedSegment[] = []
+let vadQueueDraining = false
+
 export async function startVad(vadState: VadState, cb: VadCallbacks): Promise<void> {
   if (!vadState.enabled || vadIsProcessing || vadTimer) return
 
   await initVadMic()
   vadAudioCtx = new AudioContext({ sampleRate: PCM_SAMPLE_RATE })
   await vadAudioCtx.resume()
+  // AnalyserNode 的时域数据是各声道的混音，立体声录音时 VAD 仍按单声道能量判断
   vadAnalyser = vadAudioCtx.createAnalyser()
   vadAnalyser.fftSize = 1024
   vadSource = vadAudioCtx.createMediaStreamSource(vadStream!)
   vadSource.connect(vadAnalyser)
 
   const dataArray = new Float32Array(vadAnalyser.fftSize)
+  // 分析器缓冲区是最近 fftSize 个采样的滑动窗口，每次只取上次读取后新增的部分，
+  // 按固定帧长切分后逐帧判断，RMS 与阈值不再受轮询间隔和设备缓冲大小影响
+  const vadSampleRate = vadAudioCtx.sampleRate
+  vadClock.reset(vadSampleRate)
+  vadDetector.reset()
+  const frameAccumulator = new VadFrameAccumulator(frameSamplesForMs(vadState.frameMs, vadSampleRate))
+  let lastReadSample = Math.round(vadAudioCtx.currentTime * vadSampleRate)
   const maxPreRollChunks = Math.max(1, Math.ceil(VAD_PRE_ROLL_MS / VAD_SAMPLE_INTERVAL_MS))
-  const finalizeSpeechSegment = (speechEndAt: number) => {
-    vadIsSpeaking = false
-    vadBelowThresholdSince = 0
-    const speechDuration = Math.max(0, speechEndAt - vadSpeakingStart)
+  const idleTimer = new VadIdleTimer()
+  let idleTimeoutFired = false
+  const restIdleState = () => {
+    if (vadDetector.isSpeaking) return
+    cb.setState(vadQueueDraining || vadSegmentQueue.length > 0 ? 'recognizing' : 'idle')
+  }
+
+  const drainSegmentQueue = async () => {
+    if (vadQueueDraining) return
+    vadQueueDraining = true
+    try {
+      while (vadSegmentQueue.length > 0) {
+        const segment = vadSegmentQueue.shift()!
+        if (!vadDetector.isSpeaking) cb.setState('recognizing')
+        try {
+          const result = await cb.recognizeWav(segment.wav, segment.prevAppId, segment.triggeredAt)
+          if (result) cb.showResult(result)
+        } catch (e) {
+          cb.showError(String(e))
+        }
+        // showResult/showError 会把状态复位，若下一句已经开始录音需要恢复
+        if (vadDetector.isSpeaking) cb.setState('recording')
+      }
+    } finally {
+      vadQueueDraining = false
+      restIdleState()
+    }
+  }
+
+  // discard 为 true 时只停止录音、不送识别（过短的瞬态噪声）
+  const finalizeSpeechSegment = (speechDuration: number, discard = false) => {
+    const triggeredAt = Date.now()
     const captureReady = vadCapturePromise
     const prevAppId = vadPrevAppId
     vadCapturePromise = null
@@ -538,136 +939,133 @@ export async function startVad(vadState: VadState, cb: VadCallbacks): Promise<vo
       .catch(() => null)
       .then(async () => {
         const wav = await stopCapture()
+        if (discard) {
+          console.debug(`[VAD] 语音段短于 ${vadState.minUtteranceMs}ms，丢弃 speechMs=${speechDuration}`)
+          restIdleState()
+          return
+        }
         const wavPcmBytes = Math.max(0, wav.byteLength - 44)
-        const wavDurationMs = Math.round((wavPcmBytes / 2 / PCM_SAMPLE_RATE) * 1000)
-        const effectiveSpeechMs = Math.max(speechDuration, wavDurationMs - runtimeAudioCaptureConfig.tailSilenceMs)
-        const minSpeechGateMs = Math.max(VAD_HARD_MIN_WAV_MS, Math.min(vadState.minSpeechMs, 260))
-        if (effectiveSpeechMs < minSpeechGateMs || wavDurationMs < VAD_HARD_MIN_WAV_MS) {
-          cb.setState('idle')
+        const wavChannels = wav.byteLength >= 44 ? Math.max(1, new DataView(wav).getUint16(22, true)) : 1
+        const wavDurationMs = Math.round((wavPcmBytes / 2 / wavChannels / PCM_SAMPLE_RATE) * 1000)
+        const padding = silencePaddingMs(runtimeAudioCaptureConfig)
+        const longEnough = isSpeechSegmentLongEnough({
+          speechDurationMs: speechDuration,
+          wavDurationMs,
+          leadSilenceMs: padding.lead,
+          tailSilenceMs: padding.tail,
+          minSpeechMs: vadState.minSpeechMs,
+        })
+        if (!longEnough) {
+          restIdleState()
+          return
+        }
+        if (vadSegmentQueue.length >= VAD_MAX_QUEUED_SEGMENTS) {
+          console.warn(
+            `[VAD] 待识别语音段已达上限(${VAD_MAX_QUEUED_SEGMENTS})，丢弃本段 durationMs=${wavDurationMs}`,
+          )
+          restIdleState()
           return
         }
-        cb.setState('recognizing')
-        return cb.recognizeWav(wav, prevAppId)
-          .then(result => {
-            cb.setState('idle')
-            if (result) cb.showResult(result)
-          })
-          .catch(e => {
-            cb.setState('idle')
-            cb.showError(String(e))
-          })
+        vadSegmentQueue.push({ wav, prevAppId, triggeredAt })
+        if (vadSegmentQueue.length > 1 || vadQueueDraining) {
+          console.debug(`[VAD] 语音段已排队，等待前序识别完成 queued=${vadSegmentQueue.length}`)
+        }
+        void drainSegmentQueue()
+      })
+      .catch((e) => {
+        restIdleState()
+        cb.showError(String(e))
       })
       .finally(() => {
         vadIsProcessing = false
       })
   }
 
+  const handleVadEvent = (event: VadFrameEvent) => {
+    if (event.type === 'speech-start') {
+      void cb.captureFocusSnapshot('vad-speech-start')
+        .then((appId) => { if (vadDetector.isSpeaking) vadPrevAppId = appId })
+        .catch(() => { if (vadDetector.isSpeaking) vadPrevAppId = null })
+
+      const preRollChunks = vadPreRollChunks.map((chunk) => new Float32Array(chunk))
+      vadCapturePromise = startCapture(preRollChunks).catch((e) => {
+        vadDetector.abortSpeech()
+        vadCapturePromise = null
+        vadPrevAppId = null
+        cb.setState('idle')
+        cb.showError(String(e))
+        throw e
+      })
+      cb.setState('recording')
+    } else if (event.type === 'speech-end') {
+      if (event.reason === 'max-duration') {
+        console.warn(`[VAD] 语音段超过 ${vadState.maxUtteranceMs}ms，强制切段送识别`)
+      }
+      finalizeSpeechSegment(Math.max(0, event.speechEndAt - event.speechStartAt), event.reason === 'too-short')
+    }
+  }
+
   vadTimer = setInterval(() => {
     if (!vadAnalyser || vadIsProcessing) return
-    if (!vadIsSpeaking && cb.getState() !== 'idle') return
+    // 排队识别中（recognizing）仍继续监听下一句；其他非空闲状态（如手动录音）不介入
+    const listenState = cb.getState()
+    // 说话、排队识别或手动录音等非空闲状态都算作活动，只有纯静默监听才累计空闲时间
+    if (vadDetector.isSpeaking || listenState !== 'idle' || vadQueueDraining || vadSegmentQueue.length > 0) {
+      idleTimer.touch()
+    } else if (!idleTimeoutFired && idleTimer.isExpired(vadState.autoDisableAfterIdleMs)) {
+      idleTimeoutFired = true
+      console.info(`[VAD] ${idleTimer.idleMs()}ms 未检测到语音，请求自动关闭 VAD`)
+      cb.onIdleTimeout(idleTimer.idleMs())
+      return
+    }
+    const canListen = listenState === 'idle' || (listenState === 'recognizing' && vadQueueDraining)
+    if (!vadDetector.isSpeaking && !canListen) return
 
     vadAnalyser.getFloatTimeDomainData(dataArray)
-    let sum = 0
-    for (const v of dataArray) sum += v * v
-    const rms = Math.sqrt(sum / dataArray.length)
-    if (!vadIsSpeaking) {
+    const currentSample = Math.round((vadAudioCtx?.currentTime ?? 0) * vadSampleRate)
+    const elapsedSamples = Math.max(0, currentSample - lastReadSample)
+    const freshSamples = Math.min(dataArray.length, elapsedSamples)
+    lastReadSample = currentSample
+    // 分析器每次返回整个窗口，增益作用于整个窗口（预录音同样需要），电平只统计新增部分；
+    // 录音进行中电平由录音回调统计，避免重复。VAD 使用增益前的新增采样，阈值不随增益漂移
+    const rawFresh = applyInputGainKeepingTail(dataArray, runtimeAudioCaptureConfig.inputGain, freshSamples)
+    // 轮询被拖慢时超出分析器窗口的采样已无法读取，但仍计入音频时间，避免超时判断变慢
+    vadClock.advance(elapsedSamples - freshSamples)
+    if (!vadDetector.isSpeaking) {
       vadPreRollChunks.push(new Float32Array(dataArray))
       if (vadPreRollChunks.length > maxPreRollChunks) {
         vadPreRollChunks.shift()
       }
     }
-    vadSmoothedRms = vadSmoothedRms === 0
-      ? rms
-      : vadSmoothedRms + VAD_RMS_EMA_ALPHA * (rms - vadSmoothedRms)
-    if (!vadIsSpeaking) {
-      vadNoiseFloorRms = vadNoiseFloorRms === 0
-        ? vadSmoothedRms
-        : vadNoiseFloorRms + VAD_NOISE_FLOOR_EMA_ALPHA * (vadSmoothedRms - vadNoiseFloorRms)
+    if (freshSamples === 0) return
+    if (!isCapturing && inputLevelListener) {
+      const level = inputLevelMeter.push(dataArray.subarray(dataArray.length - freshSamples), runtimeAudioCaptureConfig.inputGain)
+      if (level) inputLevelListener(level)
     }
 
-    const now = Date.now()
-    const configuredThreshold = Math.max(0.0001, vadState.threshold)
-    const adaptiveStartThreshold = Math.max(0.0001, vadNoiseFloorRms * VAD_NOISE_FLOOR_START_RATIO)
-    const startThreshold = Math.max(configuredThreshold, adaptiveStartThreshold)
-    const stopThreshold = Math.max(
-      0.00005,
-      startThreshold * VAD_STOP_HYSTERESIS_RATIO,
-      vadNoiseFloorRms * VAD_NOISE_FLOOR_STOP_RATIO,
-    )
-
-    if (!vadIsSpeaking) {
-      if (vadSmoothedRms > startThreshold) {
-        if (!vadAboveThresholdSince) vadAboveThresholdSince = now
-        if (now - vadAboveThresholdSince >= VAD_START_TRIGGER_MS) {
-          vadIsSpeaking = true
-          vadSpeakingStart = now
-          vadSilenceStart = now
-          vadAboveThresholdSince = 0
-          vadBelowThresholdSince = 0
-
-          void cb.captureFocusSnapshot('vad-speech-start')
-            .then((appId) => { if (vadIsSpeaking) vadPrevAppId = appId })
-            .catch(() => { if (vadIsSpeaking) vadPrevAppId = null })
-
-          const preRollChunks = vadPreRollChunks.map((chunk) => new Float32Array(chunk))
-          vadCapturePromise = startCapture(preRollChunks).catch((e) => {
-            vadIsSpeaking = false
-            vadCapturePromise = null
-            vadPrevAppId = null
-            cb.setState('idle')
-            cb.showError(String(e))
-            throw e
-          })
-          cb.setState('recording')
-        }
-      } else {
-        vadAboveThresholdSince = 0
+    const frames = frameAccumulator.push(rawFresh)
+    for (let i = 0; i < frames.length; i++) {
+      vadClock.advance(frames[i].length)
+      const event = vadDetector.process(computeRms(frames[i]), vadState)
+      handleVadEvent(event)
+      if (event.type === 'speech-end') {
+        // 切段后进入识别流程，本次剩余帧不再参与判断，但仍计入音频时间
+        vadClock.advance((frames.length - i - 1) * frameAccumulator.frameSamples + frameAccumulator.pendingSamples)
+        frameAccumulator.reset()
+        break
       }
-      return
-    }
-
-    if (now - vadSpeakingStart >= VAD_MAX_SPEECH_MS) {
-      vadSilenceStart = now
-      finalizeSpeechSegment(now)
-      return
-    }
-
-    if (vadSmoothedRms > stopThreshold) {
-      vadSilenceStart = now
-      vadBelowThresholdSince = 0
-      return
-    }
-
-    if (!vadBelowThresholdSince) {
-      vadBelowThresholdSince = now
-      return
     }
-    if (now - vadBelowThresholdSince < VAD_RELEASE_TRIGGER_MS) {
-      return
-    }
-
-    const effectiveSilenceMs = vadState.silenceMs + VAD_ENDPOINT_HANGOVER_MS
-    if (now - vadSilenceStart <= effectiveSilenceMs) {
-      return
-    }
-
-    finalizeSpeechSegment(vadSilenceStart)
   }, VAD_SAMPLE_INTERVAL_MS)
+  emitAudioInfo()
 }
 
 // 重置 VAD 语音状态（手动点击停止录音时调用，避免 VAD 状态机卡死）
 export function resetVadSpeakingState(): void {
-  if (!vadIsSpeaking && !vadIsProcessing) return
-  vadIsSpeaking = false
+  if (!vadDetector.isSpeaking && !vadIsProcessing) return
+  vadDetector.reset()
   vadIsProcessing = false
   vadCapturePromise = null
   vadPrevAppId = null
-  vadSpeakingStart = 0
-  vadSilenceStart = 0
-  vadSmoothedRms = 0
-  vadNoiseFloorRms = 0
-  vadAboveThresholdSince = 0
-  vadBelowThresholdSince = 0
   vadPreRollChunks = []
 }
 
@@ -678,18 +1076,17 @@ export function stopVad(): void {
   void vadAudioCtx?.close()
   vadAudioCtx = null
   vadAnalyser = null
-  if (vadIsSpeaking) {
+  if (vadDetector.isSpeaking) {
     void stopCapture().catch(() => { })
   }
   vadCapturePromise = null
   vadPrevAppId = null
-  vadSpeakingStart = 0
-  vadSilenceStart = 0
-  vadIsSpeaking = false
+  vadDetector.reset()
   vadIsProcessing = false
-  vadSmoothedRms = 0
-  vadNoiseFloorRms = 0
-  vadAboveThresholdSince = 0
-  vadBelowThresholdSince = 0
   vadPreRollChunks = []
+  if (vadSegmentQueue.length > 0) {
+    console.warn(`[VAD] 关闭 VAD，丢弃 ${vadSegmentQueue.length} 个待识别语音段`)
+  }
+  vadSegmentQueue = []
+  emitAudioInfo()
 }
@@ -1,13 +1,26 @@
 import type {
   HotwordScene,
   AppConfig,
+  InputLevel,
+  ServerStatus,
+  InputPermissionState,
+  LastError,
+  SelfTestCapture,
+  SelfTestReport,
+  AudioInfo,
+  SampleRateWarning,
+  ReattemptSuggestion,
+  HotkeyBinding,
   LlmModelConfig,
   LlmTaskPromptConfig,
   TextRuleConfig,
   TextRulesConfig,
+  PostprocessStage,
+  VoiceCommandProfileState,
 } from './types'
 import { renderModelList, setModelListHint } from './dashboard-models'
-import { withTimeout } from './utils'
+import { formatAudioInfo, withTimeout } from './utils'
+import { startCapture, stopCapture, setInputGain } from './audio'
 
 // ── Tab 切换 ──
 
@@ -43,6 +56,11 @@ function normalizeHotkey(raw: string): string {
   return main ? [...modifiers, main].join('+') : modifiers.join('+')
 }
 
+// 指令快捷键可为分号分隔的多步序列，逐步规范化
+function normalizeShortcutSequence(raw: string): string {
+  return raw.split(';').map(step => normalizeHotkey(step)).filter(Boolean).join('; ')
+}
+
 function isForbiddenRecordHotkey(hotkey: string): boolean {
   const isWin = /^win/i.test(navigator.platform || '')
   if (!isWin) return false
@@ -86,7 +104,7 @@ function hotkeyFromKeyboardEvent(event: KeyboardEvent): string | null {
   if (!main) return null
   if (main.length === 1 && /^[A-Z0-9]$/.test(main)) {
     // keep single char key as-is
-  } else if (!/^(F([1-9]|1[0-2])|SPACE|ENTER|TAB|ESC|BACKSPACE|DELETE|UP|DOWN|LEFT|RIGHT)$/.test(main)) {
+  } else if (!/^(F([1-9]|1[0-2])|SPACE|ENTER|TAB|ESC|BACKSPACE|DELETE|UP|DOWN|LEFT|RIGHT|HOME|END|PAGEUP|PAGEDOWN)$/.test(main)) {
     return null
   }
 
@@ -94,7 +112,30 @@ function hotkeyFromKeyboardEvent(event: KeyboardEvent): string | null {
   return [...orderedMods, main].join('+')
 }
 
-function attachHotkeyRecorder(input: HTMLInputElement): void {
+// 全局热键录制后立即检查是否被其它应用占用，在输入框下方提示（不阻止保存）
+async function showHotkeyAvailability(input: HTMLInputElement, hotkey: string): Promise<void> {
+  let tip = input.parentElement?.querySelector<HTMLSpanElement>('.hotkey-availability-tip') ?? null
+  if (!tip) {
+    tip = document.createElement('span')
+    tip.className = 'tip hotkey-availability-tip'
+    input.insertAdjacentElement('afterend', tip)
+  }
+  tip.textContent = ''
+  if (!hotkey) return
+  try {
+    const result = await window.electronAPI.checkHotkeyAvailable(hotkey)
+    if (input.value !== hotkey) return
+    if (result.available) return
+    tip.textContent = result.reason === 'invalid'
+      ? `无法识别该热键：${result.detail ?? hotkey}`
+      : `${hotkey} 已被其它应用占用，请换一个组合`
+    tip.style.color = '#f87171'
+  } catch {
+    // 检查失败不影响录制
+  }
+}
+
+function attachHotkeyRecorder(input: HTMLInputElement, options: { checkAvailability?: boolean } = {}): void {
   if (input.dataset.hotkeyRecorderBound === '1') return
   input.dataset.hotkeyRecorderBound = '1'
   input.readOnly = true
@@ -114,14 +155,19 @@ function attachHotkeyRecorder(input: HTMLInputElement): void {
     if (clearByDelete) {
       input.value = ''
       leaveCaptureState()
+      if (options.checkAvailability) void showHotkeyAvailability(input, '')
       return
     }
 
     const hotkey = hotkeyFromKeyboardEvent(event)
     if (!hotkey) return
-    input.value = hotkey
+    // 「追加一步」模式下把新录制的组合键接到已有序列后面
+    const appendStep = input.dataset.appendStep === '1' && Boolean(input.value.trim())
+    delete input.dataset.appendStep
+    input.value = appendStep ? `${input.value.trim()}; ${hotkey}` : hotkey
     leaveCaptureState()
     input.blur()
+    if (options.checkAvailability) void showHotkeyAvailability(input, input.value)
   })
 }
 
@@ -151,7 +197,13 @@ function stripVoiceCommandHotwords(
 
 export function initHotkeyRecorders(): void {
   const recordHotkeyInput = document.getElementById('cfg-hotkey') as HTMLInputElement | null
-  if (recordHotkeyInput) attachHotkeyRecorder(recordHotkeyInput)
+  if (recordHotkeyInput) attachHotkeyRecorder(recordHotkeyInput, { checkAvailability: true })
+  const repeatHotkeyInput = document.getElementById('cfg-repeat-hotkey') as HTMLInputElement | null
+  if (repeatHotkeyInput) attachHotkeyRecorder(repeatHotkeyInput, { checkAvailability: true })
+  const pasteShortcutInput = document.getElementById('cfg-paste-shortcut') as HTMLInputElement | null
+  if (pasteShortcutInput) attachHotkeyRecorder(pasteShortcutInput)
+  const appendNavigationKeyInput = document.getElementById('cfg-append-navigation-key') as HTMLInputElement | null
+  if (appendNavigationKeyInput) attachHotkeyRecorder(appendNavigationKeyInput)
 }
 
 function collectLlmModelsFromForm(): LlmModelConfig[] {
@@ -538,10 +590,39 @@ export async function loadConfigToForm() {
   try {
     const cfg = await window.electronAPI.getConfig()
     ;urlInput.value = cfg.server?.url || ''
+    ;(document.getElementById('cfg-server-ws') as HTMLInputElement).checked = cfg.server?.transport === 'ws'
+    ;(document.getElementById('cfg-server-flac') as HTMLInputElement).checked = cfg.server?.audioEncoding === 'flac'
+    ;(document.getElementById('cfg-server-language') as HTMLSelectElement).value = cfg.server?.language ?? 'zh'
     ;(document.getElementById('cfg-hotkey') as HTMLInputElement).value = normalizeHotkey(cfg.hotkey?.record || '')
-    ;(document.getElementById('cfg-clipboard') as HTMLInputElement).checked = cfg.input?.useClipboard || false
-    ;(document.getElementById('cfg-log-debug-enabled') as HTMLInputElement).checked = cfg.logging?.enableDebug || false
+    ;(document.getElementById('cfg-repeat-hotkey') as HTMLInputElement).value = normalizeHotkey(cfg.hotkey?.repeatCommand || '')
+    ;(document.getElementById('cfg-undo-hotkey') as HTMLInputElement).value = normalizeHotkey(cfg.hotkey?.undoLastInsert || '')
+    ;(document.getElementById('cfg-cycle-profile-hotkey') as HTMLInputElement).value = normalizeHotkey(cfg.hotkey?.cycleProfile || '')
+    ;(document.getElementById('cfg-record-mode') as HTMLSelectElement).value = cfg.hotkey?.recordMode ?? 'hold'
+    ;(document.getElementById('cfg-clipboard') as HTMLInputElement).checked = cfg.input?.useClipboard !== false
+    ;(document.getElementById('cfg-keyboard-method') as HTMLSelectElement).value = cfg.input?.keyboardMethod ?? 'text'
+    ;(document.getElementById('cfg-clipboard-on-failure') as HTMLInputElement).checked = cfg.input?.clipboardOnFailure !== false
+    ;(document.getElementById('cfg-auto-reattempt') as HTMLInputElement).checked = Boolean(cfg.input?.autoReattempt)
+    ;(document.getElementById('cfg-continuous-hold') as HTMLInputElement).checked = Boolean(cfg.input?.continuousHold)
+    ;(document.getElementById('cfg-special-chars') as HTMLTextAreaElement).value = Object.entries(cfg.input?.specialChars ?? {})
+      .map(([ch, strategy]) => `${ch}=${strategy}`).join('\n')
+    ;(document.getElementById('cfg-map-ctrl-to-cmd') as HTMLInputElement).checked = cfg.input?.mapCtrlToCmdOnMac || false
+    ;(document.getElementById('cfg-safe-mode') as HTMLInputElement).checked = cfg.input?.safeMode || false
+    ;(document.getElementById('cfg-paste-shortcut') as HTMLInputElement).value = normalizeHotkey(cfg.input?.pasteShortcut || 'CTRL+V')
+    ;(document.getElementById('cfg-paste-mode') as HTMLSelectElement).value = cfg.input?.pasteMode ?? 'replace'
+    ;(document.getElementById('cfg-append-navigation-key') as HTMLInputElement).value = normalizeHotkey(cfg.input?.appendNavigationKey || 'END')
+    ;(document.getElementById('cfg-target-window-title') as HTMLInputElement).value = cfg.input?.targetWindowTitle || ''
+    ;(document.getElementById('cfg-sound-cues') as HTMLInputElement).checked = cfg.audioCapture?.soundCues || false
+    ;(document.getElementById('cfg-retain-last-recording') as HTMLInputElement).checked = cfg.audioCapture?.retainLastRecording === true
+    ;(document.getElementById('cfg-transcript-enabled') as HTMLInputElement).checked = cfg.transcript?.enabled === true
+    ;(document.getElementById('cfg-transcript-idle-gap') as HTMLInputElement).value = String(Math.round((cfg.transcript?.idleGapMs ?? 120000) / 1000))
+    ;(document.getElementById('cfg-audio-source') as HTMLSelectElement).value = cfg.audioCapture?.source ?? 'input'
+    // 主界面录制口令样本时同样使用软件增益
+    applyInputGainToForm(setInputGain(cfg.audioCapture?.inputGain ?? 1))
+    ;(document.getElementById('cfg-tray-show-trigger') as HTMLSelectElement).value = cfg.tray?.showTrigger ?? 'left-click'
+    ;(document.getElementById('cfg-log-level') as HTMLSelectElement).value = cfg.logging?.level ?? 'info'
+    ;(document.getElementById('cfg-log-to-file') as HTMLInputElement).checked = cfg.logging?.toFile !== false
     ;(document.getElementById('cfg-float-bounds-debug') as HTMLInputElement).checked = cfg.logging?.showFloatBounds || false
+    ;(document.getElementById('cfg-log-asr-responses') as HTMLInputElement).checked = cfg.logging?.logAsrResponses || false
     ;(document.getElementById('cfg-vad') as HTMLInputElement).checked = cfg.vad?.enabled || false
     ;(document.getElementById('dashboard-vad-toggle') as HTMLInputElement).checked = cfg.vad?.enabled || false
     const threshold = cfg.vad?.speechThreshold ?? 0.06
@@ -549,6 +630,8 @@ export async function loadConfigToForm() {
     const thresholdDisplay = document.getElementById('vad-threshold-display')
     if (thresholdSlider) thresholdSlider.value = String(threshold)
     if (thresholdDisplay) thresholdDisplay.textContent = threshold.toFixed(2)
+    ;(document.getElementById('cfg-vad-activation-phrase') as HTMLInputElement).value = cfg.vad?.activationPhrase || ''
+    ;(document.getElementById('cfg-vad-backend') as HTMLSelectElement).value = cfg.vad?.backend ?? 'energy'
     ;(document.getElementById('cfg-llm-enabled') as HTMLInputElement).checked = cfg.llm?.enabled || false
     ;(document.getElementById('cfg-llm-asr-optimize') as HTMLInputElement).checked =
       typeof cfg.llm?.asrPostProcessEnabled === 'boolean'
@@ -557,20 +640,207 @@ export async function loadConfigToForm() {
     renderLlmModelRows(cfg.llm?.models || [], cfg.llm?.taskBindings)
     renderTaskPrompts(cfg.llm.prompts)
     renderTextRulesEditor(cfg.textRules)
+    ;(document.getElementById('cfg-postprocess-dedupe') as HTMLInputElement).checked = cfg.postprocess?.dedupeRepeats === true
+    ;(document.getElementById('cfg-postprocess-punctuation') as HTMLSelectElement).value = cfg.postprocess?.punctuation ?? 'off'
+    ;(document.getElementById('cfg-postprocess-stopwords') as HTMLTextAreaElement).value = (cfg.postprocess?.stopwords || []).join('\n')
+    ;(document.getElementById('cfg-postprocess-trim-trailing') as HTMLInputElement).checked = cfg.postprocess?.trimTrailingWhitespace === true
+    ;(document.getElementById('cfg-postprocess-collapse-spaces') as HTMLInputElement).checked = cfg.postprocess?.collapseSpaces === true
+    ;(document.getElementById('cfg-postprocess-strip-newlines') as HTMLInputElement).checked = cfg.postprocess?.stripNewlines === true
     const asrMode = cfg.asr?.mode ?? 'api'
     ;(document.getElementById('cfg-local-punc-enabled') as HTMLInputElement).checked = cfg.asr?.puncEnabled !== false
     ;(document.getElementById('asr-mode-api') as HTMLInputElement).checked = asrMode === 'api'
     ;(document.getElementById('asr-mode-local') as HTMLInputElement).checked = asrMode === 'local'
+    ;(document.getElementById('asr-mode-commands') as HTMLInputElement).checked = asrMode === 'commands'
     updateAsrModeUI(asrMode)
     await withTimeout(renderModelList(cfg.asr?.localModel), 8000, 'render-model-list')
     // 枚举麦克风设备并填充下拉
     await populateAudioInputDevices(cfg.audioCapture?.inputConstraints?.deviceId || '')
+    await populateAudioHosts(cfg.audioCapture?.host || 'default')
   } catch (e) {
     console.error('[Dashboard] loadConfigToForm failed:', e)
     setModelListHint(`初始化失败：${String(e)}`, true)
   }
 }
 
+export function applyInputGainToForm(gain: number) {
+  const slider = document.getElementById('cfg-input-gain') as HTMLInputElement | null
+  const display = document.getElementById('input-gain-display')
+  if (slider) slider.value = String(gain)
+  if (display) display.textContent = gain.toFixed(1)
+}
+
+// VU 表：按峰值显示电平条，出现削波时变红
+export function renderInputLevel(level: InputLevel) {
+  const bar = document.getElementById('input-level-bar')
+  if (!bar) return
+  bar.style.width = `${Math.round(Math.min(1, level.peak) * 100)}%`
+  bar.classList.toggle('clipped', level.clipped)
+}
+
+export function renderServerStatus(status: ServerStatus) {
+  const el = document.getElementById('server-status-text')
+  if (!el) return
+  const latency = status.state === 'connected' && status.latencyMs !== null ? `（${status.latencyMs}ms）` : ''
+  el.textContent = `${status.message}${latency}`
+  el.title = status.error || (status.checkedAt ? `检查时间：${new Date(status.checkedAt).toLocaleString()}` : '')
+  el.style.color = status.state === 'connected' ? '#4ade80' : status.state === 'unreachable' ? '#f87171' : ''
+}
+
+export function renderAudioInfo(info: AudioInfo) {
+  const el = document.getElementById('audio-info-text')
+  if (!el) return
+  el.textContent = formatAudioInfo(info)
+  // 源采样率与识别采样率不一致时提示，部分设备重采样后识别效果变差
+  el.title = info.sourceSampleRate !== null && info.sourceSampleRate !== info.outputSampleRate
+    ? `麦克风以 ${info.sourceSampleRate}Hz 打开，识别前重采样为 ${info.outputSampleRate}Hz`
+    : ''
+}
+
+// 录音采样率与识别要求不一致时在当前格式下方常驻提示，重启应用前不会自动消失
+export function renderSampleRateWarning(warning: SampleRateWarning) {
+  const el = document.getElementById('sample-rate-warning')
+  if (!el) return
+  el.textContent = `⚠ ${warning.message}`
+  el.hidden = false
+}
+
+// 最近一次误触录音：显示在“误触自动重录”开关下，便于判断是否需要开启
+export function renderReattemptSuggestion(info: ReattemptSuggestion) {
+  const el = document.getElementById('reattempt-suggestion')
+  if (!el) return
+  const action = info.autoRearm ? `已自动重新录音（第 ${info.attempt} 次）` : '请重新录音'
+  el.textContent = `最近一次录音仅 ${info.capturedMs}ms，判定为误触未送识别，${action}`
+  el.hidden = false
+}
+
+const HOTKEY_ACTION_LABELS: Record<HotkeyBinding['action'], string> = {
+  'record': '录音',
+  'repeat-command': '重复指令',
+  'undo-last-insert': '撤销输入',
+  'cycle-profile': '切换指令方案',
+  'vad-toggle': 'VAD 开关',
+  'rewrite': '改写',
+}
+const HOTKEY_STATUS_TEXT: Record<HotkeyBinding['status'], string> = {
+  registered: '已生效',
+  failed: '注册失败',
+  invalid: '无法解析',
+}
+
+// 每项一行；失败原因或附带问题放在行尾
+export function renderHotkeyBindings(bindings: HotkeyBinding[]) {
+  const el = document.getElementById('hotkey-status-list')
+  if (!el) return
+  if (bindings.length === 0) {
+    el.textContent = '热键尚未注册'
+    return
+  }
+  el.replaceChildren(...bindings.map((binding) => {
+    const line = document.createElement('div')
+    const detail = binding.detail ? `（${binding.detail}）` : ''
+    line.textContent = `${HOTKEY_ACTION_LABELS[binding.action]}：${binding.accelerator} ${HOTKEY_STATUS_TEXT[binding.status]}${detail}`
+    if (binding.status !== 'registered') line.style.color = '#f87171'
+    return line
+  }))
+}
+
+// ── 链路自检 ──
+
+const SELF_TEST_RECORD_MS = 2000
+const SELF_TEST_STATUS_TEXT = { passed: '通过', failed: '失败', skipped: '跳过' }
+
+// 麦克风与录音在本进程完成，录音交给主进程继续检查编码、网络与服务端识别
+async function captureForSelfTest(onStatus: (text: string) => void): Promise<SelfTestCapture> {
+  const capture: SelfTestCapture = { microphone: { durationMs: 0 }, recording: { durationMs: 0 }, wav: null }
+  let begin = Date.now()
+  try {
+    const devices = await navigator.mediaDevices.enumerateDevices()
+    if (!devices.some(d => d.kind === 'audioinput')) throw new Error('未检测到麦克风设备')
+  } catch (e) {
+    capture.microphone = { durationMs: Date.now() - begin, error: e instanceof Error ? e.message : String(e) }
+    return capture
+  }
+  capture.microphone.durationMs = Date.now() - begin
+
+  begin = Date.now()
+  try {
+    await startCapture()
+    onStatus('正在录音，请对着麦克风说话...')
+    await new Promise(resolve => setTimeout(resolve, SELF_TEST_RECORD_MS))
+    capture.wav = await stopCapture()
+    capture.recording.durationMs = Date.now() - begin
+  } catch (e) {
+    capture.recording = { durationMs: Date.now() - begin, error: `录音失败: ${e instanceof Error ? e.message : String(e)}` }
+  }
+  return capture
+}
+
+export function renderSelfTestReport(report: SelfTestReport) {
+  const list = document.getElementById('self-test-result')
+  if (!list) return
+  list.innerHTML = ''
+  for (const stage of report.stages) {
+    const item = document.createElement('li')
+    item.className = `self-test-${stage.status}`
+    const duration = stage.status === 'skipped' ? '' : `（${stage.durationMs}ms）`
+    item.textContent = `${stage.label}：${SELF_TEST_STATUS_TEXT[stage.status]}${duration} ${stage.message}`
+    list.appendChild(item)
+  }
+}
+
+let selfTestRunning = false
+
+export async function runPipelineSelfTest() {
+  const btn = document.getElementById('self-test-btn') as HTMLButtonElement | null
+  const list = document.getElementById('self-test-result')
+  if (!btn || !list || selfTestRunning) return
+  selfTestRunning = true
+  btn.disabled = true
+  // 状态文字可能含错误信息，用 textContent 写入，不拼接 HTML
+  const setStatus = (text: string) => {
+    const item = document.createElement('li')
+    item.textContent = text
+    list.replaceChildren(item)
+  }
+  setStatus('正在检查麦克风...')
+  try {
+    const capture = await captureForSelfTest(setStatus)
+    setStatus('正在检查编码、网络与服务端识别...')
+    const report = await window.electronAPI.runSelfTest(capture)
+    renderSelfTestReport(report)
+    btn.textContent = report.ok ? '自检通过，再次自检' : '自检未通过，再次自检'
+  } catch (e) {
+    setStatus(`自检失败: ${String(e)}`)
+  } finally {
+    selfTestRunning = false
+    btn.disabled = false
+  }
+}
+
+const LAST_ERROR_CATEGORY_TEXT: Record<LastError['category'], string> = {
+  recognition: '识别失败',
+  input: '输入失败',
+  model: '本地模型',
+}
+
+export function renderLastError(error: LastError | null) {
+  const card = document.getElementById('last-error-card')
+  const text = document.getElementById('last-error-text')
+  if (!card || !text) return
+  card.style.display = error ? '' : 'none'
+  text.textContent = error
+    ? `[${LAST_ERROR_CATEGORY_TEXT[error.category]}] ${new Date(error.at).toLocaleString()}：${error.message}`
+    : ''
+}
+
+export function renderInputPermission(state: InputPermissionState) {
+  const el = document.getElementById('input-permission-text')
+  if (!el) return
+  el.textContent = state.available ? '可用' : `不可用，${state.guide ?? state.error ?? ''}`
+  el.title = state.error || ''
+  el.style.color = state.available ? '#4ade80' : '#f87171'
+}
+
 export async function saveConfig() {
   const hint = document.getElementById('save-hint')!
   const llmHint = document.getElementById('llm-save-hint')
@@ -578,20 +848,58 @@ export async function saveConfig() {
   try {
     const cfg = await window.electronAPI.getConfig()
     const prevHotkey = normalizeHotkey(cfg.hotkey?.record || '')
+    const prevRepeatHotkey = normalizeHotkey(cfg.hotkey?.repeatCommand || '')
+    const prevUndoHotkey = normalizeHotkey(cfg.hotkey?.undoLastInsert || '')
+    const prevCycleProfileHotkey = normalizeHotkey(cfg.hotkey?.cycleProfile || '')
+    const prevRecordMode = cfg.hotkey?.recordMode ?? 'hold'
     cfg.server.url = (document.getElementById('cfg-url') as HTMLInputElement).value.trim()
+    cfg.server.transport = (document.getElementById('cfg-server-ws') as HTMLInputElement).checked ? 'ws' : 'http'
+    cfg.server.audioEncoding = (document.getElementById('cfg-server-flac') as HTMLInputElement).checked ? 'flac' : 'wav'
+    const language = (document.getElementById('cfg-server-language') as HTMLSelectElement).value
+    cfg.server.language = language === 'en' || language === 'auto' ? language : 'zh'
     cfg.hotkey.record = normalizeHotkey((document.getElementById('cfg-hotkey') as HTMLInputElement).value.trim())
     const nextHotkey = normalizeHotkey(cfg.hotkey.record)
     if (isForbiddenRecordHotkey(nextHotkey)) {
       throw new Error('Windows 下 Alt+Space 会触发系统菜单，导致光标丢失。请改用 Alt+E 等组合键。')
     }
-    const needsRestart = prevHotkey !== nextHotkey
+    const prevAudioHost = cfg.audioCapture?.host || 'default'
+    const nextAudioHost = (document.getElementById('cfg-audio-host') as HTMLSelectElement | null)?.value || prevAudioHost
+    cfg.hotkey.repeatCommand = normalizeHotkey((document.getElementById('cfg-repeat-hotkey') as HTMLInputElement).value.trim())
+    cfg.hotkey.undoLastInsert = normalizeHotkey((document.getElementById('cfg-undo-hotkey') as HTMLInputElement).value.trim())
+    cfg.hotkey.cycleProfile = normalizeHotkey((document.getElementById('cfg-cycle-profile-hotkey') as HTMLInputElement).value.trim())
+    cfg.hotkey.recordMode = (document.getElementById('cfg-record-mode') as HTMLSelectElement).value === 'tap-or-hold' ? 'tap-or-hold' : 'hold'
+    const hotkeyChanged = prevHotkey !== nextHotkey
+      || prevRepeatHotkey !== cfg.hotkey.repeatCommand
+      || prevUndoHotkey !== cfg.hotkey.undoLastInsert
+      || prevCycleProfileHotkey !== cfg.hotkey.cycleProfile
+      || prevRecordMode !== cfg.hotkey.recordMode
+    const needsRestart = hotkeyChanged || prevAudioHost !== nextAudioHost
     cfg.input.useClipboard = (document.getElementById('cfg-clipboard') as HTMLInputElement).checked
+    cfg.input.keyboardMethod = (document.getElementById('cfg-keyboard-method') as HTMLSelectElement).value === 'unicode' ? 'unicode' : 'text'
+    cfg.input.clipboardOnFailure = (document.getElementById('cfg-clipboard-on-failure') as HTMLInputElement).checked
+    cfg.input.autoReattempt = (document.getElementById('cfg-auto-reattempt') as HTMLInputElement).checked
+    cfg.input.continuousHold = (document.getElementById('cfg-continuous-hold') as HTMLInputElement).checked
+    cfg.input.specialChars = parseSpecialCharsText((document.getElementById('cfg-special-chars') as HTMLTextAreaElement).value)
+    cfg.input.mapCtrlToCmdOnMac = (document.getElementById('cfg-map-ctrl-to-cmd') as HTMLInputElement).checked
+    cfg.input.safeMode = (document.getElementById('cfg-safe-mode') as HTMLInputElement).checked
+    cfg.input.pasteShortcut = normalizeHotkey((document.getElementById('cfg-paste-shortcut') as HTMLInputElement).value.trim()) || 'CTRL+V'
+    cfg.input.pasteMode = (document.getElementById('cfg-paste-mode') as HTMLSelectElement).value === 'append' ? 'append' : 'replace'
+    cfg.input.appendNavigationKey = normalizeHotkey((document.getElementById('cfg-append-navigation-key') as HTMLInputElement).value.trim()) || 'END'
+    cfg.input.targetWindowTitle = (document.getElementById('cfg-target-window-title') as HTMLInputElement).value.trim()
+    const trayShowTrigger = (document.getElementById('cfg-tray-show-trigger') as HTMLSelectElement).value
+    cfg.tray = {
+      showTrigger: trayShowTrigger === 'double-click' || trayShowTrigger === 'right-click' ? trayShowTrigger : 'left-click',
+    }
     cfg.logging = {
       ...cfg.logging,
-      enableDebug: (document.getElementById('cfg-log-debug-enabled') as HTMLInputElement).checked,
+      level: cfg.logging.level,
+      enableDebug: cfg.logging.level === 'debug',
+      toFile: (document.getElementById('cfg-log-to-file') as HTMLInputElement).checked,
       showFloatBounds: (document.getElementById('cfg-float-bounds-debug') as HTMLInputElement).checked,
+      logAsrResponses: (document.getElementById('cfg-log-asr-responses') as HTMLInputElement).checked,
     }
-    const asrMode = (document.getElementById('asr-mode-local') as HTMLInputElement).checked ? 'local' : 'api'
+    const checkedAsrMode = document.querySelector<HTMLInputElement>('input[name="asr-mode"]:checked')?.value
+    const asrMode = checkedAsrMode === 'local' || checkedAsrMode === 'commands' ? checkedAsrMode : 'api'
     cfg.asr = {
       ...cfg.asr,
       mode: asrMode,
@@ -614,6 +922,28 @@ export async function saveConfig() {
       prompts: collectTaskPromptsFromForm(cfg.llm.prompts),
     }
     cfg.textRules = collectTextRulesFromForm(cfg.textRules)
+    cfg.postprocess = {
+      ...cfg.postprocess,
+      dedupeRepeats: (document.getElementById('cfg-postprocess-dedupe') as HTMLInputElement).checked,
+      punctuation: (document.getElementById('cfg-postprocess-punctuation') as HTMLSelectElement).value as AppConfig['postprocess']['punctuation'],
+      stopwords: (document.getElementById('cfg-postprocess-stopwords') as HTMLTextAreaElement).value
+        .split('\n').map(line => line.trim()).filter(Boolean),
+      trimTrailingWhitespace: (document.getElementById('cfg-postprocess-trim-trailing') as HTMLInputElement).checked,
+      collapseSpaces: (document.getElementById('cfg-postprocess-collapse-spaces') as HTMLInputElement).checked,
+      stripNewlines: (document.getElementById('cfg-postprocess-strip-newlines') as HTMLInputElement).checked,
+    }
+    const transcriptIdleGapSec = Number((document.getElementById('cfg-transcript-idle-gap') as HTMLInputElement).value)
+    cfg.transcript = {
+      enabled: (document.getElementById('cfg-transcript-enabled') as HTMLInputElement).checked,
+      idleGapMs: Number.isFinite(transcriptIdleGapSec) ? Math.round(transcriptIdleGapSec * 1000) : cfg.transcript.idleGapMs,
+    }
+    cfg.audioCapture = {
+      ...cfg.audioCapture,
+      soundCues: (document.getElementById('cfg-sound-cues') as HTMLInputElement).checked,
+      retainLastRecording: (document.getElementById('cfg-retain-last-recording') as HTMLInputElement).checked,
+      host: nextAudioHost,
+      source: (document.getElementById('cfg-audio-source') as HTMLSelectElement).value === 'loopback' ? 'loopback' : 'input',
+    }
     // 保存麦克风设备选择
     const deviceSelect = document.getElementById('cfg-audio-input-device') as HTMLSelectElement | null
     if (deviceSelect) {
@@ -629,9 +959,12 @@ export async function saveConfig() {
     cfg.vad = {
       ...cfg.vad,
       speechThreshold: thresholdSlider ? parseFloat(thresholdSlider.value) : (cfg.vad?.speechThreshold ?? 0.06),
+      activationPhrase: (document.getElementById('cfg-vad-activation-phrase') as HTMLInputElement).value.trim(),
     }
     await window.electronAPI.saveConfig(cfg)
-    hint.textContent = needsRestart ? '已保存，热键变更需重启后生效' : '已保存'
+    hint.textContent = needsRestart
+      ? `已保存，${hotkeyChanged ? '热键' : '音频后端'}变更需重启后生效`
+      : '已保存'
     hint.style.color = '#4ade80'
     if (llmHint) {
       llmHint.textContent = '应用已持久化'
@@ -642,7 +975,7 @@ export async function saveConfig() {
       textRulesHint.style.color = '#4ade80'
     }
     if (needsRestart) {
-      const shouldRestart = window.confirm('热键配置已变更，需要重启应用后生效。现在重启吗？')
+      const shouldRestart = window.confirm(`${hotkeyChanged ? '热键' : '音频后端'}配置已变更，需要重启应用后生效。现在重启吗？`)
       if (shouldRestart) {
         await window.electronAPI.restartApp()
       }
@@ -670,6 +1003,44 @@ export function updateAsrModeUI(mode: string) {
   localSettings.style.display = mode === 'local' ? '' : 'none'
 }
 
+// ── 配置备份与迁移 ──
+
+export async function exportConfigToForm() {
+  const textarea = document.getElementById('cfg-transfer-text') as HTMLTextAreaElement
+  const hint = document.getElementById('cfg-transfer-hint')!
+  try {
+    textarea.value = await window.electronAPI.exportConfig()
+    textarea.select()
+    await navigator.clipboard.writeText(textarea.value).catch(() => { })
+    hint.textContent = '已导出，并复制到剪贴板'
+    hint.style.color = '#4ade80'
+  } catch (e) {
+    hint.textContent = '导出失败: ' + String(e)
+    hint.style.color = '#f87171'
+  }
+}
+
+export async function importConfigFromForm() {
+  const textarea = document.getElementById('cfg-transfer-text') as HTMLTextAreaElement
+  const hint = document.getElementById('cfg-transfer-hint')!
+  if (!textarea.value.trim()) {
+    hint.textContent = '请先粘贴要导入的配置'
+    hint.style.color = '#f87171'
+    return
+  }
+  try {
+    await window.electronAPI.importConfig(textarea.value)
+    await loadConfigToForm()
+    await renderCommandEditor()
+    await loadHotwords()
+    hint.textContent = '已导入，热键、音频后端变更需重启后生效'
+    hint.style.color = '#4ade80'
+  } catch (e) {
+    hint.textContent = '导入失败，当前配置未修改: ' + String(e)
+    hint.style.color = '#f87171'
+  }
+}
+
 // ── 语音指令 ──
 
 export async function renderCommandList() {
@@ -677,16 +1048,15 @@ export async function renderCommandList() {
   if (!list) return
   list.innerHTML = ''
   try {
-    const cfg = await window.electronAPI.getConfig()
-    const cmds: Record<string, string> = cfg.voiceCommands || {}
-    const entries = Object.entries(cmds).sort((a, b) => a[0].localeCompare(b[0], 'zh'))
-    for (const [name, key] of entries) {
+    const entries = await window.electronAPI.listVoiceCommands()
+    for (const entry of entries) {
       const nameEl = document.createElement('span')
       nameEl.className = 'cmd-name'
-      nameEl.textContent = name
+      nameEl.textContent = entry.phrase
       const keyEl = document.createElement('span')
-      keyEl.className = 'cmd-key'
-      keyEl.textContent = key
+      keyEl.className = entry.valid ? 'cmd-key' : 'cmd-key invalid'
+      keyEl.textContent = entry.shortcut
+      if (entry.error) keyEl.title = entry.error
       list.appendChild(nameEl)
       list.appendChild(keyEl)
     }
@@ -706,11 +1076,89 @@ export async function renderCommandEditor() {
     for (const [name, key] of entries) {
       appendCommandRow(editorList, name, key)
     }
+    const matchingToggle = document.getElementById('cmd-matching-enabled') as HTMLInputElement | null
+    if (matchingToggle) matchingToggle.checked = cfg.voiceCommandOptions?.enabled !== false
+    const aliasesInput = document.getElementById('cmd-aliases') as HTMLTextAreaElement | null
+    if (aliasesInput) {
+      aliasesInput.value = Object.entries(cfg.voiceCommandAliases || {})
+        .map(([phrase, aliases]) => `${phrase}=${aliases.join(',')}`)
+        .join('\n')
+    }
+    const enterPhrasesInput = document.getElementById('cmd-enter-phrases') as HTMLTextAreaElement | null
+    if (enterPhrasesInput) {
+      enterPhrasesInput.value = (cfg.voiceCommandOptions?.enterAfterPhrases || []).join('\n')
+    }
+    const repeatPhraseInput = document.getElementById('cmd-repeat-phrase') as HTMLInputElement | null
+    if (repeatPhraseInput) {
+      repeatPhraseInput.value = cfg.voiceCommandOptions?.repeatPhrase ?? ''
+    }
+    const allowExecInput = document.getElementById('cmd-allow-exec') as HTMLInputElement | null
+    if (allowExecInput) allowExecInput.checked = cfg.voiceCommandOptions?.allowExec === true
+    await renderCommandTemplateOptions(Object.keys(cmds))
   } catch (e) {
     console.warn('[Command] renderCommandEditor failed:', e)
   }
 }
 
+// ── 离线指令样本录制 ──
+
+let templateRecording = false
+
+async function renderCommandTemplateOptions(phrases: string[]) {
+  const select = document.getElementById('cmd-template-phrase') as HTMLSelectElement | null
+  if (!select) return
+  const previous = select.value
+  const counts = await window.electronAPI.listCommandTemplates()
+  select.innerHTML = ''
+  for (const phrase of [...phrases].sort((a, b) => a.localeCompare(b, 'zh'))) {
+    const option = document.createElement('option')
+    option.value = phrase
+    option.textContent = `${phrase}（${counts[phrase] ?? 0} 条样本）`
+    select.appendChild(option)
+  }
+  if (phrases.includes(previous)) select.value = previous
+}
+
+export async function toggleCommandTemplateRecording() {
+  const select = document.getElementById('cmd-template-phrase') as HTMLSelectElement | null
+  const btn = document.getElementById('cmd-template-record-btn') as HTMLButtonElement | null
+  const status = document.getElementById('cmd-template-status')
+  if (!select || !btn || !status) return
+  const phrase = select.value
+  if (!phrase) return
+  try {
+    if (!templateRecording) {
+      await startCapture()
+      templateRecording = true
+      btn.textContent = '停止录制'
+      status.textContent = `请说出口令「${phrase}」，说完点击停止`
+      return
+    }
+    templateRecording = false
+    btn.textContent = '录制样本'
+    const wav = await stopCapture()
+    const count = await window.electronAPI.enrollCommandTemplate(phrase, wav)
+    status.textContent = `口令「${phrase}」已有 ${count} 条样本`
+    const cfg = await window.electronAPI.getConfig()
+    await renderCommandTemplateOptions(Object.keys(cfg.voiceCommands || {}))
+  } catch (e) {
+    templateRecording = false
+    btn.textContent = '录制样本'
+    status.textContent = '录制失败: ' + String(e)
+  }
+}
+
+export async function clearCommandTemplates() {
+  const select = document.getElementById('cmd-template-phrase') as HTMLSelectElement | null
+  const status = document.getElementById('cmd-template-status')
+  if (!select?.value || !status) return
+  const phrase = select.value
+  await window.electronAPI.clearCommandTemplates(phrase)
+  status.textContent = `已清空口令「${phrase}」的样本`
+  const cfg = await window.electronAPI.getConfig()
+  await renderCommandTemplateOptions(Object.keys(cfg.voiceCommands || {}))
+}
+
 export function appendCommandRow(container: HTMLElement, name = '', key = '') {
   const row = document.createElement('div')
   row.className = 'cmd-editor-row'
@@ -721,12 +1169,60 @@ export function appendCommandRow(container: HTMLElement, name = '', key = '') {
   nameInput.placeholder = '语音词'
   nameInput.value = name
 
+  // 文本扩展指令（TEXT: 前缀）输入固定文字，运行程序指令（RUN: 前缀）启动外部程序，二者共用文字输入框
+  const prefixed = /^\s*(text|run):/i.exec(key)
+  const prefixKind = prefixed ? `${prefixed[1].toUpperCase()}:` : null
+  const textInput = document.createElement('input')
+  textInput.type = 'text'
+  textInput.className = 'cmd-input cmd-text-input'
+  textInput.value = prefixed ? key.slice(prefixed[0].length) : ''
+
   const keyInput = document.createElement('input')
   keyInput.type = 'text'
   keyInput.className = 'cmd-input cmd-key-input'
   keyInput.placeholder = '点击后按下快捷键'
-  keyInput.value = normalizeHotkey(key)
+  keyInput.value = prefixKind === null ? normalizeShortcutSequence(key) : ''
   attachHotkeyRecorder(keyInput)
+  keyInput.addEventListener('blur', () => { delete keyInput.dataset.appendStep })
+
+  const appendBtn = document.createElement('button')
+  appendBtn.className = 'cmd-del-btn cmd-append-btn'
+  appendBtn.textContent = '+'
+  appendBtn.title = '追加一步按键（如 ALT+F; S; ENTER）'
+  appendBtn.addEventListener('mousedown', (event) => event.preventDefault())
+  appendBtn.addEventListener('click', () => {
+    if (keyInput.disabled) return
+    keyInput.focus()
+    keyInput.dataset.appendStep = '1'
+  })
+
+  // 换行/制表/文本扩展指令：输入文字，不发送快捷键；运行程序需开启 voiceCommandOptions.allowExec
+  const kindSelect = document.createElement('select')
+  kindSelect.className = 'cmd-input cmd-kind-select'
+  for (const [value, label] of [['', '快捷键'], ['{NEWLINE}', '输入换行'], ['{TAB}', '输入制表符'], ['TEXT:', '输入文本'], ['RUN:', '运行程序']]) {
+    const option = document.createElement('option')
+    option.value = value
+    option.textContent = label
+    kindSelect.appendChild(option)
+  }
+  const syncKind = () => {
+    const isText = kindSelect.value === 'TEXT:' || kindSelect.value === 'RUN:'
+    textInput.placeholder = kindSelect.value === 'RUN:'
+      ? '程序路径及参数，含空格的路径用双引号包住'
+      : '说出口令后输入的文字，[] 处为光标停留位置'
+    keyInput.hidden = isText
+    appendBtn.hidden = isText
+    textInput.hidden = !isText
+    keyInput.disabled = Boolean(kindSelect.value)
+    appendBtn.disabled = keyInput.disabled
+    if (kindSelect.value && !isText) keyInput.value = kindSelect.value
+    else if (keyInput.value.startsWith('{')) keyInput.value = ''
+  }
+  kindSelect.value = prefixKind !== null
+    ? prefixKind
+    : ['{NEWLINE}', '{TAB}'].includes(keyInput.value) ? keyInput.value : ''
+  kindSelect.addEventListener('change', syncKind)
+  syncKind()
 
   const delBtn = document.createElement('button')
   delBtn.className = 'cmd-del-btn'
@@ -736,10 +1232,45 @@ export function appendCommandRow(container: HTMLElement, name = '', key = '') {
 
   row.appendChild(nameInput)
   row.appendChild(keyInput)
+  row.appendChild(textInput)
+  row.appendChild(appendBtn)
+  row.appendChild(kindSelect)
   row.appendChild(delBtn)
   container.appendChild(row)
 }
 
+// 指令方案下拉框：第一项为基础指令（空字符串）
+export function renderCommandProfiles(state: VoiceCommandProfileState) {
+  const select = document.getElementById('cmd-profile-select') as HTMLSelectElement | null
+  if (!select) return
+  select.innerHTML = ''
+  const base = document.createElement('option')
+  base.value = ''
+  base.textContent = '基础指令'
+  select.appendChild(base)
+  for (const profile of state.profiles) {
+    const option = document.createElement('option')
+    option.value = profile.name
+    option.textContent = `${profile.name}（${profile.commandCount} 条）`
+    select.appendChild(option)
+  }
+  select.value = state.active
+  select.disabled = state.profiles.length === 0
+}
+
+// 每行“主口令=别名1,别名2”，中英文逗号均可
+function parseCommandAliases(text: string): Record<string, string[]> {
+  const result: Record<string, string[]> = {}
+  for (const line of text.split('\n')) {
+    const eq = line.indexOf('=')
+    if (eq < 0) continue
+    const phrase = line.slice(0, eq).trim()
+    const aliases = line.slice(eq + 1).split(/[,，]/).map(alias => alias.trim()).filter(Boolean)
+    if (phrase && aliases.length > 0) result[phrase] = [...(result[phrase] ?? []), ...aliases]
+  }
+  return result
+}
+
 export async function saveCommands() {
   const hint = document.getElementById('cmd-save-hint')!
   try {
@@ -748,10 +1279,33 @@ export async function saveCommands() {
     const newCmds: Record<string, string> = {}
     for (const row of rows) {
       const name = (row.querySelector('.cmd-name-input') as HTMLInputElement).value.trim()
-      const key = normalizeHotkey((row.querySelector('.cmd-key-input') as HTMLInputElement).value.trim())
+      const kind = (row.querySelector('.cmd-kind-select') as HTMLSelectElement).value
+      const text = (row.querySelector('.cmd-text-input') as HTMLInputElement).value
+      const key = kind === 'TEXT:' || kind === 'RUN:'
+        ? (text.trim() ? `${kind}${text}` : '')
+        : normalizeShortcutSequence((row.querySelector('.cmd-key-input') as HTMLInputElement).value.trim())
       if (name && key) newCmds[name] = key
     }
     cfg.voiceCommands = newCmds
+    const aliasesInput = document.getElementById('cmd-aliases') as HTMLTextAreaElement | null
+    if (aliasesInput) {
+      cfg.voiceCommandAliases = parseCommandAliases(aliasesInput.value)
+    }
+    const enterPhrasesInput = document.getElementById('cmd-enter-phrases') as HTMLTextAreaElement | null
+    if (enterPhrasesInput) {
+      cfg.voiceCommandOptions = {
+        ...cfg.voiceCommandOptions,
+        enterAfterPhrases: enterPhrasesInput.value.split('\n').map(line => line.trim()).filter(Boolean),
+      }
+    }
+    const repeatPhraseInput = document.getElementById('cmd-repeat-phrase') as HTMLInputElement | null
+    if (repeatPhraseInput) {
+      cfg.voiceCommandOptions = { ...cfg.voiceCommandOptions, repeatPhrase: repeatPhraseInput.value.trim() }
+    }
+    const allowExecInput = document.getElementById('cmd-allow-exec') as HTMLInputElement | null
+    if (allowExecInput) {
+      cfg.voiceCommandOptions = { ...cfg.voiceCommandOptions, allowExec: allowExecInput.checked }
+    }
     cfg.hotwords = stripVoiceCommandHotwords(cfg.hotwords, newCmds)
     hotwordScenes = stripVoiceCommandHotwords(hotwordScenes, newCmds)
     await window.electronAPI.saveConfig(cfg)
@@ -765,6 +1319,112 @@ export async function saveCommands() {
   }
 }
 
+export async function previewCommandPhrase(text: string) {
+  const resultEl = document.getElementById('cmd-preview-result')
+  if (!resultEl) return
+  if (!text.trim()) {
+    resultEl.textContent = ''
+    return
+  }
+  try {
+    const preview = await window.electronAPI.previewVoiceCommand(text)
+    if (preview.type === 'command') {
+      resultEl.textContent = `命中指令「${preview.phrase}」→ ${preview.shortcut}`
+    } else if (preview.type === 'exec') {
+      resultEl.textContent = `命中指令「${preview.phrase}」→ 运行 ${preview.shortcut}`
+    } else if (preview.phrase) {
+      resultEl.textContent = `命中指令「${preview.phrase}」→ 输入${preview.text === '\t' ? '制表符' : '换行'}`
+    } else {
+      resultEl.textContent = `不触发指令，将输入文字：${preview.text}${preview.pressEnter ? ' ⏎' : ''}`
+      if (preview.candidates?.length) {
+        resultEl.textContent += `（相近口令：${preview.candidates.map(c => `${c.phrase} ${Math.round(c.score * 100)}%`).join('、')}）`
+      }
+    }
+  } catch (e) {
+    resultEl.textContent = '预览失败: ' + String(e)
+  }
+}
+
+const POSTPROCESS_STAGE_LABELS: Record<PostprocessStage, string> = {
+  textRules: '文本规则',
+  stopwords: '删除填充词',
+  dedupe: '合并重复',
+  punctuation: '自动标点',
+  whitespace: '空白整理',
+}
+
+export async function previewPostprocessText(text: string) {
+  const resultEl = document.getElementById('postprocess-preview-result')
+  if (!resultEl) return
+  if (!text.trim()) {
+    resultEl.textContent = ''
+    return
+  }
+  try {
+    const preview = await window.electronAPI.previewPostprocess(text)
+    const steps = preview.steps.map(step => `${POSTPROCESS_STAGE_LABELS[step.stage]}：${step.text}`)
+    resultEl.textContent = `最终输入：${preview.text}${steps.length ? `（${steps.join(' → ')}）` : '（未启用任何处理）'}`
+  } catch (e) {
+    resultEl.textContent = '试测失败: ' + String(e)
+  }
+}
+
+// 用保留的最近录音重新识别，结果按正常流程输入到目标应用
+export async function recognizeLastRecording() {
+  const resultEl = document.getElementById('recognize-last-recording-result')
+  if (!resultEl) return
+  resultEl.textContent = '正在重新识别…'
+  try {
+    const result = await window.electronAPI.recognizeLastRecording()
+    if (!result) resultEl.textContent = '未识别到内容'
+    else if (result.kind === 'command') resultEl.textContent = `命中指令：${result.phrase} → ${result.shortcut}`
+    else resultEl.textContent = `识别结果：${result.text}`
+  } catch (e) {
+    resultEl.textContent = '重新识别失败: ' + String(e)
+  }
+}
+
+// 特殊字符表每行“字符=方式”，按最后一个等号拆分（字符本身可以是“=”），不认识的方式忽略，主进程再做完整校验
+function parseSpecialCharsText(text: string): AppConfig['input']['specialChars'] {
+  const result: AppConfig['input']['specialChars'] = {}
+  for (const line of text.split('\n')) {
+    const index = line.lastIndexOf('=')
+    if (index <= 0) continue
+    const ch = line.slice(0, index).trim()
+    const strategy = line.slice(index + 1).trim().toLowerCase()
+    if (ch && (strategy === 'unicode' || strategy === 'clipboard')) result[ch] = strategy
+  }
+  return result
+}
+
+// 整段口述记录：开始/结束会话、复制与导出，结果写在同一行提示里
+export async function runTranscriptAction(action: 'start' | 'end' | 'copy' | 'export') {
+  const resultEl = document.getElementById('transcript-result')
+  if (!resultEl) return
+  try {
+    if (action === 'start') {
+      await window.electronAPI.startSession()
+      resultEl.textContent = '已开始新会话，之后的口述文字会累计到本会话'
+    } else if (action === 'end') {
+      const state = await window.electronAPI.endSession()
+      resultEl.textContent = `会话已结束，共 ${state.segmentCount} 句、${Array.from(state.text).length} 字`
+    } else if (action === 'copy') {
+      const state = await window.electronAPI.getSessionTranscript()
+      if (!state.text) {
+        resultEl.textContent = '当前会话没有口述内容'
+        return
+      }
+      await navigator.clipboard.writeText(state.text)
+      resultEl.textContent = `已复制 ${state.segmentCount} 句口述内容`
+    } else {
+      const filePath = await window.electronAPI.exportSessionTranscript()
+      resultEl.textContent = filePath ? `已导出到 ${filePath}` : '已取消导出'
+    }
+  } catch (e) {
+    resultEl.textContent = '操作失败: ' + String(e)
+  }
+}
+
 // ── 热词管理 ──
 
 let hotwordScenes: HotwordScene[] = [{ name: '全局', words: [] }]
@@ -930,3 +1590,22 @@ async function populateAudioInputDevices(savedDeviceId: string): Promise<void> {
     // 权限未授予时 enumerateDevices 可能返回空标签，静默处理
   }
 }
+
+// 填充当前平台可用的音频后端
+async function populateAudioHosts(savedHost: string): Promise<void> {
+  const select = document.getElementById('cfg-audio-host') as HTMLSelectElement | null
+  if (!select) return
+  try {
+    const hosts = await window.electronAPI.listAudioHosts()
+    select.innerHTML = ''
+    for (const host of hosts) {
+      const opt = document.createElement('option')
+      opt.value = host.id
+      opt.textContent = host.label
+      select.appendChild(opt)
+    }
+    select.value = hosts.some(h => h.id === savedHost) ? savedHost : 'default'
+  } catch (e) {
+    console.warn('[Audio] listAudioHosts failed:', e)
+  }
+}
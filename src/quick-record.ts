@@ -0,0 +1,7 @@
+// 定时录音（快速记录）：触发一次后固定录音一段时间再走完整的识别、指令匹配与输入流程，
+// 适合不方便按住热键、又不想开 VAD 的场景。时长由主进程按 input.quickRecordMs 的范围限制后下发
+
+// 剩余秒数（向上取整），用于浮窗倒计时
+export function quickRecordRemainingSec(startedAt: number, durationMs: number, now: number): number {
+  return Math.max(0, Math.ceil((startedAt + durationMs - now) / 1000))
+}
@@ -0,0 +1,30 @@
+// 误触录音（按下即松开、空录音）的处理：不发起识别，提示重新录音；
+// 开启 input.autoReattempt 且为点击切换录音时自动重新开始录音，每次触发最多自动重试若干次，避免无限循环
+
+export const REATTEMPT_MIN_CAPTURE_MS = 200
+export const REATTEMPT_MAX_PER_TRIGGER = 2
+
+// 实际采集的音频（不含补齐的尾部静音）短于阈值视为误触
+export function isFumbledCapture(capturedMs: number, minMs = REATTEMPT_MIN_CAPTURE_MS): boolean {
+  return !Number.isFinite(capturedMs) || capturedMs < minMs
+}
+
+export class ReattemptGuard {
+  private attempts = 0
+
+  constructor(private readonly maxAttempts = REATTEMPT_MAX_PER_TRIGGER) { }
+
+  get used(): number { return this.attempts }
+
+  // 用户重新发起录音或得到正常长度的录音后清零
+  reset(): void {
+    this.attempts = 0
+  }
+
+  // 返回本次是否还允许自动重新录音
+  tryConsume(): boolean {
+    if (this.attempts >= this.maxAttempts) return false
+    this.attempts++
+    return true
+  }
+}
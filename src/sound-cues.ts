@@ -0,0 +1,51 @@
+// 录音开始/结束提示音：用振荡器现场生成短正弦音，不依赖音频文件，也不阻塞录音流程
+
+const CUE_DURATION_S = 0.08
+const CUE_GAIN = 0.15
+const CUE_FADE_S = 0.01
+const START_CUE_HZ = 880
+const STOP_CUE_HZ = 660
+
+let enabled = false
+let cueCtx: AudioContext | null = null
+
+export function setSoundCuesEnabled(next: boolean) {
+  enabled = next
+}
+
+export function playStartCue() {
+  playTone(START_CUE_HZ)
+}
+
+export function playStopCue() {
+  playTone(STOP_CUE_HZ)
+}
+
+function playTone(freq: number) {
+  if (!enabled) return
+  try {
+    if (!cueCtx || cueCtx.state === 'closed') cueCtx = new AudioContext()
+    const ctx = cueCtx
+    if (ctx.state === 'suspended') void ctx.resume().catch(() => { })
+    const now = ctx.currentTime
+    const osc = ctx.createOscillator()
+    const gain = ctx.createGain()
+    osc.type = 'sine'
+    osc.frequency.value = freq
+    // 首尾淡入淡出，避免爆音
+    gain.gain.setValueAtTime(0, now)
+    gain.gain.linearRampToValueAtTime(CUE_GAIN, now + CUE_FADE_S)
+    gain.gain.setValueAtTime(CUE_GAIN, now + CUE_DURATION_S - CUE_FADE_S)
+    gain.gain.linearRampToValueAtTime(0, now + CUE_DURATION_S)
+    osc.connect(gain)
+    gain.connect(ctx.destination)
+    osc.start(now)
+    osc.stop(now + CUE_DURATION_S)
+    osc.onended = () => {
+      osc.disconnect()
+      gain.disconnect()
+    }
+  } catch (e) {
+    console.warn('[SoundCue] play failed:', e)
+  }
+}
@@ -5,6 +5,8 @@ declare global {
       getConfig: () => Promise<AppConfig>
       getAppVersion: () => Promise<string>
       saveConfig: (config: AppConfig) => Promise<void>
+      exportConfig: () => Promise<string>
+      importConfig: (text: string) => Promise<AppConfig>
       getFrontmostApp: () => Promise<string | null>
       captureFocusSnapshot: (reason?: string) => Promise<string | null>
       restoreFocus: (appId: string | null) => Promise<{
@@ -15,10 +17,69 @@ declare global {
         reason: 'ok' | 'no-target' | 'frontmost-mismatch'
       }>
       getVadEnabled: () => Promise<boolean>
+      getVadBackend: () => Promise<'energy' | 'fsmn'>
+      setVadBackend: (name: string) => Promise<'energy' | 'fsmn'>
       setVadEnabled: (enabled: boolean) => Promise<boolean>
+      reportVadIdleTimeout: (idleMs: number) => Promise<boolean>
+      reportRecordingStarted: () => Promise<void>
       setVadThreshold: (threshold: number) => Promise<number>
+      setVadEnergySmoothing: (smoothing: number) => Promise<number>
+      setInputGain: (factor: number) => Promise<number>
+      quickRecord: (durationMs?: number) => Promise<boolean>
+      reportQuickRecord: (result: QuickRecordReport) => Promise<void>
+      discardRecording: () => Promise<void>
+      // 用保留的最近录音重新识别（需开启 audioCapture.retainLastRecording），未保留时抛错
+      recognizeLastRecording: () => Promise<RecognitionResult | null>
+      clearLastRecording: () => Promise<void>
+      // 整段口述记录（需开启 transcript.enabled）；导出返回保存路径，取消返回 null
+      startSession: () => Promise<SessionTranscriptState>
+      endSession: () => Promise<SessionTranscriptState>
+      getSessionTranscript: () => Promise<SessionTranscriptState>
+      exportSessionTranscript: () => Promise<string | null>
+      getInputMode: () => Promise<InputMode>
+      setUseClipboard: (enabled: boolean) => Promise<boolean>
+      getCommandMatching: () => Promise<boolean>
+      setCommandMatching: (enabled: boolean) => Promise<boolean>
+      reportInputLevel: (level: InputLevel) => Promise<void>
+      reportAudioInfo: (info: AudioInfo) => Promise<void>
+      reportAudioLifecycle: (event: AudioLifecycleEvent) => Promise<void>
+      reportSampleRateWarning: (warning: SampleRateWarning) => Promise<void>
+      reportReattemptSuggested: (info: ReattemptSuggestion) => Promise<void>
+      getAudioInfo: () => Promise<AudioInfo>
+      getInputDevice: () => Promise<InputDeviceSelection>
+      setInputDevice: (name: string) => Promise<InputDeviceSelection>
+      reportInputDeviceSwitch: (result: InputDeviceSwitchReport) => Promise<void>
       getAsrRuntimeStatus: () => Promise<AsrRuntimeStatus>
-      recognizeWav: (wavBuffer: ArrayBuffer, prevAppId: string | null) => Promise<string>
+      getLastError: () => Promise<LastError | null>
+      clearLastError: () => Promise<void>
+      getServerStatus: () => Promise<ServerStatus>
+      checkServerStatus: () => Promise<ServerStatus>
+      pingServer: () => Promise<number>
+      cancelPingServer: () => Promise<boolean>
+      getServerLatencyHistory: () => Promise<{ samples: LatencySample[]; summary: LatencySummary }>
+      recheckInputPermissions: () => Promise<InputPermissionState>
+      recognizeWav: (
+        wavBuffer: ArrayBuffer,
+        prevAppId: string | null,
+        triggeredAt?: number,
+        source?: 'vad' | 'manual' | 'segment',
+      ) => Promise<RecognitionResult | null>
+      previewVoiceCommand: (text: string) => Promise<VoiceCommandPreview>
+      previewPostprocess: (text: string) => Promise<PostprocessPreview>
+      validateShortcut: (shortcut: string) => Promise<ShortcutValidation>
+      listVoiceCommands: () => Promise<VoiceCommandEntry[]>
+      listProfiles: () => Promise<VoiceCommandProfileState>
+      // 名称不存在时抛错；空字符串回到基础指令表
+      switchProfile: (name: string) => Promise<VoiceCommandProfileState>
+      listHotkeys: () => Promise<HotkeyBinding[]>
+      checkHotkeyAvailable: (accelerator: string) => Promise<HotkeyAvailability>
+      transcribeFile: (filePath: string) => Promise<TranscribeFileResult>
+      runSelfTest: (capture: SelfTestCapture) => Promise<SelfTestReport>
+      enrollCommandTemplate: (phrase: string, wav: ArrayBuffer) => Promise<number>
+      listCommandTemplates: () => Promise<Record<string, number>>
+      clearCommandTemplates: (phrase: string) => Promise<void>
+      repeatLastCommand: (prevAppId: string | null) => Promise<RecognitionResult>
+      undoLastInsert: (prevAppId: string | null) => Promise<number>
       openDashboard: () => Promise<void>
       closeDashboard: () => Promise<void>
       showFloatContextMenu: () => Promise<void>
@@ -30,6 +91,10 @@ declare global {
         success: boolean
         reason: 'ok' | 'empty-text' | 'unknown' | 'no-foreground-window' | 'no-focused-control' | 'focused-control-without-caret' | 'type-failed' | 'restore-failed'
       }>
+      armInput: (text?: string, targetAppId?: string | null) => Promise<{
+        success: boolean
+        reason: 'ok' | 'empty-text' | 'unknown' | 'no-foreground-window' | 'no-focused-control' | 'focused-control-without-caret' | 'type-failed' | 'restore-failed'
+      }>
       setIgnoreMouseEvents: (ignore: boolean, opts?: { forward: boolean }) => Promise<void>
       getModelStatuses: () => Promise<ModelStatus[]>
       getModelCatalog: () => Promise<Array<{
@@ -40,8 +105,13 @@ declare global {
       }>>
       downloadModel: (modelId: string) => Promise<{ success: boolean; error?: string }>
       deleteModel: (modelId: string) => Promise<void>
+      listAudioHosts: () => Promise<AudioHostInfo[]>
       getLogs: () => Promise<LogEntry[]>
       clearLogs: () => Promise<void>
+      getLogLevel: () => Promise<LogLevel>
+      getLogPath: () => Promise<string>
+      openLogFolder: () => Promise<void>
+      setLogLevel: (level: LogLevel) => Promise<LogLevel>
       copyToClipboard: (text: string) => Promise<boolean>
       getStats: () => Promise<DailyStats>
       getRecentHistory: (limit?: number) => Promise<RecognitionRecord[]>
@@ -60,19 +130,57 @@ declare global {
       restartApp: () => Promise<boolean>
       onHotkeyState: (cb: (state: string) => void) => void
       onToggleVad: (cb: (enabled: boolean) => void) => void
+      onVadState: (cb: (state: { enabled: boolean; reason: 'user' | 'idle-timeout' }) => void) => void
       onVadThresholdUpdated: (cb: (threshold: number) => void) => void
+      onVadEnergySmoothingUpdated: (cb: (smoothing: number) => void) => void
+      onVadBackendUpdated: (cb: (backend: 'energy' | 'fsmn') => void) => void
+      onInputGainUpdated: (cb: (gain: number) => void) => void
+      onInputModeUpdated: (cb: (mode: InputMode) => void) => void
+      onCommandMatchingUpdated: (cb: (enabled: boolean) => void) => void
+      onProfileChanged: (cb: (state: VoiceCommandProfileState) => void) => void
+      onInputLevel: (cb: (level: InputLevel) => void) => void
+      onAudioInfo: (cb: (info: AudioInfo) => void) => void
+      onSwitchInputDevice: (cb: (request: { requestId: number; name: string }) => void) => void
+      onInputDeviceUpdated: (cb: (device: InputDeviceSelection) => void) => void
+      onAudioThreadError: (cb: (event: AudioLifecycleEvent) => void) => void
+      onSampleRateWarning: (cb: (warning: SampleRateWarning) => void) => void
+      onRecognitionStage: (cb: (event: RecognitionStageEvent) => void) => void
+      onRecognitionBusy: (cb: (state: { busy: boolean; pending: number }) => void) => void
+      onReattemptSuggested: (cb: (info: ReattemptSuggestion) => void) => void
       onAsrRuntimeStatus: (cb: (status: AsrRuntimeStatus) => void) => void
+      onLastError: (cb: (error: LastError | null) => void) => void
+      onServerStatus: (cb: (status: ServerStatus) => void) => void
+      onServerLatency: (cb: (sample: LatencySample) => void) => void
+      onQuickRecord: (cb: (request: { requestId: number; durationMs: number }) => void) => void
       onHotkeyStopRecording: (cb: (prevAppId: string | null) => void) => void
+      onHotkeyRepeatCommand: (cb: (prevAppId: string | null) => void) => void
+      onHotkeyUndoLastInsert: (cb: (prevAppId: string | null) => void) => void
       onModelDownloadProgress: (cb: (data: { modelId: string; percent: number; status?: string }) => void) => void
       onLogEntry: (cb: (entry: LogEntry) => void) => void
+      onLogLevelUpdated: (cb: (level: LogLevel) => void) => void
       onPermissionWarning: (cb: (message: string) => void) => void
+      onInputPermissionDenied: (cb: (state: InputPermissionState) => void) => void
       onFloatPasteFallback: (cb: (payload: {
         requestId: number
+        traceId?: string
         text: string
         targetAppId: string | null
-        reason: 'no-foreground-window' | 'no-focused-control' | 'focused-control-without-caret' | 'type-failed' | 'restore-failed'
+        reason: 'no-foreground-window' | 'no-focused-control' | 'focused-control-without-caret' | 'type-failed' | 'restore-failed' | 'safe-mode-unarmed'
         precheckReason: 'ok' | 'unknown' | 'no-foreground-window' | 'no-focused-control' | 'focused-control-without-caret'
+        copiedToClipboard: boolean
       }) => void) => void
+      onInputComplete: (cb: (payload: { requestId: number; traceId?: string; text: string; method: 'clipboard' | 'keyboard' | 'shortcut' }) => void) => void
+      onInputFailed: (cb: (payload: { requestId: number; traceId?: string; text: string; error: string; copiedToClipboard: boolean }) => void) => void
+      onAsrRawResponse: (cb: (payload: AsrRawResponse) => void) => void
+      onRecognitionEmptyWarning: (cb: (payload: RecognitionEmptyWarning) => void) => void
+      onResultFiltered: (cb: (payload: ResultFiltered) => void) => void
+      onVoiceCommandCandidates: (cb: (payload: {
+        requestId: number
+        text: string
+        candidates: VoiceCommandCandidate[]
+      }) => void) => void
+      onHotkeysUpdated: (cb: (bindings: HotkeyBinding[]) => void) => void
+      onHotkeyConflict: (cb: (conflict: { action: HotkeyBinding['action']; accelerator: string; message: string }) => void) => void
       onFloatDebugBoundsUpdated: (cb: (enabled: boolean) => void) => void
 
       // 重写专用通道
@@ -86,6 +194,134 @@ declare global {
   }
 }
 
+export interface VoiceCommandCandidate {
+  phrase: string
+  shortcut: string
+  score: number
+}
+
+// 文件转写结果：rawText 为识别原文，text 为经文本规则与后处理后的文字，match 为指令匹配预览（无文字时为 null）
+// 文字输入方式：clipboard 剪贴板粘贴，keyboard 键盘逐字键入（input.useClipboard）
+export type InputMode = 'clipboard' | 'keyboard'
+
+export interface TranscribeFileResult {
+  path: string
+  traceId: string
+  durationMs: number
+  rawText: string
+  text: string
+  match: VoiceCommandPreview | null
+}
+
+// 一键自检：麦克风与录音在渲染进程完成，其余步骤由主进程执行
+export type SelfTestStage = 'microphone' | 'recording' | 'signal' | 'encoding' | 'network' | 'server'
+
+export interface SelfTestCapture {
+  microphone: { durationMs: number; error?: string }
+  recording: { durationMs: number; error?: string }
+  wav: ArrayBuffer | null
+}
+
+export interface SelfTestStageResult {
+  stage: SelfTestStage
+  label: string
+  status: 'passed' | 'failed' | 'skipped'
+  durationMs: number
+  message: string
+}
+
+export interface SelfTestReport {
+  ok: boolean
+  startedAt: string
+  totalMs: number
+  stages: SelfTestStageResult[]
+}
+
+export interface VoiceCommandPreview {
+  type: 'command' | 'text' | 'exec'
+  normalized: string
+  phrase: string | null
+  shortcut: string | null
+  text: string
+  pressEnter: boolean
+  candidates?: VoiceCommandCandidate[]
+}
+
+export type PostprocessStage = 'textRules' | 'stopwords' | 'dedupe' | 'punctuation' | 'whitespace'
+
+export interface PostprocessPreview {
+  input: string
+  text: string
+  steps: Array<{ stage: PostprocessStage; text: string }>
+}
+
+export type RecognitionStage = 'encoding' | 'uploading' | 'server-processing' | 'matching' | 'inserting'
+
+export interface RecognitionStageEvent {
+  requestId: number
+  traceId: string
+  stage: RecognitionStage
+  elapsedMs: number
+}
+
+// 录音过短（误触）未送识别，建议重新录音
+export interface ReattemptSuggestion {
+  capturedMs: number
+  mode: 'toggle' | 'hold'
+  autoRearm: boolean
+  attempt: number
+}
+
+// 快捷键试解析结果：steps 为每一步的按键名称，如 [['Ctrl', 'Shift', 'F2'], ['S']]
+export interface ShortcutValidation {
+  ok: boolean
+  steps: string[][]
+  error?: string
+}
+
+// 指令方案：叠加在基础指令表之上的一组口令
+export interface VoiceCommandProfile {
+  name: string
+  commands: Record<string, string>
+}
+
+export interface VoiceCommandProfileEntry {
+  name: string
+  active: boolean
+  commandCount: number
+}
+
+// listProfiles 返回值与 profile-changed 事件载荷；active 为空字符串表示只使用基础指令表
+export interface VoiceCommandProfileState {
+  active: string
+  profiles: VoiceCommandProfileEntry[]
+}
+
+export interface VoiceCommandEntry {
+  phrase: string
+  shortcut: string
+  kind: 'shortcut' | 'text' | 'exec'
+  valid: boolean
+  error?: string
+}
+
+// 当前生效的全局热键；status 为 failed（被占用）或 invalid（无法解析）时 detail 说明原因
+export interface HotkeyBinding {
+  action: 'record' | 'repeat-command' | 'undo-last-insert' | 'cycle-profile' | 'vad-toggle' | 'rewrite'
+  accelerator: string
+  status: 'registered' | 'failed' | 'invalid'
+  detail?: string
+}
+
+// 热键可用性检查结果；ownedBy 表示本程序已注册该热键（对应动作）
+export interface HotkeyAvailability {
+  accelerator: string
+  available: boolean
+  ownedBy?: HotkeyBinding['action']
+  reason?: 'invalid' | 'conflict'
+  detail?: string
+}
+
 export interface HotwordScene {
   name: string
   words: string[]
@@ -136,12 +372,22 @@ export interface ModelCatalogItem {
   size: string
 }
 
+// 日志级别（logging.level），低于该级别的日志不记录
+export type LogLevel = 'debug' | 'info' | 'warn' | 'error'
+
 export interface LogEntry {
   time: string
   level: string
   msg: string
 }
 
+// 最近一次错误（识别失败、输入失败、本地模型启动失败），下一次识别成功或手动清除后为 null
+export interface LastError {
+  category: 'recognition' | 'input' | 'model'
+  message: string
+  at: string
+}
+
 export interface AsrRuntimeStatus {
   phase: 'idle' | 'starting' | 'ready' | 'error'
   modelId: string | null
@@ -150,6 +396,38 @@ export interface AsrRuntimeStatus {
   updatedAt: string
 }
 
+// 服务器连通性巡检结果（server-status 事件）
+export interface ServerStatus {
+  state: 'unknown' | 'connected' | 'unreachable' | 'disabled'
+  message: string
+  checkedAt: string
+  latencyMs: number | null
+  error?: string
+}
+
+// 服务器往返耗时记录（巡检与手动 ping），latencyMs 为 null 表示未连通
+export interface LatencySample {
+  at: string
+  latencyMs: number | null
+  error?: string
+}
+
+export interface LatencySummary {
+  count: number
+  failures: number
+  lastMs: number | null
+  avgMs: number | null
+  maxMs: number | null
+}
+
+// 输入模拟可用性（input-permission-denied 事件 / recheckInputPermissions 返回值）
+export interface InputPermissionState {
+  available: boolean
+  checkedAt: string
+  error?: string
+  guide?: string
+}
+
 export interface FloatLayoutMetrics {
   width: number
   height: number
@@ -197,9 +475,161 @@ export interface AudioInputConstraintsConfig {
 
 export interface AudioCaptureConfig {
   inputConstraints: AudioInputConstraintsConfig
+  channels: 'mono' | 'stereo'
   postRollMs: number
   tailSilenceMs: number
+  padMs: number
   workletFlushTimeoutMs: number
+  inputGain: number
+  soundCues: boolean
+  host: string
+  source: 'input' | 'loopback'
+  retainLastRecording: boolean
+}
+
+// 增益后的输入电平（VU 表），录音或 VAD 监听时约每 100ms 推送一次
+export interface InputLevel {
+  rms: number
+  peak: number
+  gain: number
+  clipped: boolean   // 本周期内有采样因增益过大被截断
+}
+
+// 识别结果（recognizeWav / repeatLastCommand 返回值）：command 为已发送的指令快捷键，text 为已输入（或待回显）的文字；
+// phrase 为命中的口令，repeated 表示“重复上一条指令”
+export type RecognitionResult =
+  | { kind: 'command'; text: string; phrase: string; shortcut: string; repeated?: boolean }
+  | { kind: 'text'; text: string; phrase?: string }
+
+// 当前使用的麦克风，deviceId 为空表示系统默认设备
+export interface InputDeviceSelection {
+  deviceId: string
+  deviceName: string
+}
+
+// 浮窗回报的定时录音是否已开始
+export type QuickRecordReport =
+  | { requestId: number; ok: true }
+  | { requestId: number; ok: false; message: string }
+
+// 浮窗回报的麦克风切换结果
+export type InputDeviceSwitchReport =
+  | ({ requestId: number; ok: true } & InputDeviceSelection)
+  | { requestId: number; ok: false; message: string }
+
+// 麦克风实际格式：设备打开后的源采样率/声道（getSettings 结果，部分设备不提供时为 null），
+// 识别统一重采样为 outputSampleRate（16kHz）后再编码
+export interface AudioInfo {
+  sourceSampleRate: number | null
+  sourceChannels: number | null
+  outputSampleRate: number
+  outputChannels: number
+  deviceName: string
+  capturing: boolean
+  vadListening: boolean
+  updatedAt: string
+}
+
+// 录音实际采样率与识别要求不一致（未重采样），浮窗每次运行上报一次，主进程写日志并以 sample-rate-warning 广播
+export interface SampleRateWarning {
+  actualRate: number
+  expectedRate: number
+  message: string
+}
+
+// 麦克风采集生命周期（上报主进程写日志，异常节点由主进程以 audio-thread-error 广播）
+export type AudioLifecycleStage = 'stream-opened' | 'stream-open-failed' | 'stream-ended' | 'capture-started' | 'capture-error'
+
+export interface AudioLifecycleEvent {
+  stage: AudioLifecycleStage
+  source: 'capture' | 'vad'
+  deviceName: string
+  message: string
+  at: string
+}
+
+// 远程 ASR 原始响应（logging.logAsrResponses 开启时推送）
+export interface AsrRawResponse {
+  requestId: number
+  transport: 'http' | 'ws'
+  body: string
+  length: number      // 原始响应字符数
+  truncated: boolean  // body 是否因过长被截断
+}
+
+// 连续多次识别结果为空时推送，提示用户检查麦克风
+export interface RecognitionEmptyWarning {
+  count: number     // 连续空结果次数
+  message: string
+}
+
+// 识别文字超出 postprocess.minChars / maxChars 被丢弃时推送
+export interface ResultFiltered {
+  requestId: number
+  text: string
+  reason: 'too-short' | 'too-long'
+  length: number    // 按 Unicode 字符计的字数
+  minChars: number
+  maxChars: number
+}
+
+// 整段口述记录：active 表示显式开始且尚未结束
+export interface SessionTranscriptState {
+  active: boolean
+  startedAt: number | null
+  segmentCount: number
+  text: string
+}
+
+export interface AudioHostInfo {
+  id: string
+  label: string
+}
+
+export interface PostprocessConfig {
+  dedupeRepeats: boolean
+  dedupeMinUnitChars: number
+  punctuation: 'server' | 'local' | 'off'
+  stopwords: string[]
+  trimTrailingWhitespace: boolean
+  collapseSpaces: boolean
+  stripNewlines: boolean
+  minChars: number
+  maxChars: number
+}
+
+export interface InputConfig {
+  useClipboard: boolean
+  keyboardMethod: 'text' | 'unicode'
+  mapCtrlToCmdOnMac: boolean
+  shortcutHoldMs: number
+  shortcutKeyDelayMs: number
+  shortcutStepDelayMs: number
+  releaseModifiersOnFailure: boolean
+  pasteShortcut: string
+  pasteMode: 'replace' | 'append'
+  appendNavigationKey: string
+  quickRecordMs: number
+  safeMode: boolean
+  safeModeIdleMs: number
+  clipboardOnFailure: boolean
+  autoReattempt: boolean
+  targetWindowTitle: string
+  continuousHold: boolean
+  specialChars: Record<string, 'unicode' | 'clipboard'>
+}
+
+export interface VoiceCommandOptionsConfig {
+  enabled: boolean
+  cooldownMs: number
+  cooldownOverrides: Record<string, number>
+  enterAfterPhrases: string[]
+  candidateThreshold: number
+  autoPickTopCandidate: boolean
+  repeatPhrase: string
+  postCommandLockoutMs: number
+  postCommandLockoutAction: 'text' | 'drop'
+  allowExec: boolean
 }
 
 export interface OnboardingConfig {
@@ -210,18 +640,78 @@ export interface OnboardingConfig {
 
 // 配置类型（与主进程保持一致）
 export interface AppConfig {
-  server: { url: string; asrConfigId: string }
-  hotkey: { record: string }
-  input: { useClipboard: boolean }
+  server: {
+    url: string
+    asrConfigId: string
+    autoPrependScheme: boolean
+    transport: 'http' | 'ws'
+    audioEncoding: 'wav' | 'flac'
+    audioMime: string
+    requestIdHeader: string
+    fileFieldName: string
+    configIdFieldName: string
+    minRequestIntervalMs: number
+    requestTimeoutMs: number
+    rateLimitPushToTalk: boolean
+    healthPath: string
+    healthCheckIntervalSec: number
+    redirect: 'follow' | 'error'
+    language: 'zh' | 'en' | 'auto'
+  }
+  hotkey: {
+    record: string
+    repeatCommand: string
+    undoLastInsert: string
+    cycleProfile: string
+    recordMode: 'hold' | 'tap-or-hold'
+    tapThresholdMs: number
+  }
+  input: InputConfig
   audioCapture: AudioCaptureConfig
-  vad: { enabled: boolean; speechThreshold: number; silenceTimeoutMs: number; minSpeechDurationMs: number }
+  vad: {
+    enabled: boolean
+    speechThreshold: number
+    silenceTimeoutMs: number
+    minSpeechDurationMs: number
+    frameMs: number
+    activationPhrase: string
+    minUtteranceMs: number
+    maxUtteranceMs: number
+    autoDisableAfterIdleMs: number
+    sentenceHoldMs: number
+    energySmoothing: number
+    backend: 'energy' | 'fsmn'
+  }
   voiceCommands: Record<string, string>
+  voiceCommandAliases: Record<string, string[]>
+  voiceCommandProfiles: VoiceCommandProfile[]
+  activeVoiceCommandProfile: string
+  voiceCommandOptions: VoiceCommandOptionsConfig
   hotwords: HotwordScene[]
   textRules: TextRulesConfig
-  asr: { mode: 'api' | 'local'; localModel: string; puncEnabled: boolean }
+  postprocess: PostprocessConfig
+  asr: {
+    mode: 'api' | 'local' | 'commands'
+    localModel: string
+    puncEnabled: boolean
+    commandSpotMaxDistance: number
+    emptyResultWarningCount: number
+  }
   onboarding?: OnboardingConfig
   llm: LlmConfig
-  logging: { enableDebug: boolean; showFloatBounds: boolean }
+  logging: {
+    level: LogLevel
+    enableDebug: boolean
+    showFloatBounds: boolean
+    logAsrResponses: boolean
+    toFile: boolean
+    maxFileSizeMb: number
+    maxFiles: number
+  }
+  tray: { showTrigger: 'left-click' | 'double-click' | 'right-click' }
+  webhook: { url: string; timeoutMs: number }
+  transcript: { enabled: boolean; idleGapMs: number }
+  ui: { resultDisplayMs: number }
 }
 
 export type RecordState = 'idle' | 'initializing' | 'recording' | 'recognizing' | 'success'
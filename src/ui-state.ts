@@ -1,5 +1,25 @@
-import type { RecordState, AsrRuntimeStatus, AppConfig } from './types'
-import { startCapture, stopCapture, startVad, stopVad, resetVadSpeakingState, setAudioCaptureConfig, VadState, VadCallbacks } from './audio'
+import type { RecordState, AsrRuntimeStatus, AppConfig, RecognitionResult } from './types'
+import {
+  startCapture,
+  stopCapture,
+  discardCapture,
+  startVad,
+  stopVad,
+  resetVadSpeakingState,
+  setAudioCaptureConfig,
+  getRecordingDurationMs,
+  getLastCapturedMs,
+  getLastCaptureSegmentCount,
+  VadState,
+  VadCallbacks,
+  type CaptureSegmentation,
+} from './audio'
+import { formatElapsedMs, formatRecognitionResult } from './utils'
+import { truncateText } from '../shared/text'
+import { VAD_DEFAULT_FRAME_MS, VAD_MAX_SPEECH_MS, VAD_DEFAULT_ENERGY_SMOOTHING, clampEnergySmoothing } from './vad'
+import { setSoundCuesEnabled, playStartCue, playStopCue } from './sound-cues'
+import { isFumbledCapture, ReattemptGuard } from './reattempt'
+import { quickRecordRemainingSec } from './quick-record'
 
 // ── 共享 UI 状态 ──
 
@@ -12,10 +32,18 @@ export let dashboardVadToggle: HTMLInputElement | null = null
 export let errorBar: HTMLDivElement | null = null
 let errorTimer: ReturnType<typeof setTimeout> | null = null
 let successFlashTimer: ReturnType<typeof setTimeout> | null = null
+let recordingElapsedTimer: ReturnType<typeof setInterval> | null = null
+let quickRecordTimer: ReturnType<typeof setTimeout> | null = null
+let quickRecordStartedAt = 0
+let quickRecordDurationMs = 0
 const SUCCESS_FLASH_MS = 180
+const RESULT_PREVIEW_MAX_CHARS = 40
 
 let startCapturePromise: Promise<void> | null = null
 let focusSnapshotAppId: string | null = null
+// input.continuousHold 开启时热键录音的分段参数（每次开始录音前随配置刷新），关闭时为 null
+let continuousHold: Omit<CaptureSegmentation, 'onSegment'> | null = null
+let holdSegmentsRecognized = 0
 
 const VAD_THRESHOLD_MIN = 0.01
 const VAD_THRESHOLD_MAX = 0.2
@@ -30,7 +58,18 @@ let asrRuntimeStatus: AsrRuntimeStatus = {
 }
 let lastAsrRuntimeError = ''
 
-export let vadState: VadState = { enabled: false, threshold: VAD_DEFAULT_THRESHOLD, silenceMs: 500, minSpeechMs: 300 }
+export let vadState: VadState = {
+  enabled: false,
+  threshold: VAD_DEFAULT_THRESHOLD,
+  silenceMs: 500,
+  minSpeechMs: 300,
+  frameMs: VAD_DEFAULT_FRAME_MS,
+  minUtteranceMs: 0,
+  maxUtteranceMs: VAD_MAX_SPEECH_MS,
+  autoDisableAfterIdleMs: 0,
+  sentenceHoldMs: 0,
+  energySmoothing: VAD_DEFAULT_ENERGY_SMOOTHING,
+}
 let vadSyncVersion = 0
 
 export function getState(): RecordState { return state }
@@ -73,8 +112,19 @@ export async function captureFocusSnapshot(reason: string): Promise<string | nul
 // ── UI 状态 ──
 
 export function setState(newState: RecordState | string, text?: string) {
+  const prevState = state
   state = newState as RecordState
+  // VAD 模式下提示音会被麦克风录入并触发新语音段，只在手动录音时播放
+  if (!vadState.enabled && prevState !== newState) {
+    if (newState === 'recording') playStartCue()
+    else if (prevState === 'recording') playStopCue()
+  }
+  // 开始录音时通知主进程取消上一句结果的自动隐藏
+  if (newState === 'recording' && prevState !== 'recording') {
+    void window.electronAPI.reportRecordingStarted().catch(() => { })
+  }
   recordBtn?.classList.remove('initializing', 'recording', 'recognizing', 'success')
+  if (newState !== 'recording') stopRecordingElapsedTimer()
 
   switch (newState) {
     case 'idle':
@@ -97,6 +147,7 @@ export function setState(newState: RecordState | string, text?: string) {
         statusText.classList.remove('result', 'command')
       }
       hideError()
+      startRecordingElapsedTimer()
       break
     case 'recognizing':
       recordBtn?.classList.add('recognizing')
@@ -108,6 +159,24 @@ export function setState(newState: RecordState | string, text?: string) {
   }
 }
 
+// 录音中在状态栏显示已录时长（0:03）
+function startRecordingElapsedTimer() {
+  if (recordingElapsedTimer) return
+  recordingElapsedTimer = setInterval(() => {
+    const elapsedMs = getRecordingDurationMs()
+    if (state !== 'recording' || !statusText || elapsedMs <= 0) return
+    statusText.textContent = quickRecordTimer
+      ? `定时录音 剩余 ${quickRecordRemainingSec(quickRecordStartedAt, quickRecordDurationMs, Date.now())} 秒`
+      : `录音中 ${formatElapsedMs(elapsedMs)}${holdSegmentsRecognized > 0 ? ` · 已识别 ${holdSegmentsRecognized} 段` : ''}`
+  }, 250)
+}
+
+function stopRecordingElapsedTimer() {
+  if (!recordingElapsedTimer) return
+  clearInterval(recordingElapsedTimer)
+  recordingElapsedTimer = null
+}
+
 export function showError(msg: string) {
   const text = String(msg).replace(/^Error:\s*/i, '')
   // errorBar 可能尚未通过 initFloatElements 初始化，动态查找
@@ -137,15 +206,29 @@ export function hideError() {
   if (errorTimer) { clearTimeout(errorTimer); errorTimer = null }
 }
 
-export function showResult(text: string) {
+// 最近一次识别是否已输入到目标应用（由 input-complete / input-failed 事件在结果返回前更新）
+let pendingInputOutcome: 'inserted' | 'not-inserted' | null = null
+
+export function setInputOutcome(outcome: 'inserted' | 'not-inserted') {
+  pendingInputOutcome = outcome
+}
+
+// 识别结果按类型展示；也接受普通提示文字（如候选指令、复制成功等）
+export function showResult(result: RecognitionResult | string) {
+  const text = typeof result === 'string' ? result : formatRecognitionResult(result)
   hideError()
   if (state !== 'idle') {
     setState('idle')
   }
-  flashSuccessState()
-  const isCommand = text.includes('⌨')
+  const notInserted = pendingInputOutcome === 'not-inserted'
+  pendingInputOutcome = null
+  if (!notInserted) flashSuccessState()
+  const isCommand = typeof result !== 'string' && result.kind === 'command'
   if (statusText) {
-    statusText.textContent = text || '（空）'
+    // 浮窗只显示前 RESULT_PREVIEW_MAX_CHARS 个字，悬停 title 可看完整文本（结果复位为“就绪”时一并清空）
+    statusText.textContent = text
+      ? `${truncateText(text, RESULT_PREVIEW_MAX_CHARS)}${notInserted ? '（未输入）' : ''}`
+      : '（空）'
     statusText.title = text || ''
     statusText.classList.remove('result', 'command')
     statusText.classList.add(isCommand ? 'command' : 'result')
@@ -222,6 +305,7 @@ export async function ensureAsrReadyBeforeCapture(): Promise<boolean> {
   try {
     cfg = await window.electronAPI.getConfig()
     applyAudioCaptureFromConfig(cfg)
+    applyContinuousHoldFromConfig(cfg)
   } catch {
     return true
   }
@@ -248,6 +332,47 @@ export async function ensureAsrReadyBeforeCapture(): Promise<boolean> {
 function applyAudioCaptureFromConfig(cfg: Pick<AppConfig, 'audioCapture'> | null | undefined) {
   if (!cfg) return
   setAudioCaptureConfig(cfg.audioCapture)
+  setSoundCuesEnabled(Boolean(cfg.audioCapture?.soundCues))
+}
+
+// 连续分段沿用 VAD 的阈值、静音时长、最长语音段与能量平滑，不要求开启 VAD
+function applyContinuousHoldFromConfig(cfg: Pick<AppConfig, 'input' | 'vad'>) {
+  continuousHold = cfg.input?.continuousHold
+    ? {
+      frameMs: cfg.vad.frameMs ?? VAD_DEFAULT_FRAME_MS,
+      params: {
+        threshold: clampVadThreshold(cfg.vad.speechThreshold),
+        silenceMs: cfg.vad.silenceTimeoutMs,
+        maxUtteranceMs: cfg.vad.maxUtteranceMs ?? VAD_MAX_SPEECH_MS,
+        energySmoothing: clampEnergySmoothing(cfg.vad.energySmoothing),
+      },
+    }
+    : null
+}
+
+// 热键录音开始采集；开启连续分段时，每切出一段立即送识别并输入，松开热键后由调用方识别剩余部分。
+// 各段与剩余部分在主进程按发送顺序串行识别，输入顺序与口述顺序一致；
+// 热键仍按住时主进程暂缓中间段的输入，松开热键与修饰键后再依次输入
+export function startHotkeyCapture(): Promise<void> {
+  holdSegmentsRecognized = 0
+  if (!continuousHold) return startCapture()
+  return startCapture(undefined, {
+    ...continuousHold,
+    onSegment: (wav) => {
+      uiTrace('continuous-hold.segment', { wavBytes: wav.byteLength })
+      window.electronAPI.recognizeWav(wav, null, Date.now(), 'segment')
+        .then((result) => { if (result) showSegmentResult(result) })
+        .catch((e) => showError(String(e)))
+    },
+  })
+}
+
+// 中间段的结果不改变录音状态，只计数并闪一下成功提示，完整文字已输入到目标应用
+function showSegmentResult(result: RecognitionResult) {
+  pendingInputOutcome = null
+  holdSegmentsRecognized += 1
+  flashSuccessState()
+  if (statusText) statusText.title = formatRecognitionResult(result)
 }
 
 function clampVadThreshold(raw: number): number {
@@ -265,8 +390,43 @@ export function applyVadThreshold(threshold: number): number {
   return next
 }
 
+// 运行时调整能量平滑，下一帧起生效，不重启监听
+export function applyVadEnergySmoothing(smoothing: number): number {
+  const next = clampEnergySmoothing(smoothing)
+  vadState.energySmoothing = next
+  return next
+}
+
 // ── 录音按钮点击 ──
 
+const reattemptGuard = new ReattemptGuard()
+
+// 录音过短（误触）时不发起识别：通知主进程并提示重试；点击切换录音且开启自动重试时直接重新开始录音。
+// 返回 true 表示已按误触处理
+export async function handleFumbledCapture(mode: 'toggle' | 'hold', prevAppId: string | null): Promise<boolean> {
+  const capturedMs = getLastCapturedMs()
+  // 连续分段已送出过语音段时，松开热键后剩余的一小段不算误触
+  if (getLastCaptureSegmentCount() > 0 || !isFumbledCapture(capturedMs)) {
+    reattemptGuard.reset()
+    return false
+  }
+  let autoRearm = false
+  try {
+    const cfg = await window.electronAPI.getConfig()
+    autoRearm = mode === 'toggle' && Boolean(cfg.input?.autoReattempt) && reattemptGuard.tryConsume()
+  } catch (e) {
+    console.warn('[Record] load config for reattempt failed:', e)
+  }
+  void window.electronAPI.reportReattemptSuggested({ capturedMs, mode, autoRearm, attempt: reattemptGuard.used })
+    .catch(() => { })
+  setState('idle', autoRearm ? '' : '录音太短，请重试')
+  if (autoRearm) {
+    focusSnapshotAppId = prevAppId
+    await startManualRecording(true)
+  }
+  return true
+}
+
 export async function onRecordClick() {
   uiTrace('record-click.enter')
   if (state === 'recognizing') {
@@ -275,24 +435,10 @@ export async function onRecordClick() {
   }
 
   if (state === 'idle') {
-    if (!await ensureAsrReadyBeforeCapture()) return
-    try {
-      uiTrace('record-click.start-capture.begin')
-      if (!focusSnapshotAppId) {
-        await captureFocusSnapshot('record-start')
-      }
-      uiTrace('record-click.start-capture.prev-app', { focusSnapshotAppId })
-      startCapturePromise = startCapture()
-      await startCapturePromise
-      uiTrace('record-click.start-capture.ready')
-      setState('recording')
-    } catch (e) {
-      startCapturePromise = null
-      focusSnapshotAppId = null
-      uiTrace('record-click.start-capture.error', { error: String(e) })
-      showError(String(e))
-    }
+    await startManualRecording(false)
   } else if (state === 'recording') {
+    const triggeredAt = Date.now()
+    clearQuickRecordTimer()
     // 手动点击停止录音时，重置 VAD 内部状态，避免状态机卡死
     if (vadState.enabled) resetVadSpeakingState()
     setState('recognizing')
@@ -304,8 +450,9 @@ export async function onRecordClick() {
       const wav = await stopCapture()
       const prevAppId = focusSnapshotAppId
       focusSnapshotAppId = null
+      if (await handleFumbledCapture('toggle', prevAppId)) return
       uiTrace('record-click.stop-capture.begin-recognize', { wavBytes: wav.byteLength, prevAppId })
-      const result = await window.electronAPI.recognizeWav(wav, prevAppId)
+      const result = await window.electronAPI.recognizeWav(wav, prevAppId, triggeredAt)
       uiTrace('record-click.stop-capture.result', { result })
       setState('idle')
       if (result) showResult(result)
@@ -319,6 +466,68 @@ export async function onRecordClick() {
   }
 }
 
+// 丢弃当前录音（discard-recording）：停止采集、清空缓冲，不送识别，回到空闲；
+// 与识别中的取消不同，只作用于录音阶段，未在录音时不做任何事
+export async function discardRecording() {
+  if (state !== 'recording') return
+  uiTrace('discard-recording')
+  clearQuickRecordTimer()
+  const pending = startCapturePromise
+  startCapturePromise = null
+  if (pending) await pending.catch(() => { })
+  if (vadState.enabled) resetVadSpeakingState()
+  discardCapture()
+  focusSnapshotAppId = null
+  setState('idle')
+}
+
+// 定时录音：开始录音，到时后按点击停止的流程识别并输入；期间点击录音按钮可提前结束。
+// durationMs 已由主进程限制在允许范围内；返回是否真正开始了录音
+export async function quickRecordAndRecognize(durationMs: number): Promise<boolean> {
+  if (state !== 'idle' || startCapturePromise || quickRecordTimer) {
+    showError('正在录音或识别中，无法开始定时录音')
+    return false
+  }
+  await startManualRecording(false)
+  if (state !== 'recording') return false
+  uiTrace('quick-record.start', { durationMs })
+  quickRecordStartedAt = Date.now()
+  quickRecordDurationMs = durationMs
+  quickRecordTimer = setTimeout(() => {
+    quickRecordTimer = null
+    if (state === 'recording') void onRecordClick()
+  }, durationMs)
+  return true
+}
+
+function clearQuickRecordTimer() {
+  if (!quickRecordTimer) return
+  clearTimeout(quickRecordTimer)
+  quickRecordTimer = null
+}
+
+// 点击开始录音；autoRearm 为误触后自动重新开始，不重置重试计数
+async function startManualRecording(autoRearm: boolean) {
+  if (!await ensureAsrReadyBeforeCapture()) return
+  if (!autoRearm) reattemptGuard.reset()
+  try {
+    uiTrace('record-click.start-capture.begin', { autoRearm })
+    if (!focusSnapshotAppId) {
+      await captureFocusSnapshot('record-start')
+    }
+    uiTrace('record-click.start-capture.prev-app', { focusSnapshotAppId })
+    startCapturePromise = startCapture()
+    await startCapturePromise
+    uiTrace('record-click.start-capture.ready')
+    setState('recording')
+  } catch (e) {
+    startCapturePromise = null
+    focusSnapshotAppId = null
+    uiTrace('record-click.start-capture.error', { error: String(e) })
+    showError(String(e))
+  }
+}
+
 // ── VAD 切换 ──
 
 export function syncVadUi(enabled: boolean) {
@@ -337,7 +546,13 @@ function makeVadCallbacks(): VadCallbacks {
     showError,
     showResult,
     captureFocusSnapshot,
-    recognizeWav: (wav, prevAppId) => window.electronAPI.recognizeWav(wav, prevAppId),
+    recognizeWav: (wav, prevAppId, triggeredAt) => window.electronAPI.recognizeWav(wav, prevAppId, triggeredAt, 'vad'),
+    // 由主进程统一关闭（保存配置、更新托盘），再通过 toggle-vad 回到这里停止监听
+    onIdleTimeout: (idleMs) => {
+      void window.electronAPI.reportVadIdleTimeout(idleMs).catch((e) => {
+        console.warn('[VAD] reportVadIdleTimeout failed:', e)
+      })
+    },
   }
 }
 
@@ -350,6 +565,12 @@ export async function applyVadEnabled(enabled: boolean, showHint: boolean) {
       threshold: VAD_DEFAULT_THRESHOLD,
       silenceMs: cfg.vad.silenceTimeoutMs,
       minSpeechMs: cfg.vad.minSpeechDurationMs,
+      frameMs: cfg.vad.frameMs ?? VAD_DEFAULT_FRAME_MS,
+      minUtteranceMs: cfg.vad.minUtteranceMs ?? 0,
+      maxUtteranceMs: cfg.vad.maxUtteranceMs ?? VAD_MAX_SPEECH_MS,
+      autoDisableAfterIdleMs: cfg.vad.autoDisableAfterIdleMs ?? 0,
+      sentenceHoldMs: cfg.vad.sentenceHoldMs ?? 0,
+      energySmoothing: clampEnergySmoothing(cfg.vad.energySmoothing),
     }
     applyVadThreshold(cfg.vad.speechThreshold)
     try {
@@ -380,6 +601,13 @@ export async function applyVadEnabled(enabled: boolean, showHint: boolean) {
   }
 }
 
+// 切换 VAD 检测方式后重启监听：丢弃进行中的语音段，按最新配置重新开始
+export async function restartVad() {
+  if (!vadState.enabled) return
+  stopVad()
+  await applyVadEnabled(true, false)
+}
+
 export async function initVad() {
   const version = ++vadSyncVersion
   let lastError: unknown = null
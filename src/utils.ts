@@ -1,3 +1,5 @@
+import type { AudioInfo, RecognitionResult } from './types'
+
 export function withTimeout<T>(promise: Promise<T>, timeoutMs: number, label: string): Promise<T> {
   return new Promise<T>((resolve, reject) => {
     const timer = setTimeout(() => reject(new Error(`${label} timeout (${timeoutMs}ms)`)), timeoutMs)
@@ -7,3 +9,31 @@ export function withTimeout<T>(promise: Promise<T>, timeoutMs: number, label: st
     )
   })
 }
+
+// 毫秒格式化为 m:ss（录音计时显示）
+export function formatElapsedMs(ms: number): string {
+  const totalSeconds = Math.max(0, Math.floor(ms / 1000))
+  const minutes = Math.floor(totalSeconds / 60)
+  const seconds = totalSeconds % 60
+  return `${minutes}:${String(seconds).padStart(2, '0')}`
+}
+
+// 识别结果的展示文本：指令显示为“口令 ⌨ 快捷键”，重复指令加“（重复）”，文字结果原样显示
+export function formatRecognitionResult(result: RecognitionResult): string {
+  if (result.kind === 'text') return result.text
+  return `${result.text}${result.repeated ? '（重复）' : ''} ⌨ ${result.shortcut}`
+}
+
+// 麦克风格式摘要（设置页显示），如“USB 麦克风 · 48kHz 单声道 → 16kHz 单声道”
+export function formatAudioInfo(info: AudioInfo): string {
+  const khz = (hz: number) => `${Number((hz / 1000).toFixed(1))}kHz`
+  const channels = (n: number) => n === 1 ? '单声道' : n === 2 ? '立体声' : `${n} 声道`
+  if (info.sourceSampleRate === null && !info.deviceName) return '麦克风未打开'
+  const source = [
+    info.sourceSampleRate !== null ? khz(info.sourceSampleRate) : '采样率未知',
+    info.sourceChannels !== null ? channels(info.sourceChannels) : '',
+  ].filter(Boolean).join(' ')
+  const output = `${khz(info.outputSampleRate)} ${channels(info.outputChannels)}`
+  const state = info.capturing ? '（录音中）' : info.vadListening ? '（VAD 监听中）' : ''
+  return `${info.deviceName || '默认设备'} · ${source} → ${output}${state}`
+}
@@ -0,0 +1,315 @@
+// VAD 状态机：只根据逐帧 RMS 与时间判断语音起止，不涉及音频设备，便于单元测试。
+// 时间源可注入：运行时使用 VadSampleClock（按采样数计时），测试中用假时钟模拟静音超时而无需真实等待
+
+// 逐帧能量的平滑系数：smoothed = s * smoothed + (1 - s) * rms。0 为不平滑，越大越能压住噪声毛刺但起止反应越慢
+export const VAD_DEFAULT_ENERGY_SMOOTHING = 0.72
+export const VAD_MAX_ENERGY_SMOOTHING = 0.95
+const VAD_STOP_HYSTERESIS_RATIO = 0.72
+const VAD_NOISE_FLOOR_EMA_ALPHA = 0.08
+const VAD_NOISE_FLOOR_START_RATIO = 1.6
+const VAD_NOISE_FLOOR_STOP_RATIO = 1.18
+export const VAD_START_TRIGGER_MS = 60
+export const VAD_RELEASE_TRIGGER_MS = 80
+export const VAD_ENDPOINT_HANGOVER_MS = 60
+export const VAD_MAX_SPEECH_MS = 12000
+export const VAD_HARD_MIN_WAV_MS = 90
+
+export type VadClock = () => number
+
+export interface VadFrameParams {
+  threshold: number
+  silenceMs: number
+  minUtteranceMs?: number   // 语音段短于该值时以 too-short 结束（抽屉、器械碰撞等瞬态噪声），0 或缺省不判断
+  maxUtteranceMs?: number   // 语音段超过该值时强制切段送识别，缺省为 VAD_MAX_SPEECH_MS
+  sentenceHoldMs?: number   // 静音超时后再等待的宽限期，期间重新开口则并入同一语音段，0 或缺省不等待
+  energySmoothing?: number  // 能量平滑系数 0~VAD_MAX_ENERGY_SMOOTHING，缺省为 VAD_DEFAULT_ENERGY_SMOOTHING
+}
+
+export function clampEnergySmoothing(raw: unknown): number {
+  const value = Number(raw)
+  if (!Number.isFinite(value)) return VAD_DEFAULT_ENERGY_SMOOTHING
+  return Math.min(VAD_MAX_ENERGY_SMOOTHING, Math.max(0, value))
+}
+
+export type VadSpeechEndReason = 'silence' | 'max-duration' | 'too-short'
+
+export type VadFrameEvent =
+  | { type: 'none' }
+  | { type: 'speech-start'; at: number }
+  | { type: 'speech-end'; speechStartAt: number; speechEndAt: number; reason: VadSpeechEndReason }
+
+export class VoiceActivityDetector {
+  private speaking = false
+  private speakingStart = 0
+  private silenceStart = 0
+  private smoothedRms = 0
+  private noiseFloorRms = 0
+  private aboveThresholdSince = 0
+  private belowThresholdSince = 0
+  private holdingSince = 0
+
+  constructor(private readonly now: VadClock = Date.now) { }
+
+  get isSpeaking(): boolean { return this.speaking }
+  get speechStartedAt(): number { return this.speakingStart }
+  get smoothedLevel(): number { return this.smoothedRms }
+  get noiseFloor(): number { return this.noiseFloorRms }
+
+  // 输入一帧的 RMS，返回本帧触发的事件
+  process(rms: number, params: VadFrameParams): VadFrameEvent {
+    const alpha = 1 - clampEnergySmoothing(params.energySmoothing ?? VAD_DEFAULT_ENERGY_SMOOTHING)
+    this.smoothedRms = this.smoothedRms === 0
+      ? rms
+      : this.smoothedRms + alpha * (rms - this.smoothedRms)
+    if (!this.speaking) {
+      this.noiseFloorRms = this.noiseFloorRms === 0
+        ? this.smoothedRms
+        : this.noiseFloorRms + VAD_NOISE_FLOOR_EMA_ALPHA * (this.smoothedRms - this.noiseFloorRms)
+    }
+
+    const now = this.now()
+    const configuredThreshold = Math.max(0.0001, params.threshold)
+    const adaptiveStartThreshold = Math.max(0.0001, this.noiseFloorRms * VAD_NOISE_FLOOR_START_RATIO)
+    const startThreshold = Math.max(configuredThreshold, adaptiveStartThreshold)
+    const stopThreshold = Math.max(
+      0.00005,
+      startThreshold * VAD_STOP_HYSTERESIS_RATIO,
+      this.noiseFloorRms * VAD_NOISE_FLOOR_STOP_RATIO,
+    )
+
+    if (!this.speaking) {
+      if (this.smoothedRms > startThreshold) {
+        if (!this.aboveThresholdSince) this.aboveThresholdSince = now
+        if (now - this.aboveThresholdSince >= VAD_START_TRIGGER_MS) {
+          this.speaking = true
+          this.speakingStart = now
+          this.silenceStart = now
+          this.aboveThresholdSince = 0
+          this.belowThresholdSince = 0
+          return { type: 'speech-start', at: now }
+        }
+      } else {
+        this.aboveThresholdSince = 0
+      }
+      return { type: 'none' }
+    }
+
+    if (now - this.speakingStart >= (params.maxUtteranceMs ?? VAD_MAX_SPEECH_MS)) {
+      this.silenceStart = now
+      return this.endSpeech(now, 'max-duration')
+    }
+
+    // 句内停顿宽限期：静音超时后暂不结束，按语音开始的标准（超过起始阈值持续 VAD_START_TRIGGER_MS）
+    // 判断是否重新开口，避免呼吸声、键盘声把本该结束的语音段拖长
+    if (this.holdingSince) {
+      if (this.smoothedRms > startThreshold) {
+        if (!this.aboveThresholdSince) this.aboveThresholdSince = now
+        if (now - this.aboveThresholdSince >= VAD_START_TRIGGER_MS) {
+          this.holdingSince = 0
+          this.aboveThresholdSince = 0
+          this.belowThresholdSince = 0
+          this.silenceStart = now
+        }
+        return { type: 'none' }
+      }
+      this.aboveThresholdSince = 0
+      if (now - this.holdingSince < (params.sentenceHoldMs ?? 0)) {
+        return { type: 'none' }
+      }
+      return this.endSilence(params)
+    }
+
+    if (this.smoothedRms > stopThreshold) {
+      this.silenceStart = now
+      this.belowThresholdSince = 0
+      return { type: 'none' }
+    }
+
+    if (!this.belowThresholdSince) {
+      this.belowThresholdSince = now
+      return { type: 'none' }
+    }
+    if (now - this.belowThresholdSince < VAD_RELEASE_TRIGGER_MS) {
+      return { type: 'none' }
+    }
+
+    const effectiveSilenceMs = params.silenceMs + VAD_ENDPOINT_HANGOVER_MS
+    if (now - this.silenceStart <= effectiveSilenceMs) {
+      return { type: 'none' }
+    }
+
+    if ((params.sentenceHoldMs ?? 0) > 0) {
+      this.holdingSince = now
+      return { type: 'none' }
+    }
+    return this.endSilence(params)
+  }
+
+  // 录音启动失败等情况下放弃当前语音段，保留噪声基线
+  abortSpeech(): void {
+    this.speaking = false
+    this.holdingSince = 0
+    this.aboveThresholdSince = 0
+    this.belowThresholdSince = 0
+  }
+
+  reset(): void {
+    this.speaking = false
+    this.speakingStart = 0
+    this.silenceStart = 0
+    this.smoothedRms = 0
+    this.noiseFloorRms = 0
+    this.aboveThresholdSince = 0
+    this.belowThresholdSince = 0
+    this.holdingSince = 0
+  }
+
+  // 静音结束：语音段起点到静音开始的时长不足 minUtteranceMs 时标记为 too-short
+  private endSilence(params: VadFrameParams): VadFrameEvent {
+    const minUtteranceMs = params.minUtteranceMs ?? 0
+    const tooShort = minUtteranceMs > 0 && this.silenceStart - this.speakingStart < minUtteranceMs
+    return this.endSpeech(this.silenceStart, tooShort ? 'too-short' : 'silence')
+  }
+
+  private endSpeech(speechEndAt: number, reason: VadSpeechEndReason): VadFrameEvent {
+    this.speaking = false
+    this.belowThresholdSince = 0
+    this.holdingSince = 0
+    this.aboveThresholdSince = 0
+    return { type: 'speech-end', speechStartAt: this.speakingStart, speechEndAt, reason }
+  }
+}
+
+// 按已处理的采样数计时：静音超时、最短语音等判断基于音频时间线，
+// 不受轮询间隔抖动、线程饥饿或设备批量回调的影响
+export class VadSampleClock {
+  private samples = 0
+
+  constructor(private sampleRate: number) { }
+
+  get nowMs(): number {
+    return (this.samples * 1000) / this.sampleRate
+  }
+
+  advance(samples: number): void {
+    if (samples > 0) this.samples += samples
+  }
+
+  reset(sampleRate = this.sampleRate): void {
+    this.sampleRate = sampleRate > 0 ? sampleRate : this.sampleRate
+    this.samples = 0
+  }
+}
+
+// 空闲计时：VAD 持续监听但长时间未检测到语音时，用于自动关闭 VAD 以释放麦克风与 CPU。
+// 使用墙钟而不是采样时钟：轮询暂停（手动录音、识别中）的时间同样算作空闲以外的活动，由调用方 touch
+export class VadIdleTimer {
+  private lastActivityAt: number
+
+  constructor(private readonly now: VadClock = Date.now) {
+    this.lastActivityAt = now()
+  }
+
+  touch(): void {
+    this.lastActivityAt = this.now()
+  }
+
+  idleMs(): number {
+    return Math.max(0, this.now() - this.lastActivityAt)
+  }
+
+  // timeoutMs 为 0 表示不自动关闭
+  isExpired(timeoutMs: number): boolean {
+    return timeoutMs > 0 && this.idleMs() >= timeoutMs
+  }
+}
+
+export const VAD_DEFAULT_FRAME_MS = 30
+
+export function frameSamplesForMs(frameMs: number, sampleRate: number): number {
+  const ms = Number.isFinite(frameMs) && frameMs > 0 ? frameMs : VAD_DEFAULT_FRAME_MS
+  return Math.max(1, Math.round((ms * sampleRate) / 1000))
+}
+
+// 把长度不定的采样块拼接为固定长度的帧，不足一帧的余量留到下一次
+export class VadFrameAccumulator {
+  private pending: Float32Array
+  private pendingLength = 0
+
+  constructor(readonly frameSamples: number) {
+    this.pending = new Float32Array(frameSamples)
+  }
+
+  get pendingSamples(): number { return this.pendingLength }
+
+  push(samples: Float32Array): Float32Array[] {
+    const frames: Float32Array[] = []
+    let offset = 0
+    while (offset < samples.length) {
+      const take = Math.min(this.frameSamples - this.pendingLength, samples.length - offset)
+      this.pending.set(samples.subarray(offset, offset + take), this.pendingLength)
+      this.pendingLength += take
+      offset += take
+      if (this.pendingLength === this.frameSamples) {
+        frames.push(this.pending)
+        this.pending = new Float32Array(this.frameSamples)
+        this.pendingLength = 0
+      }
+    }
+    return frames
+  }
+
+  reset(): void {
+    this.pendingLength = 0
+  }
+}
+
+// 按住说话的连续分段：录音过程中沿用 VAD 的静音判断，一句话说完（静音超时或超过最长时长）时提示调用方
+// 把已录部分切出送识别。输入为增益前的采样，立体声为交错采样、按两路合计能量判断；过短的语音段不切，随后续一起识别
+export class SilenceSegmenter {
+  private readonly clock: VadSampleClock
+  private readonly detector: VoiceActivityDetector
+  private readonly frames: VadFrameAccumulator
+  private readonly channels: number
+
+  constructor(sampleRate: number, channels = 1, frameMs = VAD_DEFAULT_FRAME_MS) {
+    this.channels = Math.max(1, Math.floor(channels))
+    this.clock = new VadSampleClock(sampleRate)
+    this.detector = new VoiceActivityDetector(() => this.clock.nowMs)
+    this.frames = new VadFrameAccumulator(frameSamplesForMs(frameMs, sampleRate) * this.channels)
+  }
+
+  get isSpeaking(): boolean { return this.detector.isSpeaking }
+
+  // 本批采样中有语音段结束时返回 true，调用方应在此批之后切段
+  push(samples: Float32Array, params: VadFrameParams): boolean {
+    let ended = false
+    for (const frame of this.frames.push(samples)) {
+      this.clock.advance(frame.length / this.channels)
+      const event = this.detector.process(computeRms(frame), params)
+      if (event.type === 'speech-end' && event.reason !== 'too-short') ended = true
+    }
+    return ended
+  }
+}
+
+export function computeRms(samples: Float32Array): number {
+  if (samples.length === 0) return 0
+  let sum = 0
+  for (const v of samples) sum += v * v
+  return Math.sqrt(sum / samples.length)
+}
+
+// 过短语音段（咳嗽、敲击等）不送识别；wavDurationMs 含首尾补齐的静音，判断前先扣除
+export function isSpeechSegmentLongEnough(segment: {
+  speechDurationMs: number
+  wavDurationMs: number
+  leadSilenceMs?: number
+  tailSilenceMs: number
+  minSpeechMs: number
+}): boolean {
+  const paddedMs = (segment.leadSilenceMs ?? 0) + segment.tailSilenceMs
+  const effectiveSpeechMs = Math.max(segment.speechDurationMs, segment.wavDurationMs - paddedMs)
+  const minSpeechGateMs = Math.max(VAD_HARD_MIN_WAV_MS, Math.min(segment.minSpeechMs, 260))
+  return effectiveSpeechMs >= minSpeechGateMs && segment.wavDurationMs >= VAD_HARD_MIN_WAV_MS
+}
@@ -10,9 +10,28 @@ import {
   initTabs,
   loadConfigToForm,
   saveConfig,
+  applyInputGainToForm,
+  renderInputLevel,
+  renderServerStatus,
+  renderInputPermission,
+  renderLastError,
+  runPipelineSelfTest,
+  renderAudioInfo,
+  renderSampleRateWarning,
+  renderReattemptSuggestion,
+  renderHotkeyBindings,
+  exportConfigToForm,
+  importConfigFromForm,
   appendCommandRow,
   saveCommands,
   renderCommandEditor,
+  previewCommandPhrase,
+  previewPostprocessText,
+  recognizeLastRecording,
+  runTranscriptAction,
+  renderCommandProfiles,
+  toggleCommandTemplateRecording,
+  clearCommandTemplates,
   loadHotwords,
   addHotword,
   addScene,
@@ -33,9 +52,10 @@ import {
   setModelListHint,
 } from '../dashboard-models'
 import { initFirstUseOnboarding } from './onboarding'
+import { setInputGain } from '../audio'
 
 import { marked } from 'marked'
-import type { RecognitionRecord } from '../types'
+import type { LogLevel, RecognitionRecord } from '../types'
 
 export function initDashboardUI() {
   document.getElementById('float-capsule-view')!.classList.remove('active')
@@ -88,9 +108,32 @@ export function initDashboardUI() {
   vadThresholdSlider?.addEventListener('input', onVadThresholdInput)
   vadThresholdSlider?.addEventListener('change', onVadThresholdChange)
 
+  // 软件增益拖动即生效：先更新显示，短暂防抖后写入配置并同步到浮窗
+  const inputGainSlider = document.getElementById('cfg-input-gain') as HTMLInputElement | null
+  let inputGainPersistTimer: ReturnType<typeof setTimeout> | null = null
+  inputGainSlider?.addEventListener('input', () => {
+    const gain = parseFloat(inputGainSlider.value)
+    applyInputGainToForm(gain)
+    if (inputGainPersistTimer) clearTimeout(inputGainPersistTimer)
+    inputGainPersistTimer = setTimeout(() => {
+      inputGainPersistTimer = null
+      void window.electronAPI.setInputGain(gain)
+        .catch((e) => showError(`保存软件增益失败: ${String(e)}`))
+    }, 150)
+  })
+  // 激活词在首页编辑，失焦即保存，不必再去设置页点保存
+  document.getElementById('cfg-vad-activation-phrase')?.addEventListener('change', (e) => {
+    const activationPhrase = (e.target as HTMLInputElement).value.trim()
+    void window.electronAPI.getConfig()
+      .then((cfg) => window.electronAPI.saveConfig({ ...cfg, vad: { ...cfg.vad, activationPhrase } }))
+      .catch((err) => showError(`保存激活词失败: ${String(err)}`))
+  })
+
   document.getElementById('save-btn')!.addEventListener('click', saveConfig)
   document.getElementById('save-text-rules-btn')?.addEventListener('click', saveConfig)
   document.getElementById('llm-save-btn')?.addEventListener('click', saveConfig)
+  document.getElementById('cfg-export-btn')?.addEventListener('click', () => void exportConfigToForm())
+  document.getElementById('cfg-import-btn')?.addEventListener('click', () => void importConfigFromForm())
   document.getElementById('close-dashboard-btn')?.addEventListener('click', () => {
     void window.electronAPI.closeDashboard()
   })
@@ -113,6 +156,26 @@ export function initDashboardUI() {
     appendCommandRow(document.getElementById('cmd-editor-list')!)
   })
   document.getElementById('save-cmd-btn')!.addEventListener('click', saveCommands)
+  document.getElementById('cmd-preview-input')!.addEventListener('input', (e) => {
+    void previewCommandPhrase((e.target as HTMLInputElement).value)
+  })
+  document.getElementById('postprocess-preview-input')!.addEventListener('input', (e) => {
+    void previewPostprocessText((e.target as HTMLInputElement).value)
+  })
+  document.getElementById('recognize-last-recording-btn')!.addEventListener('click', () => {
+    void recognizeLastRecording()
+  })
+  for (const action of ['start', 'end', 'copy', 'export'] as const) {
+    document.getElementById(`transcript-${action}-btn`)!.addEventListener('click', () => {
+      void runTranscriptAction(action)
+    })
+  }
+  document.getElementById('cmd-template-record-btn')!.addEventListener('click', () => {
+    void toggleCommandTemplateRecording()
+  })
+  document.getElementById('cmd-template-clear-btn')!.addEventListener('click', () => {
+    void clearCommandTemplates()
+  })
 
   // 热词事件绑定
   document.getElementById('hotword-input')!.addEventListener('keydown', (e) => {
@@ -155,6 +218,13 @@ export function initDashboardUI() {
     await window.electronAPI.clearLogs()
     clearLogViewCache()
   })
+  const openLogFolderBtn = document.getElementById('log-open-folder-btn')
+  void window.electronAPI.getLogPath().then((logPath) => {
+    if (openLogFolderBtn) openLogFolderBtn.title = logPath
+  }).catch(() => { })
+  openLogFolderBtn?.addEventListener('click', () => {
+    void window.electronAPI.openLogFolder().catch((e) => showError(`打开日志文件失败: ${String(e)}`))
+  })
   document.getElementById('log-copy-btn')?.addEventListener('click', async () => {
     const btn = document.getElementById('log-copy-btn') as HTMLButtonElement | null
     if (!btn) return
@@ -188,9 +258,111 @@ export function initDashboardUI() {
 
   // 实时日志推送
   window.electronAPI.onLogEntry((entry) => appendLogEntry(entry))
+  // 日志页只显示截断后的原始响应，完整内容输出到 DevTools 控制台
+  window.electronAPI.onAsrRawResponse((payload) => {
+    console.info(`[ASR#${payload.requestId}] raw response (${payload.transport})`, payload)
+  })
+  // 托盘、快捷键或空闲超时切换 VAD 后同步主界面开关
+  window.electronAPI.onVadState(({ enabled }) => {
+    if (dashboardVadToggle) dashboardVadToggle.checked = enabled
+  })
   window.electronAPI.onVadThresholdUpdated((threshold) => {
     applyVadThreshold(threshold)
   })
+  // VAD 检测方式切换立即生效；模型文件缺失等原因切换失败时恢复原选项
+  const vadBackendSelect = document.getElementById('cfg-vad-backend') as HTMLSelectElement | null
+  vadBackendSelect?.addEventListener('change', () => {
+    void window.electronAPI.setVadBackend(vadBackendSelect.value).catch(async (e) => {
+      showError(`切换 VAD 检测方式失败: ${String(e)}`)
+      vadBackendSelect.value = await window.electronAPI.getVadBackend()
+    })
+  })
+  window.electronAPI.onVadBackendUpdated((backend) => {
+    if (vadBackendSelect) vadBackendSelect.value = backend
+  })
+  // 日志级别立即生效（不随「保存」提交，避免覆盖运行时调整）
+  const logLevelSelect = document.getElementById('cfg-log-level') as HTMLSelectElement | null
+  logLevelSelect?.addEventListener('change', () => {
+    void window.electronAPI.setLogLevel(logLevelSelect.value as LogLevel).catch(async (e) => {
+      showError(`切换日志级别失败: ${String(e)}`)
+      logLevelSelect.value = await window.electronAPI.getLogLevel()
+    })
+  })
+  window.electronAPI.onLogLevelUpdated((level) => {
+    if (logLevelSelect) logLevelSelect.value = level
+  })
+  window.electronAPI.onInputGainUpdated((gain) => {
+    setInputGain(gain)
+    if (document.activeElement !== inputGainSlider) applyInputGainToForm(gain)
+  })
+  window.electronAPI.onInputLevel((level) => renderInputLevel(level))
+  // 输入方式切换立即生效（浮窗右键菜单切换时同步勾选状态）
+  const clipboardToggle = document.getElementById('cfg-clipboard') as HTMLInputElement | null
+  clipboardToggle?.addEventListener('change', () => {
+    void window.electronAPI.setUseClipboard(clipboardToggle.checked)
+      .catch((e) => showError(`切换输入方式失败: ${String(e)}`))
+  })
+  window.electronAPI.onInputModeUpdated((mode) => {
+    if (clipboardToggle) clipboardToggle.checked = mode === 'clipboard'
+  })
+  // 语音指令开关立即生效（托盘、浮窗菜单切换时同步勾选状态）
+  const matchingToggle = document.getElementById('cmd-matching-enabled') as HTMLInputElement | null
+  matchingToggle?.addEventListener('change', () => {
+    void window.electronAPI.setCommandMatching(matchingToggle.checked)
+      .catch((e) => showError(`切换语音指令失败: ${String(e)}`))
+  })
+  window.electronAPI.onCommandMatchingUpdated((enabled) => {
+    if (matchingToggle) matchingToggle.checked = enabled
+  })
+  // 指令方案切换立即生效（托盘、快捷键切换时同步选中项）
+  const profileSelect = document.getElementById('cmd-profile-select') as HTMLSelectElement | null
+  profileSelect?.addEventListener('change', () => {
+    void window.electronAPI.switchProfile(profileSelect.value)
+      .catch((e) => showError(`切换指令方案失败: ${String(e)}`))
+  })
+  void window.electronAPI.listProfiles().then(renderCommandProfiles)
+  window.electronAPI.onProfileChanged(renderCommandProfiles)
+  // 麦克风选择立即切换，新设备打不开时提示并恢复为当前设备
+  const deviceSelect = document.getElementById('cfg-audio-input-device') as HTMLSelectElement | null
+  deviceSelect?.addEventListener('change', () => {
+    void window.electronAPI.setInputDevice(deviceSelect.value).catch(async (e) => {
+      showError(`切换麦克风失败: ${String(e)}`)
+      deviceSelect.value = (await window.electronAPI.getInputDevice()).deviceId
+    })
+  })
+  window.electronAPI.onInputDeviceUpdated((device) => {
+    if (deviceSelect) deviceSelect.value = device.deviceId
+  })
+  window.electronAPI.onAudioInfo((info) => renderAudioInfo(info))
+  window.electronAPI.onSampleRateWarning((warning) => renderSampleRateWarning(warning))
+  window.electronAPI.onReattemptSuggested((info) => renderReattemptSuggestion(info))
+  void window.electronAPI.getAudioInfo().then(renderAudioInfo).catch(() => { })
+  window.electronAPI.onHotkeysUpdated((bindings) => renderHotkeyBindings(bindings))
+  void window.electronAPI.listHotkeys().then(renderHotkeyBindings).catch(() => { })
+  window.electronAPI.onServerStatus((status) => renderServerStatus(status))
+  void window.electronAPI.getServerStatus().then(renderServerStatus).catch(() => { })
+  document.getElementById('server-status-check-btn')?.addEventListener('click', () => {
+    void window.electronAPI.checkServerStatus().then(renderServerStatus)
+      .catch((e) => showError(`检查服务器连接失败: ${String(e)}`))
+  })
+  document.getElementById('self-test-btn')?.addEventListener('click', () => { void runPipelineSelfTest() })
+  // 最近错误：控制台可能晚于错误事件打开，显示时主动查询一次
+  const refreshLastError = () => {
+    void window.electronAPI.getLastError().then(renderLastError).catch(() => { })
+  }
+  window.electronAPI.onLastError((error) => renderLastError(error))
+  refreshLastError()
+  document.addEventListener('visibilitychange', () => {
+    if (document.visibilityState === 'visible') refreshLastError()
+  })
+  document.getElementById('last-error-clear-btn')?.addEventListener('click', () => {
+    void window.electronAPI.clearLastError().then(() => renderLastError(null))
+  })
+  window.electronAPI.onInputPermissionDenied((state) => renderInputPermission(state))
+  document.getElementById('input-permission-recheck-btn')?.addEventListener('click', () => {
+    void window.electronAPI.recheckInputPermissions().then(renderInputPermission)
+      .catch((e) => showError(`检测输入权限失败: ${String(e)}`))
+  })
   window.electronAPI.onAsrRuntimeStatus((status) => {
     applyAsrRuntimeStatus(status)
     if (status.phase === 'starting') {
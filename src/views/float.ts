@@ -1,5 +1,5 @@
-import { startCapture, stopCapture } from '../audio'
-import type { FloatLayoutMetrics } from '../types'
+import { stopCapture, setInputGain, setInputLevelListener, setAudioInfoListener, setAudioLifecycleListener, setSampleRateWarningListener, switchInputDevice } from '../audio'
+import type { FloatLayoutMetrics, RecognitionStage } from '../types'
 import {
   initFloatElements,
   uiTrace,
@@ -8,9 +8,16 @@ import {
   setState,
   showError,
   showResult,
+  setInputOutcome,
   onRecordClick,
+  discardRecording,
+  startHotkeyCapture,
+  quickRecordAndRecognize,
+  handleFumbledCapture,
   setVadEnabled,
   applyVadThreshold,
+  applyVadEnergySmoothing,
+  restartVad,
   applyVadEnabled,
   initVad,
   ensureAsrReadyBeforeCapture,
@@ -31,6 +38,7 @@ export function initFloatCapsuleUI() {
   const fallbackText = document.getElementById('float-fallback-text') as HTMLDivElement | null
   const fallbackCopyBtn = document.getElementById('float-fallback-copy-btn') as HTMLButtonElement | null
   const fallbackCloseBtn = document.getElementById('float-fallback-close-btn') as HTMLButtonElement | null
+  const fallbackArmBtn = document.getElementById('float-fallback-arm-btn') as HTMLButtonElement | null
   const floatView = document.getElementById('float-capsule-view') as HTMLDivElement | null
   const floatLayoutRoot = document.getElementById('float-layout-root') as HTMLDivElement | null
   const capsuleContainer = floatLayoutRoot?.querySelector('.capsule-container') as HTMLDivElement | null
@@ -44,8 +52,9 @@ export function initFloatCapsuleUI() {
     requestId: number
     text: string
     targetAppId: string | null
-    reason: 'no-foreground-window' | 'no-focused-control' | 'focused-control-without-caret' | 'type-failed' | 'restore-failed'
+    reason: 'no-foreground-window' | 'no-focused-control' | 'focused-control-without-caret' | 'type-failed' | 'restore-failed' | 'safe-mode-unarmed'
     precheckReason: 'ok' | 'unknown' | 'no-foreground-window' | 'no-focused-control' | 'focused-control-without-caret'
+    copiedToClipboard: boolean
   } | null = null
 
   const setFloatBoundsDebug = (enabled: boolean) => {
@@ -112,23 +121,22 @@ export function initFloatCapsuleUI() {
     void window.electronAPI.setFloatExpanded(false).catch(() => { })
   }
 
-  const showFallbackPanel = (payload: {
-    requestId: number
-    text: string
-    targetAppId: string | null
-    reason: 'no-foreground-window' | 'no-focused-control' | 'focused-control-without-caret' | 'type-failed' | 'restore-failed'
-    precheckReason: 'ok' | 'unknown' | 'no-foreground-window' | 'no-focused-control' | 'focused-control-without-caret'
-  }) => {
+  const showFallbackPanel = (payload: NonNullable<typeof fallbackPayload>) => {
     fallbackPayload = payload
     if (fallbackText) {
       fallbackText.textContent = payload.text
       fallbackText.title = payload.text
     }
+    const awaitingArm = payload.reason === 'safe-mode-unarmed'
     if (fallbackHint) {
-      fallbackHint.textContent = payload.reason === 'type-failed'
-        ? '自动粘贴失败，结果已暂存'
-        : '未检测到可写焦点，结果已暂存'
+      const stored = payload.copiedToClipboard ? '结果已复制到剪贴板，可手动粘贴' : '结果已暂存'
+      fallbackHint.textContent = awaitingArm
+        ? '安全模式：请先点中正确的输入框，再点「确认输入」'
+        : payload.reason === 'type-failed'
+          ? `自动粘贴失败，${stored}`
+          : `未检测到可写焦点，${stored}`
     }
+    if (fallbackArmBtn) fallbackArmBtn.hidden = !awaitingArm
     if (fallbackPanel) fallbackPanel.hidden = false
     scheduleLayoutSync()
     void window.electronAPI.setFloatExpanded(true).catch(() => { })
@@ -240,20 +248,36 @@ export function initFloatCapsuleUI() {
         if (!await ensureAsrReadyBeforeCapture()) return
         hideFallbackPanel()
         setState('recording')
-        const p = startCapture()
+        const p = startHotkeyCapture()
         setStartCapturePromise(p)
         p.catch(e => showError(String(e)))
       })()
     } else if (s === 'recognizing') {
       setState('recognizing')
     } else {
-      setState('idle')
+      // idle 由 discard-recording 发出：丢弃进行中的录音，识别中的请求不受影响
+      void discardRecording()
     }
   })
 
+  // 定时录音（托盘 / 右键菜单触发）：开始录音后回报主进程，主进程据此返回是否已开始
+  window.electronAPI.onQuickRecord(({ requestId, durationMs }) => {
+    quickRecordAndRecognize(durationMs)
+      .then((ok) => window.electronAPI.reportQuickRecord(ok
+        ? { requestId, ok: true }
+        : { requestId, ok: false, message: '正在录音或识别中，或麦克风未能打开' }))
+      .catch((e) => window.electronAPI.reportQuickRecord({
+        requestId,
+        ok: false,
+        message: e instanceof Error ? e.message : String(e),
+      }))
+      .catch(() => { })
+  })
+
   // 热键停止录音
   window.electronAPI.onHotkeyStopRecording(async (prevAppId) => {
     if (getState() !== 'recording') return
+    const triggeredAt = Date.now()
     setState('recognizing')
     try {
       const p = getStartCapturePromise()
@@ -262,7 +286,8 @@ export function initFloatCapsuleUI() {
         setStartCapturePromise(null)
       }
       const wav = await stopCapture()
-      const result = await window.electronAPI.recognizeWav(wav, prevAppId)
+      if (await handleFumbledCapture('hold', prevAppId)) return
+      const result = await window.electronAPI.recognizeWav(wav, prevAppId, triggeredAt)
       setState('idle')
       if (result) showResult(result)
     } catch (e) {
@@ -271,23 +296,158 @@ export function initFloatCapsuleUI() {
     }
   })
 
+  // 识别进度：状态栏显示当前阶段，便于看出慢在上传、服务端还是输入
+  const stageLabels: Record<RecognitionStage, string> = {
+    'encoding': '编码中',
+    'uploading': '上传中',
+    'server-processing': '识别中',
+    'matching': '匹配指令',
+    'inserting': '输入中',
+  }
+  window.electronAPI.onRecognitionStage(({ stage, elapsedMs }) => {
+    const statusText = document.getElementById('status-text')
+    if (getState() !== 'recognizing' || !statusText) return
+    statusText.textContent = `${stageLabels[stage]}...（${(elapsedMs / 1000).toFixed(1)}s）`
+  })
+
+  // 上一句仍在识别/输入时，新的识别排队等待
+  window.electronAPI.onRecognitionBusy(({ pending }) => {
+    const statusText = document.getElementById('status-text')
+    if (pending > 0 && getState() === 'recognizing' && statusText) {
+      statusText.textContent = `识别中...（排队 ${pending}）`
+    }
+  })
+
+  // 快捷键重复上一条指令
+  window.electronAPI.onHotkeyRepeatCommand(async (prevAppId) => {
+    if (getState() !== 'idle') return
+    try {
+      const result = await window.electronAPI.repeatLastCommand(prevAppId)
+      if (result) showResult(result)
+    } catch (e) {
+      showError(String(e))
+    }
+  })
+
+  // 快捷键撤销上一次输入
+  window.electronAPI.onHotkeyUndoLastInsert(async (prevAppId) => {
+    if (getState() !== 'idle') return
+    const statusText = document.getElementById('status-text')
+    try {
+      const count = await window.electronAPI.undoLastInsert(prevAppId)
+      if (statusText) statusText.textContent = count > 0 ? `已撤销 ${count} 个字` : '没有可撤销的输入'
+    } catch (e) {
+      showError(String(e))
+    }
+  })
+
   // 托盘 VAD 切换
   window.electronAPI.onToggleVad((enabled) => {
     applyVadEnabled(Boolean(enabled), true).catch((e) => showError(String(e)))
   })
+  window.electronAPI.onVadState(({ enabled, reason }) => {
+    const statusText = document.getElementById('status-text')
+    if (!enabled && reason === 'idle-timeout' && statusText) {
+      statusText.textContent = '长时间无语音，VAD 已自动关闭'
+    }
+  })
+  window.electronAPI.onCommandMatchingUpdated((enabled) => {
+    const statusText = document.getElementById('status-text')
+    if (statusText) statusText.textContent = enabled ? '语音指令已开启' : '纯听写：语音指令已关闭'
+  })
+  window.electronAPI.onProfileChanged(({ active }) => {
+    const statusText = document.getElementById('status-text')
+    if (statusText) statusText.textContent = `指令方案：${active || '基础指令'}`
+  })
   window.electronAPI.onVadThresholdUpdated((threshold) => {
     applyVadThreshold(threshold)
   })
+  window.electronAPI.onVadEnergySmoothingUpdated((smoothing) => {
+    applyVadEnergySmoothing(smoothing)
+  })
+  // VAD 检测方式切换后重启监听，进行中的语音段不跨后端
+  window.electronAPI.onVadBackendUpdated(() => {
+    restartVad().catch((e) => showError(`切换 VAD 检测方式后重启监听失败: ${String(e)}`))
+  })
+  window.electronAPI.onInputGainUpdated((gain) => {
+    setInputGain(gain)
+  })
+  // 电平转发给主界面的 VU 表，调节增益时可实时看到效果
+  setInputLevelListener((level) => {
+    void window.electronAPI.reportInputLevel(level).catch(() => { })
+  })
+  setAudioInfoListener((info) => {
+    void window.electronAPI.reportAudioInfo(info).catch(() => { })
+  })
+  setAudioLifecycleListener((event) => {
+    void window.electronAPI.reportAudioLifecycle(event).catch(() => { })
+  })
+  setSampleRateWarningListener((warning) => {
+    void window.electronAPI.reportSampleRateWarning(warning).catch(() => { })
+  })
+  // 切换麦克风：在浮窗打开新设备并回报主进程，主进程持久化后广播 input-device-updated，再在新设备上重启 VAD
+  window.electronAPI.onSwitchInputDevice(({ requestId, name }) => {
+    switchInputDevice(name)
+      .then((device) => window.electronAPI.reportInputDeviceSwitch({ requestId, ok: true, ...device }))
+      .catch((e) => window.electronAPI.reportInputDeviceSwitch({
+        requestId,
+        ok: false,
+        message: e instanceof Error ? e.message : String(e),
+      }))
+      .catch(() => { })
+  })
+  window.electronAPI.onInputDeviceUpdated((device) => {
+    const statusText = document.getElementById('status-text')
+    if (statusText) statusText.textContent = `麦克风：${device.deviceName || '系统默认'}`
+    restartVad().catch((e) => showError(`切换麦克风后重启 VAD 监听失败: ${String(e)}`))
+  })
+  // 打开失败由录音流程自身报错，这里只提示静默发生的中断
+  window.electronAPI.onAudioThreadError((event) => {
+    if (event.stage === 'stream-open-failed') return
+    showError(event.message || '麦克风采集中断')
+  })
+  window.electronAPI.onSampleRateWarning((warning) => showError(warning.message))
   window.electronAPI.onPermissionWarning((message) => {
     if (!message) return
     showError(message)
   })
+  window.electronAPI.onInputPermissionDenied((state) => {
+    showError(`无法自动输入：${state.guide ?? state.error ?? '输入模拟不可用'}`)
+  })
+  // 浮窗隐藏期间发生的错误不会再次推送，重新显示时查询最近错误
+  document.addEventListener('visibilitychange', () => {
+    if (document.visibilityState !== 'visible') return
+    void window.electronAPI.getLastError().then((error) => {
+      if (error) showError(error.message)
+    }).catch(() => { })
+  })
   window.electronAPI.onAsrRuntimeStatus((status) => {
     applyAsrRuntimeStatus(status)
   })
   window.electronAPI.onFloatPasteFallback((payload) => {
     showFallbackPanel(payload)
   })
+  window.electronAPI.onInputComplete(() => {
+    setInputOutcome('inserted')
+  })
+  window.electronAPI.onInputFailed(() => {
+    setInputOutcome('not-inserted')
+  })
+  window.electronAPI.onHotkeyConflict((conflict) => {
+    showError(conflict.message)
+  })
+  window.electronAPI.onRecognitionEmptyWarning((payload) => {
+    showError(payload.message)
+  })
+  window.electronAPI.onResultFiltered((payload) => {
+    showError(payload.reason === 'too-short'
+      ? `识别结果过短（${payload.length} 字），未输入`
+      : `识别结果过长（${payload.length} 字），未输入`)
+  })
+  window.electronAPI.onVoiceCommandCandidates((payload) => {
+    const phrases = payload.candidates.map(c => `「${c.phrase}」`).join(' / ')
+    showResult(`口令不明确，已按文字输入（相近指令：${phrases}）`)
+  })
   window.electronAPI.onFloatDebugBoundsUpdated((enabled) => {
     setFloatBoundsDebug(enabled)
   })
@@ -309,6 +469,24 @@ export function initFloatCapsuleUI() {
     }
   })
 
+  fallbackArmBtn?.addEventListener('click', async (e) => {
+    e.preventDefault()
+    e.stopPropagation()
+    if (!fallbackPayload) return
+    const { text, targetAppId } = fallbackPayload
+    hideFallbackPanel()
+    try {
+      const result = await window.electronAPI.armInput(text, targetAppId)
+      if (result.success) {
+        setInputOutcome('inserted')
+      } else {
+        showError(`已确认输入目标，但输入失败（${result.reason}）`)
+      }
+    } catch (err) {
+      showError(String(err))
+    }
+  })
+
   fallbackCloseBtn?.addEventListener('click', (e) => {
     e.preventDefault()
     e.stopPropagation()
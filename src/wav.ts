@@ -1,8 +1,28 @@
+// 主机字节序为小端时可直接通过 Int16Array 写入 PCM，避免逐个采样调用 DataView
+const HOST_LITTLE_ENDIAN = new Uint8Array(new Uint16Array([1]).buffer)[0] === 1
+
+// 采集上下文实际运行的采样率与识别要求不一致时的提示文字，一致时返回 null。
+// 目前编码时不做重采样，WAV 头按识别采样率写入，不一致时音频会变速变调，识别准确率明显下降
+export function describeSampleRateMismatch(actualRate: number, expectedRate: number): string | null {
+  if (!Number.isFinite(actualRate) || actualRate <= 0 || actualRate === expectedRate) return null
+  return `录音采样率为 ${actualRate}Hz，与识别要求的 ${expectedRate}Hz 不一致，当前不会重采样，识别准确率可能明显下降`
+}
+
 // 将 Float32 PCM 数据块编码为 16-bit WAV ArrayBuffer
-export function encodeWav(chunks: Float32Array[], sampleRate = 16000): ArrayBuffer {
-  const totalSamples = chunks.reduce((n, c) => n + c.length, 0)
+// channels > 1 时 chunks 内为交错排列的多声道采样（L R L R ...）
+// tailSamples / leadSamples 为末尾 / 开头追加的静音采样数（按交错后的采样计），直接预留在输出中，不再额外分配静音块
+export function encodeWav(
+  chunks: Float32Array[],
+  sampleRate = 16000,
+  channels = 1,
+  tailSamples = 0,
+  leadSamples = 0,
+): ArrayBuffer {
+  const lead = Math.max(0, leadSamples)
+  const totalSamples = chunks.reduce((n, c) => n + c.length, 0) + Math.max(0, tailSamples) + lead
   const buffer = new ArrayBuffer(44 + totalSamples * 2)
   const view = new DataView(buffer)
+  const blockAlign = channels * 2
 
   const writeStr = (offset: number, str: string) => {
     for (let i = 0; i < str.length; i++) view.setUint8(offset + i, str.charCodeAt(i))
@@ -14,15 +34,28 @@ export function encodeWav(chunks: Float32Array[], sampleRate = 16000): ArrayBuff
   writeStr(12, 'fmt ')
   view.setUint32(16, 16, true)
   view.setUint16(20, 1, true)             // PCM
-  view.setUint16(22, 1, true)             // 单声道
+  view.setUint16(22, channels, true)      // 声道数
   view.setUint32(24, sampleRate, true)
-  view.setUint32(28, sampleRate * 2, true)
-  view.setUint16(32, 2, true)
+  view.setUint32(28, sampleRate * blockAlign, true)
+  view.setUint16(32, blockAlign, true)
   view.setUint16(34, 16, true)
   writeStr(36, 'data')
   view.setUint32(40, totalSamples * 2, true)
 
-  let offset = 44
+  // 数据区紧跟 44 字节头，偏移为偶数，可直接建 Int16Array 视图；新建的 ArrayBuffer 已清零，首尾静音无需再写
+  if (HOST_LITTLE_ENDIAN) {
+    const pcm = new Int16Array(buffer, 44)
+    let index = lead
+    for (const chunk of chunks) {
+      for (let i = 0; i < chunk.length; i++) {
+        const s = Math.max(-1, Math.min(1, chunk[i]))
+        pcm[index++] = s < 0 ? s * 0x8000 : s * 0x7fff
+      }
+    }
+    return buffer
+  }
+
+  let offset = 44 + lead * 2
   for (const chunk of chunks) {
     for (let i = 0; i < chunk.length; i++) {
       const s = Math.max(-1, Math.min(1, chunk[i]))
@@ -32,3 +65,13 @@ export function encodeWav(chunks: Float32Array[], sampleRate = 16000): ArrayBuff
   }
   return buffer
 }
+
+// 单声道采样复制为双声道交错数据（用于把 VAD 预录音拼接到立体声录音前）
+export function monoToInterleavedStereo(mono: Float32Array): Float32Array {
+  const out = new Float32Array(mono.length * 2)
+  for (let i = 0; i < mono.length; i++) {
+    out[i * 2] = mono[i]
+    out[i * 2 + 1] = mono[i]
+  }
+  return out
+}
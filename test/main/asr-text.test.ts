@@ -1,5 +1,18 @@
 import { describe, expect, it } from 'vitest'
-import { normalizeAsrText, applyTextRules } from '../../electron/main/asr-text'
+import {
+  normalizeAsrText,
+  applyTextRules,
+  stripActivationPhrase,
+  EmptyResultTracker,
+  collapseRepeatedPhrases,
+  applyPostprocess,
+  tracePostprocess,
+  addMinimalPunctuation,
+  removeStopwords,
+  resolveTextLocale,
+  cleanWhitespace,
+  checkTextLength,
+} from '../../electron/main/asr-text'
 
 describe('normalizeAsrText', () => {
   it('字符串原样返回', () => {
@@ -66,3 +79,251 @@ describe('applyTextRules', () => {
     expect(output).toBe(input)
   })
 })
+
+describe('stripActivationPhrase', () => {
+  it('以激活词开头时去掉激活词及其后的标点', () => {
+    expect(stripActivationPhrase('洛根，肉眼所见。', '洛根')).toBe('肉眼所见。')
+    expect(stripActivationPhrase(' 洛根 下一个', '洛根')).toBe('下一个')
+    expect(stripActivationPhrase('Logan 下一个', 'logan')).toBe('下一个')
+  })
+
+  it('未以激活词开头时丢弃', () => {
+    expect(stripActivationPhrase('今天中午吃什么', '洛根')).toBeNull()
+    expect(stripActivationPhrase('我跟洛根说过了', '洛根')).toBeNull()
+  })
+
+  it('只说了激活词时返回空文本', () => {
+    expect(stripActivationPhrase('洛根。', '洛根')).toBe('')
+  })
+
+  it('未配置激活词时原样返回', () => {
+    expect(stripActivationPhrase('肉眼所见', '')).toBe('肉眼所见')
+    expect(stripActivationPhrase('肉眼所见', '  ')).toBe('肉眼所见')
+  })
+})
+
+describe('EmptyResultTracker', () => {
+  it('连续空结果达到阈值时提示，之后每满阈值再提示一次', () => {
+    const tracker = new EmptyResultTracker()
+    expect([1, 2, 3, 4, 5, 6].map(() => tracker.record('  ', 3))).toEqual([false, false, true, false, false, true])
+    expect(tracker.count).toBe(6)
+  })
+
+  it('非空结果清零计数', () => {
+    const tracker = new EmptyResultTracker()
+    tracker.record('', 3)
+    tracker.record('', 3)
+    expect(tracker.record('肉眼所见', 3)).toBe(false)
+    expect(tracker.count).toBe(0)
+    expect(tracker.record('', 3)).toBe(false)
+  })
+
+  it('阈值为 0 时不提示', () => {
+    const tracker = new EmptyResultTracker()
+    expect([1, 2, 3].map(() => tracker.record('', 0))).toEqual([false, false, false])
+  })
+})
+
+describe('collapseRepeatedPhrases', () => {
+  it('合并常见的口吃重复', () => {
+    expect(collapseRepeatedPhrases('病人病人主诉头痛')).toBe('病人主诉头痛')
+    expect(collapseRepeatedPhrases('这个这个这个肿块')).toBe('这个肿块')
+    expect(collapseRepeatedPhrases('我们发现我们发现结节')).toBe('我们发现结节')
+    expect(collapseRepeatedPhrases('左侧 左侧乳腺，鳞状上皮鳞状上皮增生')).toBe('左侧乳腺，鳞状上皮增生')
+  })
+
+  it('单字叠词、数字与字母保持不变', () => {
+    expect(collapseRepeatedPhrases('谢谢医生，看看片子')).toBe('谢谢医生，看看片子')
+    expect(collapseRepeatedPhrases('1212 abab 3.3厘米')).toBe('1212 abab 3.3厘米')
+  })
+
+  it('调大最短单元字数可保留双字叠词', () => {
+    expect(collapseRepeatedPhrases('研究研究', 2)).toBe('研究')
+    expect(collapseRepeatedPhrases('研究研究', 3)).toBe('研究研究')
+    expect(collapseRepeatedPhrases('我们发现我们发现', 3)).toBe('我们发现')
+  })
+})
+
+describe('applyPostprocess', () => {
+  it('未开启时原样返回', () => {
+    expect(applyPostprocess('病人病人', { dedupeRepeats: false, dedupeMinUnitChars: 2 })).toBe('病人病人')
+    expect(applyPostprocess('病人病人', null)).toBe('病人病人')
+    expect(applyPostprocess('病人病人', { dedupeRepeats: true })).toBe('病人')
+  })
+
+  it('标点模式：off 不处理，server 与 local 在结果无标点时本地补充', () => {
+    expect(applyPostprocess('患者 头痛三天', { punctuation: 'off' })).toBe('患者 头痛三天')
+    expect(applyPostprocess('患者 头痛三天', { punctuation: 'local' })).toBe('患者，头痛三天。')
+    expect(applyPostprocess('患者 头痛三天', { punctuation: 'server' })).toBe('患者，头痛三天。')
+    // 服务端已加标点时不再重复处理
+    expect(applyPostprocess('患者头痛三天。', { punctuation: 'server' })).toBe('患者头痛三天。')
+  })
+})
+
+describe('tracePostprocess', () => {
+  const textRules = {
+    enabled: true,
+    rules: [
+      {
+        id: 'size-normalize-default',
+        name: '尺寸表达标准化',
+        enabled: true,
+        type: 'sizeExpressionNormalize' as const,
+        options: { multiplicationWords: ['乘以'], rangeWords: ['到'], outputUnit: 'CM' },
+      },
+    ],
+  }
+  const options = { stopwords: ['嗯'], dedupeRepeats: true, punctuation: 'local' as const }
+
+  it('按文本规则、删除填充词、合并重复、标点的顺序记录每一步', () => {
+    const trace = tracePostprocess('嗯 病人病人 十六厘米乘以十二厘米', textRules, options)
+    expect(trace.input).toBe('嗯 病人病人 十六厘米乘以十二厘米')
+    expect(trace.steps.map(s => s.stage)).toEqual(['textRules', 'stopwords', 'dedupe', 'punctuation'])
+    expect(trace.steps[0].text).toBe('嗯 病人病人 16CM×12CM')
+    expect(trace.steps[1].text).toBe('病人病人 16CM×12CM')
+    expect(trace.steps[2].text).toBe('病人 16CM×12CM')
+    expect(trace.text).toBe(trace.steps[3].text)
+  })
+
+  it('最终结果与实际识别后的处理一致', () => {
+    const input = '嗯 病人病人 十六厘米乘以十二厘米'
+    const expected = applyPostprocess(applyTextRules(input, textRules), options)
+    expect(tracePostprocess(input, textRules, options).text).toBe(expected)
+  })
+
+  it('未启用任何处理时原样返回且不列出步骤', () => {
+    expect(tracePostprocess('病人病人', { enabled: false, rules: [] }, null)).toEqual({
+      input: '病人病人',
+      text: '病人病人',
+      steps: [],
+    })
+  })
+})
+
+describe('cleanWhitespace', () => {
+  it('未开启任何一项时原样返回', () => {
+    expect(cleanWhitespace('见附件。  \n', {})).toBe('见附件。  \n')
+  })
+
+  it('去掉末尾与每行行尾的空白，保留行首缩进与换行', () => {
+    expect(cleanWhitespace('  结论：  \n  未见异常 \n\n', { trimTrailingWhitespace: true }))
+      .toBe('  结论：\n  未见异常')
+    expect(cleanWhitespace('A \r\nB\u3000', { trimTrailingWhitespace: true })).toBe('A\r\nB')
+  })
+
+  it('连续空格（含全角空格）合并为一个，单个空格与换行不变', () => {
+    expect(cleanWhitespace('CT  示\u3000\u3000肺结节 2  枚\n\n', { collapseSpaces: true })).toBe('CT 示 肺结节 2 枚\n\n')
+  })
+
+  it('去掉换行：汉字之间直接相连，英文单词之间补一个空格', () => {
+    expect(cleanWhitespace('结论：\n未见异常', { stripNewlines: true })).toBe('结论：未见异常')
+    expect(cleanWhitespace('no acute\r\n\r\nfindings', { stripNewlines: true })).toBe('no acute findings')
+    expect(cleanWhitespace('胃窦 \n mucosa', { stripNewlines: true })).toBe('胃窦mucosa')
+    expect(cleanWhitespace('\n见附件。\n', { stripNewlines: true })).toBe('见附件。')
+  })
+
+  it('作为后处理最后一步执行', () => {
+    const trace = tracePostprocess('病人病人  \n', null, { dedupeRepeats: true, trimTrailingWhitespace: true })
+    expect(trace.steps.map(s => s.stage)).toEqual(['dedupe', 'whitespace'])
+    expect(trace.text).toBe('病人')
+  })
+})
+
+describe('checkTextLength', () => {
+  it('上下限均为 0 时不过滤', () => {
+    expect(checkTextLength('嗯', 0, 0)).toBeNull()
+    expect(checkTextLength('', 0, 0)).toBeNull()
+  })
+
+  it('恰好等于下限或上限时保留', () => {
+    expect(checkTextLength('胃窦', 2, 0)).toBeNull()
+    expect(checkTextLength('胃窦炎', 0, 3)).toBeNull()
+    expect(checkTextLength('胃窦', 2, 2)).toBeNull()
+  })
+
+  it('少于下限、多于上限时给出原因与字数', () => {
+    expect(checkTextLength('嗯', 2, 0)).toEqual({ reason: 'too-short', length: 1 })
+    expect(checkTextLength('胃窦炎症', 0, 3)).toEqual({ reason: 'too-long', length: 4 })
+  })
+
+  it('按 Unicode 字符而非字节计数', () => {
+    expect(checkTextLength('黏膜', 0, 2)).toBeNull()
+    expect(checkTextLength('𠀀', 2, 0)).toEqual({ reason: 'too-short', length: 1 })
+  })
+
+  it('首尾空白不计入字数', () => {
+    expect(checkTextLength('  嗯 \n', 2, 0)).toEqual({ reason: 'too-short', length: 1 })
+    expect(checkTextLength(' 胃 窦 ', 0, 3)).toBeNull()
+  })
+})
+
+describe('removeStopwords', () => {
+  const stopwords = ['嗯', '那个', '就是', 'um']
+
+  it('删除被标点或空白隔开的填充词，并清理多余的分隔符', () => {
+    expect(removeStopwords('嗯，胃窦黏膜光滑。', stopwords)).toBe('胃窦黏膜光滑。')
+    expect(removeStopwords('胃窦，那个，黏膜充血', stopwords)).toBe('胃窦，黏膜充血')
+    expect(removeStopwords('黏膜光滑，嗯。', stopwords)).toBe('黏膜光滑。')
+    expect(removeStopwords('胃窦 嗯 黏膜', stopwords)).toBe('胃窦 黏膜')
+    expect(removeStopwords('Um, the lesion', stopwords)).toBe('the lesion')
+  })
+
+  it('填充词是更长词语的一部分时保留', () => {
+    expect(removeStopwords('那个病灶位于胃角', stopwords)).toBe('那个病灶位于胃角')
+    expect(removeStopwords('就是说病灶较大', stopwords)).toBe('就是说病灶较大')
+  })
+
+  it('在后处理中先于去重与标点执行', () => {
+    expect(applyPostprocess('嗯 患者 头痛三天', { stopwords, punctuation: 'local' })).toBe('患者，头痛三天。')
+    expect(applyPostprocess('嗯，那个', { stopwords: [] })).toBe('嗯，那个')
+  })
+})
+
+describe('addMinimalPunctuation', () => {
+  it('已有标点或空文本原样返回', () => {
+    expect(addMinimalPunctuation('你好，世界')).toBe('你好，世界')
+    expect(addMinimalPunctuation('  ')).toBe('  ')
+  })
+
+  it('句末为数字或字母时同样补句号，英文单词间空格保留', () => {
+    expect(addMinimalPunctuation('大小 3CM')).toBe('大小 3CM。')
+    expect(addMinimalPunctuation('CT scan')).toBe('CT scan。')
+  })
+})
+
+describe('识别语言与后处理', () => {
+  const textRulesConfig = {
+    enabled: true,
+    rules: [{
+      id: 'size-normalize-default',
+      name: '尺寸表达标准化',
+      enabled: true,
+      type: 'sizeExpressionNormalize' as const,
+      options: { multiplicationWords: ['乘以', '乘', 'x', 'X', '×', '*'], rangeWords: ['到', '至', '-'], outputUnit: 'CM' },
+    }],
+  }
+
+  it('auto 按文本是否含汉字判断，默认按中文处理', () => {
+    expect(resolveTextLocale(undefined, 'aspirin')).toBe('zh')
+    expect(resolveTextLocale('zh', 'aspirin')).toBe('zh')
+    expect(resolveTextLocale('en', '阿司匹林')).toBe('en')
+    expect(resolveTextLocale('auto', 'aspirin 2 x 3')).toBe('en')
+    expect(resolveTextLocale('auto', '口服 aspirin')).toBe('zh')
+  })
+
+  it('英文文本同样套用用户配置的文本规则', () => {
+    expect(applyTextRules('2x3 tablets', textRulesConfig)).toBe('2×3 tablets')
+    expect(applyTextRules('nodule 2cmx3cm', textRulesConfig)).toBe('nodule 2CM×3CM')
+  })
+
+  it('中英混合文本中英文术语保持原样，中文部分照常转换', () => {
+    expect(applyTextRules('口服 B12x2 片，结节一厘米乘二厘米', textRulesConfig)).toBe('口服 B12x2 片，结节1CM×2CM')
+    expect(applyTextRules('IL-6 升高，范围两厘米到三厘米', textRulesConfig)).toBe('IL-6 升高，范围2CM-3CM')
+  })
+
+  it('英文最简标点只在句末补半角句号', () => {
+    expect(applyPostprocess('take aspirin daily', { punctuation: 'local', language: 'en' })).toBe('take aspirin daily.')
+    expect(applyPostprocess('take aspirin daily', { punctuation: 'local', language: 'auto' })).toBe('take aspirin daily.')
+    expect(applyPostprocess('患者 头痛', { punctuation: 'local', language: 'auto' })).toBe('患者，头痛。')
+  })
+})
@@ -0,0 +1,125 @@
+import { describe, it, expect, vi } from 'vitest'
+
+vi.mock('../../electron/main/logger', () => ({
+  logger: { info: vi.fn(), warn: vi.fn(), error: vi.fn(), debug: vi.fn() },
+}))
+
+import { AsrWsClient, toAsrWebSocketUrl, createDefaultAsrWebSocket, type AsrWebSocket } from '../../electron/main/asr-ws'
+
+class FakeSocket implements AsrWebSocket {
+  readyState = 0
+  binaryType = 'blob'
+  sent: Array<string | Buffer> = []
+  // 收到 end 后回复的内容，null 表示不回复
+  reply: string | null = JSON.stringify({ success: true, data: { text: '肉眼所见' } })
+  private listeners = new Map<string, Array<(event: any) => void>>()
+
+  constructor(public url: string) { }
+
+  addEventListener(type: string, listener: (event: any) => void) {
+    const list = this.listeners.get(type) ?? []
+    list.push(listener)
+    this.listeners.set(type, list)
+  }
+
+  send(data: any) {
+    this.sent.push(typeof data === 'string' ? data : Buffer.from(data))
+    if (data === JSON.stringify({ type: 'end' }) && this.reply) {
+      queueMicrotask(() => this.emit('message', { data: this.reply }))
+    }
+  }
+
+  close() {
+    this.readyState = 3
+    this.emit('close', {})
+  }
+
+  open() {
+    this.readyState = 1
+    this.emit('open', {})
+  }
+
+  emit(type: string, event: any) {
+    for (const listener of this.listeners.get(type) ?? []) listener(event)
+  }
+}
+
+function createClient(reply?: string) {
+  const sockets: FakeSocket[] = []
+  const client = new AsrWsClient((url) => {
+    const socket = new FakeSocket(url)
+    if (reply !== undefined) socket.reply = reply
+    sockets.push(socket)
+    queueMicrotask(() => socket.open())
+    return socket
+  })
+  return { client, sockets }
+}
+
+describe('toAsrWebSocketUrl', () => {
+  it('http/https 转为 ws/wss', () => {
+    expect(toAsrWebSocketUrl('http://localhost:3000/')).toBe('ws://localhost:3000/api/tasks/asr-recognize/ws')
+    expect(toAsrWebSocketUrl('https://asr.example.com')).toBe('wss://asr.example.com/api/tasks/asr-recognize/ws')
+  })
+
+  it('非法地址抛错', () => {
+    expect(() => toAsrWebSocketUrl('localhost:3000')).toThrow('缺少协议头')
+  })
+})
+
+describe('AsrWsClient', () => {
+  it('发送 start、音频帧、end 并返回识别文本', async () => {
+    const { client, sockets } = createClient()
+    const wav = Buffer.alloc(40 * 1024, 1)
+
+    const text = await client.recognize('http://localhost:3000', 'cfg-1', wav)
+
+    expect(text).toBe('肉眼所见')
+    const sent = sockets[0].sent
+    expect(JSON.parse(sent[0] as string)).toEqual({ type: 'start', asrConfigId: 'cfg-1' })
+    expect(Buffer.concat(sent.slice(1, -1) as Buffer[])).toEqual(wav)
+    expect(JSON.parse(sent[sent.length - 1] as string)).toEqual({ type: 'end' })
+  })
+
+  it('多次识别复用同一连接', async () => {
+    const { client, sockets } = createClient()
+    await client.recognize('http://localhost:3000', '', Buffer.alloc(64))
+    await client.recognize('http://localhost:3000', '', Buffer.alloc(64))
+    expect(sockets).toHaveLength(1)
+  })
+
+  it('服务端返回错误时抛出', async () => {
+    const { client } = createClient(JSON.stringify({ success: false, message: '音频太短' }))
+    await expect(client.recognize('http://localhost:3000', '', Buffer.alloc(64))).rejects.toThrow('音频太短')
+  })
+
+  it('连接断开时本次识别失败，下次重新连接', async () => {
+    const { client, sockets } = createClient()
+    await client.recognize('http://localhost:3000', '', Buffer.alloc(64))
+    sockets[0].reply = null
+    const task = client.recognize('http://localhost:3000', '', Buffer.alloc(64))
+    await vi.waitFor(() => expect(sockets[0].sent.length).toBeGreaterThan(3))
+    sockets[0].close()
+    await expect(task).rejects.toThrow('断开')
+
+    await client.recognize('http://localhost:3000', '', Buffer.alloc(64))
+    expect(sockets).toHaveLength(2)
+  })
+})
+
+describe('createDefaultAsrWebSocket', () => {
+  it('不依赖全局 WebSocket 即可创建连接', async () => {
+    const original = (globalThis as { WebSocket?: unknown }).WebSocket
+    delete (globalThis as { WebSocket?: unknown }).WebSocket
+    try {
+      const socket = createDefaultAsrWebSocket('ws://127.0.0.1:9/api/tasks/asr-recognize/ws')
+      const errored = new Promise<void>(resolve => socket.addEventListener('error', () => resolve()))
+      expect(socket.readyState).toBe(0)
+      socket.binaryType = 'arraybuffer'
+      socket.close()
+      await errored
+    } finally {
+      if (original !== undefined) (globalThis as { WebSocket?: unknown }).WebSocket = original
+    }
+  })
+})
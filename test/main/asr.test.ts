@@ -1,5 +1,6 @@
 import { describe, it, expect, vi, beforeEach } from 'vitest'
-import { recognize } from '../../electron/main/asr'
+import { recognize, validateServerUrl } from '../../electron/main/asr'
+import { SingleFlightQueue } from '../../electron/main/single-flight'
 
 // 构造最小有效 WAV Buffer（静音）
 function makeSilenceWav(samples = 160): Buffer {
@@ -78,6 +79,112 @@ describe('recognize', () => {
     expect(body.get('asrConfigId')).toBeNull()
   })
 
+  it('默认以 WAV 上传，开启 FLAC 时改用 audio/flac', async () => {
+    const mockFetch = vi.fn().mockResolvedValue({
+      ok: true,
+      status: 200,
+      json: async () => ({ success: true, data: { text: 'ok' } }),
+    })
+    vi.stubGlobal('fetch', mockFetch)
+
+    await recognize('http://localhost:3000', '', makeSilenceWav())
+    const wavFile = (mockFetch.mock.calls[0][1].body as FormData).get('file') as File
+    expect(wavFile.type).toBe('audio/wav')
+    expect(wavFile.name).toBe('recording.wav')
+
+    await recognize('http://localhost:3000', '', makeSilenceWav(16000), { audioEncoding: 'flac' })
+    const flacFile = (mockFetch.mock.calls[1][1].body as FormData).get('file') as File
+    expect(flacFile.type).toBe('audio/flac')
+    expect(flacFile.name).toBe('recording.flac')
+    const bytes = Buffer.from(await flacFile.arrayBuffer())
+    expect(bytes.subarray(0, 4).toString('latin1')).toBe('fLaC')
+    expect(bytes.length).toBeLessThan(makeSilenceWav(16000).length / 10)
+  })
+
+  it('WAV 上传使用配置的 MIME 类型，不合法时回退 audio/wav', async () => {
+    const mockFetch = vi.fn().mockResolvedValue({
+      ok: true,
+      status: 200,
+      json: async () => ({ success: true, data: { text: 'ok' } }),
+    })
+    vi.stubGlobal('fetch', mockFetch)
+
+    await recognize('http://localhost:3000', '', makeSilenceWav(), { audioMimeType: 'audio/x-wav' })
+    expect(((mockFetch.mock.calls[0][1].body as FormData).get('file') as File).type).toBe('audio/x-wav')
+
+    await recognize('http://localhost:3000', '', makeSilenceWav(), { audioMimeType: '音频 wav' })
+    expect(((mockFetch.mock.calls[1][1].body as FormData).get('file') as File).type).toBe('audio/wav')
+
+    await recognize('http://localhost:3000', '', makeSilenceWav(16000), { audioEncoding: 'flac', audioMimeType: 'audio/wave' })
+    expect(((mockFetch.mock.calls[2][1].body as FormData).get('file') as File).type).toBe('audio/flac')
+  })
+
+  it('追踪 ID 默认放入请求头，请求头名为空时改为表单字段', async () => {
+    const mockFetch = vi.fn().mockResolvedValue({
+      ok: true,
+      status: 200,
+      json: async () => ({ success: true, data: { text: 'ok' } }),
+    })
+    vi.stubGlobal('fetch', mockFetch)
+
+    await recognize('http://localhost:3000', '', makeSilenceWav(), { requestId: 'trace-1', requestIdHeader: 'X-Request-Id' })
+    const [, headerInit] = mockFetch.mock.calls[0]
+    expect(headerInit.headers).toEqual({ 'X-Request-Id': 'trace-1' })
+    expect((headerInit.body as FormData).get('requestId')).toBeNull()
+
+    await recognize('http://localhost:3000', '', makeSilenceWav(), { requestId: 'trace-2', requestIdHeader: '' })
+    const [, formInit] = mockFetch.mock.calls[1]
+    expect(formInit.headers).toEqual({})
+    expect((formInit.body as FormData).get('requestId')).toBe('trace-2')
+  })
+
+  it('仅在开启服务端标点时附加 punctuation 字段', async () => {
+    const mockFetch = vi.fn().mockResolvedValue({
+      ok: true,
+      status: 200,
+      json: async () => ({ success: true, data: { text: 'ok' } }),
+    })
+    vi.stubGlobal('fetch', mockFetch)
+
+    await recognize('http://localhost:3000', '', makeSilenceWav(), { punctuation: true })
+    await recognize('http://localhost:3000', '', makeSilenceWav())
+    expect((mockFetch.mock.calls[0][1].body as FormData).get('punctuation')).toBe('true')
+    expect((mockFetch.mock.calls[1][1].body as FormData).get('punctuation')).toBeNull()
+  })
+
+  it('设置识别语言时附加 language 字段', async () => {
+    const mockFetch = vi.fn().mockResolvedValue({
+      ok: true,
+      status: 200,
+      json: async () => ({ success: true, data: { text: 'ok' } }),
+    })
+    vi.stubGlobal('fetch', mockFetch)
+
+    await recognize('http://localhost:3000', '', makeSilenceWav(), { language: 'en' })
+    await recognize('http://localhost:3000', '', makeSilenceWav())
+    expect((mockFetch.mock.calls[0][1].body as FormData).get('language')).toBe('en')
+    expect((mockFetch.mock.calls[1][1].body as FormData).get('language')).toBeNull()
+  })
+
+  it('按配置的字段名上传音频与识别配置 ID', async () => {
+    const mockFetch = vi.fn().mockResolvedValue({
+      ok: true,
+      status: 200,
+      json: async () => ({ success: true, data: { text: 'ok' } }),
+    })
+    vi.stubGlobal('fetch', mockFetch)
+
+    await recognize('http://localhost:3000', 'cfg-9', makeSilenceWav(), {
+      fileFieldName: 'audio',
+      configIdFieldName: 'model',
+    })
+    const body = mockFetch.mock.calls[0][1].body as FormData
+    expect(body.get('audio')).toBeInstanceOf(File)
+    expect(body.get('model')).toBe('cfg-9')
+    expect(body.get('file')).toBeNull()
+    expect(body.get('asrConfigId')).toBeNull()
+  })
+
   it('HTTP 非 2xx 时抛出错误', async () => {
     vi.stubGlobal('fetch', vi.fn().mockResolvedValue({
       ok: false,
@@ -118,3 +225,143 @@ describe('recognize', () => {
       .rejects.toThrow('ECONNREFUSED')
   })
 })
+
+describe('recognize 原始响应回调', () => {
+  it('解析前回调响应原文', async () => {
+    const raw = JSON.stringify({ success: true, data: { text: '肉眼所见' }, debug: { model: 'paraformer' } })
+    vi.stubGlobal('fetch', vi.fn().mockResolvedValue({ ok: true, status: 200, text: async () => raw }))
+    const onRawResponse = vi.fn()
+
+    const result = await recognize('http://localhost:3000', '', makeSilenceWav(), { onRawResponse })
+    expect(result).toBe('肉眼所见')
+    expect(onRawResponse).toHaveBeenCalledWith(raw)
+  })
+
+  it('错误状态与非 JSON 响应也回调原文', async () => {
+    const onRawResponse = vi.fn()
+    vi.stubGlobal('fetch', vi.fn().mockResolvedValue({ ok: false, status: 500, text: async () => 'upstream timeout' }))
+    await expect(recognize('http://localhost:3000', '', makeSilenceWav(), { onRawResponse })).rejects.toThrow('500')
+    expect(onRawResponse).toHaveBeenCalledWith('upstream timeout')
+
+    vi.stubGlobal('fetch', vi.fn().mockResolvedValue({ ok: true, status: 200, text: async () => '<html>' }))
+    await expect(recognize('http://localhost:3000', '', makeSilenceWav(), { onRawResponse })).rejects.toThrow('JSON')
+  })
+})
+
+describe('recognize 重定向', () => {
+  const okResponse = { ok: true, status: 200, json: async () => ({ success: true, data: { text: 'ok' } }) }
+  const redirectTo = (status: number, location: string) => ({ ok: false, status, headers: new Headers({ location }) })
+
+  it('默认保持 POST 与请求体跟随重定向，并回调重定向信息', async () => {
+    const mockFetch = vi.fn()
+      .mockResolvedValueOnce(redirectTo(307, 'https://asr.example.com/api/tasks/asr-recognize/sync'))
+      .mockResolvedValueOnce(okResponse)
+    vi.stubGlobal('fetch', mockFetch)
+    const onRedirect = vi.fn()
+
+    const result = await recognize('http://asr.example.com', 'cfg-1', makeSilenceWav(), { onRedirect })
+    expect(result).toBe('ok')
+    const [firstUrl, firstInit] = mockFetch.mock.calls[0]
+    const [secondUrl, secondInit] = mockFetch.mock.calls[1]
+    expect(firstInit.redirect).toBe('manual')
+    expect(secondUrl).toBe('https://asr.example.com/api/tasks/asr-recognize/sync')
+    expect(secondInit.method).toBe('POST')
+    expect((secondInit.body as FormData).get('asrConfigId')).toBe('cfg-1')
+    expect(onRedirect).toHaveBeenCalledWith({ status: 307, from: firstUrl, to: secondUrl })
+  })
+
+  it('相对地址按当前地址解析', async () => {
+    const mockFetch = vi.fn()
+      .mockResolvedValueOnce(redirectTo(302, '/asr/api/tasks/asr-recognize/sync'))
+      .mockResolvedValueOnce(okResponse)
+    vi.stubGlobal('fetch', mockFetch)
+
+    await recognize('http://localhost:3000', '', makeSilenceWav())
+    expect(mockFetch.mock.calls[1][0]).toBe('http://localhost:3000/asr/api/tasks/asr-recognize/sync')
+    expect(mockFetch.mock.calls[1][1].method).toBe('POST')
+  })
+
+  it('redirect 为 error 时不跟随并提示重定向目标', async () => {
+    const mockFetch = vi.fn().mockResolvedValue(redirectTo(307, 'https://asr.example.com/sync'))
+    vi.stubGlobal('fetch', mockFetch)
+
+    await expect(recognize('http://asr.example.com', '', makeSilenceWav(), { redirect: 'error' }))
+      .rejects.toThrow('https://asr.example.com/sync')
+    expect(mockFetch).toHaveBeenCalledTimes(1)
+  })
+
+  it('重定向次数过多时报错', async () => {
+    vi.stubGlobal('fetch', vi.fn().mockResolvedValue(redirectTo(308, 'http://loop.example.com/')))
+    await expect(recognize('http://asr.example.com', '', makeSilenceWav())).rejects.toThrow('重定向次数过多')
+  })
+})
+
+describe('recognize 超时', () => {
+  beforeEach(() => {
+    vi.restoreAllMocks()
+  })
+
+  // 服务端一直不响应：只有请求被中止时才结束
+  function hangingFetch(init: RequestInit): Promise<never> {
+    return new Promise((_resolve, reject) => {
+      init.signal?.addEventListener('abort', () => reject(init.signal!.reason))
+    })
+  }
+
+  it('服务端不响应时按 timeoutMs 中止并报超时', async () => {
+    vi.stubGlobal('fetch', vi.fn((_url: string, init: RequestInit) => hangingFetch(init)))
+
+    await expect(recognize('http://localhost:3000', '', makeSilenceWav(), { timeoutMs: 50 }))
+      .rejects.toThrow('ASR 请求超时（50ms 内未返回）')
+  })
+
+  it('串行识别时卡住的请求超时后，下一句照常识别', async () => {
+    const mockFetch = vi.fn()
+      .mockImplementationOnce((_url: string, init: RequestInit) => hangingFetch(init))
+      .mockResolvedValueOnce({
+        ok: true,
+        status: 200,
+        json: async () => ({ success: true, data: { text: '下一句' } }),
+      })
+    vi.stubGlobal('fetch', mockFetch)
+    const queue = new SingleFlightQueue()
+
+    const first = queue.run(() => recognize('http://localhost:3000', '', makeSilenceWav(), { timeoutMs: 50 }))
+    const second = queue.run(() => recognize('http://localhost:3000', '', makeSilenceWav(), { timeoutMs: 50 }))
+
+    await expect(first).rejects.toThrow('超时')
+    await expect(second).resolves.toBe('下一句')
+  })
+})
+
+describe('validateServerUrl', () => {
+  it('合法 http/https 地址', () => {
+    expect(validateServerUrl('http://192.168.1.100:3000')).toEqual({ ok: true, url: 'http://192.168.1.100:3000' })
+    expect(validateServerUrl(' https://asr.example.com/ ')).toEqual({ ok: true, url: 'https://asr.example.com' })
+  })
+
+  it('缺少协议头时报错', () => {
+    const r = validateServerUrl('192.168.1.100:3000')
+    expect(r.ok).toBe(false)
+    if (!r.ok) expect(r.error).toContain('缺少协议头')
+  })
+
+  it('开启自动补全时补 http://', () => {
+    expect(validateServerUrl('192.168.1.100:3000', { autoPrependScheme: true }))
+      .toEqual({ ok: true, url: 'http://192.168.1.100:3000' })
+  })
+
+  it('非 http 协议与无法解析的地址报错', () => {
+    expect(validateServerUrl('ftp://example.com').ok).toBe(false)
+    expect(validateServerUrl('http://').ok).toBe(false)
+    expect(validateServerUrl('not a url', { autoPrependScheme: true }).ok).toBe(false)
+    expect(validateServerUrl('').ok).toBe(false)
+  })
+
+  it('识别前校验地址，不发起请求', async () => {
+    const fetchMock = vi.fn()
+    vi.stubGlobal('fetch', fetchMock)
+    await expect(recognize('localhost:3000', '', makeSilenceWav())).rejects.toThrow('缺少协议头')
+    expect(fetchMock).not.toHaveBeenCalled()
+  })
+})
@@ -0,0 +1,32 @@
+import { describe, it, expect } from 'vitest'
+import { listAudioHosts, resolveAudioHost } from '../../electron/main/audio-host'
+
+describe('listAudioHosts', () => {
+  it('Windows 提供 WASAPI 独占模式', () => {
+    expect(listAudioHosts('win32').map(h => h.id)).toEqual(['default', 'wasapi-exclusive'])
+  })
+
+  it('其他平台只有系统默认', () => {
+    expect(listAudioHosts('darwin').map(h => h.id)).toEqual(['default'])
+    expect(listAudioHosts('linux').map(h => h.id)).toEqual(['default'])
+  })
+})
+
+describe('resolveAudioHost', () => {
+  it('可用后端返回对应启动开关', () => {
+    expect(resolveAudioHost('wasapi-exclusive', 'win32')).toEqual({
+      id: 'wasapi-exclusive',
+      switches: [['enable-exclusive-audio']],
+      fellBack: false,
+    })
+  })
+
+  it('当前平台不可用或未知时回退到默认', () => {
+    expect(resolveAudioHost('wasapi-exclusive', 'darwin')).toEqual({ id: 'default', switches: [], fellBack: true })
+    expect(resolveAudioHost('asio', 'win32').fellBack).toBe(true)
+  })
+
+  it('未配置时使用默认且不视为回退', () => {
+    expect(resolveAudioHost(undefined, 'win32')).toEqual({ id: 'default', switches: [], fellBack: false })
+  })
+})
@@ -0,0 +1,47 @@
+import { describe, it, expect } from 'vitest'
+import { applyInputGain, InputLevelMeter } from '../../src/audio-level'
+import { clampInputGain } from '../../shared/input-gain'
+
+describe('applyInputGain', () => {
+  it('按倍数放大并截断到 [-1, 1]', () => {
+    const samples = new Float32Array([0.1, -0.2, 0.4, -0.6])
+    const clipped = applyInputGain(samples, 2)
+    expect(samples[0]).toBeCloseTo(0.2)
+    expect(samples[1]).toBeCloseTo(-0.4)
+    expect(samples[2]).toBeCloseTo(0.8)
+    expect(samples[3]).toBe(-1)
+    expect(clipped).toBe(1)
+  })
+
+  it('增益为 1 时不修改采样', () => {
+    const samples = new Float32Array([0.5, -1.5])
+    expect(applyInputGain(samples, 1)).toBe(0)
+    expect(Array.from(samples)).toEqual([0.5, -1.5])
+  })
+})
+
+describe('clampInputGain', () => {
+  it('限制在 0.1~10，非法值回退 1', () => {
+    expect(clampInputGain(0)).toBe(0.1)
+    expect(clampInputGain(25)).toBe(10)
+    expect(clampInputGain(2.5)).toBe(2.5)
+    expect(clampInputGain(Number.NaN)).toBe(1)
+    expect(clampInputGain('3')).toBe(1)
+  })
+})
+
+describe('InputLevelMeter', () => {
+  it('按间隔汇总电平，周期内有削波时标记', () => {
+    let now = 1000
+    const meter = new InputLevelMeter(100, () => now)
+    expect(meter.push(new Float32Array([0.5, -0.5]), 2)).toMatchObject({ rms: 0.5, peak: 0.5, gain: 2, clipped: false })
+
+    now += 50
+    expect(meter.push(new Float32Array([1, -1]), 2, 2)).toBeNull()
+    now += 60
+    const level = meter.push(new Float32Array([0, 0]), 2)
+    expect(level?.peak).toBe(1)
+    expect(level?.clipped).toBe(true)
+    expect(level?.rms).toBeCloseTo(Math.sqrt(0.5))
+  })
+})
@@ -0,0 +1,57 @@
+import { describe, it, expect } from 'vitest'
+import {
+  normalizeAudioLifecycleEvent,
+  isAudioLifecycleError,
+  describeAudioLifecycleEvent,
+} from '../../electron/main/audio-lifecycle'
+
+describe('normalizeAudioLifecycleEvent', () => {
+  it('保留合法字段，来源未知时按录音处理', () => {
+    expect(normalizeAudioLifecycleEvent({
+      stage: 'stream-ended',
+      source: 'other',
+      deviceName: 'USB Mic',
+      message: '设备已断开',
+      at: '2026-01-01T00:00:00.000Z',
+    })).toEqual({
+      stage: 'stream-ended',
+      source: 'capture',
+      deviceName: 'USB Mic',
+      message: '设备已断开',
+      at: '2026-01-01T00:00:00.000Z',
+    })
+  })
+
+  it('未知阶段或非对象时返回 null', () => {
+    expect(normalizeAudioLifecycleEvent({ stage: 'crashed' })).toBeNull()
+    expect(normalizeAudioLifecycleEvent(null)).toBeNull()
+  })
+
+  it('过长的错误信息被截断', () => {
+    const event = normalizeAudioLifecycleEvent({ stage: 'capture-error', message: 'x'.repeat(2000) })
+    expect(event?.message).toHaveLength(500)
+  })
+})
+
+describe('isAudioLifecycleError', () => {
+  it('只有打开失败、音轨结束与采集出错视为异常', () => {
+    expect(isAudioLifecycleError('stream-open-failed')).toBe(true)
+    expect(isAudioLifecycleError('stream-ended')).toBe(true)
+    expect(isAudioLifecycleError('capture-error')).toBe(true)
+    expect(isAudioLifecycleError('stream-opened')).toBe(false)
+    expect(isAudioLifecycleError('capture-started')).toBe(false)
+  })
+})
+
+describe('describeAudioLifecycleEvent', () => {
+  it('包含来源、阶段、设备名与原因', () => {
+    const text = describeAudioLifecycleEvent({
+      stage: 'stream-ended',
+      source: 'vad',
+      deviceName: 'USB Mic',
+      message: '设备已断开',
+      at: '',
+    })
+    expect(text).toBe('VAD 监听麦克风音轨意外结束 device="USB Mic": 设备已断开')
+  })
+})
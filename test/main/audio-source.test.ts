@@ -0,0 +1,85 @@
+import { describe, it, expect, vi } from 'vitest'
+
+const getSourcesMock = vi.fn()
+
+vi.mock('electron', () => ({
+  desktopCapturer: { getSources: (...args: any[]) => getSourcesMock(...args) },
+}))
+
+import { installLoopbackCaptureHandler, normalizeAudioSource } from '../../electron/main/audio-source'
+
+describe('normalizeAudioSource', () => {
+  it('仅 Windows 保留系统声音（环回），其他平台回退为麦克风', () => {
+    expect(normalizeAudioSource('loopback', 'win32')).toBe('loopback')
+    expect(normalizeAudioSource('loopback', 'darwin')).toBe('input')
+    expect(normalizeAudioSource('loopback', 'linux')).toBe('input')
+  })
+
+  it('未知值按麦克风处理', () => {
+    expect(normalizeAudioSource(undefined, 'win32')).toBe('input')
+    expect(normalizeAudioSource('speaker', 'win32')).toBe('input')
+  })
+})
+
+describe('installLoopbackCaptureHandler', () => {
+  const floatFrame = { processId: 1, routingId: 1 }
+  const isFloatFrame = (frame: unknown) => frame === floatFrame
+
+  function install(source: 'input' | 'loopback', platform: NodeJS.Platform = 'win32') {
+    const setDisplayMediaRequestHandler = vi.fn()
+    const installed = installLoopbackCaptureHandler({ setDisplayMediaRequestHandler } as any, source, isFloatFrame as any, platform)
+    return { installed, setDisplayMediaRequestHandler, handler: setDisplayMediaRequestHandler.mock.calls[0]?.[0] }
+  }
+
+  it('Windows 上为浮窗的 getDisplayMedia 授予屏幕源与 loopback 音频', async () => {
+    const screenSource = { id: 'screen:0:0', name: 'Entire Screen' }
+    getSourcesMock.mockResolvedValue([screenSource])
+    const { installed, handler } = install('loopback')
+
+    expect(installed).toBe(true)
+    const callback = vi.fn()
+    handler({ frame: floatFrame, audioRequested: true, videoRequested: true }, callback)
+    await vi.waitFor(() => expect(callback).toHaveBeenCalled())
+
+    expect(getSourcesMock).toHaveBeenCalledWith({ types: ['screen'] })
+    expect(callback).toHaveBeenCalledWith({ video: screenSource, audio: 'loopback' })
+  })
+
+  it('未请求视频时只授予 loopback 音频，不枚举屏幕', () => {
+    getSourcesMock.mockClear()
+    const { handler } = install('loopback')
+    const callback = vi.fn()
+    handler({ frame: floatFrame, audioRequested: true, videoRequested: false }, callback)
+
+    expect(callback).toHaveBeenCalledWith({ audio: 'loopback' })
+    expect(getSourcesMock).not.toHaveBeenCalled()
+  })
+
+  it('拒绝浮窗以外页面的请求与不含音频的请求', () => {
+    getSourcesMock.mockClear()
+    const { handler } = install('loopback')
+    const other = vi.fn()
+    handler({ frame: { processId: 2, routingId: 1 }, audioRequested: true, videoRequested: true }, other)
+    const detached = vi.fn()
+    handler({ frame: null, audioRequested: true, videoRequested: true }, detached)
+    const videoOnly = vi.fn()
+    handler({ frame: floatFrame, audioRequested: false, videoRequested: true }, videoOnly)
+
+    expect(other).toHaveBeenCalledWith({})
+    expect(detached).toHaveBeenCalledWith({})
+    expect(videoOnly).toHaveBeenCalledWith({})
+    expect(getSourcesMock).not.toHaveBeenCalled()
+  })
+
+  it('录音来源为麦克风时移除授权', () => {
+    const { installed, setDisplayMediaRequestHandler } = install('input')
+    expect(installed).toBe(false)
+    expect(setDisplayMediaRequestHandler).toHaveBeenCalledWith(null)
+  })
+
+  it('其他平台不注册', () => {
+    const { installed, setDisplayMediaRequestHandler } = install('loopback', 'linux')
+    expect(installed).toBe(false)
+    expect(setDisplayMediaRequestHandler).not.toHaveBeenCalled()
+  })
+})
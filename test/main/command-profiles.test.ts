@@ -0,0 +1,79 @@
+import { describe, it, expect } from 'vitest'
+import {
+  applyCommandProfile,
+  listCommandProfiles,
+  nextCommandProfile,
+  profileLabel,
+  resolveCommandProfileName,
+} from '../../electron/main/command-profiles'
+
+const profiles = [
+  { name: '病理', commands: { 保存报告: 'F2', 取材: 'ALT+Q' } },
+  { name: '细胞学', commands: { 保存报告: 'CTRL+S' } },
+]
+
+describe('listCommandProfiles', () => {
+  it('列出方案名称、口令数与当前方案', () => {
+    expect(listCommandProfiles(profiles, '细胞学')).toEqual([
+      { name: '病理', active: false, commandCount: 2 },
+      { name: '细胞学', active: true, commandCount: 1 },
+    ])
+  })
+
+  it('使用基础指令时没有方案被选中', () => {
+    expect(listCommandProfiles(profiles, '').every(p => !p.active)).toBe(true)
+  })
+})
+
+describe('resolveCommandProfileName', () => {
+  it('存在的方案名去空白后返回', () => {
+    expect(resolveCommandProfileName(profiles, ' 病理 ')).toBe('病理')
+  })
+
+  it('空字符串、null 回到基础指令', () => {
+    expect(resolveCommandProfileName(profiles, '')).toBe('')
+    expect(resolveCommandProfileName(profiles, '  ')).toBe('')
+    expect(resolveCommandProfileName(profiles, null)).toBe('')
+  })
+
+  it('不存在的方案或非字符串报错', () => {
+    expect(() => resolveCommandProfileName(profiles, '胃镜')).toThrow('指令方案不存在: "胃镜"')
+    expect(() => resolveCommandProfileName(profiles, 1)).toThrow('必须是字符串')
+  })
+})
+
+describe('nextCommandProfile', () => {
+  it('基础指令 → 各方案 → 基础指令循环', () => {
+    expect(nextCommandProfile(profiles, '')).toBe('病理')
+    expect(nextCommandProfile(profiles, '病理')).toBe('细胞学')
+    expect(nextCommandProfile(profiles, '细胞学')).toBe('')
+  })
+
+  it('没有方案时保持基础指令', () => {
+    expect(nextCommandProfile([], '')).toBe('')
+  })
+
+  it('当前方案已被删除时回到基础指令', () => {
+    expect(nextCommandProfile(profiles, '胃镜')).toBe('')
+  })
+})
+
+describe('applyCommandProfile', () => {
+  const base = { 保存报告: 'ALT+S', 上一个: 'ALT+A' }
+
+  it('方案口令叠加在基础指令表之上，同名以方案为准', () => {
+    expect(applyCommandProfile(base, profiles, '病理')).toEqual({ 保存报告: 'F2', 上一个: 'ALT+A', 取材: 'ALT+Q' })
+  })
+
+  it('未选方案或方案不存在时使用基础指令表', () => {
+    expect(applyCommandProfile(base, profiles, '')).toBe(base)
+    expect(applyCommandProfile(base, profiles, '胃镜')).toBe(base)
+  })
+})
+
+describe('profileLabel', () => {
+  it('空名称显示为基础指令', () => {
+    expect(profileLabel('')).toBe('基础指令')
+    expect(profileLabel('病理')).toBe('病理')
+  })
+})
@@ -0,0 +1,32 @@
+import { describe, it, expect } from 'vitest'
+import { migrateConfig, CONFIG_VERSION } from '../../electron/main/config-migration'
+
+describe('migrateConfig', () => {
+  it('旧配置中不生效的 useClipboard: false 改回 true，保持升级前的粘贴行为', () => {
+    const legacy: Record<string, any> = { input: { useClipboard: false, pasteShortcut: 'CTRL+V' } }
+    expect(migrateConfig(legacy)).toBe(true)
+    expect(legacy).toEqual({ configVersion: CONFIG_VERSION, input: { useClipboard: true, pasteShortcut: 'CTRL+V' } })
+  })
+
+  it('新版本保存的 useClipboard: false 是用户的选择，不再改动', () => {
+    const config: Record<string, any> = { configVersion: CONFIG_VERSION, input: { useClipboard: false } }
+    expect(migrateConfig(config)).toBe(false)
+    expect(config.input.useClipboard).toBe(false)
+  })
+
+  it('缺少 input 或版本号无效时按旧配置升级', () => {
+    const empty: Record<string, any> = {}
+    expect(migrateConfig(empty)).toBe(true)
+    expect(empty).toEqual({ configVersion: CONFIG_VERSION })
+
+    const invalid: Record<string, any> = { configVersion: 'x', input: { useClipboard: false } }
+    migrateConfig(invalid)
+    expect(invalid.input.useClipboard).toBe(true)
+  })
+
+  it('比当前版本新的配置不处理', () => {
+    const future: Record<string, any> = { configVersion: CONFIG_VERSION + 1, input: { useClipboard: false } }
+    expect(migrateConfig(future)).toBe(false)
+    expect(future.input.useClipboard).toBe(false)
+  })
+})
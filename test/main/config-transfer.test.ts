@@ -0,0 +1,94 @@
+import { describe, it, expect } from 'vitest'
+import { exportConfigText, parseConfigImport } from '../../electron/main/config-transfer'
+import type { AppConfig } from '../../electron/main/config'
+
+const baseConfig = {
+  configVersion: 1,
+  server: { url: 'http://192.168.1.100:3000', asrConfigId: '', autoPrependScheme: false, transport: 'http' },
+  hotkey: { record: 'ALT+E', repeatCommand: '' },
+  voiceCommands: { 下一个: 'ALT+N', 保存: 'CTRL+S', 换行: '{NEWLINE}', 另存为: 'ALT+F; A; ENTER' },
+  asr: { mode: 'api' },
+} as unknown as AppConfig
+
+describe('exportConfigText / parseConfigImport', () => {
+  it('导出内容可原样导入', () => {
+    const result = parseConfigImport(exportConfigText(baseConfig), 'win32')
+    expect(result.ok).toBe(true)
+    if (result.ok) expect(result.config).toEqual(baseConfig)
+  })
+
+  it('非 JSON 或非对象时拒绝导入', () => {
+    expect(parseConfigImport('server = 1', 'win32')).toMatchObject({ ok: false, error: expect.stringContaining('JSON') })
+    expect(parseConfigImport('[1, 2]', 'win32')).toMatchObject({ ok: false, error: expect.stringContaining('JSON 对象') })
+  })
+
+  it('服务地址不合法时拒绝导入', () => {
+    const text = JSON.stringify({ ...baseConfig, server: { ...baseConfig.server, url: 'ftp://host' } })
+    expect(parseConfigImport(text, 'win32')).toMatchObject({ ok: false, error: expect.stringContaining('http/https') })
+  })
+
+  it('开启自动补全协议时规范化服务地址', () => {
+    const text = JSON.stringify({ ...baseConfig, server: { ...baseConfig.server, url: '10.0.0.2:3000/', autoPrependScheme: true } })
+    const result = parseConfigImport(text, 'win32')
+    expect(result.ok && result.config.server?.url).toBe('http://10.0.0.2:3000')
+  })
+
+  it('本地识别模式下允许服务地址为空', () => {
+    const text = JSON.stringify({ ...baseConfig, server: { ...baseConfig.server, url: '' }, asr: { mode: 'local' } })
+    expect(parseConfigImport(text, 'win32').ok).toBe(true)
+  })
+
+  it('指令快捷键非法时指出对应口令', () => {
+    const text = JSON.stringify({ ...baseConfig, voiceCommands: { 保存: 'CTRL+S;;ENTER' } })
+    expect(parseConfigImport(text, 'win32')).toMatchObject({ ok: false, error: expect.stringContaining('保存') })
+    const nonString = JSON.stringify({ ...baseConfig, voiceCommands: { 保存: 1 } })
+    expect(parseConfigImport(nonString, 'win32').ok).toBe(false)
+  })
+
+  it('全局热键不允许多步序列', () => {
+    const text = JSON.stringify({ ...baseConfig, hotkey: { record: 'ALT+E; F', repeatCommand: '' } })
+    expect(parseConfigImport(text, 'win32')).toMatchObject({ ok: false, error: expect.stringContaining('hotkey.record') })
+  })
+
+  it('粘贴快捷键非法时拒绝导入', () => {
+    const text = JSON.stringify({ ...baseConfig, input: { pasteShortcut: 'CTRL+V;;' } })
+    expect(parseConfigImport(text, 'win32')).toMatchObject({ ok: false, error: expect.stringContaining('input.pasteShortcut') })
+  })
+
+  it('旧版本导出的配置导入时同样升级 useClipboard', () => {
+    const { configVersion: _version, ...legacy } = baseConfig as unknown as Record<string, unknown>
+    const result = parseConfigImport(JSON.stringify({ ...legacy, input: { useClipboard: false } }), 'win32')
+    expect(result).toMatchObject({ ok: true, config: { configVersion: 1, input: { useClipboard: true } } })
+  })
+
+  it('导入文件中的 allowExec 被丢弃，不能同时打开运行程序开关', () => {
+    const text = JSON.stringify({
+      ...baseConfig,
+      voiceCommands: { ...baseConfig.voiceCommands, 计算器: 'RUN:calc.exe' },
+      voiceCommandOptions: { enabled: true, allowExec: true },
+    })
+    const result = parseConfigImport(text, 'win32')
+    expect(result.ok).toBe(true)
+    if (!result.ok) return
+    expect(result.config.voiceCommandOptions).toEqual({ enabled: true })
+    expect(result.config.voiceCommands).toMatchObject({ 计算器: 'RUN:calc.exe' })
+  })
+
+  it('RUN: 指令缺少程序路径时拒绝导入，指令方案中的指令同样校验', () => {
+    const emptyRun = JSON.stringify({ ...baseConfig, voiceCommands: { 运行: 'RUN:   ' } })
+    expect(parseConfigImport(emptyRun, 'win32')).toMatchObject({ ok: false, error: expect.stringContaining('运行') })
+
+    const badProfile = JSON.stringify({
+      ...baseConfig,
+      voiceCommandProfiles: [{ name: '病理', commands: { 取材: 'RUN:' } }],
+    })
+    expect(parseConfigImport(badProfile, 'win32')).toMatchObject({ ok: false, error: expect.stringContaining('病理') })
+
+    const badShortcut = JSON.stringify({
+      ...baseConfig,
+      voiceCommandProfiles: [{ name: '病理', commands: { 取材: 'CTRL+V;;' } }],
+    })
+    expect(parseConfigImport(badShortcut, 'win32').ok).toBe(false)
+  })
+})
+
@@ -0,0 +1,109 @@
+import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest'
+import * as fs from 'fs'
+import * as os from 'os'
+import * as path from 'path'
+
+let userDataDir = ''
+let storeWrites = 0
+
+vi.mock('electron', () => ({
+  app: { getPath: () => userDataDir },
+}))
+
+// 内存版 electron-store：与真实实现一样，创建时把默认值写入缺失的配置文件，每次赋值 store 写一次盘
+vi.mock('../../electron/main/config-store', () => ({
+  createConfigStore: (name: string, defaults: unknown) => {
+    const file = path.join(userDataDir, `${name}.json`)
+    if (!fs.existsSync(file)) fs.writeFileSync(file, JSON.stringify(defaults))
+    return {
+      get store() {
+        return { ...(defaults as object), ...JSON.parse(fs.readFileSync(file, 'utf8')) }
+      },
+      set store(value: unknown) {
+        storeWrites++
+        fs.writeFileSync(file, JSON.stringify(value))
+      },
+    }
+  },
+}))
+
+async function loadConfigModule() {
+  return import('../../electron/main/config')
+}
+
+describe('配置保存合并写盘', () => {
+  beforeEach(() => {
+    vi.resetModules()
+    vi.spyOn(console, 'info').mockImplementation(() => { })
+    userDataDir = fs.mkdtempSync(path.join(os.tmpdir(), 'logene-config-test-'))
+    storeWrites = 0
+  })
+
+  afterEach(() => {
+    vi.restoreAllMocks()
+    fs.rmSync(userDataDir, { recursive: true, force: true })
+  })
+
+  it('连续读取-修改-保存只在合并后写一次盘，读取返回尚未写盘的最新值', async () => {
+    const { initConfigStore, getConfig, saveConfig, flushConfig } = await loadConfigModule()
+    initConfigStore()
+    flushConfig()
+    storeWrites = 0
+
+    const first = getConfig()
+    first.input.quickRecordMs = 3000
+    saveConfig(first)
+    const second = getConfig()
+    expect(second.input.quickRecordMs).toBe(3000)
+    second.ui.resultDisplayMs = 2000
+    saveConfig(second)
+    expect(storeWrites).toBe(0)
+
+    flushConfig()
+    expect(storeWrites).toBe(1)
+    const saved = JSON.parse(fs.readFileSync(path.join(userDataDir, 'config.json'), 'utf8'))
+    expect(saved.input.quickRecordMs).toBe(3000)
+    expect(saved.ui.resultDisplayMs).toBe(2000)
+  })
+})
+
+describe('要求已有配置文件（LOGENE_REQUIRE_CONFIG）', () => {
+  const configFile = () => path.join(userDataDir, 'config.json')
+
+  beforeEach(() => {
+    vi.resetModules()
+    vi.spyOn(console, 'info').mockImplementation(() => { })
+    userDataDir = fs.mkdtempSync(path.join(os.tmpdir(), 'logene-config-test-'))
+  })
+
+  afterEach(() => {
+    vi.restoreAllMocks()
+    vi.unstubAllEnvs()
+    fs.rmSync(userDataDir, { recursive: true, force: true })
+  })
+
+  it('设置环境变量且配置文件缺失时拒绝启动，不写入默认配置', async () => {
+    vi.stubEnv('LOGENE_REQUIRE_CONFIG', '1')
+    const { initConfigStore } = await loadConfigModule()
+
+    expect(() => initConfigStore()).toThrow('未找到配置文件')
+    expect(fs.existsSync(configFile())).toBe(false)
+  })
+
+  it('设置环境变量且配置文件存在时正常加载', async () => {
+    vi.stubEnv('LOGENE_REQUIRE_CONFIG', '1')
+    fs.writeFileSync(configFile(), JSON.stringify({ configVersion: 1, server: { url: 'http://10.0.0.8:3000' } }))
+    const { initConfigStore, getConfig } = await loadConfigModule()
+
+    expect(initConfigStore()).toEqual({ path: configFile(), created: false, requireExisting: true })
+    expect(getConfig().server.url).toBe('http://10.0.0.8:3000')
+  })
+
+  it('未设置环境变量时缺失的配置文件自动写入默认配置', async () => {
+    vi.stubEnv('LOGENE_REQUIRE_CONFIG', '')
+    const { initConfigStore } = await loadConfigModule()
+
+    expect(initConfigStore()).toEqual({ path: configFile(), created: true, requireExisting: false })
+    expect(fs.existsSync(configFile())).toBe(true)
+  })
+})
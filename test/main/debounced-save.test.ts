@@ -0,0 +1,60 @@
+import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest'
+import { DebouncedSaver } from '../../electron/main/debounced-save'
+
+describe('DebouncedSaver', () => {
+  beforeEach(() => {
+    vi.useFakeTimers()
+  })
+
+  afterEach(() => {
+    vi.useRealTimers()
+  })
+
+  it('连续多次保存在静默期后只写一次最新值', () => {
+    const write = vi.fn()
+    const saver = new DebouncedSaver<{ gain: number }>(write, 500)
+
+    saver.schedule({ gain: 1 })
+    vi.advanceTimersByTime(300)
+    saver.schedule({ gain: 2 })
+    vi.advanceTimersByTime(300)
+    saver.schedule({ gain: 3 })
+    expect(saver.pending).toEqual({ gain: 3 })
+    vi.advanceTimersByTime(499)
+    expect(write).not.toHaveBeenCalled()
+
+    vi.advanceTimersByTime(1)
+    expect(write).toHaveBeenCalledTimes(1)
+    expect(write).toHaveBeenCalledWith({ gain: 3 })
+    expect(saver.pending).toBeUndefined()
+  })
+
+  it('flush 立即写入并取消定时写入，无待保存值时不写', () => {
+    const write = vi.fn()
+    const saver = new DebouncedSaver<number>(write, 500)
+
+    saver.flush()
+    expect(write).not.toHaveBeenCalled()
+
+    saver.schedule(1)
+    saver.flush()
+    vi.advanceTimersByTime(1000)
+    expect(write.mock.calls).toEqual([[1]])
+  })
+
+  it('写入失败时保留待保存值，下次 flush 重试', () => {
+    const onError = vi.fn()
+    const write = vi.fn()
+      .mockImplementationOnce(() => { throw new Error('EBUSY') })
+    const saver = new DebouncedSaver<number>(write, 500, onError)
+
+    saver.schedule(7)
+    vi.advanceTimersByTime(500)
+    expect(onError).toHaveBeenCalledTimes(1)
+    expect(saver.pending).toBe(7)
+
+    saver.flush()
+    expect(write).toHaveBeenLastCalledWith(7)
+    expect(saver.pending).toBeUndefined()
+  })
+})
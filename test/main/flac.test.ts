@@ -0,0 +1,183 @@
+import { describe, it, expect } from 'vitest'
+import { encodeWav } from '../../src/wav'
+import { encodeWavToFlac } from '../../electron/main/flac'
+import { WAV_HEADER_BYTES } from '../../electron/main/wav-info'
+
+// 按 FLAC 规范独立实现的最小解码器（覆盖 CONSTANT / VERBATIM / FIXED + Rice），用于验证编码结果可解码且无损
+class BitReader {
+  private pos = 0
+
+  constructor(private readonly buf: Buffer) { }
+
+  get bytePos(): number { return this.pos >> 3 }
+
+  read(bits: number): number {
+    let value = 0
+    for (let i = 0; i < bits; i++) {
+      value = value * 2 + ((this.buf[this.pos >> 3] >> (7 - (this.pos & 7))) & 1)
+      this.pos++
+    }
+    return value
+  }
+
+  readSigned(bits: number): number {
+    const value = this.read(bits)
+    return value >= 2 ** (bits - 1) ? value - 2 ** bits : value
+  }
+
+  readUnary(): number {
+    let q = 0
+    while (this.read(1) === 0) q++
+    return q
+  }
+
+  align(): void {
+    this.pos = Math.ceil(this.pos / 8) * 8
+  }
+}
+
+function crc16(bytes: Uint8Array): number {
+  let crc = 0
+  for (const byte of bytes) {
+    crc ^= byte << 8
+    for (let i = 0; i < 8; i++) crc = crc & 0x8000 ? ((crc << 1) ^ 0x8005) & 0xffff : (crc << 1) & 0xffff
+  }
+  return crc
+}
+
+function decodeSubframe(r: BitReader, blockSize: number, bps: number): Int32Array {
+  const samples = new Int32Array(blockSize)
+  expect(r.read(1)).toBe(0)
+  const type = r.read(6)
+  expect(r.read(1)).toBe(0)
+  if (type === 0) {
+    samples.fill(r.readSigned(bps))
+    return samples
+  }
+  if (type === 1) {
+    for (let i = 0; i < blockSize; i++) samples[i] = r.readSigned(bps)
+    return samples
+  }
+  expect(type >= 8 && type <= 12).toBe(true)
+  const order = type - 8
+  for (let i = 0; i < order; i++) samples[i] = r.readSigned(bps)
+  expect(r.read(2)).toBe(0)
+  const partitionOrder = r.read(4)
+  const partitions = 2 ** partitionOrder
+  let i = order
+  for (let p = 0; p < partitions; p++) {
+    const k = r.read(4)
+    const count = blockSize / partitions - (p === 0 ? order : 0)
+    for (let n = 0; n < count; n++) {
+      const u = r.readUnary() * 2 ** k + r.read(k)
+      const residual = u % 2 === 1 ? -(u + 1) / 2 : u / 2
+      const s = samples
+      const predicted = [
+        0,
+        s[i - 1],
+        2 * s[i - 1] - s[i - 2],
+        3 * s[i - 1] - 3 * s[i - 2] + s[i - 3],
+        4 * s[i - 1] - 6 * s[i - 2] + 4 * s[i - 3] - s[i - 4],
+      ][order]
+      samples[i++] = residual + predicted
+    }
+  }
+  return samples
+}
+
+function decodeFlac(buf: Buffer): { sampleRate: number; channels: number; samples: Int16Array } {
+  const r = new BitReader(buf)
+  expect(r.read(32)).toBe(0x664c6143)
+  let sampleRate = 0
+  let channels = 0
+  let bps = 0
+  let total = 0
+  let last = 0
+  do {
+    last = r.read(1)
+    const type = r.read(7)
+    const length = r.read(24)
+    expect(type).toBe(0)
+    expect(length).toBe(34)
+    r.read(16 + 16 + 24 + 24)
+    sampleRate = r.read(20)
+    channels = r.read(3) + 1
+    bps = r.read(5) + 1
+    total = r.read(36)
+    r.read(64)
+    r.read(64)
+  } while (!last)
+
+  const samples = new Int16Array(total * channels)
+  let written = 0
+  while (written < total) {
+    const frameStart = r.bytePos
+    expect(r.read(14)).toBe(0x3ffe)
+    r.read(2)
+    expect(r.read(4)).toBe(0b0111)
+    r.read(4)
+    expect(r.read(4)).toBe(channels - 1)
+    r.read(4)
+    const first = r.read(8)
+    let extraBytes = 0
+    for (let bit = 6; first & 0x80 && bit >= 0 && (first >> bit) & 1; bit--) extraBytes++
+    r.read(8 * extraBytes)
+    const blockSize = r.read(16) + 1
+    r.read(8)
+    const decoded = Array.from({ length: channels }, () => decodeSubframe(r, blockSize, bps))
+    r.align()
+    const crcPos = r.bytePos
+    expect(r.read(16)).toBe(crc16(buf.subarray(frameStart, crcPos)))
+    for (let i = 0; i < blockSize; i++) {
+      for (let c = 0; c < channels; c++) samples[(written + i) * channels + c] = decoded[c][i]
+    }
+    written += blockSize
+  }
+  expect(r.bytePos).toBe(buf.length)
+  return { sampleRate, channels, samples }
+}
+
+function pcmOf(wav: Buffer): Int16Array {
+  return new Int16Array(wav.buffer.slice(wav.byteOffset + WAV_HEADER_BYTES, wav.byteOffset + wav.byteLength))
+}
+
+describe('encodeWavToFlac', () => {
+  it('语音类信号可无损解码且体积明显小于 WAV', () => {
+    const audio = new Float32Array(16000 * 3)
+    for (let i = 0; i < audio.length; i++) {
+      const t = i / 16000
+      audio[i] = 0.3 * Math.sin(2 * Math.PI * 220 * t) * Math.sin(2 * Math.PI * 3 * t)
+        + 0.1 * Math.sin(2 * Math.PI * 1250 * t)
+    }
+    const wav = Buffer.from(encodeWav([audio]))
+    const flac = encodeWavToFlac(wav)
+    const decoded = decodeFlac(flac)
+
+    expect(decoded.sampleRate).toBe(16000)
+    expect(decoded.channels).toBe(1)
+    expect(decoded.samples).toEqual(pcmOf(wav))
+    expect(flac.length).toBeLessThan(wav.length * 0.7)
+  })
+
+  it('静音、噪声与多声道均可还原', () => {
+    let seed = 7
+    const noise = () => {
+      seed = (seed * 1103515245 + 12345) % 2147483648
+      return seed / 2147483648 - 0.5
+    }
+    const audio = new Float32Array(5000 * 2)
+    for (let i = 2000; i < audio.length; i++) audio[i] = noise() * 1.8
+    const wav = Buffer.from(encodeWav([audio], 48000, 2))
+    const decoded = decodeFlac(encodeWavToFlac(wav))
+
+    expect(decoded.sampleRate).toBe(48000)
+    expect(decoded.channels).toBe(2)
+    expect(decoded.samples).toEqual(pcmOf(wav))
+  })
+
+  it('空音频只输出文件头', () => {
+    const flac = encodeWavToFlac(Buffer.from(encodeWav([new Float32Array(0)])))
+    expect(flac.length).toBe(4 + 4 + 34)
+    expect(decodeFlac(flac).samples).toHaveLength(0)
+  })
+})
@@ -1,5 +1,5 @@
 import { describe, expect, it, vi } from 'vitest'
-import { FocusController } from '../../electron/main/focus-controller'
+import { FocusController, pickWindowByTitle } from '../../electron/main/focus-controller'
 
 describe('FocusController.isSelfAppFrontmost', () => {
   it('当前前台是本应用时返回 true', async () => {
@@ -20,3 +20,22 @@ describe('FocusController.isSelfAppFrontmost', () => {
     await expect(focusController.isSelfAppFrontmost('test')).resolves.toBe(false)
   })
 })
+
+describe('pickWindowByTitle', () => {
+  const windows = [
+    { hwnd: '1', title: '记事本 - LIS 备忘.txt' },
+    { hwnd: '2', title: 'LIS' },
+    { hwnd: '3', title: '病理信息系统 LIS v5' },
+  ]
+
+  it('优先完全一致（忽略大小写），其次取第一个包含关键字的窗口', () => {
+    expect(pickWindowByTitle(windows, 'lis')).toBe('2')
+    expect(pickWindowByTitle(windows, '病理信息')).toBe('3')
+    expect(pickWindowByTitle(windows, 'LIS v')).toBe('3')
+  })
+
+  it('无匹配或关键字为空时返回 null', () => {
+    expect(pickWindowByTitle(windows, 'HIS')).toBeNull()
+    expect(pickWindowByTitle(windows, '  ')).toBeNull()
+  })
+})
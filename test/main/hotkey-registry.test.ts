@@ -0,0 +1,86 @@
+import { describe, it, expect, vi } from 'vitest'
+import { HotkeyRegistry, checkHotkeyAvailable } from '../../electron/main/hotkey-registry'
+
+describe('HotkeyRegistry', () => {
+  it('按固定动作顺序列出，并保留每项的注册状态', () => {
+    const registry = new HotkeyRegistry()
+    registry.set({ action: 'rewrite', accelerator: 'Alt+W', status: 'failed', detail: '被其它应用占用或系统不允许' })
+    registry.set({ action: 'record', accelerator: 'Alt+E', status: 'registered' })
+    registry.set({ action: 'undo-last-insert', accelerator: 'Ctrl+Alt+?', status: 'invalid', detail: 'Error: conversion failure' })
+
+    expect(registry.list()).toEqual([
+      { action: 'record', accelerator: 'Alt+E', status: 'registered' },
+      { action: 'undo-last-insert', accelerator: 'Ctrl+Alt+?', status: 'invalid', detail: 'Error: conversion failure' },
+      { action: 'rewrite', accelerator: 'Alt+W', status: 'failed', detail: '被其它应用占用或系统不允许' },
+    ])
+  })
+
+  it('同一动作重新注册时覆盖，注销后不再列出', () => {
+    const registry = new HotkeyRegistry()
+    registry.set({ action: 'record', accelerator: 'Alt+E', status: 'registered' })
+    registry.set({ action: 'record', accelerator: 'F9', status: 'registered' })
+    registry.set({ action: 'vad-toggle', accelerator: 'Alt+Shift+V', status: 'registered' })
+    expect(registry.list().map(b => b.accelerator)).toEqual(['F9', 'Alt+Shift+V'])
+
+    registry.remove('record')
+    expect(registry.list().map(b => b.action)).toEqual(['vad-toggle'])
+    registry.clear()
+    expect(registry.list()).toEqual([])
+  })
+
+  it('返回副本，外部修改不影响记录', () => {
+    const registry = new HotkeyRegistry()
+    registry.set({ action: 'record', accelerator: 'Alt+E', status: 'registered' })
+    registry.list()[0].status = 'failed'
+    expect(registry.list()[0].status).toBe('registered')
+  })
+})
+
+function fakeShortcuts(taken: string[] = []) {
+  const registered = new Set<string>()
+  return {
+    registered,
+    register: vi.fn((accelerator: string) => {
+      if (accelerator.includes('?')) throw new Error('conversion failure')
+      if (taken.includes(accelerator) || registered.has(accelerator)) return false
+      registered.add(accelerator)
+      return true
+    }),
+    unregister: vi.fn((accelerator: string) => { registered.delete(accelerator) }),
+    isRegistered: vi.fn((accelerator: string) => registered.has(accelerator)),
+  }
+}
+
+describe('checkHotkeyAvailable', () => {
+  it('可注册时立即注销，不留下测试注册', () => {
+    const shortcuts = fakeShortcuts()
+    expect(checkHotkeyAvailable('Alt+E', shortcuts, new HotkeyRegistry()))
+      .toEqual({ accelerator: 'Alt+E', available: true })
+    expect(shortcuts.unregister).toHaveBeenCalledWith('Alt+E')
+    expect(shortcuts.registered.size).toBe(0)
+  })
+
+  it('被其它应用占用时报告冲突，且不调用注销', () => {
+    const shortcuts = fakeShortcuts(['Alt+W'])
+    expect(checkHotkeyAvailable('Alt+W', shortcuts, new HotkeyRegistry()))
+      .toMatchObject({ accelerator: 'Alt+W', available: false, reason: 'conflict' })
+    expect(shortcuts.unregister).not.toHaveBeenCalled()
+  })
+
+  it('无法解析或为空时报告无效', () => {
+    const shortcuts = fakeShortcuts()
+    expect(checkHotkeyAvailable('Ctrl+?', shortcuts, new HotkeyRegistry()))
+      .toMatchObject({ available: false, reason: 'invalid' })
+    expect(checkHotkeyAvailable('  ', shortcuts, new HotkeyRegistry()))
+      .toMatchObject({ available: false, reason: 'invalid' })
+  })
+
+  it('本程序已注册的热键按可用返回并注明动作，不再重复注册', () => {
+    const shortcuts = fakeShortcuts()
+    const registry = new HotkeyRegistry()
+    registry.set({ action: 'vad-toggle', accelerator: 'Alt+Shift+V', status: 'registered' })
+    expect(checkHotkeyAvailable('ALT+SHIFT+V', shortcuts, registry))
+      .toEqual({ accelerator: 'ALT+SHIFT+V', available: true, ownedBy: 'vad-toggle' })
+    expect(shortcuts.register).not.toHaveBeenCalled()
+  })
+})
@@ -0,0 +1,56 @@
+import { describe, it, expect, vi, afterEach } from 'vitest'
+import { InputDeviceSwitchRequests, normalizeInputDeviceSwitchReport } from '../../electron/main/input-device-switch'
+
+describe('normalizeInputDeviceSwitchReport', () => {
+  it('缺少 requestId 时忽略', () => {
+    expect(normalizeInputDeviceSwitchReport({ ok: true, deviceId: 'a' })).toBeNull()
+    expect(normalizeInputDeviceSwitchReport(null)).toBeNull()
+  })
+
+  it('失败回报缺少原因时补默认提示', () => {
+    expect(normalizeInputDeviceSwitchReport({ requestId: 3, ok: false })).toEqual({
+      requestId: 3,
+      result: { ok: false, message: '切换麦克风失败' },
+    })
+  })
+})
+
+describe('InputDeviceSwitchRequests', () => {
+  afterEach(() => {
+    vi.useRealTimers()
+  })
+
+  it('浮窗回报成功后返回实际设备', async () => {
+    const requests = new InputDeviceSwitchRequests()
+    const sent: { requestId: number; name: string }[] = []
+    const pending = requests.request('USB 耳麦', (request) => {
+      sent.push(request)
+      return true
+    })
+
+    expect(sent).toEqual([{ requestId: 1, name: 'USB 耳麦' }])
+    expect(requests.settle({ requestId: 1, ok: true, deviceId: 'dev-1', deviceName: 'USB 耳麦 (Jabra)' })).toBe(true)
+    await expect(pending).resolves.toEqual({ ok: true, deviceId: 'dev-1', deviceName: 'USB 耳麦 (Jabra)' })
+  })
+
+  it('设备打不开时返回浮窗给出的原因', async () => {
+    const requests = new InputDeviceSwitchRequests()
+    const pending = requests.request('坏设备', () => true)
+    requests.settle({ requestId: 1, ok: false, message: '无法打开麦克风「坏设备」，仍使用原设备' })
+    await expect(pending).resolves.toEqual({ ok: false, message: '无法打开麦克风「坏设备」，仍使用原设备' })
+  })
+
+  it('浮窗不可用时立即失败', async () => {
+    const requests = new InputDeviceSwitchRequests()
+    await expect(requests.request('', () => false)).resolves.toEqual({ ok: false, message: '浮窗未就绪，无法切换麦克风' })
+  })
+
+  it('超时后失败，迟到的回报被忽略', async () => {
+    vi.useFakeTimers()
+    const requests = new InputDeviceSwitchRequests(2000)
+    const pending = requests.request('USB 耳麦', () => true)
+    vi.advanceTimersByTime(2000)
+    await expect(pending).resolves.toEqual({ ok: false, message: '切换麦克风超时（2s 内浮窗未响应）' })
+    expect(requests.settle({ requestId: 1, ok: true, deviceId: 'dev-1', deviceName: 'USB 耳麦' })).toBe(false)
+  })
+})
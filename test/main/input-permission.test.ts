@@ -0,0 +1,54 @@
+import { describe, it, expect, vi } from 'vitest'
+import {
+  InputPermissionGate,
+  InputPermissionDeniedError,
+  isInputPermissionError,
+} from '../../electron/main/input-permission'
+
+describe('InputPermissionGate', () => {
+  it('只检测一次并缓存结果，不可用时直接拒绝且只回调一次', async () => {
+    const probe = vi.fn().mockResolvedValue({ ok: false, error: '未授予辅助功能权限' })
+    const onDenied = vi.fn()
+    const action = vi.fn()
+    const gate = new InputPermissionGate(probe, onDenied, 'darwin')
+
+    await expect(gate.run(action)).rejects.toBeInstanceOf(InputPermissionDeniedError)
+    await expect(gate.run(action)).rejects.toThrow('辅助功能')
+    expect(probe).toHaveBeenCalledTimes(1)
+    expect(onDenied).toHaveBeenCalledTimes(1)
+    expect(action).not.toHaveBeenCalled()
+  })
+
+  it('重新检测通过后恢复输入', async () => {
+    const probe = vi.fn()
+      .mockResolvedValueOnce({ ok: false, error: 'xdotool 不可用' })
+      .mockResolvedValueOnce({ ok: true })
+    const gate = new InputPermissionGate(probe, () => { }, 'linux')
+
+    await expect(gate.run(async () => 'typed')).rejects.toThrow('xdotool')
+    expect((await gate.recheck()).available).toBe(true)
+    await expect(gate.run(async () => 'typed')).resolves.toBe('typed')
+  })
+
+  it('运行中出现权限类报错时标记为不可用，其他错误原样抛出', async () => {
+    const onDenied = vi.fn()
+    const gate = new InputPermissionGate(async () => ({ ok: true }), onDenied, 'darwin')
+
+    await expect(gate.run(async () => { throw new Error('boom') })).rejects.toThrow('boom')
+    expect(gate.state?.available).toBe(true)
+
+    await expect(gate.run(async () => {
+      throw new Error('System Events got an error: osascript is not allowed to send keystrokes. (1002)')
+    })).rejects.toBeInstanceOf(InputPermissionDeniedError)
+    expect(gate.state?.available).toBe(false)
+    expect(onDenied).toHaveBeenCalledTimes(1)
+  })
+})
+
+describe('isInputPermissionError', () => {
+  it('识别常见的权限/环境缺失报错', () => {
+    expect(isInputPermissionError(new Error('spawn xdotool ENOENT'))).toBe(true)
+    expect(isInputPermissionError('execution error: Not authorized to send Apple events to System Events. (-1743)')).toBe(true)
+    expect(isInputPermissionError(new Error('SendInput 粘贴失败: sent=0, expected=4'))).toBe(false)
+  })
+})
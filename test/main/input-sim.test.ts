@@ -0,0 +1,406 @@
+import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest'
+
+const writeTextMock = vi.fn()
+const readTextMock = vi.fn(() => '')
+const execMock = vi.fn()
+const execFileMock = vi.fn()
+
+vi.mock('electron', () => ({
+  clipboard: {
+    writeText: (...args: any[]) => writeTextMock(...args),
+    readText: () => readTextMock(),
+    clear: vi.fn(),
+  },
+}))
+
+vi.mock('child_process', () => ({
+  exec: (...args: any[]) => execMock(...args),
+  execFile: (...args: any[]) => execFileMock(...args),
+}))
+
+vi.mock('../../electron/main/win32-focus', () => ({}))
+
+vi.mock('../../electron/main/logger', () => ({
+  logger: { info: vi.fn(), warn: vi.fn(), error: vi.fn(), debug: vi.fn() },
+}))
+
+// promisify 只关心最后一个回调参数
+function succeedCallback(...args: any[]) {
+  const cb = args[args.length - 1]
+  if (typeof cb === 'function') cb(null, { stdout: '', stderr: '' })
+}
+
+const originalPlatform = process.platform
+
+describe('typeText', () => {
+  beforeEach(() => {
+    vi.clearAllMocks()
+    vi.resetModules()
+    Object.defineProperty(process, 'platform', { value: 'linux' })
+    execMock.mockImplementation(succeedCallback)
+    execFileMock.mockImplementation(succeedCallback)
+  })
+
+  afterEach(() => {
+    Object.defineProperty(process, 'platform', { value: originalPlatform })
+  })
+
+  it('剪贴板可用时走粘贴', async () => {
+    let stored = ''
+    writeTextMock.mockImplementation((text: string) => { stored = text })
+    readTextMock.mockImplementation(() => stored)
+    const { typeText } = await import('../../electron/main/input-sim')
+
+    await expect(typeText('肉眼所见')).resolves.toBe('clipboard')
+
+    expect(execMock).toHaveBeenCalledWith('xdotool key ctrl+v', expect.any(Function))
+    expect(execFileMock).not.toHaveBeenCalled()
+  })
+
+  it('剪贴板写入抛错时回退键盘键入', async () => {
+    writeTextMock.mockImplementation(() => { throw new Error('clipboard locked') })
+    const { typeText } = await import('../../electron/main/input-sim')
+
+    await expect(typeText('肉眼所见')).resolves.toBe('keyboard')
+
+    expect(execFileMock).toHaveBeenCalledWith(
+      'xdotool',
+      ['type', '--clearmodifiers', '--', '肉眼所见'],
+      expect.any(Function),
+    )
+    expect(execMock).not.toHaveBeenCalled()
+  })
+
+  it('剪贴板内容未写入（被占用）时回退键盘键入', async () => {
+    readTextMock.mockImplementation(() => 'other app content')
+    const { typeText } = await import('../../electron/main/input-sim')
+
+    await typeText('灰白色')
+
+    expect(execFileMock).toHaveBeenCalledWith(
+      'xdotool',
+      ['type', '--clearmodifiers', '--', '灰白色'],
+      expect.any(Function),
+    )
+  })
+
+  it('换行与制表符原样写入剪贴板', async () => {
+    let stored = ''
+    writeTextMock.mockImplementation((text: string) => { stored = text })
+    readTextMock.mockImplementation(() => stored)
+    const { typeText } = await import('../../electron/main/input-sim')
+
+    await typeText('\n')
+
+    expect(writeTextMock).toHaveBeenCalledWith('\n')
+    expect(execMock).toHaveBeenCalledWith('xdotool key ctrl+v', expect.any(Function))
+  })
+
+  it('自定义粘贴快捷键时按配置发送', async () => {
+    let stored = ''
+    writeTextMock.mockImplementation((text: string) => { stored = text })
+    readTextMock.mockImplementation(() => stored)
+    const { typeText } = await import('../../electron/main/input-sim')
+
+    await expect(typeText('肉眼所见', { pasteShortcut: 'CTRL+SHIFT+V' })).resolves.toBe('clipboard')
+
+    expect(execMock).toHaveBeenCalledWith('xdotool key ctrl+shift+v', expect.any(Function))
+    expect(execMock).not.toHaveBeenCalledWith('xdotool key ctrl+v', expect.any(Function))
+  })
+
+  it('追加方式先按 End 再粘贴', async () => {
+    let stored = ''
+    writeTextMock.mockImplementation((text: string) => { stored = text })
+    readTextMock.mockImplementation(() => stored)
+    const { typeText } = await import('../../electron/main/input-sim')
+
+    await typeText('灰白色', { pasteMode: 'append' })
+
+    expect(execMock.mock.calls.map(([cmd]) => cmd)).toEqual(['xdotool key End', 'xdotool key ctrl+v'])
+  })
+
+  it('关闭剪贴板注入时直接键盘键入，不写剪贴板', async () => {
+    const { typeText } = await import('../../electron/main/input-sim')
+
+    await expect(typeText('肉眼所见', { useClipboard: false, pasteMode: 'append' })).resolves.toBe('keyboard')
+
+    expect(writeTextMock).not.toHaveBeenCalled()
+    expect(execMock.mock.calls.map(([cmd]) => cmd)).toEqual(['xdotool key End'])
+    expect(execFileMock).toHaveBeenCalledWith(
+      'xdotool',
+      ['type', '--clearmodifiers', '--', '肉眼所见'],
+      expect.any(Function),
+    )
+  })
+
+  it('粘贴快捷键为默认 CTRL+V 时走原生粘贴', async () => {
+    let stored = ''
+    writeTextMock.mockImplementation((text: string) => { stored = text })
+    readTextMock.mockImplementation(() => stored)
+    const { typeText } = await import('../../electron/main/input-sim')
+
+    await typeText('灰白色', { pasteShortcut: 'control + v' })
+
+    expect(execMock).toHaveBeenCalledWith('xdotool key ctrl+v', expect.any(Function))
+  })
+
+  it('键盘键入路径保留换行与制表符', async () => {
+    writeTextMock.mockImplementation(() => { throw new Error('clipboard locked') })
+    const { typeText } = await import('../../electron/main/input-sim')
+
+    await typeText('\t')
+
+    expect(execFileMock).toHaveBeenCalledWith(
+      'xdotool',
+      ['type', '--clearmodifiers', '--', '\t'],
+      expect.any(Function),
+    )
+  })
+
+  it('unicode 键入方式逐字符按码位发送，默认仍按文本键入', async () => {
+    const { typeText } = await import('../../electron/main/input-sim')
+
+    await typeText('肉眼\n𠀀', { useClipboard: false, keyboardMethod: 'unicode' })
+    await typeText('肉眼', { useClipboard: false })
+
+    expect(execFileMock.mock.calls.map(([file, args]) => [file, args])).toEqual([
+      ['xdotool', ['key', '--clearmodifiers', '--delay', '0', 'U8089', 'U773C', 'Return', 'U20000']],
+      ['xdotool', ['type', '--clearmodifiers', '--', '肉眼']],
+    ])
+  })
+
+  it('特殊字符按配置改走码位发送或剪贴板，其余文字照常键入', async () => {
+    let stored = ''
+    writeTextMock.mockImplementation((text: string) => { stored = text })
+    readTextMock.mockImplementation(() => stored)
+    const { typeText } = await import('../../electron/main/input-sim')
+
+    await typeText('3×4cm，5μm', { useClipboard: false, specialChars: { '×': 'unicode', 'μ': 'clipboard' } })
+
+    expect(execFileMock.mock.calls.map(([file, args]) => [file, args])).toEqual([
+      ['xdotool', ['type', '--clearmodifiers', '--', '3']],
+      ['xdotool', ['key', '--clearmodifiers', '--delay', '0', 'U00D7']],
+      ['xdotool', ['type', '--clearmodifiers', '--', '4cm，5']],
+      ['xdotool', ['type', '--clearmodifiers', '--', 'm']],
+    ])
+    expect(writeTextMock).toHaveBeenCalledTimes(1)
+    expect(writeTextMock).toHaveBeenCalledWith('μ')
+    expect(execMock).toHaveBeenCalledWith('xdotool key ctrl+v', expect.any(Function))
+  })
+
+  it('特殊字符剪贴板写入失败时改为按码位键入', async () => {
+    writeTextMock.mockImplementation(() => { throw new Error('clipboard locked') })
+    const { typeText } = await import('../../electron/main/input-sim')
+
+    await expect(typeText('5μm', { specialChars: { 'μ': 'clipboard' } })).resolves.toBe('keyboard')
+
+    expect(execFileMock.mock.calls.map(([file, args]) => [file, args])).toEqual([
+      ['xdotool', ['type', '--clearmodifiers', '--', '5']],
+      ['xdotool', ['key', '--clearmodifiers', '--delay', '0', 'U03BC']],
+      ['xdotool', ['type', '--clearmodifiers', '--', 'm']],
+    ])
+    expect(execMock).not.toHaveBeenCalled()
+  })
+
+  it('剪贴板注入模式整段粘贴，不拆分特殊字符', async () => {
+    let stored = ''
+    writeTextMock.mockImplementation((text: string) => { stored = text })
+    readTextMock.mockImplementation(() => stored)
+    const { typeText } = await import('../../electron/main/input-sim')
+
+    await typeText('3×4cm', { specialChars: { '×': 'unicode' } })
+
+    expect(writeTextMock).toHaveBeenCalledWith('3×4cm')
+    expect(execFileMock).not.toHaveBeenCalled()
+  })
+})
+
+describe('splitSpecialCharSegments', () => {
+  it('相邻同一方式的字符合并为一段，未配置时整段按键盘键入', async () => {
+    const { splitSpecialCharSegments } = await import('../../electron/main/input-sim')
+
+    expect(splitSpecialCharSegments('××2μ', { '×': 'unicode', 'μ': 'clipboard' })).toEqual([
+      { text: '××', strategy: 'unicode' },
+      { text: '2', strategy: 'keyboard' },
+      { text: 'μ', strategy: 'clipboard' },
+    ])
+    expect(splitSpecialCharSegments('灰白色 3×4', {})).toEqual([{ text: '灰白色 3×4', strategy: 'keyboard' }])
+    expect(splitSpecialCharSegments('', { '×': 'unicode' })).toEqual([])
+  })
+
+  it('按码位切分，不拆开 BMP 之外的字符', async () => {
+    const { splitSpecialCharSegments } = await import('../../electron/main/input-sim')
+
+    expect(splitSpecialCharSegments('𠀀×', { '×': 'unicode' })).toEqual([
+      { text: '𠀀', strategy: 'keyboard' },
+      { text: '×', strategy: 'unicode' },
+    ])
+  })
+})
+
+describe('sendShortcut 失败时补发抬起', () => {
+  beforeEach(() => {
+    vi.clearAllMocks()
+    vi.resetModules()
+    Object.defineProperty(process, 'platform', { value: 'linux' })
+    execMock.mockImplementation(succeedCallback)
+    execFileMock.mockImplementation(succeedCallback)
+  })
+
+  afterEach(() => {
+    Object.defineProperty(process, 'platform', { value: originalPlatform })
+  })
+
+  // 第一次 xdotool 调用（按下序列）中途失败，之后的调用正常
+  function failFirstExecFile() {
+    execFileMock.mockImplementationOnce((...args: any[]) => {
+      const cb = args[args.length - 1]
+      cb(new Error('xdotool: BadWindow'))
+    })
+  }
+
+  it('中途失败时按逆序抬起已按下的键并抛出原错误', async () => {
+    failFirstExecFile()
+    const { sendShortcut } = await import('../../electron/main/input-sim')
+
+    await expect(sendShortcut('CTRL+SHIFT+F2', { holdMs: 50 })).rejects.toThrow('BadWindow')
+
+    expect(execFileMock.mock.calls.map(([file, args]) => [file, args])).toEqual([
+      ['xdotool', ['keydown', 'ctrl', 'keydown', 'shift', 'keydown', 'f2', 'sleep', '0.05', 'keyup', 'f2', 'keyup', 'shift', 'keyup', 'ctrl']],
+      ['xdotool', ['keyup', 'f2', 'shift', 'ctrl']],
+    ])
+  })
+
+  it('开启 releaseModifiersOnFailure 时额外抬起其余修饰键', async () => {
+    failFirstExecFile()
+    const { sendShortcut } = await import('../../electron/main/input-sim')
+
+    await expect(sendShortcut('ALT+R', { holdMs: 50, releaseModifiersOnFailure: true })).rejects.toThrow('BadWindow')
+
+    expect(execFileMock.mock.calls[1]).toEqual(['xdotool', ['keyup', 'r', 'alt', 'ctrl', 'shift', 'super'], expect.any(Function)])
+  })
+
+  it('补发抬起本身失败时仍抛出原错误，成功发送时不补发', async () => {
+    execFileMock.mockImplementation((...args: any[]) => {
+      const cb = args[args.length - 1]
+      cb(new Error(args[1][0] === 'keyup' ? 'release failed' : 'xdotool: BadWindow'))
+    })
+    const { sendShortcut } = await import('../../electron/main/input-sim')
+
+    await expect(sendShortcut('ALT+R', { holdMs: 50 })).rejects.toThrow('BadWindow')
+
+    execFileMock.mockImplementation(succeedCallback)
+    execFileMock.mockClear()
+    await sendShortcut('ALT+R', { holdMs: 50 })
+    expect(execFileMock).toHaveBeenCalledTimes(1)
+  })
+})
+
+describe('sendShortcut 具名键', () => {
+  beforeEach(() => {
+    vi.clearAllMocks()
+    vi.resetModules()
+    execMock.mockImplementation(succeedCallback)
+    execFileMock.mockImplementation(succeedCallback)
+  })
+
+  afterEach(() => {
+    Object.defineProperty(process, 'platform', { value: originalPlatform })
+  })
+
+  it('macOS 下 ENTER 按 key code 36 发送，而不是键入 "enter"', async () => {
+    Object.defineProperty(process, 'platform', { value: 'darwin' })
+    const { sendShortcut } = await import('../../electron/main/input-sim')
+
+    await sendShortcut('ALT+F; S; ENTER')
+
+    expect(execMock.mock.calls.map(([command]) => command)).toEqual([
+      `osascript -e 'tell application "System Events" to keystroke "f" using {option down}'`,
+      `osascript -e 'tell application "System Events" to keystroke "s" '`,
+      `osascript -e 'tell application "System Events" to key code 36 '`,
+    ])
+
+    execMock.mockClear()
+    await sendShortcut('RETURN', { holdMs: 50 })
+    expect(execMock).not.toHaveBeenCalled()
+    expect(execFileMock.mock.calls[0][1]).toEqual([
+      '-e', 'tell application "System Events"', '-e', 'key code 36', '-e', 'delay 0.05', '-e', 'end tell',
+    ])
+  })
+
+  it('macOS 下所有具名键都按 key code 发送', async () => {
+    Object.defineProperty(process, 'platform', { value: 'darwin' })
+    const { sendShortcut } = await import('../../electron/main/input-sim')
+    const keys = ['SPACE', 'ENTER', 'RETURN', 'TAB', 'ESCAPE', 'ESC', 'BACKSPACE', 'DELETE', 'DEL',
+      'UP', 'DOWN', 'LEFT', 'RIGHT', 'HOME', 'END', 'PAGEUP', 'PAGEDOWN', 'F1', 'F12', 'NUMPAD0', 'NUMPAD9']
+
+    await sendShortcut(keys.join('; '))
+
+    const commands = execMock.mock.calls.map(([command]) => String(command))
+    expect(commands).toHaveLength(keys.length)
+    for (const command of commands) expect(command).toMatch(/to key code \d+ '$/)
+  })
+
+  it('Linux 下 ENTER 等具名键使用 X keysym 名称', async () => {
+    Object.defineProperty(process, 'platform', { value: 'linux' })
+    const { sendShortcut } = await import('../../electron/main/input-sim')
+
+    await sendShortcut('ALT+F; S; ENTER')
+    await sendShortcut('SHIFT+TAB; ESC; BACKSPACE; SPACE; UP')
+
+    expect(execMock.mock.calls.map(([command]) => command)).toEqual([
+      'xdotool key alt+f',
+      'xdotool key s',
+      'xdotool key Return',
+      'xdotool key shift+Tab',
+      'xdotool key Escape',
+      'xdotool key BackSpace',
+      'xdotool key space',
+      'xdotool key Up',
+    ])
+
+    execMock.mockClear()
+    await sendShortcut('ENTER', { holdMs: 50 })
+    expect(execFileMock.mock.calls[0][1]).toEqual(['keydown', 'Return', 'sleep', '0.05', 'keyup', 'Return'])
+  })
+})
+
+describe('pressLeftArrow', () => {
+  beforeEach(() => {
+    vi.clearAllMocks()
+    vi.resetModules()
+    execFileMock.mockImplementation(succeedCallback)
+  })
+
+  afterEach(() => {
+    Object.defineProperty(process, 'platform', { value: originalPlatform })
+  })
+
+  it('Linux 下合并为一次 xdotool --repeat 调用', async () => {
+    Object.defineProperty(process, 'platform', { value: 'linux' })
+    const { pressLeftArrow } = await import('../../electron/main/input-sim')
+
+    await pressLeftArrow(12)
+
+    expect(execFileMock).toHaveBeenCalledTimes(1)
+    expect(execFileMock).toHaveBeenCalledWith(
+      'xdotool',
+      ['key', '--clearmodifiers', '--repeat', '12', '--delay', '0', 'Left'],
+      expect.any(Function),
+    )
+  })
+
+  it('macOS 下用一段 AppleScript 循环发送，次数为 0 时不调用', async () => {
+    Object.defineProperty(process, 'platform', { value: 'darwin' })
+    const { pressLeftArrow } = await import('../../electron/main/input-sim')
+
+    await pressLeftArrow(0)
+    expect(execFileMock).not.toHaveBeenCalled()
+
+    await pressLeftArrow(3)
+    expect(execFileMock).toHaveBeenCalledTimes(1)
+    expect(execFileMock.mock.calls[0][1]).toEqual([
+      '-e', 'tell application "System Events"', '-e', 'repeat 3 times', '-e', 'key code 123', '-e', 'end repeat', '-e', 'end tell',
+    ])
+  })
+})
@@ -0,0 +1,70 @@
+import { describe, it, expect, vi, afterEach } from 'vitest'
+import { KeyReleaseGate } from '../../electron/main/key-release-gate'
+
+describe('KeyReleaseGate', () => {
+  it('未按住时立即放行', async () => {
+    const gate = new KeyReleaseGate()
+    await expect(gate.wait()).resolves.toBe(true)
+  })
+
+  it('按住期间识别完成的中间段在松开后才输入，且按等待顺序输入', async () => {
+    const gate = new KeyReleaseGate()
+    const events: string[] = []
+    gate.hold()
+    const first = gate.wait().then(() => events.push('输入第 1 段'))
+    const second = gate.wait().then(() => events.push('输入第 2 段'))
+    await Promise.resolve()
+    events.push('松开热键')
+    gate.release()
+    await Promise.all([first, second])
+    expect(events).toEqual(['松开热键', '输入第 1 段', '输入第 2 段'])
+    expect(gate.isHeld).toBe(false)
+  })
+
+  it('松开后再次按住，之前的放行不影响新的等待', async () => {
+    const gate = new KeyReleaseGate()
+    gate.hold()
+    gate.release()
+    gate.hold()
+    let done = false
+    const waiting = gate.wait().then(() => { done = true })
+    await Promise.resolve()
+    expect(done).toBe(false)
+    gate.release()
+    await waiting
+    expect(done).toBe(true)
+  })
+
+  describe('超时', () => {
+    afterEach(() => {
+      vi.useRealTimers()
+    })
+
+    it('超时仍未松开时单独放行并返回 false，闸门保持按住', async () => {
+      vi.useFakeTimers()
+      const gate = new KeyReleaseGate()
+      gate.hold()
+      const timedOut = gate.wait(1000)
+      const untimed = gate.wait()
+      let settled = false
+      void untimed.then(() => { settled = true })
+      await vi.advanceTimersByTimeAsync(1000)
+      await expect(timedOut).resolves.toBe(false)
+      expect(settled).toBe(false)
+      expect(gate.isHeld).toBe(true)
+      gate.release()
+      await expect(untimed).resolves.toBe(true)
+    })
+
+    it('超时前松开时返回 true，之后的计时不再生效', async () => {
+      vi.useFakeTimers()
+      const gate = new KeyReleaseGate()
+      gate.hold()
+      const waiting = gate.wait(1000)
+      gate.release()
+      await expect(waiting).resolves.toBe(true)
+      await vi.advanceTimersByTimeAsync(1000)
+      expect(vi.getTimerCount()).toBe(0)
+    })
+  })
+})
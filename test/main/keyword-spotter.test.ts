@@ -0,0 +1,99 @@
+import { describe, it, expect, vi } from 'vitest'
+
+vi.mock('electron', () => ({
+  app: { getPath: () => '/fake/userData' },
+}))
+vi.mock('../../electron/main/logger', () => ({
+  logger: { info: vi.fn(), warn: vi.fn(), error: vi.fn(), debug: vi.fn() },
+}))
+
+import { KeywordSpotter, extractFeatures, dtwDistance } from '../../electron/main/keyword-spotter'
+
+const SAMPLE_RATE = 16000
+
+// 线性扫频信号，模拟频谱随时间变化的口令
+function chirp(fromHz: number, toHz: number, seconds: number, amplitude: number): Float32Array {
+  const n = Math.round(SAMPLE_RATE * seconds)
+  const out = new Float32Array(n)
+  let phase = 0
+  for (let i = 0; i < n; i++) {
+    const freq = fromHz + ((toHz - fromHz) * i) / n
+    phase += (2 * Math.PI * freq) / SAMPLE_RATE
+    out[i] = amplitude * Math.sin(phase)
+  }
+  return out
+}
+
+function noise(seconds: number, amplitude: number): Float32Array {
+  const n = Math.round(SAMPLE_RATE * seconds)
+  const out = new Float32Array(n)
+  let seed = 1
+  for (let i = 0; i < n; i++) {
+    seed = (seed * 1103515245 + 12345) % 2147483648
+    out[i] = amplitude * 2 * (seed / 2147483648 - 0.5)
+  }
+  return out
+}
+
+// 首尾补静音，验证裁剪
+function withSilence(pcm: Float32Array, seconds: number): Float32Array {
+  const pad = Math.round(SAMPLE_RATE * seconds)
+  const out = new Float32Array(pcm.length + pad * 2)
+  out.set(pcm, pad)
+  return out
+}
+
+function createSpotter() {
+  const spotter = new KeywordSpotter()
+  spotter.enroll('下一项', chirp(300, 900, 0.5, 0.5))
+  spotter.enroll('保存报告', chirp(2000, 1200, 0.5, 0.5))
+  return spotter
+}
+
+describe('extractFeatures', () => {
+  it('裁掉首尾静音，全静音返回空', () => {
+    const plain = extractFeatures(chirp(300, 900, 0.5, 0.5))
+    const padded = extractFeatures(withSilence(chirp(300, 900, 0.5, 0.5), 0.3))
+    expect(Math.abs(padded.length - plain.length)).toBeLessThanOrEqual(4)
+    expect(extractFeatures(new Float32Array(SAMPLE_RATE))).toEqual([])
+  })
+
+  it('相同信号 DTW 距离为 0', () => {
+    const a = extractFeatures(chirp(300, 900, 0.5, 0.5))
+    expect(dtwDistance(a, a)).toBeCloseTo(0)
+    expect(dtwDistance(a, [])).toBe(Number.POSITIVE_INFINITY)
+  })
+})
+
+describe('KeywordSpotter', () => {
+  it('语速、音量不同仍匹配到对应口令', () => {
+    const result = createSpotter().match(withSilence(chirp(300, 900, 0.65, 0.2), 0.2))
+    expect(result?.phrase).toBe('下一项')
+  })
+
+  it('与所有样本差异过大时不匹配', () => {
+    expect(createSpotter().match(noise(0.5, 0.3))).toBeNull()
+  })
+
+  it('只在指定口令范围内匹配', () => {
+    const result = createSpotter().match(chirp(300, 900, 0.5, 0.5), { phrases: ['保存报告'] })
+    expect(result).toBeNull()
+  })
+
+  it('静音样本无法录入', () => {
+    expect(() => new KeywordSpotter().enroll('下一项', new Float32Array(SAMPLE_RATE))).toThrow('静音')
+  })
+
+  it('样本计数、清空与序列化', () => {
+    const spotter = createSpotter()
+    spotter.enroll('下一项', chirp(300, 900, 0.6, 0.4))
+    expect(spotter.counts()).toEqual({ 下一项: 2, 保存报告: 1 })
+
+    const restored = new KeywordSpotter()
+    restored.load(JSON.parse(JSON.stringify(spotter.toJSON())))
+    expect(restored.counts()).toEqual({ 下一项: 2, 保存报告: 1 })
+
+    restored.clear('下一项')
+    expect(restored.counts()).toEqual({ 保存报告: 1 })
+  })
+})
@@ -0,0 +1,30 @@
+import { describe, it, expect, vi } from 'vitest'
+import { LastErrorStore } from '../../electron/main/last-error'
+
+describe('LastErrorStore', () => {
+  it('记录最近一次错误的分类、信息与时间，后一次覆盖前一次', () => {
+    const onChange = vi.fn()
+    const store = new LastErrorStore(onChange, () => Date.UTC(2026, 0, 1))
+    expect(store.current).toBeNull()
+
+    store.record('recognition', new Error('Error: ASR 服务错误: 500'))
+    expect(store.current).toEqual({ category: 'recognition', message: 'ASR 服务错误: 500', at: '2026-01-01T00:00:00.000Z' })
+
+    store.record('input', '剪贴板被占用')
+    expect(store.current).toMatchObject({ category: 'input', message: '剪贴板被占用' })
+    expect(onChange).toHaveBeenCalledTimes(2)
+  })
+
+  it('清除后为 null，已为空时不重复回调', () => {
+    const onChange = vi.fn()
+    const store = new LastErrorStore(onChange)
+    store.clear()
+    expect(onChange).not.toHaveBeenCalled()
+
+    store.record('model', '本地识别启动失败')
+    store.clear()
+    store.clear()
+    expect(store.current).toBeNull()
+    expect(onChange.mock.calls.map(([error]) => error?.category ?? null)).toEqual(['model', null])
+  })
+})
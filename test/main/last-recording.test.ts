@@ -0,0 +1,71 @@
+import { describe, it, expect } from 'vitest'
+import { LastRecordingStore } from '../../electron/main/last-recording'
+import { encodeWav } from '../../src/wav'
+import { readWavInfo } from '../../electron/main/wav-info'
+
+function wavOf(frames: number): ArrayBuffer {
+  return encodeWav([new Float32Array(frames)])
+}
+
+function frameCount(wav: ArrayBuffer): number {
+  return readWavInfo(Buffer.from(wav)).frameCount
+}
+
+describe('LastRecordingStore', () => {
+  it('初始为空', () => {
+    expect(new LastRecordingStore().current).toBeNull()
+  })
+
+  it('只保留最后一段并记录时间', () => {
+    let now = 1000
+    const store = new LastRecordingStore(() => now)
+    store.retain(new Uint8Array([1, 2]).buffer)
+    now = 2000
+    store.retain(new Uint8Array([3, 4, 5]).buffer)
+    expect(Array.from(new Uint8Array(store.current!.wav))).toEqual([3, 4, 5])
+    expect(store.current!.recordedAt).toBe(2000)
+  })
+
+  it('保存副本，调用方之后修改原缓冲不影响', () => {
+    const store = new LastRecordingStore()
+    const bytes = new Uint8Array([7, 8, 9])
+    store.retain(bytes.buffer)
+    bytes[0] = 0
+    expect(Array.from(new Uint8Array(store.current!.wav))).toEqual([7, 8, 9])
+  })
+
+  it('清除后为空，返回是否有内容被清除', () => {
+    const store = new LastRecordingStore()
+    store.retain(new Uint8Array([1]).buffer)
+    expect(store.clear()).toBe(true)
+    expect(store.current).toBeNull()
+    expect(store.clear()).toBe(false)
+  })
+
+  it('连续分段录音的中间段与剩余部分拼成一整段，时间取第一段', () => {
+    let now = 1000
+    const store = new LastRecordingStore(() => now)
+    store.appendSegment(wavOf(100))
+    now = 2000
+    store.appendSegment(wavOf(50))
+    expect(frameCount(store.current!.wav)).toBe(150)
+    now = 3000
+    store.retain(wavOf(20))
+    expect(frameCount(store.current!.wav)).toBe(170)
+    expect(store.current!.recordedAt).toBe(1000)
+
+    now = 4000
+    store.retain(wavOf(30))
+    expect(frameCount(store.current!.wav)).toBe(30)
+    expect(store.current!.recordedAt).toBe(4000)
+  })
+
+  it('新录音开始后不与上一次未收尾的中间段拼接', () => {
+    const store = new LastRecordingStore()
+    store.appendSegment(wavOf(100))
+    store.beginRecording()
+    expect(frameCount(store.current!.wav)).toBe(100)
+    store.appendSegment(wavOf(40))
+    expect(frameCount(store.current!.wav)).toBe(40)
+  })
+})
@@ -0,0 +1,87 @@
+import { describe, it, expect, afterEach } from 'vitest'
+import * as fs from 'fs'
+import * as os from 'os'
+import * as path from 'path'
+import { RotatingLogFile, rotateLogFiles, rotatedLogPath } from '../../electron/main/log-rotation'
+
+const dirs: string[] = []
+
+function makeDir(): string {
+  const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'logene-log-test-'))
+  dirs.push(dir)
+  return dir
+}
+
+function read(dir: string, index: number): string | null {
+  const file = rotatedLogPath(dir, index)
+  return fs.existsSync(file) ? fs.readFileSync(file, 'utf8') : null
+}
+
+describe('日志文件轮转', () => {
+  afterEach(() => {
+    for (const dir of dirs.splice(0)) fs.rmSync(dir, { recursive: true, force: true })
+  })
+
+  it('依次后移编号并删除超出份数的最旧文件', () => {
+    const dir = makeDir()
+    fs.writeFileSync(rotatedLogPath(dir, 0), 'current')
+    fs.writeFileSync(rotatedLogPath(dir, 1), 'older')
+    fs.writeFileSync(rotatedLogPath(dir, 2), 'oldest')
+
+    rotateLogFiles(dir, 3)
+
+    expect(read(dir, 0)).toBeNull()
+    expect(read(dir, 1)).toBe('current')
+    expect(read(dir, 2)).toBe('older')
+    expect(read(dir, 3)).toBeNull()
+  })
+
+  it('超过大小上限时轮转，新内容写入新文件', async () => {
+    const dir = path.join(makeDir(), 'logs')
+    const file = new RotatingLogFile(dir, { maxBytes: 10, maxFiles: 2 })
+    file.write('aaaaaa\n')
+    file.write('bbbbbb\n')
+    file.write('cccccc\n')
+    await file.close()
+
+    expect(read(dir, 0)).toBe('cccccc\n')
+    expect(read(dir, 1)).toBe('bbbbbb\n')
+    expect(read(dir, 2)).toBeNull()
+  })
+
+  it('续写已有文件时计入原有大小，单行超限也不会反复轮转', async () => {
+    const dir = makeDir()
+    fs.writeFileSync(rotatedLogPath(dir, 0), '123456789\n')
+    const file = new RotatingLogFile(dir, { maxBytes: 12, maxFiles: 3 })
+    file.write('x'.repeat(20) + '\n')
+    file.write('y\n')
+    await file.close()
+
+    expect(read(dir, 2)).toBe('123456789\n')
+    expect(read(dir, 1)).toBe('x'.repeat(20) + '\n')
+    expect(read(dir, 0)).toBe('y\n')
+  })
+
+  it('写入不阻塞调用方，排队的多行按顺序写入', async () => {
+    const dir = makeDir()
+    const file = new RotatingLogFile(dir, { maxBytes: 1024, maxFiles: 2 })
+    for (let i = 0; i < 5; i++) file.write(`line ${i}\n`)
+    expect(read(dir, 0)).toBeNull()
+    await file.close()
+
+    expect(read(dir, 0)).toBe('line 0\nline 1\nline 2\nline 3\nline 4\n')
+  })
+
+  it('退出前同步写出仍在排队的日志，之后的后台写入不会重复写', async () => {
+    const dir = makeDir()
+    fs.writeFileSync(rotatedLogPath(dir, 0), 'old\n')
+    const file = new RotatingLogFile(dir, { maxBytes: 1024, maxFiles: 2 })
+    file.write('a\n')
+    file.write('b\n')
+    file.flushSync()
+    expect(read(dir, 0)).toBe('old\na\nb\n')
+
+    await file.close()
+    expect(read(dir, 0)).toBe('old\na\nb\n')
+  })
+})
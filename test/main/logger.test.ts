@@ -0,0 +1,69 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+const insertAppLogMock = vi.fn()
+let configuredLogging: { level?: string } = { level: 'info' }
+
+vi.mock('electron', () => ({
+  app: { getPath: () => '/tmp' },
+}))
+
+vi.mock('../../electron/main/db', () => ({
+  insertAppLog: (...args: any[]) => insertAppLogMock(...args),
+  getRecentLogs: () => [],
+  clearAppLogs: vi.fn(),
+}))
+
+vi.mock('../../electron/main/config', () => ({
+  getConfig: () => ({ logging: configuredLogging }),
+}))
+
+function loggedLevels(): string[] {
+  return insertAppLogMock.mock.calls.map(([entry]) => `${entry.level}:${entry.msg}`)
+}
+
+describe('日志级别', () => {
+  beforeEach(() => {
+    vi.clearAllMocks()
+    vi.resetModules()
+    vi.spyOn(console, 'log').mockImplementation(() => { })
+    vi.spyOn(console, 'debug').mockImplementation(() => { })
+    vi.spyOn(console, 'error').mockImplementation(() => { })
+    configuredLogging = { level: 'info' }
+  })
+
+  it('未初始化时按配置的级别过滤', async () => {
+    configuredLogging = { level: 'warn' }
+    const { logger, getLogLevel } = await import('../../electron/main/logger')
+
+    logger.info('忽略')
+    logger.warn('记录')
+
+    expect(getLogLevel()).toBe('warn')
+    expect(loggedLevels()).toEqual(['warn:记录'])
+  })
+
+  it('运行时切换级别立即生效，切换本身总会记录', async () => {
+    const { logger, setLogLevel, getLogLevel } = await import('../../electron/main/logger')
+
+    logger.debug('切换前')
+    setLogLevel('debug')
+    logger.debug('切换后')
+    setLogLevel('error')
+    logger.warn('被过滤')
+    logger.error('出错')
+
+    expect(getLogLevel()).toBe('error')
+    expect(loggedLevels()).toEqual([
+      'info:[Log] 日志级别 info → debug',
+      'debug:切换后',
+      'info:[Log] 日志级别 debug → error',
+      'error:出错',
+    ])
+  })
+
+  it('级别未变化时不重复记录', async () => {
+    const { setLogLevel } = await import('../../electron/main/logger')
+    setLogLevel('info')
+    expect(insertAppLogMock).not.toHaveBeenCalled()
+  })
+})
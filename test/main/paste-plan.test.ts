@@ -1,6 +1,7 @@
 import { describe, expect, it } from 'vitest'
 import {
   buildPasteExecutionPlan,
+  buildPasteKeySequence,
   classifyPasteTargetProbe,
   type FocusRestoreResult,
   type PasteTargetAssessment,
@@ -78,3 +79,19 @@ describe('buildPasteExecutionPlan', () => {
     expect(plan).toEqual({ action: 'paste' })
   })
 })
+
+describe('buildPasteKeySequence', () => {
+  it('replace 只发送粘贴快捷键', () => {
+    expect(buildPasteKeySequence('replace', 'CTRL+V')).toEqual(['CTRL+V'])
+    expect(buildPasteKeySequence('replace', 'CTRL+V', 'CTRL+END')).toEqual(['CTRL+V'])
+  })
+
+  it('append 先发送导航键再粘贴，默认 END', () => {
+    expect(buildPasteKeySequence('append', 'CTRL+V')).toEqual(['END', 'CTRL+V'])
+    expect(buildPasteKeySequence('append', 'CTRL+SHIFT+V', ' CTRL+END ')).toEqual(['CTRL+END', 'CTRL+SHIFT+V'])
+  })
+
+  it('append 导航键为空时退化为直接粘贴', () => {
+    expect(buildPasteKeySequence('append', 'CTRL+V', '  ')).toEqual(['CTRL+V'])
+  })
+})
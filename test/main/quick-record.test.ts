@@ -0,0 +1,75 @@
+import { describe, it, expect, vi, afterEach } from 'vitest'
+import {
+  clampQuickRecordMs,
+  normalizeQuickRecordReport,
+  QuickRecordRequests,
+  QUICK_RECORD_DEFAULT_MS,
+  QUICK_RECORD_MAX_MS,
+  QUICK_RECORD_MIN_MS,
+} from '../../electron/main/quick-record'
+import { quickRecordRemainingSec } from '../../src/quick-record'
+
+describe('clampQuickRecordMs', () => {
+  it('限制在上下限之间，非法值回退默认 5 秒', () => {
+    expect(clampQuickRecordMs(5000)).toBe(5000)
+    expect(clampQuickRecordMs(10)).toBe(QUICK_RECORD_MIN_MS)
+    expect(clampQuickRecordMs(10 * 60 * 1000)).toBe(QUICK_RECORD_MAX_MS)
+    expect(clampQuickRecordMs(2500.4)).toBe(2500)
+    expect(clampQuickRecordMs(Number.NaN)).toBe(QUICK_RECORD_DEFAULT_MS)
+    expect(clampQuickRecordMs('5000')).toBe(QUICK_RECORD_DEFAULT_MS)
+  })
+})
+
+describe('quickRecordRemainingSec', () => {
+  it('按秒向上取整，结束后为 0', () => {
+    expect(quickRecordRemainingSec(1000, 5000, 1000)).toBe(5)
+    expect(quickRecordRemainingSec(1000, 5000, 1001)).toBe(5)
+    expect(quickRecordRemainingSec(1000, 5000, 5100)).toBe(1)
+    expect(quickRecordRemainingSec(1000, 5000, 7000)).toBe(0)
+  })
+})
+
+describe('normalizeQuickRecordReport', () => {
+  it('缺少 requestId 视为无效，失败原因缺省时补默认值', () => {
+    expect(normalizeQuickRecordReport({ ok: true })).toBeNull()
+    expect(normalizeQuickRecordReport(null)).toBeNull()
+    expect(normalizeQuickRecordReport({ requestId: 2, ok: true })).toEqual({ requestId: 2, result: { ok: true } })
+    expect(normalizeQuickRecordReport({ requestId: 3, ok: false })).toEqual({
+      requestId: 3,
+      result: { ok: false, message: '浮窗未能开始定时录音' },
+    })
+  })
+})
+
+describe('QuickRecordRequests', () => {
+  afterEach(() => {
+    vi.useRealTimers()
+  })
+
+  it('等浮窗回报开始后才返回成功', async () => {
+    const requests = new QuickRecordRequests()
+    let sent: { requestId: number; durationMs: number } | null = null
+    const pending = requests.request(5000, (request) => {
+      sent = request
+      return true
+    })
+    expect(sent).toEqual({ requestId: 1, durationMs: 5000 })
+    expect(requests.settle({ requestId: 1, ok: false, message: '正在录音或识别中' })).toBe(true)
+    await expect(pending).resolves.toEqual({ ok: false, message: '正在录音或识别中' })
+    expect(requests.settle({ requestId: 1, ok: true })).toBe(false)
+  })
+
+  it('浮窗不可用时直接失败', async () => {
+    const requests = new QuickRecordRequests()
+    await expect(requests.request(5000, () => false)).resolves.toEqual({ ok: false, message: '浮窗未就绪，无法开始定时录音' })
+  })
+
+  it('超时未回报时失败，之后到达的回报被忽略', async () => {
+    vi.useFakeTimers()
+    const requests = new QuickRecordRequests(2000)
+    const pending = requests.request(5000, () => true)
+    vi.advanceTimersByTime(2000)
+    await expect(pending).resolves.toEqual({ ok: false, message: '开始定时录音超时（2s 内浮窗未响应）' })
+    expect(requests.settle({ requestId: 1, ok: true })).toBe(false)
+  })
+})
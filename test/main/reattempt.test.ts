@@ -0,0 +1,27 @@
+import { describe, it, expect } from 'vitest'
+import { isFumbledCapture, ReattemptGuard, REATTEMPT_MAX_PER_TRIGGER } from '../../src/reattempt'
+
+describe('isFumbledCapture', () => {
+  it('短于 200ms 或无效时长视为误触', () => {
+    expect(isFumbledCapture(0)).toBe(true)
+    expect(isFumbledCapture(199)).toBe(true)
+    expect(isFumbledCapture(200)).toBe(false)
+    expect(isFumbledCapture(Number.NaN)).toBe(true)
+    expect(isFumbledCapture(250, 300)).toBe(true)
+  })
+})
+
+describe('ReattemptGuard', () => {
+  it('每次触发最多自动重试固定次数，reset 后重新计数', () => {
+    const guard = new ReattemptGuard()
+    for (let i = 0; i < REATTEMPT_MAX_PER_TRIGGER; i++) expect(guard.tryConsume()).toBe(true)
+    expect(guard.tryConsume()).toBe(false)
+    expect(guard.used).toBe(REATTEMPT_MAX_PER_TRIGGER)
+    guard.reset()
+    expect(guard.tryConsume()).toBe(true)
+  })
+
+  it('上限为 0 时从不自动重试', () => {
+    expect(new ReattemptGuard(0).tryConsume()).toBe(false)
+  })
+})
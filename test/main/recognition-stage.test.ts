@@ -0,0 +1,48 @@
+import { describe, it, expect } from 'vitest'
+import {
+  RecognitionStageTracker,
+  resolveTriggeredAt,
+  type RecognitionStageEvent,
+} from '../../electron/main/recognition-stage'
+
+describe('RecognitionStageTracker', () => {
+  it('按阶段发出事件，耗时从触发时刻起算，结束时汇总各阶段耗时', () => {
+    let now = 1000
+    const events: RecognitionStageEvent[] = []
+    const tracker = new RecognitionStageTracker(7, 'trace-1', (e) => events.push(e), 880, () => now)
+
+    now = 1010
+    tracker.enter('encoding')
+    now = 1015
+    tracker.enter('uploading')
+    tracker.enter('uploading')
+    now = 1400
+    tracker.enter('matching')
+    now = 1402
+    tracker.enter('inserting')
+    now = 1480
+
+    expect(events.map(e => [e.stage, e.elapsedMs])).toEqual([
+      ['encoding', 130],
+      ['uploading', 135],
+      ['matching', 520],
+      ['inserting', 522],
+    ])
+    expect(events[0]).toMatchObject({ requestId: 7, traceId: 'trace-1' })
+    expect(tracker.finish()).toBe('encoding=5ms uploading=385ms matching=2ms inserting=78ms total=600ms')
+  })
+
+  it('未进入任何阶段时摘要为空', () => {
+    const tracker = new RecognitionStageTracker(1, 't', () => { }, 0, () => 10)
+    expect(tracker.finish()).toBe('')
+  })
+})
+
+describe('resolveTriggeredAt', () => {
+  it('触发时间缺失、晚于当前或过旧时使用当前时间', () => {
+    expect(resolveTriggeredAt(9_500, 10_000)).toBe(9_500)
+    expect(resolveTriggeredAt(undefined, 10_000)).toBe(10_000)
+    expect(resolveTriggeredAt(10_500, 10_000)).toBe(10_000)
+    expect(resolveTriggeredAt(10_000 - 120_000, 10_000)).toBe(10_000)
+  })
+})
@@ -0,0 +1,83 @@
+import { describe, it, expect } from 'vitest'
+import { RecordHotkeyPressTracker, classifyRecordPress } from '../../electron/main/record-hotkey-press'
+
+describe('classifyRecordPress', () => {
+  it('短于阈值为轻按，等于或超过阈值为按住', () => {
+    expect(classifyRecordPress(299, 300)).toBe('tap')
+    expect(classifyRecordPress(300, 300)).toBe('hold')
+    expect(classifyRecordPress(1200, 300)).toBe('hold')
+  })
+})
+
+describe('RecordHotkeyPressTracker', () => {
+  function setup(mode: 'hold' | 'tap-or-hold') {
+    let now = 0
+    const tracker = new RecordHotkeyPressTracker(mode, 300, () => now)
+    return { tracker, advance: (ms: number) => { now += ms } }
+  }
+
+  it('tap-or-hold：轻按后保持录音，再按一次结束，结束键松开不再触发', () => {
+    const { tracker, advance } = setup('tap-or-hold')
+    expect(tracker.press()).toBe('start')
+    advance(120)
+    expect(tracker.release()).toBe('keep')
+    advance(5000)
+    expect(tracker.press()).toBe('stop')
+    expect(tracker.press()).toBe('ignore')
+    expect(tracker.release()).toBe('ignore')
+    expect(tracker.press()).toBe('start')
+  })
+
+  it('tap-or-hold：按住达到阈值时松开即结束', () => {
+    const { tracker, advance } = setup('tap-or-hold')
+    expect(tracker.press()).toBe('start')
+    advance(300)
+    expect(tracker.release()).toBe('stop')
+    expect(tracker.press()).toBe('start')
+  })
+
+  it('按住期间的按键重复被忽略', () => {
+    const { tracker, advance } = setup('tap-or-hold')
+    tracker.press()
+    advance(500)
+    expect(tracker.press()).toBe('ignore')
+    expect(tracker.release()).toBe('stop')
+  })
+
+  it('hold 模式下轻按也在松开时结束', () => {
+    const { tracker, advance } = setup('hold')
+    expect(tracker.press()).toBe('start')
+    advance(50)
+    expect(tracker.release()).toBe('stop')
+  })
+
+  it('未按下时松开不触发', () => {
+    const { tracker } = setup('tap-or-hold')
+    expect(tracker.release()).toBe('ignore')
+  })
+
+  it('按住时丢弃录音：按键重复与松开都不再触发，松开后可重新开始', () => {
+    const { tracker, advance } = setup('hold')
+    tracker.press()
+    advance(500)
+    tracker.reset()
+    expect(tracker.press()).toBe('ignore')
+    expect(tracker.release()).toBe('ignore')
+    expect(tracker.press()).toBe('start')
+  })
+
+  it('轻按保持录音时丢弃：下一次按下直接开始新录音', () => {
+    const { tracker, advance } = setup('tap-or-hold')
+    tracker.press()
+    advance(50)
+    expect(tracker.release()).toBe('keep')
+    tracker.reset()
+    expect(tracker.press()).toBe('start')
+  })
+
+  it('未在录音时丢弃不改变状态', () => {
+    const { tracker } = setup('tap-or-hold')
+    tracker.reset()
+    expect(tracker.press()).toBe('start')
+  })
+})
@@ -0,0 +1,34 @@
+import { describe, it, expect } from 'vitest'
+import { AsrRequestRateLimiter, isRateLimited } from '../../electron/main/request-rate-limit'
+
+describe('isRateLimited', () => {
+  it('间隔为 0 时不限流', () => {
+    expect(isRateLimited('vad', { minRequestIntervalMs: 0, rateLimitPushToTalk: true })).toBe(false)
+  })
+
+  it('默认只限制 VAD，按键录音需显式开启', () => {
+    const options = { minRequestIntervalMs: 2000, rateLimitPushToTalk: false }
+    expect(isRateLimited('vad', options)).toBe(true)
+    expect(isRateLimited('manual', options)).toBe(false)
+    expect(isRateLimited('manual', { ...options, rateLimitPushToTalk: true })).toBe(true)
+  })
+})
+
+describe('AsrRequestRateLimiter', () => {
+  it('首个请求不受限，之后按间隔计算剩余等待时间', () => {
+    let now = 1000
+    const limiter = new AsrRequestRateLimiter(() => now)
+    expect(limiter.remaining(2000)).toBe(0)
+    limiter.markSent()
+    now += 500
+    expect(limiter.remaining(2000)).toBe(1500)
+    now += 1500
+    expect(limiter.remaining(2000)).toBe(0)
+  })
+
+  it('间隔为 0 时始终可以发送', () => {
+    const limiter = new AsrRequestRateLimiter(() => 0)
+    limiter.markSent()
+    expect(limiter.remaining(0)).toBe(0)
+  })
+})
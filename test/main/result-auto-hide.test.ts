@@ -0,0 +1,60 @@
+import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest'
+import { ResultAutoHide } from '../../electron/main/result-auto-hide'
+
+describe('ResultAutoHide', () => {
+  beforeEach(() => {
+    vi.useFakeTimers()
+  })
+
+  afterEach(() => {
+    vi.useRealTimers()
+  })
+
+  it('到时后隐藏一次', () => {
+    const hide = vi.fn()
+    const autoHide = new ResultAutoHide(hide)
+
+    expect(autoHide.schedule(3000)).toBe(true)
+    expect(autoHide.pending).toBe(true)
+    vi.advanceTimersByTime(2999)
+    expect(hide).not.toHaveBeenCalled()
+    vi.advanceTimersByTime(1)
+    expect(hide).toHaveBeenCalledTimes(1)
+    expect(autoHide.pending).toBe(false)
+  })
+
+  it('新的识别开始时取消待执行的隐藏', () => {
+    const hide = vi.fn()
+    const autoHide = new ResultAutoHide(hide)
+
+    autoHide.schedule(3000)
+    vi.advanceTimersByTime(2000)
+    autoHide.cancel()
+    vi.advanceTimersByTime(5000)
+    expect(hide).not.toHaveBeenCalled()
+  })
+
+  it('重复安排时以最后一次为准', () => {
+    const hide = vi.fn()
+    const autoHide = new ResultAutoHide(hide)
+
+    autoHide.schedule(3000)
+    vi.advanceTimersByTime(2000)
+    autoHide.schedule(3000)
+    vi.advanceTimersByTime(2000)
+    expect(hide).not.toHaveBeenCalled()
+    vi.advanceTimersByTime(1000)
+    expect(hide).toHaveBeenCalledTimes(1)
+  })
+
+  it('时长为 0 时不隐藏，并取消之前的安排', () => {
+    const hide = vi.fn()
+    const autoHide = new ResultAutoHide(hide)
+
+    autoHide.schedule(3000)
+    expect(autoHide.schedule(0)).toBe(false)
+    vi.advanceTimersByTime(10000)
+    expect(hide).not.toHaveBeenCalled()
+    expect(autoHide.pending).toBe(false)
+  })
+})
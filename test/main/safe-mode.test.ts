@@ -0,0 +1,36 @@
+import { describe, it, expect } from 'vitest'
+import { InputArmState } from '../../electron/main/safe-mode'
+
+describe('InputArmState', () => {
+  it('未确认前不允许输入', () => {
+    const state = new InputArmState()
+    expect(state.isArmed(1000, 60000)).toBe(false)
+    state.touch(1000)
+    expect(state.isArmed(1000, 60000)).toBe(false)
+  })
+
+  it('确认后在空闲超时内保持可输入，输入会刷新计时', () => {
+    const state = new InputArmState()
+    state.arm(1000)
+    expect(state.isArmed(50000, 60000)).toBe(true)
+    state.touch(50000)
+    expect(state.isArmed(100000, 60000)).toBe(true)
+  })
+
+  it('空闲超时后自动解除，需要重新确认', () => {
+    const state = new InputArmState()
+    state.arm(1000)
+    expect(state.isArmed(61002, 60000)).toBe(false)
+    // 解除后即使时间回到窗口内也保持未确认
+    expect(state.isArmed(2000, 60000)).toBe(false)
+    state.arm(70000)
+    expect(state.isArmed(70001, 60000)).toBe(true)
+  })
+
+  it('disarm 立即解除', () => {
+    const state = new InputArmState()
+    state.arm(1000)
+    state.disarm()
+    expect(state.isArmed(1001, 60000)).toBe(false)
+  })
+})
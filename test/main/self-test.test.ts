@@ -0,0 +1,73 @@
+import { describe, it, expect, vi } from 'vitest'
+import { runSelfTest, type SelfTestCapture, type SelfTestDeps } from '../../electron/main/self-test'
+import { encodeWav } from '../../src/wav'
+
+function tone(amplitude: number): ArrayBuffer {
+  const samples = new Float32Array(16000)
+  for (let i = 0; i < samples.length; i++) samples[i] = amplitude * Math.sin(i / 5)
+  return encodeWav([samples])
+}
+
+function capture(wav: ArrayBuffer | null, overrides: Partial<SelfTestCapture> = {}): SelfTestCapture {
+  return { microphone: { durationMs: 3 }, recording: { durationMs: 1000 }, wav, ...overrides }
+}
+
+function deps(overrides: Partial<SelfTestDeps> = {}): SelfTestDeps {
+  return {
+    encode: (wav) => ({ format: 'wav', bytes: wav.byteLength }),
+    checkNetwork: async () => ({ ok: true, message: '已连接' }),
+    recognize: async () => '肉眼所见',
+    ...overrides,
+  }
+}
+
+const statuses = (report: Awaited<ReturnType<typeof runSelfTest>>) =>
+  Object.fromEntries(report.stages.map(s => [s.stage, s.status]))
+
+describe('runSelfTest', () => {
+  it('各步骤均成功时整体通过，并给出识别结果', async () => {
+    const report = await runSelfTest(capture(tone(0.3)), deps())
+    expect(report.ok).toBe(true)
+    expect(report.stages.map(s => s.stage)).toEqual(['microphone', 'recording', 'signal', 'encoding', 'network', 'server'])
+    expect(report.stages.every(s => s.status === 'passed')).toBe(true)
+    expect(report.stages[5].message).toContain('肉眼所见')
+    expect(report.stages.map(s => s.label)).toEqual(['麦克风', '录音', '音量检测', '音频编码', '网络连接', '服务端识别'])
+  })
+
+  it('麦克风不可用时跳过依赖录音的步骤，网络仍单独检查', async () => {
+    const checkNetwork = vi.fn().mockResolvedValue({ ok: true, message: '已连接' })
+    const report = await runSelfTest(capture(null, { microphone: { durationMs: 1, error: '未检测到麦克风设备' } }), deps({ checkNetwork }))
+    expect(report.ok).toBe(false)
+    expect(statuses(report)).toEqual({
+      microphone: 'failed', recording: 'skipped', signal: 'skipped', encoding: 'skipped', network: 'passed', server: 'skipped',
+    })
+    expect(report.stages[0].message).toBe('未检测到麦克风设备')
+    expect(checkNetwork).toHaveBeenCalled()
+  })
+
+  it('静音录音单独报错，不影响编码与识别', async () => {
+    const report = await runSelfTest(capture(tone(0)), deps())
+    expect(statuses(report)).toMatchObject({ signal: 'failed', encoding: 'passed', server: 'passed' })
+    expect(report.stages.find(s => s.stage === 'signal')?.message).toContain('静音')
+  })
+
+  it('编码失败或网络不可达时跳过识别，服务端报错单独标记', async () => {
+    const recognize = vi.fn().mockResolvedValue('ok')
+    const encodeFailed = await runSelfTest(capture(tone(0.3)), deps({ encode: () => { throw new Error('FLAC 编码失败') }, recognize }))
+    expect(statuses(encodeFailed)).toMatchObject({ encoding: 'failed', server: 'skipped' })
+
+    const offline = await runSelfTest(capture(tone(0.3)), deps({ checkNetwork: async () => ({ ok: false, message: '无法连接到服务器' }), recognize }))
+    expect(statuses(offline)).toMatchObject({ network: 'failed', server: 'skipped' })
+    expect(recognize).not.toHaveBeenCalled()
+
+    const serverError = await runSelfTest(capture(tone(0.3)), deps({ recognize: async () => { throw new Error('ASR 服务错误: 401') } }))
+    expect(statuses(serverError)).toMatchObject({ network: 'passed', server: 'failed' })
+    expect(serverError.stages[5].message).toBe('ASR 服务错误: 401')
+  })
+
+  it('本地识别模式无需网络时跳过网络检查', async () => {
+    const report = await runSelfTest(capture(tone(0.3)), deps({ checkNetwork: async () => null }))
+    expect(report.ok).toBe(true)
+    expect(statuses(report).network).toBe('skipped')
+  })
+})
@@ -0,0 +1,131 @@
+import { describe, it, expect, vi, afterEach } from 'vitest'
+import {
+  buildHealthCheckUrl,
+  checkServerHealth,
+  ServerHealthMonitor,
+  LatencyHistory,
+  trayTooltipFor,
+  type ServerHealthTarget,
+  type ServerStatus,
+} from '../../electron/main/server-health'
+
+const target: ServerHealthTarget = {
+  url: 'http://localhost:3000',
+  healthPath: '',
+  autoPrependScheme: false,
+  intervalMs: 0,
+}
+
+afterEach(() => {
+  vi.unstubAllGlobals()
+})
+
+describe('checkServerHealth', () => {
+  it('收到非 5xx 响应视为已连接，并请求配置的健康检查路径', async () => {
+    const mockFetch = vi.fn().mockResolvedValue({ status: 404 })
+    vi.stubGlobal('fetch', mockFetch)
+
+    const status = await checkServerHealth({ ...target, healthPath: 'health' })
+    expect(status.state).toBe('connected')
+    expect(status.message).toBe('已连接')
+    expect(mockFetch.mock.calls[0][0]).toBe('http://localhost:3000/health')
+    expect(mockFetch.mock.calls[0][1].method).toBe('GET')
+  })
+
+  it('5xx、网络错误与无效地址都视为无法连接，不抛出异常', async () => {
+    vi.stubGlobal('fetch', vi.fn().mockResolvedValue({ status: 503 }))
+    expect(await checkServerHealth(target)).toMatchObject({ state: 'unreachable', message: '无法连接到服务器' })
+
+    vi.stubGlobal('fetch', vi.fn().mockRejectedValue(new Error('ECONNREFUSED')))
+    expect(await checkServerHealth(target)).toMatchObject({ state: 'unreachable', error: 'ECONNREFUSED' })
+
+    expect((await checkServerHealth({ ...target, url: '' })).state).toBe('unreachable')
+  })
+
+  it('取消信号中止请求，结果为无法连接', async () => {
+    const mockFetch = vi.fn((_url: string, init: { signal: AbortSignal }) => new Promise((_resolve, reject) => {
+      init.signal.addEventListener('abort', () => reject(new Error('aborted')))
+    }))
+    vi.stubGlobal('fetch', mockFetch)
+    const controller = new AbortController()
+
+    const pending = checkServerHealth(target, Date.now, controller.signal)
+    controller.abort()
+    expect(await pending).toMatchObject({ state: 'unreachable', latencyMs: null, error: 'aborted' })
+  })
+
+  it('拼接健康检查路径', () => {
+    expect(buildHealthCheckUrl('http://a:3000', '')).toBe('http://a:3000')
+    expect(buildHealthCheckUrl('http://a:3000', '/health')).toBe('http://a:3000/health')
+  })
+})
+
+describe('ServerHealthMonitor', () => {
+  const status = (state: ServerStatus['state']): ServerStatus => ({ state, message: state, checkedAt: '', latencyMs: null })
+
+  it('仅在状态变化时回调', async () => {
+    const results = [status('connected'), status('connected'), status('unreachable')]
+    const onChange = vi.fn()
+    const monitor = new ServerHealthMonitor(() => target, onChange, async () => results.shift()!)
+
+    await monitor.refresh()
+    await monitor.refresh()
+    await monitor.refresh()
+    expect(onChange.mock.calls.map(([s]) => s.state)).toEqual(['connected', 'unreachable'])
+    expect(monitor.status.state).toBe('unreachable')
+  })
+
+  it('无需检查时为 disabled，托盘提示不附加状态', async () => {
+    const monitor = new ServerHealthMonitor(() => null, () => { }, async () => status('connected'))
+    expect((await monitor.refresh()).state).toBe('disabled')
+    expect(trayTooltipFor(monitor.status)).toBe('朗珈语音输入法')
+    expect(trayTooltipFor({ ...status('unreachable'), message: '无法连接到服务器' })).toBe('朗珈语音输入法 - 无法连接到服务器')
+  })
+
+  it('按间隔定时检查，stop 后不再检查', async () => {
+    vi.useFakeTimers()
+    try {
+      const check = vi.fn().mockResolvedValue(status('connected'))
+      const monitor = new ServerHealthMonitor(() => ({ ...target, intervalMs: 1000 }), () => { }, check)
+      await monitor.refresh()
+      await vi.advanceTimersByTimeAsync(2500)
+      expect(check).toHaveBeenCalledTimes(3)
+      monitor.stop()
+      await vi.advanceTimersByTimeAsync(5000)
+      expect(check).toHaveBeenCalledTimes(3)
+    } finally {
+      vi.useRealTimers()
+    }
+  })
+})
+
+describe('LatencyHistory', () => {
+  const connected = (latencyMs: number): ServerStatus => ({ state: 'connected', message: '已连接', checkedAt: 't', latencyMs })
+  const unreachable: ServerStatus = { state: 'unreachable', message: '无法连接到服务器', checkedAt: 't', latencyMs: 12, error: 'HTTP 503' }
+
+  it('记录往返耗时，未连通记为失败', () => {
+    const history = new LatencyHistory()
+    history.record(connected(40))
+    history.record(unreachable)
+    history.record(connected(80))
+
+    expect(history.list()).toEqual([
+      { at: 't', latencyMs: 40 },
+      { at: 't', latencyMs: null, error: 'HTTP 503' },
+      { at: 't', latencyMs: 80 },
+    ])
+    expect(history.summary()).toEqual({ count: 3, failures: 1, lastMs: 80, avgMs: 60, maxMs: 80 })
+  })
+
+  it('只保留最近的记录', () => {
+    const history = new LatencyHistory(2)
+    history.record(connected(10))
+    history.record(connected(20))
+    history.record(connected(30))
+    expect(history.list().map(s => s.latencyMs)).toEqual([20, 30])
+  })
+
+  it('没有记录时统计为空', () => {
+    expect(new LatencyHistory().summary()).toEqual({ count: 0, failures: 0, lastMs: null, avgMs: null, maxMs: null })
+  })
+})
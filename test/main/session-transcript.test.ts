@@ -0,0 +1,79 @@
+import { describe, it, expect } from 'vitest'
+import { SessionTranscript, joinTranscriptSegments, transcriptFileName } from '../../electron/main/session-transcript'
+
+describe('joinTranscriptSegments', () => {
+  it('中文直接相连，英文单词与数字之间补空格', () => {
+    expect(joinTranscriptSegments(['镜下见', '异型细胞。', 'HE', '染色', 'CD20', '阳性'])).toBe('镜下见异型细胞。HE染色CD20阳性')
+    expect(joinTranscriptSegments(['Ki-67', 'index 30', '40%'])).toBe('Ki-67 index 30 40%')
+  })
+
+  it('跳过空白句并去掉首尾空白', () => {
+    expect(joinTranscriptSegments(['  肿物大小 ', '', '   ', '3cm'])).toBe('肿物大小3cm')
+  })
+})
+
+describe('SessionTranscript', () => {
+  it('初始为空', () => {
+    expect(new SessionTranscript().state).toEqual({ active: false, startedAt: null, segmentCount: 0, text: '' })
+  })
+
+  it('间隔内的句子累计到同一会话', () => {
+    let now = 1000
+    const transcript = new SessionTranscript(() => now)
+    transcript.record('第一句。', 60000)
+    now = 30000
+    transcript.record('第二句。', 60000)
+    expect(transcript.state).toMatchObject({ startedAt: 1000, segmentCount: 2, text: '第一句。第二句。' })
+  })
+
+  it('停顿超过 idleGapMs 自动开始新会话', () => {
+    let now = 1000
+    const transcript = new SessionTranscript(() => now)
+    transcript.record('旧内容', 60000)
+    now = 70000
+    transcript.record('新内容', 60000)
+    expect(transcript.state).toMatchObject({ startedAt: 70000, segmentCount: 1, text: '新内容' })
+  })
+
+  it('idleGapMs 为 0 时不按间隔分段', () => {
+    let now = 0
+    const transcript = new SessionTranscript(() => now)
+    transcript.record('一', 0)
+    now = 10_000_000
+    transcript.record('二', 0)
+    expect(transcript.state.text).toBe('一二')
+  })
+
+  it('显式会话不受停顿影响，结束后下一句开始新会话', () => {
+    let now = 1000
+    const transcript = new SessionTranscript(() => now)
+    transcript.record('会话前', 60000)
+    now = 2000
+    transcript.start()
+    expect(transcript.state).toMatchObject({ active: true, startedAt: 2000, segmentCount: 0 })
+    transcript.record('甲', 60000)
+    now = 500000
+    transcript.record('乙', 60000)
+    const ended = transcript.end()
+    expect(ended).toMatchObject({ active: false, segmentCount: 2, text: '甲乙' })
+    now = 501000
+    transcript.record('丙', 60000)
+    expect(transcript.state).toMatchObject({ startedAt: 501000, text: '丙' })
+  })
+
+  it('空白文字不记录，clear 清空全部状态', () => {
+    const transcript = new SessionTranscript(() => 0)
+    transcript.record('   ', 60000)
+    expect(transcript.state.segmentCount).toBe(0)
+    transcript.start()
+    transcript.record('内容', 60000)
+    transcript.clear()
+    expect(transcript.state).toEqual({ active: false, startedAt: null, segmentCount: 0, text: '' })
+  })
+})
+
+describe('transcriptFileName', () => {
+  it('按本地时间生成文件名', () => {
+    expect(transcriptFileName(new Date(2026, 2, 16, 9, 5))).toBe('口述记录-20260316-0905.txt')
+  })
+})
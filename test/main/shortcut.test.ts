@@ -0,0 +1,159 @@
+import { describe, it, expect } from 'vitest'
+import { parseShortcut, parseShortcutSequence, splitShortcutSequence, describeShortcutSequence, validateShortcut } from '../../electron/main/shortcut'
+
+describe('parseShortcut', () => {
+  it('拆分修饰键与主键', () => {
+    expect(parseShortcut('ctrl+shift+F2', 'win32')).toEqual({ modifiers: ['ctrl', 'shift'], key: 'F2' })
+  })
+
+  it('CMD/COMMAND/WIN/SUPER 均视为 Meta', () => {
+    for (const alias of ['CMD', 'COMMAND', 'WIN', 'SUPER', 'META']) {
+      expect(parseShortcut(`${alias}+S`, 'linux').modifiers).toEqual(['meta'])
+    }
+  })
+
+  it('Windows 下不受 mapCtrlToCmdOnMac 影响', () => {
+    expect(parseShortcut('CTRL+S', 'win32', { mapCtrlToCmdOnMac: true })).toEqual({ modifiers: ['ctrl'], key: 'S' })
+  })
+
+  it('Linux 下不受 mapCtrlToCmdOnMac 影响', () => {
+    expect(parseShortcut('CTRL+S', 'linux', { mapCtrlToCmdOnMac: true })).toEqual({ modifiers: ['ctrl'], key: 'S' })
+  })
+
+  it('macOS 开启映射时 Ctrl 与 Cmd 互换', () => {
+    expect(parseShortcut('CTRL+S', 'darwin', { mapCtrlToCmdOnMac: true })).toEqual({ modifiers: ['meta'], key: 'S' })
+    expect(parseShortcut('CMD+S', 'darwin', { mapCtrlToCmdOnMac: true })).toEqual({ modifiers: ['ctrl'], key: 'S' })
+  })
+
+  it('macOS 未开启映射时保持原样', () => {
+    expect(parseShortcut('CTRL+S', 'darwin')).toEqual({ modifiers: ['ctrl'], key: 'S' })
+  })
+
+  it('重复修饰键只保留一次', () => {
+    expect(parseShortcut('CTRL+CONTROL+A', 'win32')).toEqual({ modifiers: ['ctrl'], key: 'A' })
+  })
+
+  it('全角字母、数字与加号归一为半角', () => {
+    expect(parseShortcut('ＡＬＴ＋Ｒ', 'win32')).toEqual({ modifiers: ['alt'], key: 'R' })
+    expect(parseShortcut('ｃｔｒｌ+Ｆ２', 'win32')).toEqual({ modifiers: ['ctrl'], key: 'F2' })
+  })
+})
+
+describe('parseShortcutSequence', () => {
+  it('单个组合键保持原有解析结果', () => {
+    expect(parseShortcutSequence('ALT+R', 'win32')).toEqual([{ modifiers: ['alt'], key: 'R' }])
+  })
+
+  it('按分号拆分为多步，逐步解析', () => {
+    expect(parseShortcutSequence('ALT+F; S ;ENTER', 'win32')).toEqual([
+      { modifiers: ['alt'], key: 'F' },
+      { modifiers: [], key: 'S' },
+      { modifiers: [], key: 'ENTER' },
+    ])
+    expect(splitShortcutSequence('ALT+F; S ;ENTER')).toEqual(['ALT+F', 'S', 'ENTER'])
+  })
+
+  it('全角分号同样作为步骤分隔', () => {
+    expect(parseShortcutSequence('ＡＬＴ＋Ｆ；Ｓ', 'win32')).toEqual([
+      { modifiers: ['alt'], key: 'F' },
+      { modifiers: [], key: 'S' },
+    ])
+  })
+
+  it('每一步都应用 macOS 的 Ctrl/Cmd 映射', () => {
+    expect(parseShortcutSequence('CTRL+A; CTRL+C', 'darwin', { mapCtrlToCmdOnMac: true })
+      .map(step => step.modifiers)).toEqual([['meta'], ['meta']])
+  })
+
+  it('存在空步骤时抛错并指出第几步', () => {
+    expect(() => parseShortcutSequence('ALT+F;; ENTER', 'win32')).toThrow('第 2 步无效')
+    expect(() => parseShortcutSequence('ALT+F; ', 'win32')).toThrow('第 2 步无效')
+    expect(() => parseShortcutSequence('ALT+F; +', 'win32')).toThrow('第 2 步无效')
+  })
+
+  it('单个组合键为空时抛错', () => {
+    expect(() => parseShortcutSequence('  ', 'win32')).toThrow('快捷键无效')
+  })
+})
+
+describe('describeShortcutSequence', () => {
+  it('各修饰键按 Ctrl/Alt/Shift/Meta 顺序给出名称', () => {
+    expect(describeShortcutSequence('shift+alt+ctrl+A', 'win32')).toEqual([['Ctrl', 'Alt', 'Shift', 'A']])
+    expect(describeShortcutSequence('CMD+OPTION+S', 'darwin')).toEqual([['Option', 'Cmd', 'S']])
+    expect(describeShortcutSequence('SUPER+L', 'linux')).toEqual([['Super', 'L']])
+  })
+
+  it('只有修饰键的一步也有效', () => {
+    expect(describeShortcutSequence('SHIFT', 'win32')).toEqual([['Shift']])
+  })
+
+  it('功能键与导航键使用规范名称', () => {
+    expect(describeShortcutSequence('F2; f12; esc; return; pageup', 'win32'))
+      .toEqual([['F2'], ['F12'], ['Esc'], ['Enter'], ['PageUp']])
+  })
+
+  it('单个字母、数字与标点原样返回', () => {
+    expect(describeShortcutSequence('ctrl+a; 1; ALT+/', 'win32')).toEqual([['Ctrl', 'A'], ['1'], ['Alt', '/']])
+  })
+
+  it('小键盘数字', () => {
+    expect(describeShortcutSequence('NUMPAD0; ctrl+numpad9', 'win32')).toEqual([['Numpad0'], ['Ctrl', 'Numpad9']])
+  })
+
+  it('macOS 开启映射时展示互换后的修饰键', () => {
+    expect(describeShortcutSequence('CTRL+S', 'darwin', { mapCtrlToCmdOnMac: true })).toEqual([['Cmd', 'S']])
+  })
+
+  it('未知键名报错', () => {
+    expect(() => describeShortcutSequence('ALT+F13', 'win32')).toThrow('未知按键: "F13"')
+    expect(() => describeShortcutSequence('CTRL+ENTRE', 'win32')).toThrow('未知按键')
+  })
+
+  it('一步内多个主键报错', () => {
+    expect(() => describeShortcutSequence('ALT+A+B', 'win32')).toThrow('多个主键')
+  })
+
+  it('空步骤报错并指出第几步', () => {
+    expect(() => describeShortcutSequence('ALT+F; ; ENTER', 'win32')).toThrow('第 2 步无效')
+  })
+
+  it('Windows 上不支持 Win 键', () => {
+    expect(() => describeShortcutSequence('WIN+D', 'win32')).toThrow('Win 键')
+    expect(describeShortcutSequence('WIN+D', 'linux')).toEqual([['Super', 'D']])
+  })
+
+  it('具名键在三个平台上都可用', () => {
+    for (const platform of ['win32', 'darwin', 'linux'] as const) {
+      expect(describeShortcutSequence('ALT+F; S; ENTER', platform)[2]).toEqual(['Enter'])
+      expect(describeShortcutSequence('TAB; ESC; SPACE; BACKSPACE; DEL; UP; LEFT', platform))
+        .toEqual([['Tab'], ['Esc'], ['Space'], ['Backspace'], ['Delete'], ['Up'], ['Left']])
+    }
+  })
+
+  it('macOS 上拒绝会破坏 AppleScript 的引号与反斜杠', () => {
+    expect(() => describeShortcutSequence('CMD+"', 'darwin')).toThrow('无法模拟')
+    expect(() => describeShortcutSequence("CMD+'", 'darwin')).toThrow('无法模拟')
+    expect(() => describeShortcutSequence('CMD+\\', 'darwin')).toThrow('无法模拟')
+    expect(describeShortcutSequence('CMD+/', 'darwin')).toEqual([['Cmd', '/']])
+    expect(describeShortcutSequence('CTRL+"', 'win32')).toEqual([['Ctrl', '"']])
+  })
+
+  it('Linux 上字符键只接受字母与数字', () => {
+    expect(describeShortcutSequence('CTRL+A; 1', 'linux')).toEqual([['Ctrl', 'A'], ['1']])
+    expect(() => describeShortcutSequence('ALT+/', 'linux')).toThrow('第 1 步的按键 "/" 在当前系统上无法模拟')
+    expect(validateShortcut('CTRL+S; &', 'linux')).toMatchObject({ ok: false, error: expect.stringContaining('第 2 步') })
+  })
+})
+
+describe('validateShortcut', () => {
+  it('成功时返回各步按键名称', () => {
+    expect(validateShortcut('ALT+F; S', 'win32')).toEqual({ ok: true, steps: [['Alt', 'F'], ['S']] })
+  })
+
+  it('失败时返回错误信息而不抛出', () => {
+    const result = validateShortcut('ALT+FOO', 'win32')
+    expect(result.ok).toBe(false)
+    expect(result.steps).toEqual([])
+    expect(result.error).toContain('FOO')
+  })
+})
@@ -0,0 +1,45 @@
+import { describe, it, expect } from 'vitest'
+import { SingleFlightQueue, type SingleFlightState } from '../../electron/main/single-flight'
+
+function deferred<T>() {
+  let resolve!: (value: T) => void
+  let reject!: (err: unknown) => void
+  const promise = new Promise<T>((res, rej) => { resolve = res; reject = rej })
+  return { promise, resolve, reject }
+}
+
+describe('SingleFlightQueue', () => {
+  it('快速连续触发时按顺序执行，不会重叠', async () => {
+    const queue = new SingleFlightQueue()
+    const log: string[] = []
+    const first = deferred<string>()
+    const a = queue.run(async () => { log.push('a:start'); const v = await first.promise; log.push('a:end'); return v })
+    const b = queue.run(async () => { log.push('b:start'); return 'b' })
+
+    await Promise.resolve()
+    await Promise.resolve()
+    expect(log).toEqual(['a:start'])
+    expect(queue.state).toEqual({ busy: true, pending: 1 })
+
+    first.resolve('a')
+    await expect(a).resolves.toBe('a')
+    await expect(b).resolves.toBe('b')
+    expect(log).toEqual(['a:start', 'a:end', 'b:start'])
+  })
+
+  it('任务抛错时释放占用，后续请求继续执行并回调空闲状态', async () => {
+    const states: SingleFlightState[] = []
+    const queue = new SingleFlightQueue((state) => states.push(state))
+
+    const failed = queue.run(async () => { throw new Error('ASR 超时') })
+    const next = queue.run(async () => 'ok')
+    await expect(failed).rejects.toThrow('ASR 超时')
+    await expect(next).resolves.toBe('ok')
+    await new Promise((r) => setTimeout(r, 0))
+
+    expect(queue.state).toEqual({ busy: false, pending: 0 })
+    expect(states[0]).toEqual({ busy: true, pending: 0 })
+    expect(states[1]).toEqual({ busy: true, pending: 1 })
+    expect(states[states.length - 1]).toEqual({ busy: false, pending: 0 })
+  })
+})
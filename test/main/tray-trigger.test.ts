@@ -0,0 +1,22 @@
+import { describe, it, expect } from 'vitest'
+import { trayMouseAction, usesNativeContextMenu } from '../../electron/main/tray-trigger'
+
+describe('trayMouseAction', () => {
+  it('默认左键单击显示浮窗，双击与右键不显示', () => {
+    expect(trayMouseAction('left-click', 'click')).toBe('show-window')
+    expect(trayMouseAction('left-click', 'double-click')).toBe('none')
+    expect(trayMouseAction('left-click', 'right-click')).toBe('none')
+    expect(usesNativeContextMenu('left-click')).toBe(true)
+  })
+
+  it('双击模式下单击不显示', () => {
+    expect(trayMouseAction('double-click', 'click')).toBe('none')
+    expect(trayMouseAction('double-click', 'double-click')).toBe('show-window')
+  })
+
+  it('右键模式下右键显示浮窗，左键单击弹出菜单', () => {
+    expect(trayMouseAction('right-click', 'right-click')).toBe('show-window')
+    expect(trayMouseAction('right-click', 'click')).toBe('popup-menu')
+    expect(usesNativeContextMenu('right-click')).toBe(false)
+  })
+})
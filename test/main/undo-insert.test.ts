@@ -0,0 +1,32 @@
+import { describe, it, expect } from 'vitest'
+import { LastInsertTracker, undoKeystrokeCount } from '../../electron/main/undo-insert'
+
+describe('undoKeystrokeCount', () => {
+  it('按字符计数，中文、emoji 各算一个，CRLF 算一个换行', () => {
+    expect(undoKeystrokeCount('肉眼所见')).toBe(4)
+    expect(undoKeystrokeCount('ok👍')).toBe(3)
+    expect(undoKeystrokeCount('第一行\r\n第二行')).toBe(7)
+    expect(undoKeystrokeCount('')).toBe(0)
+  })
+})
+
+describe('LastInsertTracker', () => {
+  it('取出最近一次输入的退格数后清空', () => {
+    const tracker = new LastInsertTracker()
+    expect(tracker.take()).toBeNull()
+    tracker.recordText('灰白色')
+    tracker.recordText('结节，')
+    expect(tracker.pending).toBe(3)
+    expect(tracker.take()).toBe(3)
+    expect(tracker.take()).toBeNull()
+  })
+
+  it('最近一次动作是指令或空文字时不可撤销', () => {
+    const tracker = new LastInsertTracker()
+    tracker.recordText('灰白色')
+    tracker.recordCommand()
+    expect(tracker.take()).toBeNull()
+    tracker.recordText('')
+    expect(tracker.take()).toBeNull()
+  })
+})
@@ -0,0 +1,51 @@
+import { describe, it, expect } from 'vitest'
+import { formatAudioInfo, formatElapsedMs, formatRecognitionResult } from '../../src/utils'
+
+describe('formatElapsedMs', () => {
+  it('不足一分钟', () => {
+    expect(formatElapsedMs(3400)).toBe('0:03')
+  })
+
+  it('超过一分钟', () => {
+    expect(formatElapsedMs(75000)).toBe('1:15')
+  })
+
+  it('负数按 0 处理', () => {
+    expect(formatElapsedMs(-10)).toBe('0:00')
+  })
+})
+
+describe('formatAudioInfo', () => {
+  const base = {
+    sourceSampleRate: 48000,
+    sourceChannels: 1,
+    outputSampleRate: 16000,
+    outputChannels: 1,
+    deviceName: 'USB 麦克风',
+    capturing: false,
+    vadListening: false,
+    updatedAt: '',
+  }
+
+  it('显示源格式与重采样后的识别格式', () => {
+    expect(formatAudioInfo(base)).toBe('USB 麦克风 · 48kHz 单声道 → 16kHz 单声道')
+    expect(formatAudioInfo({ ...base, sourceSampleRate: 44100, sourceChannels: 2, capturing: true }))
+      .toBe('USB 麦克风 · 44.1kHz 立体声 → 16kHz 单声道（录音中）')
+  })
+
+  it('未打开麦克风或设备未提供采样率', () => {
+    expect(formatAudioInfo({ ...base, sourceSampleRate: null, deviceName: '' })).toBe('麦克风未打开')
+    expect(formatAudioInfo({ ...base, sourceSampleRate: null, sourceChannels: null, vadListening: true }))
+      .toBe('USB 麦克风 · 采样率未知 → 16kHz 单声道（VAD 监听中）')
+  })
+})
+
+describe('formatRecognitionResult', () => {
+  it('文字结果原样显示，指令显示口令与快捷键', () => {
+    expect(formatRecognitionResult({ kind: 'text', text: '肉眼所见' })).toBe('肉眼所见')
+    expect(formatRecognitionResult({ kind: 'command', text: '下一个', phrase: '下一个', shortcut: 'ALT+N' }))
+      .toBe('下一个 ⌨ ALT+N')
+    expect(formatRecognitionResult({ kind: 'command', text: '下一个', phrase: '下一个', shortcut: 'ALT+N', repeated: true }))
+      .toBe('下一个（重复） ⌨ ALT+N')
+  })
+})
@@ -0,0 +1,36 @@
+import { describe, it, expect } from 'vitest'
+import { isVadBackendName, vadBackendUnavailableReason } from '../../electron/main/vad-backend'
+import type { ModelDependencyStatus } from '../../electron/main/model-manager'
+
+function vadDependency(overrides: Partial<ModelDependencyStatus> = {}): ModelDependencyStatus {
+  return {
+    role: 'VAD',
+    modelName: 'iic/speech_fsmn_vad_zh-cn-16k-common-onnx',
+    backend: 'funasr_onnx_vad',
+    quantize: true,
+    cached: true,
+    complete: true,
+    missingFiles: [],
+    issue: '',
+    ...overrides,
+  }
+}
+
+describe('VAD 后端', () => {
+  it('只接受已知的后端名称', () => {
+    expect(isVadBackendName('energy')).toBe(true)
+    expect(isVadBackendName('fsmn')).toBe(true)
+    expect(isVadBackendName('silero')).toBe(false)
+    expect(isVadBackendName(undefined)).toBe(false)
+  })
+
+  it('能量检测始终可用', () => {
+    expect(vadBackendUnavailableReason('energy', undefined)).toBeNull()
+  })
+
+  it('FSMN 模型文件缺失或没有配套模型时不可切换', () => {
+    expect(vadBackendUnavailableReason('fsmn', vadDependency())).toBeNull()
+    expect(vadBackendUnavailableReason('fsmn', vadDependency({ complete: false, issue: '模型未下载' }))).toContain('模型未下载')
+    expect(vadBackendUnavailableReason('fsmn', undefined)).toContain('没有配套的 VAD 模型')
+  })
+})
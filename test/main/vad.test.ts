@@ -0,0 +1,417 @@
+import { describe, it, expect } from 'vitest'
+import {
+  VoiceActivityDetector,
+  VadFrameAccumulator,
+  VadIdleTimer,
+  VadSampleClock,
+  SilenceSegmenter,
+  frameSamplesForMs,
+  computeRms,
+  isSpeechSegmentLongEnough,
+  clampEnergySmoothing,
+  VAD_DEFAULT_ENERGY_SMOOTHING,
+  VAD_MAX_ENERGY_SMOOTHING,
+  VAD_MAX_SPEECH_MS,
+  type VadFrameEvent,
+  type VadFrameParams,
+} from '../../src/vad'
+import { applyInputGainKeepingTail } from '../../src/audio-level'
+
+const FRAME_MS = 40
+const params = { threshold: 0.05, silenceMs: 300 }
+
+// 用假时钟驱动状态机：每帧推进 40ms，与渲染进程轮询间隔一致
+function createHarness(frameParams: VadFrameParams = params) {
+  let now = 0
+  const detector = new VoiceActivityDetector(() => now)
+  const feed = (rms: number, frames: number): VadFrameEvent[] => {
+    const events: VadFrameEvent[] = []
+    for (let i = 0; i < frames; i++) {
+      now += FRAME_MS
+      const event = detector.process(rms, frameParams)
+      if (event.type !== 'none') events.push(event)
+    }
+    return events
+  }
+  return { detector, feed, now: () => now }
+}
+
+describe('VoiceActivityDetector', () => {
+  it('持续超过阈值后判定语音开始', () => {
+    const { detector, feed } = createHarness()
+    expect(feed(0.001, 10)).toEqual([])
+    const events = feed(0.2, 5)
+    expect(events).toHaveLength(1)
+    expect(events[0].type).toBe('speech-start')
+    expect(detector.isSpeaking).toBe(true)
+  })
+
+  it('短促噪声不触发语音开始', () => {
+    const { detector, feed } = createHarness()
+    feed(0.001, 10)
+    expect(feed(0.2, 1)).toEqual([])
+    expect(feed(0.0005, 10)).toEqual([])
+    expect(detector.isSpeaking).toBe(false)
+  })
+
+  it('静音超过设定时长后结束语音段', () => {
+    const { detector, feed } = createHarness()
+    feed(0.001, 10)
+    feed(0.2, 20)
+    const events = feed(0.0005, 30)
+    expect(events).toHaveLength(1)
+    const end = events[0]
+    expect(end.type).toBe('speech-end')
+    if (end.type === 'speech-end') {
+      expect(end.reason).toBe('silence')
+      expect(end.speechEndAt - end.speechStartAt).toBeGreaterThanOrEqual(700)
+    }
+    expect(detector.isSpeaking).toBe(false)
+  })
+
+  it('静音未达设定时长时保持录音', () => {
+    const { detector, feed } = createHarness()
+    feed(0.001, 10)
+    feed(0.2, 20)
+    expect(feed(0.0005, 5)).toEqual([])
+    expect(detector.isSpeaking).toBe(true)
+  })
+
+  it('超过最长语音时长强制切段', () => {
+    const { feed } = createHarness()
+    feed(0.001, 10)
+    const events = feed(0.2, Math.ceil(VAD_MAX_SPEECH_MS / FRAME_MS) + 5)
+    const end = events.find(e => e.type === 'speech-end')
+    expect(end).toMatchObject({ type: 'speech-end', reason: 'max-duration' })
+  })
+
+  it('按 maxUtteranceMs 强制切段，切段后持续的声音重新开始新语音段', () => {
+    const { detector, feed } = createHarness({ ...params, maxUtteranceMs: 2000 })
+    feed(0.001, 10)
+    const [start] = feed(0.2, 3)
+    expect(start.type).toBe('speech-start')
+    const startAt = start.type === 'speech-start' ? start.at : 0
+
+    // 到达上限前一帧仍在录音，到达上限的那一帧切段
+    expect(feed(0.2, 2000 / FRAME_MS - 1)).toEqual([])
+    expect(detector.isSpeaking).toBe(true)
+    expect(feed(0.2, 1)).toEqual([
+      { type: 'speech-end', speechStartAt: startAt, speechEndAt: startAt + 2000, reason: 'max-duration' },
+    ])
+    expect(detector.isSpeaking).toBe(false)
+    expect(feed(0.2, 3).map(e => e.type)).toEqual(['speech-start'])
+  })
+
+  it('按 minUtteranceMs 把过短语音段标记为 too-short，达到下限的语音段正常结束', () => {
+    const endOf = (speechFrames: number) => {
+      const { feed } = createHarness({ ...params, minUtteranceMs: 400 })
+      feed(0.001, 10)
+      const events = [...feed(0.2, speechFrames), ...feed(0.0005, 30)]
+      return events.find(e => e.type === 'speech-end')
+    }
+
+    const bang = endOf(3)
+    const speech = endOf(15)
+    expect(bang).toMatchObject({ type: 'speech-end', reason: 'too-short' })
+    expect(speech).toMatchObject({ type: 'speech-end', reason: 'silence' })
+    if (bang?.type === 'speech-end') expect(bang.speechEndAt - bang.speechStartAt).toBeLessThan(400)
+    if (speech?.type === 'speech-end') expect(speech.speechEndAt - speech.speechStartAt).toBeGreaterThanOrEqual(400)
+  })
+
+  describe('sentenceHoldMs 句内停顿宽限期', () => {
+    // 说 400ms、停顿 pauseFrames 帧、再说 400ms，返回各事件类型
+    const speakWithPause = (pauseFrames: number, sentenceHoldMs: number, pauseRms = 0.001) => {
+      const { feed } = createHarness({ ...params, sentenceHoldMs })
+      feed(0.001, 10)
+      return [...feed(0.2, 10), ...feed(pauseRms, pauseFrames), ...feed(0.2, 10), ...feed(0.001, 40)]
+    }
+
+    it('静音超时后在宽限期内重新开口，合并为同一语音段', () => {
+      expect(speakWithPause(20, 0).map(e => e.type)).toEqual(['speech-start', 'speech-end', 'speech-start', 'speech-end'])
+      const merged = speakWithPause(20, 500)
+      expect(merged.map(e => e.type)).toEqual(['speech-start', 'speech-end'])
+      if (merged[1].type === 'speech-end') {
+        expect(merged[1].reason).toBe('silence')
+        expect(merged[1].speechEndAt - merged[1].speechStartAt).toBeGreaterThan(1200)
+      }
+    })
+
+    it('停顿超过宽限期时仍分成两段，结束时间为静音开始处', () => {
+      const events = speakWithPause(28, 500)
+      expect(events.map(e => e.type)).toEqual(['speech-start', 'speech-end', 'speech-start', 'speech-end'])
+      if (events[1].type === 'speech-end') expect(events[1].speechEndAt - events[1].speechStartAt).toBeLessThan(600)
+    })
+
+    it('宽限期内低于起始阈值的声音不算重新开口', () => {
+      const { feed } = createHarness({ ...params, sentenceHoldMs: 500 })
+      feed(0.001, 10)
+      const events = [...feed(0.2, 10), ...feed(0.001, 14), ...feed(0.045, 8), ...feed(0.001, 40)]
+      expect(events.map(e => e.type)).toEqual(['speech-start', 'speech-end'])
+      if (events[1].type === 'speech-end') expect(events[1].speechEndAt - events[1].speechStartAt).toBeLessThan(600)
+    })
+  })
+
+  describe('能量平滑', () => {
+    // 底噪中夹杂每次 3 帧的噪声尖峰，不平滑时每个尖峰都足以触发一次起止
+    const feedNoisyBursts = (feed: (rms: number, frames: number) => VadFrameEvent[]) => {
+      const events = [...feed(0.005, 10)]
+      for (let i = 0; i < 5; i++) events.push(...feed(0.09, 3), ...feed(0.005, 12))
+      return events
+    }
+
+    it('平滑后噪声尖峰引起的起止切换更少', () => {
+      const raw = createHarness({ ...params, energySmoothing: 0 })
+      const smoothed = createHarness({ ...params, energySmoothing: 0.8 })
+      const rawEvents = feedNoisyBursts(raw.feed)
+      const smoothedEvents = feedNoisyBursts(smoothed.feed)
+      expect(rawEvents.filter(e => e.type === 'speech-start')).toHaveLength(5)
+      expect(smoothedEvents.length).toBeLessThan(rawEvents.length)
+      expect(smoothedEvents).toEqual([])
+    })
+
+    it('平滑后持续语音仍能触发语音开始', () => {
+      const { feed } = createHarness({ ...params, energySmoothing: 0.8 })
+      feed(0.005, 10)
+      expect(feed(0.2, 10).map(e => e.type)).toEqual(['speech-start'])
+    })
+
+    it('超出范围的平滑系数被限制，非法值使用默认值', () => {
+      expect(clampEnergySmoothing(-1)).toBe(0)
+      expect(clampEnergySmoothing(2)).toBe(VAD_MAX_ENERGY_SMOOTHING)
+      expect(clampEnergySmoothing('abc')).toBe(VAD_DEFAULT_ENERGY_SMOOTHING)
+    })
+  })
+
+  it('reset 清空语音状态与噪声基线', () => {
+    const { detector, feed } = createHarness()
+    feed(0.001, 10)
+    feed(0.2, 5)
+    expect(detector.isSpeaking).toBe(true)
+    detector.reset()
+    expect(detector.isSpeaking).toBe(false)
+    expect(detector.smoothedLevel).toBe(0)
+    expect(detector.noiseFloor).toBe(0)
+    expect(feed(0.0005, 10)).toEqual([])
+  })
+
+  it('abortSpeech 放弃当前语音段后可重新触发', () => {
+    const { detector, feed } = createHarness()
+    feed(0.001, 10)
+    feed(0.2, 5)
+    detector.abortSpeech()
+    expect(detector.isSpeaking).toBe(false)
+    expect(feed(0.2, 5).map(e => e.type)).toEqual(['speech-start'])
+  })
+})
+
+describe('isSpeechSegmentLongEnough', () => {
+  it('过短语音段被丢弃', () => {
+    expect(isSpeechSegmentLongEnough({ speechDurationMs: 40, wavDurationMs: 300, tailSilenceMs: 240, minSpeechMs: 250 }))
+      .toBe(false)
+    expect(isSpeechSegmentLongEnough({ speechDurationMs: 500, wavDurationMs: 60, tailSilenceMs: 0, minSpeechMs: 0 }))
+      .toBe(false)
+  })
+
+  it('正常语音段保留', () => {
+    expect(isSpeechSegmentLongEnough({ speechDurationMs: 800, wavDurationMs: 1100, tailSilenceMs: 240, minSpeechMs: 250 }))
+      .toBe(true)
+  })
+
+  it('句首补齐的静音同样不计入语音时长', () => {
+    expect(isSpeechSegmentLongEnough({ speechDurationMs: 40, wavDurationMs: 600, tailSilenceMs: 240, minSpeechMs: 250 }))
+      .toBe(true)
+    expect(isSpeechSegmentLongEnough({
+      speechDurationMs: 40,
+      wavDurationMs: 600,
+      leadSilenceMs: 300,
+      tailSilenceMs: 240,
+      minSpeechMs: 250,
+    })).toBe(false)
+  })
+})
+
+describe('computeRms', () => {
+  it('计算均方根，空帧为 0', () => {
+    expect(computeRms(new Float32Array([0.5, -0.5, 0.5, -0.5]))).toBeCloseTo(0.5)
+    expect(computeRms(new Float32Array(0))).toBe(0)
+  })
+})
+
+describe('SilenceSegmenter', () => {
+  const SAMPLE_RATE = 16000
+  const CHUNK_MS = 40
+  const segmentParams = { threshold: 0.05, silenceMs: 300 }
+
+  // 按 40ms 一块送入恒定幅度的采样，返回需要切段的块序号
+  const feedChunks = (segmenter: SilenceSegmenter, level: number, ms: number, channels = 1, start = 0) => {
+    const cuts: number[] = []
+    const chunkSamples = (SAMPLE_RATE * CHUNK_MS / 1000) * channels
+    for (let i = 0; i < ms / CHUNK_MS; i++) {
+      if (segmenter.push(new Float32Array(chunkSamples).fill(level), segmentParams)) cuts.push(start + i)
+    }
+    return cuts
+  }
+
+  it('一句话说完、静音超时后提示切段，说话过程中不切', () => {
+    const segmenter = new SilenceSegmenter(SAMPLE_RATE, 1, 40)
+    expect(feedChunks(segmenter, 0.001, 400)).toEqual([])
+    expect(feedChunks(segmenter, 0.2, 800)).toEqual([])
+    expect(segmenter.isSpeaking).toBe(true)
+    expect(feedChunks(segmenter, 0.001, 800)).toHaveLength(1)
+    expect(segmenter.isSpeaking).toBe(false)
+  })
+
+  it('连续多句各切一次', () => {
+    const segmenter = new SilenceSegmenter(SAMPLE_RATE, 1, 40)
+    feedChunks(segmenter, 0.001, 400)
+    let cuts = 0
+    for (let i = 0; i < 3; i++) {
+      cuts += feedChunks(segmenter, 0.2, 600).length
+      cuts += feedChunks(segmenter, 0.001, 800).length
+    }
+    expect(cuts).toBe(3)
+  })
+
+  it('停顿短于静音时长时不切', () => {
+    const segmenter = new SilenceSegmenter(SAMPLE_RATE, 1, 40)
+    feedChunks(segmenter, 0.001, 400)
+    expect([
+      ...feedChunks(segmenter, 0.2, 600),
+      ...feedChunks(segmenter, 0.001, 120),
+      ...feedChunks(segmenter, 0.2, 600),
+    ]).toEqual([])
+  })
+
+  it('立体声交错采样按音频时长判断', () => {
+    const segmenter = new SilenceSegmenter(SAMPLE_RATE, 2, 40)
+    feedChunks(segmenter, 0.001, 400, 2)
+    expect(feedChunks(segmenter, 0.2, 800, 2)).toEqual([])
+    // 静音开始后约 0.4s 内切段，与单声道一致
+    const cuts = feedChunks(segmenter, 0.001, 800, 2)
+    expect(cuts).toHaveLength(1)
+    expect(cuts[0]).toBeLessThan(16)
+  })
+})
+
+describe('VadFrameAccumulator', () => {
+  it('按固定帧长切分，余量留到下一次', () => {
+    const frameSamples = frameSamplesForMs(30, 16000)
+    expect(frameSamples).toBe(480)
+    const accumulator = new VadFrameAccumulator(frameSamples)
+
+    // 模拟设备每次回调长度不同
+    const first = accumulator.push(new Float32Array(1000).fill(0.1))
+    expect(first.map(f => f.length)).toEqual([480, 480])
+    expect(accumulator.pendingSamples).toBe(40)
+
+    const second = accumulator.push(new Float32Array(500).fill(0.2))
+    expect(second.map(f => f.length)).toEqual([480])
+    expect(second[0][39]).toBeCloseTo(0.1)
+    expect(second[0][40]).toBeCloseTo(0.2)
+    expect(accumulator.pendingSamples).toBe(60)
+  })
+
+  it('不足一帧时不输出，reset 丢弃余量', () => {
+    const accumulator = new VadFrameAccumulator(480)
+    expect(accumulator.push(new Float32Array(100))).toEqual([])
+    accumulator.reset()
+    expect(accumulator.pendingSamples).toBe(0)
+    expect(accumulator.push(new Float32Array(480))).toHaveLength(1)
+  })
+
+  it('帧长非法时使用默认值', () => {
+    expect(frameSamplesForMs(Number.NaN, 16000)).toBe(480)
+  })
+})
+
+describe('VadSampleClock', () => {
+  it('按已处理采样数计时', () => {
+    const clock = new VadSampleClock(16000)
+    expect(clock.nowMs).toBe(0)
+    clock.advance(480)
+    clock.advance(1600)
+    expect(clock.nowMs).toBe(130)
+    clock.advance(-10)
+    expect(clock.nowMs).toBe(130)
+    clock.reset(48000)
+    clock.advance(480)
+    expect(clock.nowMs).toBe(10)
+  })
+
+  it('设备一次回调大批采样时静音超时仍按音频时长判断', () => {
+    // 模拟回调批量到达：同一时刻处理完 1 秒语音 + 0.5 秒静音，墙钟几乎没走，但音频时间线正确推进
+    const frameSamples = frameSamplesForMs(30, 16000)
+    const clock = new VadSampleClock(16000)
+    const detector = new VoiceActivityDetector(() => clock.nowMs)
+    const accumulator = new VadFrameAccumulator(frameSamples)
+    const feedBatch = (amplitude: number, ms: number): VadFrameEvent[] => {
+      const events: VadFrameEvent[] = []
+      for (const frame of accumulator.push(new Float32Array((16000 * ms) / 1000).fill(amplitude))) {
+        clock.advance(frame.length)
+        const event = detector.process(computeRms(frame), params)
+        if (event.type !== 'none') events.push(event)
+      }
+      return events
+    }
+
+    feedBatch(0.001, 300)
+    expect(feedBatch(0.2, 1000).map(e => e.type)).toEqual(['speech-start'])
+    // 静音 200ms 未达到 silenceMs(300) + 缓冲，不切段
+    expect(feedBatch(0.0005, 200)).toEqual([])
+    const events = feedBatch(0.0005, 600)
+    expect(events).toHaveLength(1)
+    const end = events[0]
+    expect(end.type).toBe('speech-end')
+    if (end.type === 'speech-end') {
+      expect(end.reason).toBe('silence')
+      expect(end.speechEndAt - end.speechStartAt).toBeGreaterThanOrEqual(900)
+      expect(end.speechEndAt - end.speechStartAt).toBeLessThanOrEqual(1100)
+    }
+  })
+})
+
+describe('VadIdleTimer', () => {
+  it('超过空闲时长后到期，有活动时重新计时', () => {
+    let now = 1000
+    const timer = new VadIdleTimer(() => now)
+    now += 59_000
+    expect(timer.isExpired(60_000)).toBe(false)
+    now += 1000
+    expect(timer.isExpired(60_000)).toBe(true)
+    timer.touch()
+    expect(timer.idleMs()).toBe(0)
+    expect(timer.isExpired(60_000)).toBe(false)
+  })
+
+  it('超时为 0 时从不到期', () => {
+    let now = 0
+    const timer = new VadIdleTimer(() => now)
+    now += 24 * 60 * 60 * 1000
+    expect(timer.isExpired(0)).toBe(false)
+  })
+})
+
+describe('VAD 与软件增益', () => {
+  // 模拟一次轮询：窗口内采样先应用增益，VAD 用增益前的新增部分计算能量
+  function detectWithGain(amplitude: number, gain: number): VadFrameEvent[] {
+    const { feed } = createHarness()
+    const window = new Float32Array(640).fill(amplitude)
+    const raw = applyInputGainKeepingTail(window, gain, 640)
+    expect(window[0]).toBeCloseTo(Math.min(1, amplitude * gain))
+    return feed(computeRms(raw), 5)
+  }
+
+  it('相同声学电平下，不同增益的检测结果一致', () => {
+    for (const gain of [0.5, 1, 4, 10]) {
+      expect(detectWithGain(0.02, gain)).toEqual([])
+      expect(detectWithGain(0.2, gain).map(e => e.type)).toEqual(['speech-start'])
+    }
+  })
+
+  it('只复制窗口末尾的新增采样', () => {
+    const window = new Float32Array([0.5, 0.25, 0.125])
+    expect(Array.from(applyInputGainKeepingTail(window, 2, 2))).toEqual([0.25, 0.125])
+    expect(Array.from(window)).toEqual([1, 0.5, 0.25])
+    expect(applyInputGainKeepingTail(window, 2, 0)).toHaveLength(0)
+  })
+})
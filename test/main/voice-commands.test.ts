@@ -1,5 +1,26 @@
 import { describe, it, expect } from 'vitest'
-import { matchVoiceCommand } from '../../electron/main/voice-commands'
+import {
+  matchVoiceCommand,
+  previewVoiceCommand,
+  listVoiceCommands,
+  findClosestVoiceCommand,
+  VoiceCommandCooldown,
+  LastCommandTracker,
+  PostCommandLockout,
+  applyPostCommandLockout,
+  isRepeatCommand,
+  resolveCommandCooldownMs,
+  resolveTextCommandToken,
+  resolveTextExpansion,
+  matchVoiceCommandCandidates,
+  isAmbiguousCandidates,
+  expandVoiceCommandAliases,
+  canonicalCommandPhrase,
+  findVoiceCommandPhrase,
+  placeCursorAtPlaceholder,
+  resolveExecCommand,
+  splitCommandLine,
+} from '../../electron/main/voice-commands'
 
 const commands = {
   肉眼所见: 'ALT+R',
@@ -53,6 +74,142 @@ describe('matchVoiceCommand', () => {
     })
   })
 
+  describe('输入后回车', () => {
+    const options = { enterAfterPhrases: ['阴性', '未见异常'] }
+
+    it('标记的短语输入后追加回车', () => {
+      const r = matchVoiceCommand('阴性。', commands, options)
+      expect(r).toEqual({ type: 'text', text: '阴性。', pressEnter: true })
+    })
+
+    it('未标记的短语不追加回车', () => {
+      const r = matchVoiceCommand('阳性', commands, options)
+      expect(r).toEqual({ type: 'text', text: '阳性' })
+    })
+
+    it('包含标记短语的长句不追加回车', () => {
+      const r = matchVoiceCommand('结果为阴性', commands, options)
+      expect(r).toEqual({ type: 'text', text: '结果为阴性' })
+    })
+
+    it('指令优先于回车短语', () => {
+      const r = matchVoiceCommand('保存报告', commands, { enterAfterPhrases: ['保存报告'] })
+      expect(r).toEqual({ type: 'command', shortcut: 'F2' })
+    })
+  })
+
+  describe('换行/制表占位符', () => {
+    const tokenCommands = { ...commands, 换行: '{NEWLINE}', 下一格: '{tab}' }
+
+    it('占位符指令输入控制字符而不是快捷键', () => {
+      expect(matchVoiceCommand('换行。', tokenCommands)).toEqual({ type: 'text', text: '\n', phrase: '换行' })
+      expect(matchVoiceCommand('下一格', tokenCommands)).toEqual({ type: 'text', text: '\t', phrase: '下一格' })
+    })
+
+    it('普通快捷键不受影响', () => {
+      expect(resolveTextCommandToken('ENTER')).toBeNull()
+      expect(matchVoiceCommand('保存报告', tokenCommands)).toEqual({ type: 'command', shortcut: 'F2' })
+    })
+
+    it('预览中标出命中的口令', () => {
+      const r = previewVoiceCommand('换行', tokenCommands)
+      expect(r).toMatchObject({ type: 'text', phrase: '换行', shortcut: null, text: '\n' })
+    })
+  })
+
+  describe('文本扩展', () => {
+    const expansionCommands = {
+      ...commands,
+      标准诊断: 'TEXT:（胃窦）黏膜慢性炎，未见明显异常。',
+      签名: 'text: 报告医生：',
+    }
+
+    it('文本扩展指令输入配置的文字，快捷键指令仍发送快捷键', () => {
+      expect(matchVoiceCommand('标准诊断。', expansionCommands)).toEqual({
+        type: 'text',
+        text: '（胃窦）黏膜慢性炎，未见明显异常。',
+        phrase: '标准诊断',
+      })
+      expect(matchVoiceCommand('签名', expansionCommands)).toEqual({ type: 'text', text: ' 报告医生：', phrase: '签名' })
+      expect(matchVoiceCommand('肉眼所见', expansionCommands)).toEqual({ type: 'command', shortcut: 'ALT+R' })
+    })
+
+    it('前缀后为空或不是 TEXT: 前缀时不是文本扩展', () => {
+      expect(resolveTextExpansion('TEXT:  ')).toBeNull()
+      expect(resolveTextExpansion('CTRL+T')).toBeNull()
+      expect(resolveTextExpansion('TEXTS:abc')).toBeNull()
+    })
+
+    it('指令列表中标为文字指令', () => {
+      expect(listVoiceCommands(expansionCommands, 'win32').find(e => e.phrase === '标准诊断'))
+        .toMatchObject({ kind: 'text', valid: true })
+    })
+
+    it('无占位符时不移动光标', () => {
+      expect(placeCursorAtPlaceholder('见附件。')).toEqual({ text: '见附件。', cursorLeft: 0 })
+    })
+
+    it('光标左移次数等于占位符之后的字符数', () => {
+      expect(placeCursorAtPlaceholder('大小约[]cm。')).toEqual({ text: '大小约cm。', cursorLeft: 3 })
+      expect(placeCursorAtPlaceholder('结论：[]')).toEqual({ text: '结论：', cursorLeft: 0 })
+      expect(placeCursorAtPlaceholder('[]个')).toEqual({ text: '个', cursorLeft: 1 })
+    })
+
+    it('多个占位符时停在第一个，其余占位符不输入', () => {
+      expect(placeCursorAtPlaceholder('大小[]cm，数量[]个')).toEqual({ text: '大小cm，数量个', cursorLeft: 6 })
+    })
+
+    it('换行按一步计，CRLF 也只算一步', () => {
+      expect(placeCursorAtPlaceholder('诊断：[]\n签名')).toEqual({ text: '诊断：\n签名', cursorLeft: 3 })
+      expect(placeCursorAtPlaceholder('诊断：[]\r\n签名')).toEqual({ text: '诊断：\r\n签名', cursorLeft: 3 })
+    })
+
+    it('代理对字符按一个字符计', () => {
+      expect(placeCursorAtPlaceholder('[]𠀀好')).toEqual({ text: '𠀀好', cursorLeft: 2 })
+    })
+
+    it('命中含占位符的文本扩展时带上左移次数', () => {
+      expect(matchVoiceCommand('大小模板', { 大小模板: 'TEXT:大小约[]cm。' }))
+        .toEqual({ type: 'text', text: '大小约cm。', phrase: '大小模板', cursorLeft: 3 })
+    })
+
+    it('锁定期内文本扩展口令按原文输入', () => {
+      const match = matchVoiceCommand('标准诊断', expansionCommands)
+      expect(applyPostCommandLockout(match, '标准诊断', 500, 'text')).toEqual({ type: 'text', text: '标准诊断' })
+    })
+  })
+
+  describe('全半角与大小写', () => {
+    const latinCommands = { CT报告: 'ALT+C', 第1页: 'CTRL+1', ＰＥＴ结果: 'ALT+P', 报告模板: 'TEXT:见附件。' }
+
+    it('拉丁字母不区分大小写', () => {
+      expect(matchVoiceCommand('ct报告', latinCommands)).toEqual({ type: 'command', shortcut: 'ALT+C' })
+      expect(matchVoiceCommand('Ct报告。', latinCommands)).toEqual({ type: 'command', shortcut: 'ALT+C' })
+    })
+
+    it('全角字母、数字与半角一致', () => {
+      expect(matchVoiceCommand('ＣＴ报告', latinCommands)).toEqual({ type: 'command', shortcut: 'ALT+C' })
+      expect(matchVoiceCommand('第１页！', latinCommands)).toEqual({ type: 'command', shortcut: 'CTRL+1' })
+      expect(matchVoiceCommand('pet结果', latinCommands)).toEqual({ type: 'command', shortcut: 'ALT+P' })
+    })
+
+    it('命中的口令为指令表中的原始写法', () => {
+      expect(findVoiceCommandPhrase('ｐｅｔ结果', latinCommands)).toBe('ＰＥＴ结果')
+      expect(findVoiceCommandPhrase('第１页', latinCommands)).toBe('第1页')
+      expect(findVoiceCommandPhrase('MRI报告', latinCommands)).toBeNull()
+      expect(previewVoiceCommand('ct报告', latinCommands).phrase).toBe('CT报告')
+    })
+
+    it('回车短语同样归一后比较', () => {
+      expect(matchVoiceCommand('ＯＫ', {}, { enterAfterPhrases: ['ok'] }))
+        .toEqual({ type: 'text', text: 'ＯＫ', pressEnter: true })
+    })
+
+    it('未命中时原文不做归一', () => {
+      expect(matchVoiceCommand('ＣＴ未见异常', latinCommands)).toEqual({ type: 'text', text: 'ＣＴ未见异常' })
+    })
+  })
+
   describe('边界情况', () => {
     it('空字符串返回 text', () => {
       const r = matchVoiceCommand('', commands)
@@ -69,4 +226,274 @@ describe('matchVoiceCommand', () => {
       expect(r.type).toBe('text')
     })
   })
+
+  describe('关闭指令匹配（纯听写）', () => {
+    it('口令、占位符与回车短语都按原文输入', () => {
+      const options = { enabled: false, enterAfterPhrases: ['阴性'] }
+      const withToken = { ...commands, 换行: '{NEWLINE}' }
+      expect(matchVoiceCommand('保存报告。', withToken, options)).toEqual({ type: 'text', text: '保存报告。' })
+      expect(matchVoiceCommand('换行', withToken, options)).toEqual({ type: 'text', text: '换行' })
+      expect(matchVoiceCommand('阴性', withToken, options)).toEqual({ type: 'text', text: '阴性' })
+    })
+
+    it('预览不再列出候选指令', () => {
+      const preview = previewVoiceCommand('保存报', commands, { enabled: false })
+      expect(preview).toMatchObject({ type: 'text', phrase: null, text: '保存报' })
+      expect(preview.candidates).toBeUndefined()
+    })
+  })
+})
+
+describe('previewVoiceCommand', () => {
+  it('命中指令时返回口令与快捷键', () => {
+    const r = previewVoiceCommand(' 保存报告。', commands)
+    expect(r).toEqual({
+      type: 'command',
+      normalized: '保存报告',
+      phrase: '保存报告',
+      shortcut: 'F2',
+      text: '',
+      pressEnter: false,
+    })
+  })
+
+  it('未命中时返回将要输入的文本', () => {
+    const r = previewVoiceCommand('你好。', commands)
+    expect(r).toEqual({ type: 'text', normalized: '你好', phrase: null, shortcut: null, text: '你好。', pressEnter: false })
+  })
+})
+
+describe('matchVoiceCommandCandidates', () => {
+  const similarCommands = { 保存报告: 'F2', 保存草稿: 'F3', 打印报告: 'F4' }
+
+  it('两个口令同样接近时判定为有歧义', () => {
+    const r = matchVoiceCommandCandidates('保存', similarCommands, { threshold: 0.5 })
+    expect(r.map(c => c.phrase).sort()).toEqual(['保存报告', '保存草稿'])
+    expect(r.every(c => c.score === 0.5)).toBe(true)
+    expect(isAmbiguousCandidates(r)).toBe(true)
+  })
+
+  it('按相似度降序排列，领先明显时无歧义', () => {
+    const r = matchVoiceCommandCandidates('保存报', similarCommands, { threshold: 0.5 })
+    expect(r).toEqual([
+      { phrase: '保存报告', shortcut: 'F2', score: 0.75 },
+      { phrase: '保存草稿', shortcut: 'F3', score: 0.5 },
+    ])
+    expect(isAmbiguousCandidates(r)).toBe(false)
+  })
+
+  it('低于阈值的口令不列为候选', () => {
+    expect(matchVoiceCommandCandidates('保存', similarCommands)).toEqual([])
+    expect(matchVoiceCommandCandidates('', similarCommands, { threshold: 0 })).toEqual([])
+  })
+
+  it('limit 限制候选数量', () => {
+    const r = matchVoiceCommandCandidates('报告', similarCommands, { threshold: 0, limit: 1 })
+    expect(r).toHaveLength(1)
+  })
+
+  it('预览中列出相近口令', () => {
+    const r = previewVoiceCommand('保存报', similarCommands, { candidateThreshold: 0.7 })
+    expect(r.type).toBe('text')
+    expect(r.candidates).toEqual([{ phrase: '保存报告', shortcut: 'F2', score: 0.75 }])
+  })
+})
+
+describe('findClosestVoiceCommand', () => {
+  it('返回编辑距离最近的口令', () => {
+    expect(findClosestVoiceCommand('保存报高。', commands)).toEqual({ phrase: '保存报告', distance: 1 })
+  })
+
+  it('完全相等时距离为 0', () => {
+    expect(findClosestVoiceCommand('上一个', commands)).toEqual({ phrase: '上一个', distance: 0 })
+  })
+
+  it('空指令表返回 null', () => {
+    expect(findClosestVoiceCommand('上一个', {})).toBeNull()
+  })
+})
+
+describe('PostCommandLockout', () => {
+  it('执行指令后锁定期内返回剩余毫秒数，过后解除；未执行过或锁定期为 0 时不锁定', () => {
+    let now = 1000
+    const lockout = new PostCommandLockout(() => now)
+    expect(lockout.remaining(2000)).toBe(0)
+    lockout.markExecuted()
+    now = 1500
+    expect(lockout.remaining(2000)).toBe(1500)
+    expect(lockout.remaining(0)).toBe(0)
+    now = 3000
+    expect(lockout.remaining(2000)).toBe(0)
+  })
+
+  it('锁定期内指令按原文作为普通文字，过后正常执行', () => {
+    let now = 0
+    const lockout = new PostCommandLockout(() => now)
+    const match = () => applyPostCommandLockout(
+      matchVoiceCommand('保存报告。', commands), '保存报告。', lockout.remaining(2000), 'text',
+    )
+    lockout.markExecuted()
+    now = 500
+    expect(match()).toEqual({ type: 'text', text: '保存报告。' })
+    now = 2500
+    expect(match()).toEqual({ type: 'command', shortcut: 'F2' })
+  })
+
+  it('drop 时锁定期内丢弃任何输入，普通文字在 text 模式下不受影响', () => {
+    const text = matchVoiceCommand('灰白色组织', commands)
+    expect(applyPostCommandLockout(text, '灰白色组织', 800, 'drop')).toBeNull()
+    expect(applyPostCommandLockout(matchVoiceCommand('保存报告', commands), '保存报告', 800, 'drop')).toBeNull()
+    expect(applyPostCommandLockout(text, '灰白色组织', 800, 'text')).toBe(text)
+    const newline = applyPostCommandLockout({ type: 'text', text: '\n', phrase: '换行' }, '换行', 800, 'text')
+    expect(newline).toEqual({ type: 'text', text: '换行' })
+  })
+})
+
+describe('VoiceCommandCooldown', () => {
+  it('冷却时间内返回剩余毫秒数，过后可再次执行', () => {
+    let now = 1000
+    const cooldown = new VoiceCommandCooldown(() => now)
+    expect(cooldown.remaining('下一个', 500)).toBe(0)
+    cooldown.markFired('下一个')
+    now = 1200
+    expect(cooldown.remaining('下一个', 500)).toBe(300)
+    now = 1500
+    expect(cooldown.remaining('下一个', 500)).toBe(0)
+  })
+
+  it('不同口令互不影响', () => {
+    const cooldown = new VoiceCommandCooldown(() => 0)
+    cooldown.markFired('下一个')
+    expect(cooldown.remaining('上一个', 500)).toBe(0)
+  })
+
+  it('冷却为 0 时不限制', () => {
+    const cooldown = new VoiceCommandCooldown(() => 0)
+    cooldown.markFired('下一个')
+    expect(cooldown.remaining('下一个', 0)).toBe(0)
+  })
+
+  it('单条指令覆盖全局冷却', () => {
+    const options = { cooldownMs: 500, cooldownOverrides: { 保存报告: 2000 } }
+    expect(resolveCommandCooldownMs('保存报告', options)).toBe(2000)
+    expect(resolveCommandCooldownMs('下一个', options)).toBe(500)
+  })
+})
+
+describe('LastCommandTracker', () => {
+  it('初始没有可重复的指令', () => {
+    expect(new LastCommandTracker().last).toBeNull()
+  })
+
+  it('记录最近一次执行的指令，后者覆盖前者', () => {
+    const tracker = new LastCommandTracker()
+    tracker.record('下一个', 'ALT+B')
+    tracker.record('保存报告', 'F2')
+    expect(tracker.last).toEqual({ phrase: '保存报告', shortcut: 'F2' })
+    tracker.clear()
+    expect(tracker.last).toBeNull()
+  })
+})
+
+describe('isRepeatCommand', () => {
+  it('识别“重复”口令，忽略标点', () => {
+    expect(isRepeatCommand('重复。', '重复', commands)).toBe(true)
+    expect(isRepeatCommand('重复一下', '重复', commands)).toBe(false)
+  })
+
+  it('口令留空时关闭，用户同名指令优先', () => {
+    expect(isRepeatCommand('重复', '', commands)).toBe(false)
+    expect(isRepeatCommand('重复', '重复', { ...commands, 重复: 'F5' })).toBe(false)
+  })
+})
+
+describe('运行程序指令', () => {
+  const execCommands = {
+    ...commands,
+    计算器: 'RUN:calc.exe',
+    检验系统: 'run: "C:\\Program Files\\LIS\\lis.exe" --patient 123',
+  }
+
+  it('解析 RUN: 前缀后的程序与参数，不区分大小写', () => {
+    expect(resolveExecCommand('RUN:calc.exe')).toEqual({ command: 'calc.exe', args: [] })
+    expect(resolveExecCommand('  run:  notepad.exe  report.txt ')).toEqual({ command: 'notepad.exe', args: ['report.txt'] })
+  })
+
+  it('双引号包住含空格的路径，反斜杠原样保留', () => {
+    expect(resolveExecCommand(execCommands.检验系统)).toEqual({
+      command: 'C:\\Program Files\\LIS\\lis.exe',
+      args: ['--patient', '123'],
+    })
+  })
+
+  it('前缀后为空或不是 RUN: 前缀时不是运行程序指令', () => {
+    expect(resolveExecCommand('RUN:   ')).toBeNull()
+    expect(resolveExecCommand('ALT+R')).toBeNull()
+    expect(resolveExecCommand('TEXT:RUN:calc')).toBeNull()
+    expect(resolveExecCommand('RUNS:calc')).toBeNull()
+  })
+
+  it('命令行拆分：空引号为空参数，未闭合引号到行尾', () => {
+    expect(splitCommandLine('a "" b')).toEqual(['a', '', 'b'])
+    expect(splitCommandLine('open "https://example.com/a b')).toEqual(['open', 'https://example.com/a b'])
+    expect(splitCommandLine('x"y z"w')).toEqual(['xy zw'])
+    expect(splitCommandLine('   ')).toEqual([])
+  })
+
+  it('命中口令时返回 exec 结果，快捷键指令不受影响', () => {
+    expect(matchVoiceCommand('计算器。', execCommands)).toEqual({ type: 'exec', command: 'calc.exe', args: [], phrase: '计算器' })
+    expect(matchVoiceCommand('肉眼所见', execCommands)).toEqual({ type: 'command', shortcut: 'ALT+R' })
+  })
+
+  it('预览与指令列表标为运行程序', () => {
+    expect(previewVoiceCommand('计算器', execCommands)).toMatchObject({ type: 'exec', phrase: '计算器', shortcut: 'RUN:calc.exe' })
+    expect(listVoiceCommands(execCommands, 'win32').find(e => e.phrase === '计算器'))
+      .toEqual({ phrase: '计算器', shortcut: 'RUN:calc.exe', kind: 'exec', valid: true })
+  })
+
+  it('锁定期内按原文输入', () => {
+    const match = matchVoiceCommand('计算器', execCommands)
+    expect(applyPostCommandLockout(match, '计算器', 500, 'text')).toEqual({ type: 'text', text: '计算器' })
+  })
+})
+
+describe('listVoiceCommands', () => {
+  it('列出每条指令及快捷键是否可解析，占位符视为文字指令', () => {
+    const entries = listVoiceCommands({ 保存: 'CTRL+S', 换行: '{NEWLINE}', 提交: 'ALT+F; ; ENTER' }, 'win32')
+    expect(entries).toHaveLength(3)
+    expect(entries.find(e => e.phrase === '保存')).toEqual({ phrase: '保存', shortcut: 'CTRL+S', kind: 'shortcut', valid: true })
+    expect(entries.find(e => e.phrase === '换行')).toMatchObject({ kind: 'text', valid: true })
+    const broken = entries.find(e => e.phrase === '提交')
+    expect(broken?.valid).toBe(false)
+    expect(broken?.error).toContain('第 2 步')
+  })
+})
+
+describe('指令别名', () => {
+  const aliases = { 保存报告: ['保存', '存一下。', ' 保存一下 '] }
+
+  it('所有别名都解析为主口令的快捷键', () => {
+    const expanded = expandVoiceCommandAliases(commands, aliases)
+    for (const phrase of ['保存报告', '保存', '存一下', '保存一下']) {
+      expect(matchVoiceCommand(phrase, expanded)).toEqual({ type: 'command', shortcut: 'F2' })
+    }
+    expect(matchVoiceCommand('肉眼所见', expanded)).toEqual({ type: 'command', shortcut: 'ALT+R' })
+  })
+
+  it('不修改原指令表，与已有口令同名的别名以指令表为准', () => {
+    const expanded = expandVoiceCommandAliases(commands, { 保存报告: ['上一个'] })
+    expect(expanded.上一个).toBe('ALT+A')
+    expect(commands).not.toHaveProperty('保存')
+  })
+
+  it('主口令不存在时忽略该组别名', () => {
+    expect(expandVoiceCommandAliases(commands, { 打印报告: ['打印'] })).toEqual(commands)
+  })
+
+  it('别名映射回主口令，其他口令原样返回', () => {
+    expect(canonicalCommandPhrase('存一下', commands, aliases)).toBe('保存报告')
+    expect(canonicalCommandPhrase('保存报告', commands, aliases)).toBe('保存报告')
+    expect(canonicalCommandPhrase('上一个', commands, aliases)).toBe('上一个')
+    expect(canonicalCommandPhrase('打印', commands, aliases)).toBe('打印')
+  })
 })
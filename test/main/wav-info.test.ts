@@ -0,0 +1,97 @@
+import { describe, it, expect } from 'vitest'
+import { encodeWav } from '../../src/wav'
+import { readWavInfo, downmixWavToMono, wavToMonoFloat32, normalizeWavFile, concatWavs } from '../../electron/main/wav-info'
+
+describe('readWavInfo', () => {
+  it('单声道时长', () => {
+    const info = readWavInfo(Buffer.from(encodeWav([new Float32Array(16000)])))
+    expect(info).toEqual({ channels: 1, sampleRate: 16000, frameCount: 16000, durationMs: 1000 })
+  })
+
+  it('双声道按帧计算时长', () => {
+    const info = readWavInfo(Buffer.from(encodeWav([new Float32Array(16000)], 16000, 2)))
+    expect(info.channels).toBe(2)
+    expect(info.frameCount).toBe(8000)
+    expect(info.durationMs).toBe(500)
+  })
+
+  it('不足文件头长度视为空音频', () => {
+    expect(readWavInfo(Buffer.alloc(10)).frameCount).toBe(0)
+  })
+})
+
+describe('downmixWavToMono', () => {
+  it('双声道平均混音为单声道', () => {
+    const stereo = Buffer.from(encodeWav([new Float32Array([0.5, 0, -0.5, -0.5])], 16000, 2))
+    const mono = downmixWavToMono(stereo)
+    const info = readWavInfo(mono)
+    expect(info.channels).toBe(1)
+    expect(info.frameCount).toBe(2)
+    expect(mono.readUInt16LE(32)).toBe(2)
+    expect(mono.readInt16LE(44)).toBe(8192)
+    expect(mono.readInt16LE(46)).toBe(-0x4000)
+  })
+
+  it('单声道原样返回', () => {
+    const mono = Buffer.from(encodeWav([new Float32Array(4)]))
+    expect(downmixWavToMono(mono)).toBe(mono)
+  })
+})
+
+describe('concatWavs', () => {
+  it('按顺序拼接采样并回填文件头长度', () => {
+    const first = Buffer.from(encodeWav([new Float32Array([0.5, 0.25])]))
+    const second = Buffer.from(encodeWav([new Float32Array([-0.5])]))
+    const joined = concatWavs([first, second])
+    expect(readWavInfo(joined).frameCount).toBe(3)
+    expect(joined.readUInt32LE(4)).toBe(36 + 6)
+    expect(joined.readUInt32LE(40)).toBe(6)
+    expect(Array.from(wavToMonoFloat32(joined))).toEqual([...wavToMonoFloat32(first), ...wavToMonoFloat32(second)])
+  })
+
+  it('忽略不足文件头长度的片段', () => {
+    const only = Buffer.from(encodeWav([new Float32Array([0.5])]))
+    expect(concatWavs([Buffer.alloc(10), only]).equals(only)).toBe(true)
+    expect(concatWavs([]).byteLength).toBe(0)
+  })
+})
+
+describe('wavToMonoFloat32', () => {
+  it('双声道混音后转为浮点采样', () => {
+    const stereo = Buffer.from(encodeWav([new Float32Array([0.5, 0, -0.5, -0.5])], 16000, 2))
+    const pcm = wavToMonoFloat32(stereo)
+    expect(pcm).toHaveLength(2)
+    expect(pcm[0]).toBeCloseTo(0.25, 3)
+    expect(pcm[1]).toBeCloseTo(-0.5, 3)
+  })
+})
+
+describe('normalizeWavFile', () => {
+  // 在 fmt 与 data 之间插入 LIST 块，模拟录音软件导出的文件
+  function withListChunk(wav: Buffer): Buffer {
+    const list = Buffer.alloc(8 + 5 + 1)
+    list.write('LIST', 0, 'ascii')
+    list.writeUInt32LE(5, 4)
+    list.write('INFOx', 8, 'ascii')
+    const out = Buffer.concat([wav.subarray(0, 36), list, wav.subarray(36)])
+    out.writeUInt32LE(out.length - 8, 4)
+    return out
+  }
+
+  it('带额外块的 16-bit PCM 文件转为标准 44 字节头', () => {
+    const wav = Buffer.from(encodeWav([new Float32Array([0.5, -0.5, 0.25])], 22050, 1))
+    const normalized = normalizeWavFile(withListChunk(wav))
+    expect(normalized).toEqual(wav)
+    expect(readWavInfo(normalized)).toMatchObject({ sampleRate: 22050, frameCount: 3 })
+  })
+
+  it('非 WAV 或非 16-bit PCM 时给出明确错误', () => {
+    expect(() => normalizeWavFile(Buffer.from('ID3 not a wav file'))).toThrow('不是有效的 WAV 文件')
+    const float32 = Buffer.from(encodeWav([new Float32Array(4)]))
+    float32.writeUInt16LE(3, 20)
+    float32.writeUInt16LE(32, 34)
+    expect(() => normalizeWavFile(float32)).toThrow('仅支持 16-bit PCM WAV，当前为 编码格式 0x3')
+    const noData = Buffer.from(encodeWav([new Float32Array(4)])).subarray(0, 36)
+    expect(() => normalizeWavFile(noData)).toThrow('缺少 data 块')
+  })
+})
@@ -1,5 +1,5 @@
 import { describe, it, expect } from 'vitest'
-import { encodeWav } from '../../src/wav'
+import { encodeWav, monoToInterleavedStereo, describeSampleRateMismatch } from '../../src/wav'
 
 // 解析 WAV 文件头
 function parseWavHeader(buf: ArrayBuffer) {
@@ -128,3 +128,119 @@ describe('encodeWav', () => {
     })
   })
 })
+
+describe('encodeWav 立体声', () => {
+  it('双声道文件头', () => {
+    const h = parseWavHeader(encodeWav([new Float32Array(4)], 16000, 2))
+    expect(h.channels).toBe(2)
+    expect(h.blockAlign).toBe(4)
+    expect(h.byteRate).toBe(16000 * 4)
+    expect(h.dataSize).toBe(8)
+  })
+
+  it('交错采样按顺序写入', () => {
+    const buf = encodeWav([new Float32Array([1.0, -1.0])], 16000, 2)
+    const view = new DataView(buf)
+    expect(view.getInt16(44, true)).toBe(0x7fff)
+    expect(view.getInt16(46, true)).toBe(-0x8000)
+  })
+
+  it('单声道转交错双声道', () => {
+    expect(Array.from(monoToInterleavedStereo(new Float32Array([0.5, -0.25])))).toEqual([0.5, 0.5, -0.25, -0.25])
+  })
+})
+
+describe('encodeWav 直接写入预分配缓冲', () => {
+  // 原先逐个采样经 DataView 写入、尾部静音作为额外数据块追加的实现，作为逐字节对照
+  function encodeWavByDataView(chunks: Float32Array[], sampleRate = 16000, channels = 1): ArrayBuffer {
+    const totalSamples = chunks.reduce((n, c) => n + c.length, 0)
+    const buffer = new ArrayBuffer(44 + totalSamples * 2)
+    const view = new DataView(buffer)
+    const blockAlign = channels * 2
+    const writeStr = (offset: number, str: string) => {
+      for (let i = 0; i < str.length; i++) view.setUint8(offset + i, str.charCodeAt(i))
+    }
+    writeStr(0, 'RIFF')
+    view.setUint32(4, 36 + totalSamples * 2, true)
+    writeStr(8, 'WAVE')
+    writeStr(12, 'fmt ')
+    view.setUint32(16, 16, true)
+    view.setUint16(20, 1, true)
+    view.setUint16(22, channels, true)
+    view.setUint32(24, sampleRate, true)
+    view.setUint32(28, sampleRate * blockAlign, true)
+    view.setUint16(32, blockAlign, true)
+    view.setUint16(34, 16, true)
+    writeStr(36, 'data')
+    view.setUint32(40, totalSamples * 2, true)
+    let offset = 44
+    for (const chunk of chunks) {
+      for (let i = 0; i < chunk.length; i++) {
+        const s = Math.max(-1, Math.min(1, chunk[i]))
+        view.setInt16(offset, s < 0 ? s * 0x8000 : s * 0x7fff, true)
+        offset += 2
+      }
+    }
+    return buffer
+  }
+
+  it('输出与逐采样写入的实现逐字节一致（含越界、NaN、多块与尾部静音）', () => {
+    let seed = 7
+    const random = () => {
+      seed = (seed * 1103515245 + 12345) % 2147483648
+      return seed / 2147483648
+    }
+    const chunks = [
+      new Float32Array([0, 1, -1, 1.5, -2, Number.NaN, 0.5, -0.5, 1e-6, -1e-6, 0.99999]),
+      Float32Array.from({ length: 4096 }, () => random() * 2.4 - 1.2),
+      new Float32Array(0),
+      Float32Array.from({ length: 333 }, () => random() * 2 - 1),
+    ]
+    for (const channels of [1, 2]) {
+      const expected = Buffer.from(encodeWavByDataView([...chunks, new Float32Array(160 * channels)], 16000, channels))
+      const actual = Buffer.from(encodeWav(chunks, 16000, channels, 160 * channels))
+      expect(actual.equals(expected)).toBe(true)
+    }
+    expect(Buffer.from(encodeWav(chunks)).equals(Buffer.from(encodeWavByDataView(chunks)))).toBe(true)
+  })
+})
+
+describe('encodeWav 首尾静音补齐', () => {
+  it('输出采样数按首尾补齐的静音增加，语音采样整体后移', () => {
+    const speech = new Float32Array([0.5, -0.5, 0.25])
+    const lead = 160
+    const tail = 320
+    const buf = encodeWav([speech], 16000, 1, tail, lead)
+    const h = parseWavHeader(buf)
+    expect(h.dataSize / 2).toBe(speech.length + lead + tail)
+    expect(h.fileSize).toBe(36 + h.dataSize)
+
+    const pcm = new Int16Array(buf, 44)
+    expect(pcm.subarray(0, lead).every((v) => v === 0)).toBe(true)
+    expect(pcm[lead]).toBe(Math.floor(0.5 * 0x7fff))
+    expect(pcm[lead + 1]).toBe(-0.5 * 0x8000)
+    expect(pcm.subarray(lead + speech.length).every((v) => v === 0)).toBe(true)
+  })
+
+  it('负数补齐按 0 处理', () => {
+    const buf = encodeWav([new Float32Array(10)], 16000, 1, -5, -5)
+    expect(parseWavHeader(buf).dataSize).toBe(20)
+  })
+})
+
+describe('describeSampleRateMismatch', () => {
+  it('采样率一致时不提示', () => {
+    expect(describeSampleRateMismatch(16000, 16000)).toBeNull()
+  })
+
+  it('不一致时提示中包含实际采样率与识别要求', () => {
+    const message = describeSampleRateMismatch(44100, 16000)
+    expect(message).toContain('44100Hz')
+    expect(message).toContain('16000Hz')
+  })
+
+  it('无效采样率不提示', () => {
+    expect(describeSampleRateMismatch(0, 16000)).toBeNull()
+    expect(describeSampleRateMismatch(NaN, 16000)).toBeNull()
+  })
+})
@@ -0,0 +1,63 @@
+import { describe, it, expect, vi } from 'vitest'
+
+vi.mock('../../electron/main/logger', () => ({
+  logger: { debug: vi.fn(), info: vi.fn(), warn: vi.fn(), error: vi.fn() },
+}))
+
+import { buildWebhookPayload, postWebhook, sendWebhook } from '../../electron/main/webhook'
+
+const fixedNow = () => Date.UTC(2026, 0, 2, 3, 4, 5)
+
+describe('buildWebhookPayload', () => {
+  it('普通听写只含文本与时间戳', () => {
+    expect(buildWebhookPayload('肉眼所见：灰白色组织。', null, fixedNow)).toEqual({
+      text: '肉眼所见：灰白色组织。',
+      timestamp: '2026-01-02T03:04:05.000Z',
+    })
+  })
+
+  it('命中指令时带上口令', () => {
+    expect(buildWebhookPayload('保存报告', '保存报告', fixedNow)).toEqual({
+      text: '保存报告',
+      timestamp: '2026-01-02T03:04:05.000Z',
+      matched_command: '保存报告',
+    })
+  })
+})
+
+describe('postWebhook', () => {
+  it('以 JSON POST 到配置的地址', async () => {
+    const fetchMock = vi.fn().mockResolvedValue({ ok: true, status: 200 })
+    const payload = buildWebhookPayload('你好', undefined, fixedNow)
+    await expect(postWebhook({ url: ' http://audit.local/hook ', timeoutMs: 3000 }, payload, fetchMock as any))
+      .resolves.toBe(true)
+    const [url, init] = fetchMock.mock.calls[0]
+    expect(url).toBe('http://audit.local/hook')
+    expect(init.method).toBe('POST')
+    expect(init.headers).toEqual({ 'Content-Type': 'application/json' })
+    expect(JSON.parse(init.body)).toEqual(payload)
+    expect(init.signal).toBeInstanceOf(AbortSignal)
+  })
+
+  it('未配置地址时跳过，不发请求', async () => {
+    const fetchMock = vi.fn()
+    await expect(postWebhook({ url: '  ', timeoutMs: 3000 }, buildWebhookPayload('你好'), fetchMock as any))
+      .resolves.toBe(false)
+    expect(fetchMock).not.toHaveBeenCalled()
+  })
+
+  it('非 2xx 响应视为失败', async () => {
+    const fetchMock = vi.fn().mockResolvedValue({ ok: false, status: 503 })
+    await expect(postWebhook({ url: 'http://audit.local/hook', timeoutMs: 3000 }, buildWebhookPayload('你好'), fetchMock as any))
+      .rejects.toThrow('HTTP 503')
+  })
+})
+
+describe('sendWebhook', () => {
+  it('未配置地址时不调用 fetch', () => {
+    const fetchSpy = vi.spyOn(globalThis, 'fetch')
+    sendWebhook({ url: '', timeoutMs: 3000 }, buildWebhookPayload('你好'), '[ASR#1]')
+    expect(fetchSpy).not.toHaveBeenCalled()
+    fetchSpy.mockRestore()
+  })
+})
@@ -0,0 +1,25 @@
+import { describe, it, expect } from 'vitest'
+import { truncateText } from '../../shared/text'
+
+describe('truncateText', () => {
+  it('中文按字截断并追加省略号，总长度不超过上限', () => {
+    expect(truncateText('肉眼所见灰白色组织一块', 5)).toBe('肉眼所见…')
+    expect(Array.from(truncateText('肉眼所见灰白色组织一块', 5))).toHaveLength(5)
+    expect(truncateText('取材医生签名已完成', 4)).toBe('取材医…')
+  })
+
+  it('未超长时原样返回', () => {
+    expect(truncateText('下一个', 3)).toBe('下一个')
+    expect(truncateText('', 3)).toBe('')
+  })
+
+  it('不切开代理对与组合字符', () => {
+    expect(truncateText('𠮷𠮷𠮷𠮷', 3)).toBe('𠮷𠮷…')
+    expect(truncateText('👍🏻👍🏻👍🏻', 2)).toBe('👍🏻…')
+  })
+
+  it('上限小于省略号长度时只截断', () => {
+    expect(truncateText('病理报告', 1)).toBe('病')
+    expect(truncateText('病理报告', 0)).toBe('')
+  })
+})
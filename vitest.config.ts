@@ -9,7 +9,7 @@ export default defineConfig({
       provider: 'v8',
       reporter: ['text', 'html', 'json', 'clover'],
       all: true,
-      include: ['electron/main/**/*.ts', 'src/**/*.ts'],
+      include: ['electron/main/**/*.ts', 'src/**/*.ts', 'shared/**/*.ts'],
       exclude: ['**/*.d.ts'],
     },
   },